@@ -0,0 +1,149 @@
+use std::{process::Stdio, time::Duration};
+
+use aster_macro::action;
+use common::{
+    action::error::ActionError,
+    tokio::{io::AsyncWriteExt, process::Command, time::timeout as with_timeout},
+};
+use serde_json::{Value, json};
+
+pub type Text = String;
+
+/// ```
+///    -----------
+/// -> + command +-- Success
+///    -----+-----
+///         | Failure
+/// ```
+#[result]
+enum Result {
+    /// 退出码为 0
+    #[right(zh_cn = "成功", en = "Success")]
+    Success(plug!(Value as nesting! { stdout: String, stderr: String, code: i64, json: Value })),
+    /// 退出码非 0 或被信号终止
+    #[bottom(zh_cn = "失败", en = "Failure")]
+    Failure(plug!(Value as nesting! { stdout: String, stderr: String, code: i64, json: Value })),
+    #[top(zh_cn = "超时", en = "Timeout")]
+    Timeout,
+}
+
+// 多行文本按行拆分，忽略空行
+fn lines(text: &Option<Text>) -> Vec<&str> {
+    text.as_deref()
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+#[action(zh_cn = "执行命令", en = "Run command")]
+#[entry(program, args, option { env, cwd, stdin, timeout, parse_json })]
+#[description(
+    zh_cn = "运行一个外部程序并捕获其输出",
+    en = "Run an external program and capture its output"
+)]
+pub async fn command_action(
+    #[name(zh_cn = "程序", en = "Program")]
+    #[description(zh_cn = "程序名称或路径", en = "Program name or path")]
+    program: String,
+    #[name(zh_cn = "参数", en = "Arguments")]
+    #[description(zh_cn = "每行一个参数", en = "One argument per line")]
+    args: Option<Text>,
+    #[name(zh_cn = "环境变量", en = "Environment variables")]
+    #[description(zh_cn = "每行一个 KEY=VALUE", en = "One KEY=VALUE per line")]
+    env: Option<Text>,
+    #[name(zh_cn = "工作目录", en = "Working directory")]
+    #[description(zh_cn = "程序运行时所在的目录", en = "Directory the program runs in")]
+    cwd: Option<String>,
+    #[name(zh_cn = "标准输入", en = "Standard input")]
+    #[description(
+        zh_cn = "写入程序标准输入的内容，可连接上游输出",
+        en = "Content written to stdin, can be plugged from upstream output"
+    )]
+    stdin: Option<Text>,
+    #[name(zh_cn = "超时时间", en = "Timeout")]
+    #[description(
+        zh_cn = "超时后结束程序（毫秒），不填则一直等待",
+        en = "Kill the program after this many milliseconds, wait forever if empty"
+    )]
+    timeout: Option<u64>,
+    #[name(zh_cn = "解析 JSON", en = "Parse JSON")]
+    #[description(
+        zh_cn = "将标准输出解析为 JSON",
+        en = "Parse standard output as JSON"
+    )]
+    parse_json: Option<bool>,
+) -> Result {
+    let mut command = Command::new(&program);
+    command
+        .args(lines(&args))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        // 超时放弃等待时同时结束子进程
+        .kill_on_drop(true);
+    for line in lines(&env) {
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(Box::new(ActionError::RunActionCardError(format!(
+                "Invalid environment variable {}, expected KEY=VALUE",
+                line
+            ))));
+        };
+        command.env(key.trim(), value.trim());
+    }
+    if let Some(cwd) = cwd.filter(|cwd| !cwd.is_empty()) {
+        command.current_dir(cwd);
+    }
+    // 服务进程中运行时不弹出控制台窗口
+    #[cfg(windows)]
+    command.creation_flags(0x08000000);
+
+    let mut child = command.spawn()?;
+    let input = child.stdin.take();
+    let run = async move {
+        // 与读取输出同时写入，避免管道写满后互相等待
+        let write = async move {
+            if let Some(mut input_pipe) = input {
+                let content = stdin.unwrap_or_default();
+                input_pipe.write_all(content.as_bytes()).await?;
+            }
+            Ok::<(), std::io::Error>(())
+        };
+        let (write, output) = common::tokio::join!(write, child.wait_with_output());
+        // 程序未读取标准输入就退出时会出现管道关闭，此时以程序的输出为准
+        if let Err(e) = write {
+            if e.kind() != std::io::ErrorKind::BrokenPipe {
+                return Err(e);
+            }
+        }
+        output
+    };
+    let output = match timeout {
+        Some(timeout) => match with_timeout(Duration::from_millis(timeout), run).await {
+            Ok(output) => output?,
+            Err(_) => return Result::Timeout,
+        },
+        None => run.await?,
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    // 失败时的输出通常是错误信息，只在成功时解析，无法解析时为 null
+    let json = if output.status.success() && parse_json.unwrap_or(false) {
+        serde_json::from_str(&stdout).unwrap_or(Value::Null)
+    } else {
+        Value::Null
+    };
+    let result = json!({
+        "stdout": stdout,
+        "stderr": stderr,
+        "code": output.status.code(),
+        "json": json,
+    });
+    if output.status.success() {
+        Result::Success(result)
+    } else {
+        Result::Failure(result)
+    }
+}
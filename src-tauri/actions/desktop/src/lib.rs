@@ -0,0 +1,279 @@
+use std::{io::Cursor, time::Duration};
+
+use aster_macro::action;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use common::{
+    desktop::{ClipboardImage, DesktopError, DesktopRequest, request},
+    tokio::fs,
+};
+use image::{ImageFormat, RgbaImage, load_from_memory};
+use serde_json::{Value, json};
+
+pub type File = String;
+pub type Text = String;
+
+/// 默认等待 UI 进程响应的时间（毫秒）
+const DEFAULT_TIMEOUT: u64 = 5_000;
+/// 输入框默认等待用户五分钟
+const DEFAULT_PROMPT_TIMEOUT: u64 = 300_000;
+
+/// 桌面交互的结果，没有 UI 进程连接时同样进入 Timeout
+/// ```
+///         Timeout
+///    --------+-------
+/// -> +    notify     +-- Success
+///    ----------------
+/// ```
+#[result]
+enum DoneResult {
+    #[right(zh_cn = "成功", en = "Success")]
+    Success,
+    #[top(zh_cn = "超时", en = "Timeout")]
+    Timeout,
+}
+
+#[result]
+enum TextResult {
+    #[right(zh_cn = "成功", en = "Success")]
+    Success(plug!(String as String)),
+    /// 剪贴板中没有文本
+    #[bottom(zh_cn = "无内容", en = "Empty")]
+    Empty,
+    #[top(zh_cn = "超时", en = "Timeout")]
+    Timeout,
+}
+
+#[result]
+enum ImageResult {
+    #[right(zh_cn = "成功", en = "Success")]
+    Success(plug!(Value as nesting! { width: i64, height: i64, png: String, path: String })),
+    /// 剪贴板中没有图片
+    #[bottom(zh_cn = "无内容", en = "Empty")]
+    Empty,
+    #[top(zh_cn = "超时", en = "Timeout")]
+    Timeout,
+}
+
+#[result]
+enum PromptResult {
+    #[right(zh_cn = "已提交", en = "Submitted")]
+    Submitted(plug!(String as String)),
+    /// 用户点击了取消或关闭了输入框
+    #[bottom(zh_cn = "已取消", en = "Cancelled")]
+    Cancelled,
+    #[top(zh_cn = "超时", en = "Timeout")]
+    Timeout,
+}
+
+// 未连接 UI 进程与等待超时均返回 None
+async fn send(
+    desktop_request: DesktopRequest,
+    timeout: u64,
+) -> std::result::Result<Option<Value>, DesktopError> {
+    match request(desktop_request, Duration::from_millis(timeout)).await {
+        Ok(value) => Ok(Some(value)),
+        Err(DesktopError::NotConnectedError | DesktopError::TimeoutError) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+#[action(zh_cn = "桌面通知", en = "Notification")]
+#[entry(title, body, option { timeout })]
+#[description(zh_cn = "在桌面上显示一条通知", en = "Show a notification on the desktop")]
+pub async fn notify_action(
+    #[name(zh_cn = "标题", en = "Title")]
+    #[description(zh_cn = "通知的标题", en = "Title of the notification")]
+    title: String,
+    #[name(zh_cn = "内容", en = "Body")]
+    #[description(zh_cn = "通知的内容", en = "Body of the notification")]
+    body: Text,
+    #[name(zh_cn = "超时时间", en = "Timeout")]
+    #[description(
+        zh_cn = "等待界面响应的时间（毫秒），默认为 5000",
+        en = "Time to wait for the UI (ms), 5000 by default"
+    )]
+    timeout: Option<u64>,
+) -> DoneResult {
+    let request = DesktopRequest::Notify { title, body };
+    match send(request, timeout.unwrap_or(DEFAULT_TIMEOUT)).await? {
+        Some(_) => DoneResult::Success,
+        None => DoneResult::Timeout,
+    }
+}
+
+#[action(zh_cn = "读取剪贴板", en = "Read clipboard")]
+#[entry(option { timeout })]
+#[description(zh_cn = "读取剪贴板中的文本", en = "Read the text in the clipboard")]
+pub async fn read_clipboard_action(
+    #[name(zh_cn = "超时时间", en = "Timeout")]
+    #[description(
+        zh_cn = "等待界面响应的时间（毫秒），默认为 5000",
+        en = "Time to wait for the UI (ms), 5000 by default"
+    )]
+    timeout: Option<u64>,
+) -> TextResult {
+    let request = DesktopRequest::ReadClipboardText;
+    match send(request, timeout.unwrap_or(DEFAULT_TIMEOUT)).await? {
+        Some(Value::String(text)) => TextResult::Success(text),
+        Some(_) => TextResult::Empty,
+        None => TextResult::Timeout,
+    }
+}
+
+#[action(zh_cn = "写入剪贴板", en = "Write clipboard")]
+#[entry(text, option { timeout })]
+#[description(zh_cn = "将文本复制到剪贴板", en = "Copy text to the clipboard")]
+pub async fn write_clipboard_action(
+    #[name(zh_cn = "文本", en = "Text")]
+    #[description(zh_cn = "要复制的文本", en = "Text to copy")]
+    text: Text,
+    #[name(zh_cn = "超时时间", en = "Timeout")]
+    #[description(
+        zh_cn = "等待界面响应的时间（毫秒），默认为 5000",
+        en = "Time to wait for the UI (ms), 5000 by default"
+    )]
+    timeout: Option<u64>,
+) -> DoneResult {
+    let request = DesktopRequest::WriteClipboardText { text };
+    match send(request, timeout.unwrap_or(DEFAULT_TIMEOUT)).await? {
+        Some(_) => DoneResult::Success,
+        None => DoneResult::Timeout,
+    }
+}
+
+#[action(zh_cn = "读取剪贴板图片", en = "Read clipboard image")]
+#[entry(option { path, timeout })]
+#[description(
+    zh_cn = "读取剪贴板中的图片，以 PNG 输出",
+    en = "Read the image in the clipboard as PNG"
+)]
+pub async fn read_clipboard_image_action(
+    #[name(zh_cn = "保存路径", en = "Save path")]
+    #[description(
+        zh_cn = "将图片保存为 PNG 文件，留空时只输出 Base64",
+        en = "Save the image as a PNG file, only output Base64 when empty"
+    )]
+    path: Option<File>,
+    #[name(zh_cn = "超时时间", en = "Timeout")]
+    #[description(
+        zh_cn = "等待界面响应的时间（毫秒），默认为 5000",
+        en = "Time to wait for the UI (ms), 5000 by default"
+    )]
+    timeout: Option<u64>,
+) -> ImageResult {
+    let request = DesktopRequest::ReadClipboardImage;
+    let image = match send(request, timeout.unwrap_or(DEFAULT_TIMEOUT)).await? {
+        Some(Value::Null) => return ImageResult::Empty,
+        Some(value) => serde_json::from_value::<ClipboardImage>(value)?,
+        None => return ImageResult::Timeout,
+    };
+    let (width, height) = (image.width, image.height);
+    let Some(image) = RgbaImage::from_raw(width, height, STANDARD.decode(image.rgba)?) else {
+        return ImageResult::Empty;
+    };
+    let mut png = vec![];
+    image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+
+    let path = path.filter(|path| !path.is_empty()).unwrap_or_default();
+    if !path.is_empty() {
+        fs::write(&path, &png).await?;
+    }
+    ImageResult::Success(json!({
+        "width": width,
+        "height": height,
+        "png": STANDARD.encode(&png),
+        "path": path,
+    }))
+}
+
+#[action(zh_cn = "写入剪贴板图片", en = "Write clipboard image")]
+#[entry(image, option { timeout })]
+#[description(
+    zh_cn = "将 PNG 或 JPEG 图片复制到剪贴板",
+    en = "Copy a PNG or JPEG image to the clipboard"
+)]
+pub async fn write_clipboard_image_action(
+    #[name(zh_cn = "图片", en = "Image")]
+    #[description(zh_cn = "要复制的图片文件", en = "Image file to copy")]
+    image: File,
+    #[name(zh_cn = "超时时间", en = "Timeout")]
+    #[description(
+        zh_cn = "等待界面响应的时间（毫秒），默认为 5000",
+        en = "Time to wait for the UI (ms), 5000 by default"
+    )]
+    timeout: Option<u64>,
+) -> DoneResult {
+    let image = load_from_memory(&fs::read(&image).await?)?.to_rgba8();
+    let request = DesktopRequest::WriteClipboardImage {
+        image: ClipboardImage {
+            width: image.width(),
+            height: image.height(),
+            rgba: STANDARD.encode(image.into_raw()),
+        },
+    };
+    match send(request, timeout.unwrap_or(DEFAULT_TIMEOUT)).await? {
+        Some(_) => DoneResult::Success,
+        None => DoneResult::Timeout,
+    }
+}
+
+#[action(zh_cn = "打开", en = "Open")]
+#[entry(target, option { timeout })]
+#[description(
+    zh_cn = "使用默认程序打开网址或文件",
+    en = "Open a URL or file with the default app"
+)]
+pub async fn open_action(
+    #[name(zh_cn = "目标", en = "Target")]
+    #[description(zh_cn = "网址或文件路径", en = "URL or file path")]
+    target: String,
+    #[name(zh_cn = "超时时间", en = "Timeout")]
+    #[description(
+        zh_cn = "等待界面响应的时间（毫秒），默认为 5000",
+        en = "Time to wait for the UI (ms), 5000 by default"
+    )]
+    timeout: Option<u64>,
+) -> DoneResult {
+    let request = DesktopRequest::Open { target };
+    match send(request, timeout.unwrap_or(DEFAULT_TIMEOUT)).await? {
+        Some(_) => DoneResult::Success,
+        None => DoneResult::Timeout,
+    }
+}
+
+#[action(zh_cn = "询问用户", en = "Prompt user")]
+#[entry(title, message, option { default, timeout })]
+#[description(
+    zh_cn = "弹出输入框并等待用户输入",
+    en = "Show a dialog and wait for the user's input"
+)]
+pub async fn prompt_action(
+    #[name(zh_cn = "标题", en = "Title")]
+    #[description(zh_cn = "输入框的标题", en = "Title of the dialog")]
+    title: String,
+    #[name(zh_cn = "提示", en = "Message")]
+    #[description(zh_cn = "向用户说明需要输入什么", en = "Tell the user what to input")]
+    message: Text,
+    #[name(zh_cn = "默认值", en = "Default")]
+    #[description(zh_cn = "输入框中预先填入的值", en = "Value prefilled in the dialog")]
+    default: Option<String>,
+    #[name(zh_cn = "超时时间", en = "Timeout")]
+    #[description(
+        zh_cn = "等待用户输入的时间（毫秒），默认为 300000",
+        en = "Time to wait for the input (ms), 300000 by default"
+    )]
+    timeout: Option<u64>,
+) -> PromptResult {
+    let timeout = timeout.unwrap_or(DEFAULT_PROMPT_TIMEOUT);
+    let request = DesktopRequest::Prompt {
+        title,
+        message,
+        default,
+        timeout: Some(timeout),
+    };
+    match send(request, timeout).await? {
+        Some(Value::String(value)) => PromptResult::Submitted(value),
+        Some(_) => PromptResult::Cancelled,
+        None => PromptResult::Timeout,
+    }
+}
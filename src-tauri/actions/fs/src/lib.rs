@@ -0,0 +1,584 @@
+use std::{
+    fs,
+    io::{self, ErrorKind, Write},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use aster_macro::action;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use glob::{MatchOptions, Pattern};
+use serde_json::{Value, json};
+use sha2::Digest;
+use zip::{CompressionMethod, ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+pub type File = String;
+pub type Directory = String;
+pub type Text = String;
+
+/// 读取内容的结果
+/// ```
+///    --------
+/// -> + read +-- Success
+///    --+--+--
+///      |  | Permission
+///      | NotFound
+/// ```
+#[result]
+enum ContentResult {
+    #[right(zh_cn = "成功", en = "Success")]
+    Success(plug!(String as String)),
+    #[bottom(zh_cn = "不存在", en = "Not found")]
+    NotFound(plug!(String as String)),
+    #[bottom(zh_cn = "无权限", en = "Permission denied")]
+    Permission(plug!(String as String)),
+}
+
+/// 修改文件的结果，成功时输出目标路径
+/// ```
+///         Exists
+///    -------+------
+/// -> + write/copy +-- Success
+///    ---+------+---
+///       |      | Permission
+///    NotFound
+/// ```
+#[result]
+enum PathResult {
+    #[right(zh_cn = "成功", en = "Success")]
+    Success(plug!(String as String)),
+    #[bottom(zh_cn = "不存在", en = "Not found")]
+    NotFound(plug!(String as String)),
+    #[bottom(zh_cn = "无权限", en = "Permission denied")]
+    Permission(plug!(String as String)),
+    #[top(zh_cn = "已存在", en = "Exists")]
+    Exists(plug!(String as String)),
+}
+
+#[result]
+enum ListResult {
+    #[right(zh_cn = "成功", en = "Success")]
+    Success(plug!(Value as nesting! { entries: Value, count: i64 })),
+    #[bottom(zh_cn = "不存在", en = "Not found")]
+    NotFound(plug!(String as String)),
+    #[bottom(zh_cn = "无权限", en = "Permission denied")]
+    Permission(plug!(String as String)),
+}
+
+#[options]
+pub enum Encoding {
+    #[label(zh_cn = "文本", en = "Text")]
+    Utf8,
+    Base64,
+}
+
+#[options]
+pub enum WriteMode {
+    #[label(zh_cn = "覆盖", en = "Overwrite")]
+    Overwrite,
+    #[label(zh_cn = "追加", en = "Append")]
+    Append,
+    #[label(zh_cn = "仅新建", en = "Create new")]
+    CreateNew,
+}
+
+#[options]
+pub enum Algorithm {
+    Sha256,
+    Sha512,
+    Sha1,
+    Md5,
+}
+
+// 常见的 IO 错误进入对应的分支，其余错误使运行失败
+macro_rules! io_branch {
+    ($result:ident, $path:expr, $io:expr) => {
+        match $io {
+            Ok(value) => $result::Success(value),
+            Err(e) => {
+                let message = format!("{}: {}", $path, e);
+                match e.kind() {
+                    ErrorKind::NotFound => $result::NotFound(message),
+                    ErrorKind::PermissionDenied => $result::Permission(message),
+                    _ => Err(e.into()),
+                }
+            }
+        }
+    };
+    ($result:ident, $path:expr, $io:expr, exists) => {
+        match $io {
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                $result::Exists(format!("{}: {}", $path, e))
+            }
+            io => io_branch!($result, $path, io),
+        }
+    };
+}
+
+fn exists_error(path: &Path) -> io::Error {
+    io::Error::new(
+        ErrorKind::AlreadyExists,
+        format!("{} already exists", path.display()),
+    )
+}
+
+// 不允许覆盖时，目标已存在视为错误
+fn check_target(target: &Path, overwrite: Option<bool>) -> io::Result<()> {
+    if !overwrite.unwrap_or(false) && target.exists() {
+        return Err(exists_error(target));
+    }
+    Ok(())
+}
+
+fn zip_error(error: zip::result::ZipError) -> io::Error {
+    match error {
+        zip::result::ZipError::Io(e) => e,
+        e => io::Error::other(e),
+    }
+}
+
+// 解析路径中的链接，路径尚不存在时解析已存在的上级目录
+fn resolve(path: &Path) -> io::Result<PathBuf> {
+    let path = std::path::absolute(path)?;
+    let mut base = path.as_path();
+    loop {
+        match fs::canonicalize(base) {
+            Ok(resolved) => return Ok(resolved.join(path.strip_prefix(base).unwrap_or(&path))),
+            Err(e) if e.kind() == ErrorKind::NotFound => match base.parent() {
+                Some(parent) => base = parent,
+                None => return Ok(path),
+            },
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+// 目录不能复制或移动到自身之中
+fn check_nesting(source: &Path, target: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(source)?.is_dir() && resolve(target)?.starts_with(resolve(source)?) {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("{} is inside {}", target.display(), source.display()),
+        ));
+    }
+    Ok(())
+}
+
+fn copy_link(source: &Path, target: &Path) -> io::Result<()> {
+    let link = fs::read_link(source)?;
+    // 覆盖时先删除已存在的文件或链接
+    if fs::symlink_metadata(target).is_ok_and(|metadata| !metadata.is_dir()) {
+        fs::remove_file(target)?;
+    }
+    #[cfg(unix)]
+    return std::os::unix::fs::symlink(link, target);
+    #[cfg(windows)]
+    return if source.is_dir() {
+        std::os::windows::fs::symlink_dir(link, target)
+    } else {
+        std::os::windows::fs::symlink_file(link, target)
+    };
+}
+
+// 链接按链接复制，不进入链接指向的目录
+fn copy_recursive(source: &Path, target: &Path) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(source)?;
+    if metadata.is_symlink() {
+        copy_link(source, target)?;
+    } else if metadata.is_dir() {
+        fs::create_dir_all(target)?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &target.join(entry.file_name()))?;
+        }
+    } else {
+        fs::copy(source, target)?;
+    }
+    Ok(())
+}
+
+fn remove(path: &Path, recursive: bool) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(path)?;
+    if !metadata.is_dir() {
+        fs::remove_file(path)
+    } else if recursive {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_dir(path)
+    }
+}
+
+fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn collect_entries(
+    root: &Path,
+    dir: &Path,
+    pattern: &Option<Pattern>,
+    recursive: bool,
+    entries: &mut Vec<Value>,
+) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let metadata = entry.metadata()?;
+        let relative = relative_path(root, &path);
+        let name = entry.file_name().to_string_lossy().to_string();
+        // 不含 / 的模式只匹配文件名
+        let matched = match pattern {
+            Some(pattern) if pattern.as_str().contains('/') => {
+                pattern.matches_with(&relative, MatchOptions::new())
+            }
+            Some(pattern) => pattern.matches(&name),
+            None => true,
+        };
+        if matched {
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|time| time.as_millis() as u64);
+            entries.push(json!({
+                "path": path.to_string_lossy(),
+                "relative": relative,
+                "name": name,
+                "is_dir": metadata.is_dir(),
+                "size": metadata.len(),
+                "modified": modified,
+            }));
+        }
+        if recursive && metadata.is_dir() {
+            collect_entries(root, &path, pattern, recursive, entries)?;
+        }
+    }
+    Ok(())
+}
+
+// 链接以链接写入，压缩包位于源目录中时跳过压缩包本身
+fn zip_recursive(
+    writer: &mut ZipWriter<fs::File>,
+    root: &Path,
+    path: &Path,
+    archive: &Path,
+    options: SimpleFileOptions,
+) -> io::Result<()> {
+    let name = relative_path(root, path);
+    let metadata = fs::symlink_metadata(path)?;
+    if metadata.is_symlink() {
+        let link = fs::read_link(path)?;
+        writer
+            .add_symlink(name, link.to_string_lossy(), options)
+            .map_err(zip_error)?;
+    } else if metadata.is_dir() {
+        if !name.is_empty() {
+            writer
+                .add_directory(format!("{}/", name), options)
+                .map_err(zip_error)?;
+        }
+        for entry in fs::read_dir(path)? {
+            let entry = entry?.path();
+            if entry != archive {
+                zip_recursive(writer, root, &entry, archive, options)?;
+            }
+        }
+    } else {
+        writer.start_file(name, options).map_err(zip_error)?;
+        io::copy(&mut fs::File::open(path)?, writer)?;
+    }
+    Ok(())
+}
+
+fn digest<D: Digest + Write>(path: &str) -> io::Result<String> {
+    let mut hasher = D::new();
+    io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+#[action(zh_cn = "读取文件", en = "Read file")]
+#[description(zh_cn = "读取文件的内容", en = "Read the content of a file")]
+pub fn read_file_action(
+    #[name(zh_cn = "文件", en = "File")]
+    #[description(zh_cn = "要读取的文件", en = "File to read")]
+    path: File,
+    #[name(zh_cn = "编码", en = "Encoding")]
+    #[description(
+        zh_cn = "以文本读取，或将二进制内容编码为 Base64",
+        en = "Read as text, or encode binary content as Base64"
+    )]
+    encoding: Option<Encoding>,
+) -> ContentResult {
+    let content = match encoding {
+        Some(Encoding::Base64) => fs::read(&path).map(|bytes| STANDARD.encode(bytes)),
+        _ => fs::read_to_string(&path),
+    };
+    io_branch!(ContentResult, path, content)
+}
+
+#[action(zh_cn = "写入文件", en = "Write file")]
+#[entry(path, content, option { encoding, mode })]
+#[description(zh_cn = "写入或追加文件内容", en = "Write or append content to a file")]
+pub fn write_file_action(
+    #[name(zh_cn = "文件", en = "File")]
+    #[description(zh_cn = "要写入的文件", en = "File to write")]
+    path: File,
+    #[name(zh_cn = "内容", en = "Content")]
+    #[description(zh_cn = "写入的内容", en = "Content to write")]
+    content: Text,
+    #[name(zh_cn = "编码", en = "Encoding")]
+    #[description(
+        zh_cn = "内容为文本，或为 Base64 编码的二进制",
+        en = "Content is text, or Base64 encoded binary"
+    )]
+    encoding: Option<Encoding>,
+    #[name(zh_cn = "写入方式", en = "Mode")]
+    #[description(
+        zh_cn = "覆盖、追加，或仅在文件不存在时新建",
+        en = "Overwrite, append, or create only if the file does not exist"
+    )]
+    mode: Option<WriteMode>,
+) -> PathResult {
+    let bytes = match encoding {
+        Some(Encoding::Base64) => STANDARD.decode(content.trim())?,
+        _ => content.into_bytes(),
+    };
+    let mut options = fs::OpenOptions::new();
+    match mode {
+        Some(WriteMode::Append) => options.create(true).append(true),
+        Some(WriteMode::CreateNew) => options.write(true).create_new(true),
+        _ => options.write(true).create(true).truncate(true),
+    };
+    let written = options
+        .open(&path)
+        .and_then(|mut file| file.write_all(&bytes))
+        .map(|_| path.clone());
+    io_branch!(PathResult, path, written, exists)
+}
+
+#[action(zh_cn = "复制", en = "Copy")]
+#[description(zh_cn = "复制文件或目录", en = "Copy a file or directory")]
+pub fn copy_action(
+    #[name(zh_cn = "源路径", en = "Source")]
+    #[description(zh_cn = "要复制的文件或目录", en = "File or directory to copy")]
+    source: File,
+    #[name(zh_cn = "目标路径", en = "Target")]
+    #[description(zh_cn = "复制到的路径", en = "Path to copy to")]
+    target: File,
+    #[name(zh_cn = "覆盖", en = "Overwrite")]
+    #[description(zh_cn = "目标已存在时覆盖", en = "Overwrite the target if it exists")]
+    overwrite: Option<bool>,
+) -> PathResult {
+    let target_path = PathBuf::from(&target);
+    let copied = check_target(&target_path, overwrite)
+        .and_then(|_| check_nesting(Path::new(&source), &target_path))
+        .and_then(|_| copy_recursive(Path::new(&source), &target_path))
+        .map(|_| target.clone());
+    io_branch!(PathResult, source, copied, exists)
+}
+
+#[action(zh_cn = "移动", en = "Move")]
+#[description(zh_cn = "移动文件或目录", en = "Move a file or directory")]
+pub fn move_action(
+    #[name(zh_cn = "源路径", en = "Source")]
+    #[description(zh_cn = "要移动的文件或目录", en = "File or directory to move")]
+    source: File,
+    #[name(zh_cn = "目标路径", en = "Target")]
+    #[description(zh_cn = "移动到的路径", en = "Path to move to")]
+    target: File,
+    #[name(zh_cn = "覆盖", en = "Overwrite")]
+    #[description(zh_cn = "目标已存在时覆盖", en = "Overwrite the target if it exists")]
+    overwrite: Option<bool>,
+) -> PathResult {
+    let (source_path, target_path) = (Path::new(&source), Path::new(&target));
+    let moved = check_target(target_path, overwrite)
+        .and_then(|_| check_nesting(source_path, target_path))
+        .and_then(|_| match fs::rename(source_path, target_path) {
+            // 跨磁盘等无法直接重命名的情况，复制后删除源路径
+            Err(e) if !matches!(e.kind(), ErrorKind::NotFound | ErrorKind::PermissionDenied) => {
+                copy_recursive(source_path, target_path)?;
+                remove(source_path, true)
+            }
+            moved => moved,
+        })
+        .map(|_| target.clone());
+    io_branch!(PathResult, source, moved, exists)
+}
+
+#[action(zh_cn = "重命名", en = "Rename")]
+#[description(
+    zh_cn = "在原目录中重命名文件或目录",
+    en = "Rename a file or directory in place"
+)]
+pub fn rename_action(
+    #[name(zh_cn = "路径", en = "Path")]
+    #[description(zh_cn = "要重命名的文件或目录", en = "File or directory to rename")]
+    path: File,
+    #[name(zh_cn = "新名称", en = "New name")]
+    #[description(zh_cn = "不包含目录的新名称", en = "New name without directories")]
+    name: String,
+) -> PathResult {
+    let source = Path::new(&path);
+    if name.is_empty() || name.contains(['/', '\\']) {
+        return Err(Box::new(io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("Invalid file name {}", name),
+        )));
+    }
+    let target = source.with_file_name(&name);
+    let renamed = check_target(&target, None)
+        .and_then(|_| fs::rename(source, &target))
+        .map(|_| target.to_string_lossy().to_string());
+    io_branch!(PathResult, path, renamed, exists)
+}
+
+#[action(zh_cn = "删除", en = "Delete")]
+#[description(zh_cn = "删除文件或目录", en = "Delete a file or directory")]
+pub fn delete_action(
+    #[name(zh_cn = "路径", en = "Path")]
+    #[description(zh_cn = "要删除的文件或目录", en = "File or directory to delete")]
+    path: File,
+    #[name(zh_cn = "递归删除", en = "Recursive")]
+    #[description(
+        zh_cn = "删除非空目录及其中的全部内容",
+        en = "Delete a non-empty directory and everything in it"
+    )]
+    recursive: Option<bool>,
+) -> PathResult {
+    let removed = remove(Path::new(&path), recursive.unwrap_or(false)).map(|_| path.clone());
+    io_branch!(PathResult, path, removed)
+}
+
+#[action(zh_cn = "列出目录", en = "List directory")]
+#[description(
+    zh_cn = "列出目录中的文件，可按通配符过滤",
+    en = "List files in a directory, optionally filtered by a glob"
+)]
+pub fn list_dir_action(
+    #[name(zh_cn = "目录", en = "Directory")]
+    #[description(zh_cn = "要列出的目录", en = "Directory to list")]
+    dir: Directory,
+    #[name(zh_cn = "匹配模式", en = "Pattern")]
+    #[description(
+        zh_cn = "如 *.txt，包含 / 时匹配相对路径，如 logs/**/*.log",
+        en = "Such as *.txt, patterns with / match relative paths, such as logs/**/*.log"
+    )]
+    pattern: Option<String>,
+    #[name(zh_cn = "包含子目录", en = "Recursive")]
+    #[description(zh_cn = "同时列出子目录中的文件", en = "Also list files in subdirectories")]
+    recursive: Option<bool>,
+) -> ListResult {
+    let pattern = pattern
+        .filter(|pattern| !pattern.is_empty())
+        .map(|pattern| Pattern::new(&pattern))
+        .transpose()?;
+    let root = Path::new(&dir);
+    let mut entries = vec![];
+    let listed = collect_entries(root, root, &pattern, recursive.unwrap_or(false), &mut entries)
+        .map(|_| json!({ "count": entries.len(), "entries": entries }));
+    io_branch!(ListResult, dir, listed)
+}
+
+#[action(zh_cn = "压缩", en = "Zip")]
+#[description(zh_cn = "将文件或目录压缩为 zip", en = "Compress a file or directory into a zip")]
+pub fn zip_action(
+    #[name(zh_cn = "源路径", en = "Source")]
+    #[description(zh_cn = "要压缩的文件或目录", en = "File or directory to compress")]
+    source: File,
+    #[name(zh_cn = "压缩包", en = "Archive")]
+    #[description(zh_cn = "生成的 zip 文件", en = "The zip file to create")]
+    target: File,
+    #[name(zh_cn = "覆盖", en = "Overwrite")]
+    #[description(zh_cn = "压缩包已存在时覆盖", en = "Overwrite the archive if it exists")]
+    overwrite: Option<bool>,
+) -> PathResult {
+    let target_path = Path::new(&target);
+    let zipped = check_target(target_path, overwrite).and_then(|_| {
+        if !Path::new(&source).exists() {
+            return Err(io::Error::new(
+                ErrorKind::NotFound,
+                format!("{} does not exist", source),
+            ));
+        }
+        // 以解析后的路径遍历，才能与同样解析后的压缩包路径比较
+        let source_path = resolve(Path::new(&source))?;
+        // 目录以其内容为根，文件以所在目录为根
+        let root = if source_path.is_dir() {
+            source_path.as_path()
+        } else {
+            source_path.parent().unwrap_or(&source_path)
+        };
+        let mut writer = ZipWriter::new(fs::File::create(target_path)?);
+        let archive = resolve(target_path)?;
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        zip_recursive(&mut writer, root, &source_path, &archive, options)?;
+        writer.finish().map_err(zip_error)?;
+        Ok(target.clone())
+    });
+    io_branch!(PathResult, source, zipped, exists)
+}
+
+#[action(zh_cn = "解压", en = "Unzip")]
+#[description(zh_cn = "将 zip 解压到目录", en = "Extract a zip into a directory")]
+pub fn unzip_action(
+    #[name(zh_cn = "压缩包", en = "Archive")]
+    #[description(zh_cn = "要解压的 zip 文件", en = "The zip file to extract")]
+    archive: File,
+    #[name(zh_cn = "目标目录", en = "Target directory")]
+    #[description(zh_cn = "解压到的目录", en = "Directory to extract into")]
+    target: Directory,
+) -> PathResult {
+    let extracted = fs::File::open(&archive)
+        .and_then(|file| ZipArchive::new(file).map_err(zip_error))
+        // extract 会拒绝指向目标目录之外的条目
+        .and_then(|mut zip| zip.extract(&target).map_err(zip_error))
+        .map(|_| target.clone());
+    io_branch!(PathResult, archive, extracted)
+}
+
+#[action(zh_cn = "计算哈希", en = "Hash file")]
+#[description(zh_cn = "计算文件的摘要", en = "Compute the digest of a file")]
+pub fn hash_action(
+    #[name(zh_cn = "文件", en = "File")]
+    #[description(zh_cn = "要计算的文件", en = "File to hash")]
+    path: File,
+    #[name(zh_cn = "算法", en = "Algorithm")]
+    #[description(zh_cn = "默认为 SHA-256", en = "SHA-256 by default")]
+    algorithm: Option<Algorithm>,
+) -> ContentResult {
+    let hash = match algorithm {
+        Some(Algorithm::Sha512) => digest::<sha2::Sha512>(&path),
+        Some(Algorithm::Sha1) => digest::<sha1::Sha1>(&path),
+        Some(Algorithm::Md5) => digest::<md5::Md5>(&path),
+        _ => digest::<sha2::Sha256>(&path),
+    };
+    io_branch!(ContentResult, path, hash)
+}
+
+#[action(zh_cn = "确保目录存在", en = "Ensure directory")]
+#[description(
+    zh_cn = "目录不存在时创建，包括上级目录",
+    en = "Create the directory and its parents if missing"
+)]
+pub fn ensure_dir_action(
+    #[name(zh_cn = "目录", en = "Directory")]
+    #[description(zh_cn = "需要存在的目录", en = "Directory that should exist")]
+    path: Directory,
+) -> PathResult {
+    let dir = Path::new(&path);
+    // 同名文件已存在时无法创建目录
+    let ensured = if dir.is_file() {
+        Err(exists_error(dir))
+    } else {
+        fs::create_dir_all(dir).map(|_| path.clone())
+    };
+    io_branch!(PathResult, path, ensured, exists)
+}
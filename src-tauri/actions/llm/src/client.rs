@@ -0,0 +1,271 @@
+use std::time::Duration;
+
+use common::{
+    config::{AiConfig, MaxToken},
+    secret::resolve_secret,
+    ty::TokenUsage,
+};
+use reqwest::{Client, Response};
+use serde_json::{Value, json};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum LlmError {
+    #[error("Failed to send request: {0}")]
+    RequestError(String),
+    #[error("Request failed with status {0}: {1}")]
+    StatusError(u16, String),
+    #[error("Request timed out")]
+    TimeoutError,
+    #[error("Failed to parse response: {0}")]
+    ParseError(String),
+}
+
+impl From<reqwest::Error> for LlmError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            LlmError::TimeoutError
+        } else {
+            LlmError::RequestError(e.to_string())
+        }
+    }
+}
+
+pub struct ChatRequest {
+    /// 为空时使用配置中的模型
+    pub model: Option<String>,
+    pub system: Option<String>,
+    pub prompt: String,
+    pub json_mode: bool,
+    pub stream: bool,
+    pub timeout: Option<Duration>,
+}
+
+pub struct Completion {
+    pub text: String,
+    pub usage: Option<TokenUsage>,
+}
+
+fn request_body(config: &AiConfig, request: &ChatRequest) -> Value {
+    let mut messages = vec![];
+    if let Some(system) = request.system.as_deref().filter(|system| !system.is_empty()) {
+        messages.push(json!({ "role": "system", "content": system }));
+    }
+    messages.push(json!({ "role": "user", "content": request.prompt }));
+
+    let model = match request.model.as_deref() {
+        Some(model) if !model.is_empty() => model,
+        _ => config.model.as_str(),
+    };
+    let mut body = json!({
+        "model": model,
+        "messages": messages,
+        "temperature": config.temperature,
+        "top_p": config.top_p,
+        "frequency_penalty": config.frequency_penalty,
+        "stream": request.stream,
+    });
+    if let MaxToken::Value(max_tokens) = config.max_tokens {
+        body["max_tokens"] = json!(max_tokens);
+    }
+    if request.json_mode {
+        body["response_format"] = json!({ "type": "json_object" });
+    }
+    if request.stream {
+        // 流式响应只有在声明后才会在最后一个分块中附带用量
+        body["stream_options"] = json!({ "include_usage": true });
+    }
+    body
+}
+
+fn parse_usage(value: &Value) -> Option<TokenUsage> {
+    serde_json::from_value(value.get("usage")?.clone()).ok()
+}
+
+/// 调用 OpenAI 兼容的 chat completions 接口
+pub async fn complete(config: &AiConfig, request: &ChatRequest) -> Result<Completion, LlmError> {
+    let api_key =
+        resolve_secret(&config.api_key).map_err(|e| LlmError::RequestError(e.to_string()))?;
+    let mut builder = Client::builder();
+    if let Some(timeout) = request.timeout {
+        builder = builder.timeout(timeout);
+    }
+    let response = builder
+        .build()?
+        .post(format!(
+            "{}/chat/completions",
+            config.base_url.trim_end_matches('/')
+        ))
+        .bearer_auth(api_key)
+        .json(&request_body(config, request))
+        .send()
+        .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(LlmError::StatusError(
+            status.as_u16(),
+            response.text().await.unwrap_or_default(),
+        ));
+    }
+    if request.stream {
+        read_stream(response).await
+    } else {
+        let value: Value = response.json().await?;
+        let text = value["choices"][0]["message"]["content"]
+            .as_str()
+            .ok_or_else(|| LlmError::ParseError(value.to_string()))?;
+        Ok(Completion {
+            text: text.to_string(),
+            usage: parse_usage(&value),
+        })
+    }
+}
+
+// 逐行读取 SSE，拼接每个分块中的增量内容
+async fn read_stream(mut response: Response) -> Result<Completion, LlmError> {
+    let mut completion = Completion {
+        text: String::new(),
+        usage: None,
+    };
+    let mut buffer = String::new();
+    while let Some(chunk) = response.chunk().await? {
+        buffer.push_str(&String::from_utf8_lossy(&chunk));
+        while let Some(index) = buffer.find('\n') {
+            let line = buffer[..index].trim().to_string();
+            buffer.drain(..=index);
+            let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+                continue;
+            };
+            if data == "[DONE]" {
+                return Ok(completion);
+            }
+            let value: Value =
+                serde_json::from_str(data).map_err(|e| LlmError::ParseError(e.to_string()))?;
+            if let Some(content) = value["choices"][0]["delta"]["content"].as_str() {
+                completion.text.push_str(content);
+            }
+            if let Some(usage) = parse_usage(&value) {
+                completion.usage = Some(usage);
+            }
+        }
+    }
+    Ok(completion)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc::{Receiver, channel},
+        thread,
+    };
+
+    use super::*;
+
+    /// 只处理一次请求的模拟服务器，返回地址与收到的请求体
+    fn mock_server(status: &'static str, response: &'static str) -> (String, Receiver<Value>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}/v1", listener.local_addr().unwrap());
+        let (sender, receiver) = channel();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            sender.send(serde_json::from_slice(&body).unwrap()).unwrap();
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                response.len(),
+                response
+            )
+            .unwrap();
+        });
+        (address, receiver)
+    }
+
+    fn config(base_url: String) -> AiConfig {
+        AiConfig {
+            api_key: "test".to_string(),
+            model: "mock-model".to_string(),
+            base_url,
+            ..Default::default()
+        }
+    }
+
+    fn request(json_mode: bool, stream: bool) -> ChatRequest {
+        ChatRequest {
+            model: None,
+            system: Some("You are a test".to_string()),
+            prompt: "Hello".to_string(),
+            json_mode,
+            stream,
+            timeout: Some(Duration::from_secs(5)),
+        }
+    }
+
+    #[tokio::test]
+    async fn completes_in_json_mode() {
+        let (address, receiver) = mock_server(
+            "200 OK",
+            r#"{"choices":[{"message":{"content":"{\"ok\":true}"}}],"usage":{"prompt_tokens":3,"completion_tokens":2,"total_tokens":5}}"#,
+        );
+        let completion = complete(&config(address), &request(true, false))
+            .await
+            .unwrap();
+        assert_eq!(completion.text, r#"{"ok":true}"#);
+        assert_eq!(completion.usage.unwrap().total_tokens, 5);
+
+        let body = receiver.recv().unwrap();
+        assert_eq!(body["model"], "mock-model");
+        assert_eq!(body["messages"][0]["role"], "system");
+        assert_eq!(body["response_format"]["type"], "json_object");
+    }
+
+    #[tokio::test]
+    async fn reads_streamed_chunks() {
+        let (address, receiver) = mock_server(
+            "200 OK",
+            concat!(
+                "data: {\"choices\":[{\"delta\":{\"content\":\"Hel\"}}]}\n\n",
+                "data: {\"choices\":[{\"delta\":{\"content\":\"lo\"}}]}\n\n",
+                "data: {\"choices\":[],\"usage\":{\"prompt_tokens\":4,\"completion_tokens\":2,\"total_tokens\":6}}\n\n",
+                "data: [DONE]\n\n",
+            ),
+        );
+        let completion = complete(&config(address), &request(false, true))
+            .await
+            .unwrap();
+        assert_eq!(completion.text, "Hello");
+        assert_eq!(completion.usage.unwrap().prompt_tokens, 4);
+
+        let body = receiver.recv().unwrap();
+        assert_eq!(body["stream"], true);
+        assert_eq!(body["stream_options"]["include_usage"], true);
+    }
+
+    #[tokio::test]
+    async fn reports_error_status() {
+        let (address, _receiver) =
+            mock_server("429 Too Many Requests", r#"{"error":"rate limited"}"#);
+        let error = complete(&config(address), &request(false, false))
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(error, LlmError::StatusError(429, _)));
+    }
+}
@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use aster_macro::action;
+use client::{ChatRequest, LlmError, complete};
+use common::{
+    config::AiConfig,
+    ty::{Context, Data, USAGE_KEY},
+    utils::lookup,
+};
+use serde_json::{Map, Value, json};
+
+pub mod client;
+
+pub type Text = String;
+
+/// 声明返回分支
+/// ```
+///          Timeout
+///    --------+-------
+/// -> +      llm      +-- Success
+///    ---+--------+---
+///    Invalid   Failed
+/// ```
+#[result]
+enum Result {
+    #[right(zh_cn = "成功", en = "Success")]
+    Success(plug!(Data as nesting! { text: String, json: Value })),
+    /// JSON 模式下模型的输出不是合法的 JSON
+    #[bottom(zh_cn = "无效 JSON", en = "Invalid JSON")]
+    Invalid(plug!(String as String)),
+    /// 接口返回了错误状态，如密钥无效或超出限额
+    #[bottom(zh_cn = "请求失败", en = "Failed")]
+    Failed(plug!(Value as nesting! { status: i64, body: String })),
+    #[top(zh_cn = "超时", en = "Timeout")]
+    Timeout,
+}
+
+/// 将 `{{ 节点id.路径 }}` 替换为上游输出中的值，不存在的路径输出为空
+fn render(template: &str, context: &Value) -> String {
+    let mut output = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+        match lookup(context, rest[start + 2..start + end].trim()) {
+            Some(Value::String(text)) => output.push_str(text),
+            Some(Value::Null) | None => {}
+            Some(value) => output.push_str(&value.to_string()),
+        }
+        rest = &rest[start + end + 2..];
+    }
+    output.push_str(rest);
+    output
+}
+
+// 部分兼容接口会用 Markdown 代码块包裹 JSON
+fn strip_code_fence(text: &str) -> &str {
+    let text = text.trim();
+    text.strip_prefix("```json")
+        .or_else(|| text.strip_prefix("```"))
+        .and_then(|text| text.strip_suffix("```"))
+        .map(str::trim)
+        .unwrap_or(text)
+}
+
+#[action(zh_cn = "AI 对话", en = "AI chat")]
+#[entry(prompt, system, option { json_mode, stream, model, timeout })]
+#[description(
+    zh_cn = "使用设置中的 AI 配置调用 OpenAI 兼容的接口",
+    en = "Call an OpenAI-compatible endpoint with the AI config from settings"
+)]
+pub async fn llm_action(
+    #[name(zh_cn = "提示词", en = "Prompt")]
+    #[description(
+        zh_cn = "可使用 {{ 节点id.路径 }} 引用上游输出",
+        en = "Reference upstream outputs with {{ node_id.path }}"
+    )]
+    prompt: Text,
+    #[name(zh_cn = "系统提示词", en = "System prompt")]
+    #[description(
+        zh_cn = "设定模型的角色与规则，同样支持引用上游输出",
+        en = "Sets the role and rules of the model, also supports upstream references"
+    )]
+    system: Option<Text>,
+    #[name(zh_cn = "JSON 模式", en = "JSON mode")]
+    #[description(
+        zh_cn = "要求模型输出 JSON，并解析到 json 中",
+        en = "Ask the model for JSON and parse it into json"
+    )]
+    json_mode: Option<bool>,
+    #[name(zh_cn = "流式传输", en = "Stream")]
+    #[description(
+        zh_cn = "以流式接收输出，适合较长的回复",
+        en = "Receive the output as a stream, suitable for long replies"
+    )]
+    stream: Option<bool>,
+    #[name(zh_cn = "模型", en = "Model")]
+    #[description(zh_cn = "留空时使用设置中的模型", en = "The model from settings when empty")]
+    model: Option<String>,
+    #[name(zh_cn = "超时时间", en = "Timeout")]
+    #[description(zh_cn = "请求超时时间（毫秒）", en = "Request timeout (ms)")]
+    timeout: Option<u64>,
+    context: Context,
+) -> Result {
+    let context = Value::Object(
+        context
+            .into_iter()
+            .map(|(id, data)| (id, data.value()))
+            .collect(),
+    );
+    let request = ChatRequest {
+        model,
+        system: system.map(|system| render(&system, &context)),
+        prompt: render(&prompt, &context),
+        json_mode: json_mode.unwrap_or(false),
+        stream: stream.unwrap_or(false),
+        timeout: timeout.map(Duration::from_millis),
+    };
+
+    let completion = match complete(&AiConfig::load(), &request).await {
+        Ok(completion) => completion,
+        Err(LlmError::TimeoutError) => return Result::Timeout,
+        Err(LlmError::StatusError(status, body)) => {
+            return Result::Failed(json!({ "status": status, "body": body }));
+        }
+        Err(e) => return Err(Box::new(e)),
+    };
+    let json = if request.json_mode {
+        match serde_json::from_str(strip_code_fence(&completion.text)) {
+            Ok(json) => json,
+            Err(e) => return Result::Invalid(format!("{}: {}", e, completion.text)),
+        }
+    } else {
+        Value::Null
+    };
+
+    let mut output = Map::new();
+    output.insert("text".to_string(), Value::String(completion.text));
+    output.insert("json".to_string(), json);
+    // 用量由运行时取出并记录到运行历史中
+    if let Some(usage) = completion.usage {
+        output.insert(USAGE_KEY.to_string(), serde_json::to_value(usage)?);
+    }
+    Result::Success(Data::Json(output))
+}
@@ -0,0 +1,406 @@
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+use common::secret::resolve_secret;
+use imap::Session;
+use mail_parser::{Address, MessageParser, MimeHeaders};
+use native_tls::TlsConnector;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ImapError {
+    #[error("Failed to connect to {0}: {1}")]
+    ConnectError(String, String),
+    #[error("Failed to login: {0}")]
+    LoginError(String),
+    #[error("Failed to read mailbox {0}: {1}")]
+    MailboxError(String, String),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Security {
+    Tls,
+    StartTls,
+    /// 仅用于本地测试服务器
+    Plain,
+}
+
+/// imap_trigger 的参数
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImapConfig {
+    pub host: String,
+    pub port: Option<u16>,
+    pub security: Option<Security>,
+    /// 用户名与密码均可使用 env: 或 secret: 引用密钥
+    pub username: String,
+    pub password: String,
+    #[serde(default = "default_folder")]
+    pub folder: String,
+    /// 只匹配发件人包含该值的邮件
+    pub from: Option<String>,
+    /// 只匹配主题包含该值的邮件
+    pub subject: Option<String>,
+    #[serde(default)]
+    pub unseen_only: bool,
+    /// 为 false 时使用 BODY.PEEK 读取，不改变邮件的已读状态
+    #[serde(default)]
+    pub mark_seen: bool,
+    /// 轮询间隔（毫秒）
+    #[serde(default = "default_interval")]
+    pub interval: u64,
+}
+
+fn default_folder() -> String {
+    "INBOX".to_string()
+}
+
+fn default_interval() -> u64 {
+    60_000
+}
+
+fn login_error<T>((e, _): (imap::Error, T)) -> ImapError {
+    ImapError::LoginError(e.to_string())
+}
+
+/// 已经报告过的位置，UID 只在相同的 UIDVALIDITY 下有意义
+#[derive(Debug, Clone, Copy)]
+struct Position {
+    uid_validity: Option<u32>,
+    uid: u32,
+}
+
+/// 轮询邮箱，只报告开始监听之后到达的邮件
+pub struct ImapWatcher {
+    config: ImapConfig,
+    last: Option<Position>,
+}
+
+impl ImapWatcher {
+    pub fn new(config: ImapConfig) -> Self {
+        Self { config, last: None }
+    }
+    pub fn interval(&self) -> Duration {
+        // 避免过于频繁地访问邮件服务器
+        Duration::from_millis(self.config.interval.max(5_000))
+    }
+    /// 拉取新邮件，第一次调用只记录邮箱当前的位置
+    pub fn poll(&mut self) -> Result<Vec<Value>, ImapError> {
+        let config = &self.config;
+        let host = config.host.as_str();
+        let connect_error = |e: &dyn std::fmt::Display| {
+            ImapError::ConnectError(host.to_string(), e.to_string())
+        };
+        let username = resolve_secret(&config.username)
+            .map_err(|e| ImapError::LoginError(e.to_string()))?;
+        let password = resolve_secret(&config.password)
+            .map_err(|e| ImapError::LoginError(e.to_string()))?;
+
+        match config.security.clone() {
+            Some(Security::Plain) => {
+                let stream = TcpStream::connect((host, config.port.unwrap_or(143)))
+                    .map_err(|e| connect_error(&e))?;
+                let mut client = imap::Client::new(stream);
+                client.read_greeting().map_err(|e| connect_error(&e))?;
+                let session = client.login(username, password).map_err(login_error)?;
+                self.poll_session(session)
+            }
+            Some(Security::StartTls) => {
+                let tls = TlsConnector::new().map_err(|e| connect_error(&e))?;
+                let client =
+                    imap::connect_starttls((host, config.port.unwrap_or(143)), host, &tls)
+                        .map_err(|e| connect_error(&e))?;
+                let session = client.login(username, password).map_err(login_error)?;
+                self.poll_session(session)
+            }
+            _ => {
+                let tls = TlsConnector::new().map_err(|e| connect_error(&e))?;
+                let client = imap::connect((host, config.port.unwrap_or(993)), host, &tls)
+                    .map_err(|e| connect_error(&e))?;
+                let session = client.login(username, password).map_err(login_error)?;
+                self.poll_session(session)
+            }
+        }
+    }
+    fn poll_session<T: Read + Write>(
+        &mut self,
+        mut session: Session<T>,
+    ) -> Result<Vec<Value>, ImapError> {
+        let result = self.fetch_new(&mut session);
+        let _ = session.logout();
+        result
+    }
+    fn fetch_new<T: Read + Write>(
+        &mut self,
+        session: &mut Session<T>,
+    ) -> Result<Vec<Value>, ImapError> {
+        let folder = self.config.folder.clone();
+        let mailbox_error =
+            |e: imap::Error| ImapError::MailboxError(folder.clone(), e.to_string());
+        let uid_validity = session.select(&folder).map_err(mailbox_error)?.uid_validity;
+
+        // 邮箱被重建后 UIDVALIDITY 改变，旧的 UID 会被复用，与第一次调用一样重新记录位置
+        let last_uid = match self.last {
+            Some(last) if last.uid_validity == uid_validity => last.uid,
+            _ => {
+                let uids = session.uid_search("ALL").map_err(mailbox_error)?;
+                self.last = Some(Position {
+                    uid_validity,
+                    uid: uids.into_iter().max().unwrap_or(0),
+                });
+                return Ok(vec![]);
+            }
+        };
+        // UID n:* 至少会返回最后一封邮件，需要再次过滤
+        let mut uids = session
+            .uid_search(search_query(&self.config, last_uid))
+            .map_err(mailbox_error)?
+            .into_iter()
+            .filter(|uid| *uid > last_uid)
+            .collect::<Vec<_>>();
+        if uids.is_empty() {
+            return Ok(vec![]);
+        }
+        uids.sort();
+
+        let set = uids
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        let query = if self.config.mark_seen {
+            "RFC822"
+        } else {
+            "BODY.PEEK[]"
+        };
+        let fetches = session.uid_fetch(set, query).map_err(mailbox_error)?;
+        let mut messages = fetches
+            .iter()
+            .filter_map(|fetch| parse_message(fetch.uid?, fetch.body()?))
+            .collect::<Vec<_>>();
+        messages.sort_by_key(|message| message["uid"].as_u64());
+        self.last = uids.last().map(|&uid| Position { uid_validity, uid });
+        Ok(messages)
+    }
+}
+
+// 按 IMAP 的规则为字符串加上引号
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn search_query(config: &ImapConfig, last_uid: u32) -> String {
+    let mut query = vec![format!("UID {}:*", last_uid + 1)];
+    if config.unseen_only {
+        query.push("UNSEEN".to_string());
+    }
+    if let Some(from) = config.from.as_deref().filter(|from| !from.is_empty()) {
+        query.push(format!("FROM {}", quote(from)));
+    }
+    if let Some(subject) = config.subject.as_deref().filter(|subject| !subject.is_empty()) {
+        query.push(format!("SUBJECT {}", quote(subject)));
+    }
+    query.join(" ")
+}
+
+fn addresses(address: Option<&Address>) -> Vec<String> {
+    let Some(address) = address else {
+        return vec![];
+    };
+    address
+        .iter()
+        .map(|addr| match (addr.name(), addr.address()) {
+            (Some(name), Some(address)) => format!("{} <{}>", name, address),
+            (name, address) => name.or(address).unwrap_or_default().to_string(),
+        })
+        .collect()
+}
+
+/// 将原始邮件解析为放入工作流 context 的数据
+pub fn parse_message(uid: u32, raw: &[u8]) -> Option<Value> {
+    let message = MessageParser::default().parse(raw)?;
+    Some(json!({
+        "uid": uid,
+        "message_id": message.message_id(),
+        "from": addresses(message.from()),
+        "to": addresses(message.to()),
+        "cc": addresses(message.cc()),
+        "subject": message.subject(),
+        "date": message.date().map(|date| date.to_rfc3339()),
+        "text": message.body_text(0),
+        "html": message.body_html(0),
+        "attachments": message
+            .attachments()
+            .filter_map(|part| part.attachment_name())
+            .collect::<Vec<_>>(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::*;
+
+    const MESSAGE: &str = concat!(
+        "From: Daisy <daisy@example.com>\r\n",
+        "To: ops@example.com\r\n",
+        "Subject: Disk usage\r\n",
+        "\r\n",
+        "Disk is 91% full\r\n",
+    );
+
+    // 模拟服务器对一条命令的未标记响应，邮箱中为 uids 中的邮件
+    fn untagged(uid_validity: u32, uids: &[u32], command: &str) -> String {
+        let mut response = String::new();
+        if command.starts_with("SELECT") {
+            response += &format!("* {} EXISTS\r\n", uids.len());
+            response += &format!("* OK [UIDVALIDITY {}] UIDs valid\r\n", uid_validity);
+        } else if let Some(query) = command.strip_prefix("UID SEARCH ") {
+            let from = query
+                .strip_prefix("UID ")
+                .and_then(|range| range.split(':').next()?.parse().ok())
+                .unwrap_or(0);
+            // 与真实服务器一样，UID n:* 至少返回最后一封邮件
+            let mut found = uids.iter().filter(|uid| **uid >= from).collect::<Vec<_>>();
+            if found.is_empty() {
+                found.extend(uids.last());
+            }
+            response += "* SEARCH";
+            for uid in found {
+                response += &format!(" {}", uid);
+            }
+            response += "\r\n";
+        } else if let Some(query) = command.strip_prefix("UID FETCH ") {
+            let set = query.split(' ').next().unwrap_or_default();
+            for uid in set.split(',') {
+                let seq = uids.iter().position(|id| id.to_string() == uid).unwrap() + 1;
+                response += &format!(
+                    "* {} FETCH (UID {} BODY[] {{{}}}\r\n{})\r\n",
+                    seq,
+                    uid,
+                    MESSAGE.len(),
+                    MESSAGE
+                );
+            }
+        } else if command == "LOGOUT" {
+            response += "* BYE Logging out\r\n";
+        }
+        response
+    }
+
+    /// 模拟 IMAP 服务器，第 n 次连接时邮箱的 UIDVALIDITY 与邮件 UID 为 mailboxes[n]，返回端口
+    fn mock_server(mailboxes: Vec<(u32, Vec<u32>)>) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            for (uid_validity, uids) in mailboxes {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                stream.write_all(b"* OK IMAP4rev1 ready\r\n").unwrap();
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap_or(0) > 0 {
+                    let (tag, command) = line.trim_end().split_once(' ').unwrap();
+                    let command = command.to_ascii_uppercase();
+                    let response = untagged(uid_validity, &uids, &command);
+                    write!(stream, "{}{} OK completed\r\n", response, tag).unwrap();
+                    if command == "LOGOUT" {
+                        break;
+                    }
+                    line.clear();
+                }
+            }
+        });
+        port
+    }
+
+    fn config() -> ImapConfig {
+        serde_json::from_value(json!({
+            "host": "127.0.0.1",
+            "security": "plain",
+            "username": "daisy",
+            "password": "daisy",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn builds_search_query() {
+        let mut config = config();
+        assert_eq!(search_query(&config, 41), "UID 42:*");
+
+        config.unseen_only = true;
+        config.from = Some("alerts@example.com".to_string());
+        config.subject = Some("say \"hi\"".to_string());
+        assert_eq!(
+            search_query(&config, 0),
+            r#"UID 1:* UNSEEN FROM "alerts@example.com" SUBJECT "say \"hi\"""#
+        );
+    }
+
+    #[test]
+    fn parses_message() {
+        let raw = concat!(
+            "From: Daisy <daisy@example.com>\r\n",
+            "To: ops@example.com\r\n",
+            "Subject: Disk usage\r\n",
+            "Message-ID: <1@example.com>\r\n",
+            "Date: Mon, 19 Oct 2026 08:00:00 +0000\r\n",
+            "\r\n",
+            "Disk is 91% full\r\n",
+        );
+        let message = parse_message(7, raw.as_bytes()).unwrap();
+        assert_eq!(message["uid"], 7);
+        assert_eq!(message["from"], json!(["Daisy <daisy@example.com>"]));
+        assert_eq!(message["to"], json!(["ops@example.com"]));
+        assert_eq!(message["subject"], "Disk usage");
+        assert_eq!(message["message_id"], "1@example.com");
+        assert!(message["date"].as_str().unwrap().starts_with("2026-10-19T08:00:00"));
+        assert_eq!(message["text"].as_str().unwrap().trim(), "Disk is 91% full");
+    }
+
+    #[test]
+    fn polls_new_messages() {
+        let port = mock_server(vec![(1, vec![1]), (1, vec![1, 2]), (1, vec![1, 2])]);
+        let mut config = config();
+        config.port = Some(port);
+        let mut watcher = ImapWatcher::new(config);
+
+        // 第一次只记录邮箱当前的位置
+        assert!(watcher.poll().unwrap().is_empty());
+
+        let messages = watcher.poll().unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["uid"], 2);
+        assert_eq!(messages[0]["subject"], "Disk usage");
+
+        // 没有新邮件时不会重复报告最后一封
+        assert!(watcher.poll().unwrap().is_empty());
+    }
+
+    #[test]
+    fn resets_when_uid_validity_changes() {
+        // 邮箱重建后 UID 从 1 重新开始
+        let port = mock_server(vec![(1, vec![1, 2, 3]), (2, vec![1, 2]), (2, vec![1, 2, 3])]);
+        let mut config = config();
+        config.port = Some(port);
+        let mut watcher = ImapWatcher::new(config);
+
+        assert!(watcher.poll().unwrap().is_empty());
+        // 重新记录位置，不会因为旧的 UID 3 跳过之后的邮件
+        assert!(watcher.poll().unwrap().is_empty());
+
+        let messages = watcher.poll().unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["uid"], 3);
+    }
+}
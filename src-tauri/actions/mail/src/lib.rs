@@ -0,0 +1,204 @@
+use std::path::Path;
+
+use aster_macro::action;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use common::{action::error::ActionError, secret::resolve_secret, tokio::fs::read};
+use lettre::{
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+    message::{Attachment, Mailbox, MultiPart, SinglePart, header::ContentType},
+    transport::smtp::authentication::Credentials,
+};
+use serde_json::{Value, json};
+
+pub mod imap;
+
+pub type Text = String;
+
+/// 发送邮件的结果
+/// ```
+///    ------------
+/// -> + send_mail +-- Success
+///    -----+------
+///         | Rejected
+/// ```
+#[result]
+enum Result {
+    #[right(zh_cn = "成功", en = "Success")]
+    Success(plug!(Value as nesting! { code: i64, message: String })),
+    /// 服务器拒绝了发件人、收件人或邮件内容
+    #[bottom(zh_cn = "被拒绝", en = "Rejected")]
+    Rejected(plug!(String as String)),
+}
+
+#[options]
+pub enum Security {
+    #[label(zh_cn = "SSL/TLS", en = "SSL/TLS")]
+    Tls,
+    StartTls,
+    #[label(zh_cn = "不加密", en = "None")]
+    Plain,
+}
+
+#[options]
+pub enum BodyFormat {
+    #[label(zh_cn = "纯文本", en = "Text")]
+    Text,
+    Html,
+}
+
+fn secret(value: &str) -> std::result::Result<String, ActionError> {
+    resolve_secret(value).map_err(|e| ActionError::RunActionCardError(e.to_string()))
+}
+
+// 收件人可用逗号、分号或换行分隔
+fn mailboxes(text: &str) -> std::result::Result<Vec<Mailbox>, lettre::address::AddressError> {
+    text.split([',', ';', '\n'])
+        .map(str::trim)
+        .filter(|mailbox| !mailbox.is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+async fn attachment(line: &str) -> std::result::Result<SinglePart, Box<dyn std::error::Error>> {
+    let content_type = ContentType::parse("application/octet-stream")?;
+    // name=base64:... 直接附加 Base64 内容，其余视为文件路径
+    if let Some((name, content)) = line.split_once("=base64:") {
+        let content = STANDARD.decode(content.trim())?;
+        return Ok(Attachment::new(name.trim().to_string()).body(content, content_type));
+    }
+    let name = Path::new(line)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    Ok(Attachment::new(name).body(read(line).await?, content_type))
+}
+
+#[action(zh_cn = "发送邮件", en = "Send mail")]
+#[entry(
+    to,
+    subject,
+    body,
+    option { cc, bcc, format, attachments },
+    server { host, port, security, username, password, from }
+)]
+#[description(zh_cn = "通过 SMTP 发送邮件", en = "Send an email over SMTP")]
+pub async fn send_mail_action(
+    #[name(zh_cn = "SMTP 服务器", en = "SMTP server")]
+    #[description(zh_cn = "如 smtp.example.com", en = "Such as smtp.example.com")]
+    host: String,
+    #[name(zh_cn = "端口", en = "Port")]
+    #[description(
+        zh_cn = "默认为 465、587 或 25，取决于加密方式",
+        en = "465, 587 or 25 by default, depending on the security"
+    )]
+    #[validate(min = 1, max = 65535)]
+    port: Option<u16>,
+    #[name(zh_cn = "加密方式", en = "Security")]
+    #[description(zh_cn = "默认为 SSL/TLS", en = "SSL/TLS by default")]
+    security: Option<Security>,
+    #[name(zh_cn = "用户名", en = "Username")]
+    #[description(
+        zh_cn = "留空时不进行认证，可使用 env: 或 secret: 引用密钥",
+        en = "No authentication when empty, may reference env: or secret: secrets"
+    )]
+    username: Option<String>,
+    #[name(zh_cn = "密码", en = "Password")]
+    #[description(
+        zh_cn = "可使用 env:NAME 或 secret:NAME 引用密钥",
+        en = "Reference secrets with env:NAME or secret:NAME"
+    )]
+    password: Option<String>,
+    #[name(zh_cn = "发件人", en = "From")]
+    #[description(
+        zh_cn = "如 Daisy <daisy@example.com>",
+        en = "Such as Daisy <daisy@example.com>"
+    )]
+    from: String,
+    #[name(zh_cn = "收件人", en = "To")]
+    #[description(
+        zh_cn = "多个收件人以逗号分隔",
+        en = "Separate multiple recipients with commas"
+    )]
+    to: String,
+    #[name(zh_cn = "抄送", en = "Cc")]
+    #[description(
+        zh_cn = "多个收件人以逗号分隔",
+        en = "Separate multiple recipients with commas"
+    )]
+    cc: Option<String>,
+    #[name(zh_cn = "密送", en = "Bcc")]
+    #[description(
+        zh_cn = "多个收件人以逗号分隔",
+        en = "Separate multiple recipients with commas"
+    )]
+    bcc: Option<String>,
+    #[name(zh_cn = "主题", en = "Subject")]
+    #[description(zh_cn = "邮件的主题", en = "Subject of the email")]
+    subject: String,
+    #[name(zh_cn = "正文", en = "Body")]
+    #[description(zh_cn = "邮件的正文", en = "Body of the email")]
+    body: Text,
+    #[name(zh_cn = "正文格式", en = "Body format")]
+    #[description(zh_cn = "默认为纯文本", en = "Plain text by default")]
+    format: Option<BodyFormat>,
+    #[name(zh_cn = "附件", en = "Attachments")]
+    #[description(
+        zh_cn = "每行一个文件路径，或 name=base64:内容",
+        en = "One file path per line, or name=base64:content"
+    )]
+    attachments: Option<Text>,
+) -> Result {
+    let mut builder = Message::builder().from(from.parse()?).subject(subject);
+    for mailbox in mailboxes(&to)? {
+        builder = builder.to(mailbox);
+    }
+    for mailbox in mailboxes(&cc.unwrap_or_default())? {
+        builder = builder.cc(mailbox);
+    }
+    for mailbox in mailboxes(&bcc.unwrap_or_default())? {
+        builder = builder.bcc(mailbox);
+    }
+
+    let part = match format {
+        Some(BodyFormat::Html) => SinglePart::html(body),
+        _ => SinglePart::plain(body),
+    };
+    let attachments = attachments.unwrap_or_default();
+    let lines = attachments
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+    let message = if lines.is_empty() {
+        builder.singlepart(part)?
+    } else {
+        let mut multipart = MultiPart::mixed().singlepart(part);
+        for line in lines {
+            multipart = multipart.singlepart(attachment(line).await?);
+        }
+        builder.multipart(multipart)?
+    };
+
+    let mut transport = match security {
+        Some(Security::StartTls) => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&host)?,
+        // 仅用于本地测试服务器或内网中继
+        Some(Security::Plain) => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&host),
+        _ => AsyncSmtpTransport::<Tokio1Executor>::relay(&host)?,
+    };
+    if let Some(port) = port {
+        transport = transport.port(port);
+    }
+    if let Some(username) = username.filter(|username| !username.is_empty()) {
+        let password = secret(&password.unwrap_or_default())?;
+        transport = transport.credentials(Credentials::new(secret(&username)?, password));
+    }
+
+    match transport.build().send(message).await {
+        Ok(response) => Result::Success(json!({
+            "code": response.code().to_string().parse::<i64>().unwrap_or_default(),
+            "message": response.message().collect::<Vec<_>>().join("\n"),
+        })),
+        Err(e) if e.is_permanent() || e.is_transient() => Result::Rejected(e.to_string()),
+        Err(e) => Err(Box::new(e)),
+    }
+}
@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+use aster_macro::action;
+use aster_runtime::{Limits, capability::Capabilities, error::RuntimeError, get_runtime};
+use common::ty::{Context, Data};
+
+pub type Code = String;
+pub type Text = String;
+
+/// ```
+///      Timeout  Denied
+///    ------+-------+-----
+/// -> +     program      +-- Success
+///    ---------+----------
+///             | Error
+/// ```
+#[result]
+enum Result {
+    #[right(zh_cn = "成功", en = "Success")]
+    Success(plug!(Data as Data)),
+    #[bottom(zh_cn = "失败", en = "Error")]
+    Error(plug!(String as String)),
+    #[top(zh_cn = "超时", en = "Timeout")]
+    Timeout,
+    #[top(zh_cn = "权限不足", en = "Denied")]
+    Denied(plug!(String as String)),
+}
+
+#[options]
+pub enum Lang {
+    JavaScript,
+    Lua,
+    Python,
+    PowerShell,
+    Bash,
+}
+
+#[action(zh_cn = "执行代码", en = "Execute Code")]
+#[description(zh_cn = "执行一段代码", en = "Execute a code segment")]
+pub fn program_action(
+    #[name(zh_cn = "代码内容", en = "Code content")]
+    #[description(
+        zh_cn = "要执行的代码，上游输出可通过 context 读取，返回值或输出作为结果",
+        en = "Code to execute, read upstream outputs from context, the returned value or output is the result"
+    )]
+    code: Code,
+    #[name(zh_cn = "语言", en = "Language")]
+    #[description(zh_cn = "使用的编程语言", en = "Programming language used")]
+    lang: Lang,
+    #[name(zh_cn = "超时时间", en = "Timeout")]
+    #[description(zh_cn = "脚本最长执行时间（毫秒）", en = "Maximum execution time (ms)")]
+    timeout: Option<u64>,
+    #[name(zh_cn = "内存上限", en = "Memory limit")]
+    #[description(zh_cn = "脚本可使用的内存（MB）", en = "Memory available to the script (MB)")]
+    memory: Option<u64>,
+    #[name(zh_cn = "权限清单", en = "Capabilities")]
+    #[description(
+        zh_cn = "JSON 格式，可声明 fs、net、process、env，未声明的访问一律拒绝；Python、PowerShell 与 Bash 无法限制 fs 与 net，只能声明 process 与 env",
+        en = "JSON declaring fs, net, process and env, any undeclared access is denied; Python, PowerShell and Bash cannot restrict fs and net, so only process and env may be declared"
+    )]
+    capabilities: Option<Text>,
+    context: Context,
+) -> Result {
+    let capabilities: Capabilities = match capabilities {
+        Some(manifest) if !manifest.trim().is_empty() => serde_json::from_str(&manifest)?,
+        _ => Capabilities::default(),
+    };
+    let runtime = get_runtime(&lang.to_string())?;
+    let mut limits = Limits::default();
+    if let Some(timeout) = timeout {
+        limits.timeout = Duration::from_millis(timeout);
+    }
+    if let Some(memory) = memory {
+        limits.memory = memory as usize * 1024 * 1024;
+    }
+    match runtime.execute(&code, &context, &limits, &capabilities) {
+        Ok(data) => Result::Success(data),
+        Err(RuntimeError::TimeoutError(_)) => Result::Timeout,
+        Err(RuntimeError::PermissionDeniedError(message)) => Result::Denied(message),
+        Err(e @ RuntimeError::UnenforceableCapabilityError(..)) => Result::Denied(e.to_string()),
+        Err(e) => Result::Error(e.to_string()),
+    }
+}
@@ -0,0 +1,746 @@
+use std::fmt::Write;
+
+use aster_macro::action;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use common::utils::lookup;
+use csv::{ReaderBuilder, WriterBuilder};
+use regex::{Regex, RegexBuilder};
+use serde_json::{Map, Value, json};
+use serde_json_path::JsonPath;
+
+pub type Text = String;
+
+/// 查询的结果
+/// ```
+///    ---------
+/// -> + query +-- Found
+///    --+---+--
+///      |   | Invalid
+///    Empty
+/// ```
+#[result]
+enum QueryResult {
+    #[right(zh_cn = "找到", en = "Found")]
+    Found(plug!(Value as Value)),
+    #[bottom(zh_cn = "无结果", en = "Empty")]
+    Empty,
+    /// 输入不是合法的 JSON
+    #[bottom(zh_cn = "无效输入", en = "Invalid")]
+    Invalid(plug!(String as String)),
+}
+
+#[result]
+enum MatchResult {
+    #[right(zh_cn = "匹配", en = "Matched")]
+    Matched(plug!(Value as nesting! { text: String, groups: Value, named: Value })),
+    #[bottom(zh_cn = "不匹配", en = "No match")]
+    NoMatch,
+}
+
+#[result]
+enum ExtractResult {
+    #[right(zh_cn = "匹配", en = "Matched")]
+    Matched(plug!(Value as nesting! { matches: Value, count: i64 })),
+    #[bottom(zh_cn = "不匹配", en = "No match")]
+    NoMatch,
+}
+
+#[result]
+enum ReplaceResult {
+    #[right(zh_cn = "成功", en = "Success")]
+    Success(plug!(Value as nesting! { text: String, count: i64 })),
+}
+
+#[result]
+enum TextResult {
+    #[right(zh_cn = "成功", en = "Success")]
+    Success(plug!(String as String)),
+    #[bottom(zh_cn = "无效输入", en = "Invalid")]
+    Invalid(plug!(String as String)),
+}
+
+#[result]
+enum TableResult {
+    #[right(zh_cn = "成功", en = "Success")]
+    Success(plug!(Value as nesting! { rows: Value, count: i64 })),
+    #[bottom(zh_cn = "无效输入", en = "Invalid")]
+    Invalid(plug!(String as String)),
+}
+
+#[result]
+enum ValueResult {
+    #[right(zh_cn = "成功", en = "Success")]
+    Success(plug!(Value as Value)),
+    #[bottom(zh_cn = "无效输入", en = "Invalid")]
+    Invalid(plug!(String as String)),
+}
+
+#[result]
+enum DateResult {
+    #[right(zh_cn = "成功", en = "Success")]
+    Success(plug!(Value as nesting! { text: String, timestamp: i64, iso: String })),
+    #[bottom(zh_cn = "无效日期", en = "Invalid")]
+    Invalid(plug!(String as String)),
+}
+
+#[options]
+pub enum Syntax {
+    JsonPath,
+    JmesPath,
+}
+
+#[options]
+pub enum Timezone {
+    #[label(zh_cn = "本地", en = "Local")]
+    Local,
+    Utc,
+}
+
+// 输入不是合法 JSON 时进入 Invalid 分支
+macro_rules! parse_json {
+    ($result:ident, $text:expr) => {
+        match serde_json::from_str::<Value>(&$text) {
+            Ok(value) => value,
+            Err(e) => return $result::Invalid(e.to_string()),
+        }
+    };
+}
+
+// 按路径写入值，途经的对象不存在时自动创建
+fn insert_path(value: &mut Value, path: &str, new_value: Value) {
+    let mut current = value;
+    for segment in path.split('.').filter(|segment| !segment.is_empty()) {
+        if !current.is_object() {
+            *current = Value::Object(Map::new());
+        }
+        current = current
+            .as_object_mut()
+            .unwrap()
+            .entry(segment)
+            .or_insert(Value::Null);
+    }
+    *current = new_value;
+}
+
+fn remove_path(value: &mut Value, path: &str) -> Option<Value> {
+    let (parent, key) = match path.rsplit_once('.') {
+        Some((parent, key)) => (
+            parent.split('.').try_fold(value, |value, segment| match value {
+                Value::Object(map) => map.get_mut(segment),
+                Value::Array(list) => segment
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|i| list.get_mut(i)),
+                _ => None,
+            })?,
+            key,
+        ),
+        None => (value, path),
+    };
+    match parent {
+        Value::Object(map) => map.remove(key),
+        _ => None,
+    }
+}
+
+fn merge(base: &mut Value, other: Value, deep: bool) {
+    match (base, other) {
+        (Value::Object(base), Value::Object(other)) => {
+            for (key, value) in other {
+                match base.get_mut(&key) {
+                    Some(exist) if deep => merge(exist, value, deep),
+                    _ => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        // 非对象之间的合并，以后者为准
+        (base, other) => *base = other,
+    }
+}
+
+// 字符串原样输出，其余值输出为 JSON
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+fn lines(text: &str) -> impl Iterator<Item = &str> {
+    text.lines().map(str::trim).filter(|line| !line.is_empty())
+}
+
+// 开启 arbitrary_precision 后数字会序列化成特殊结构，jmespath 无法识别，这里改为按原生数字序列化
+struct JmesInput<'a>(&'a Value);
+
+impl serde::Serialize for JmesInput<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::{SerializeMap, SerializeSeq};
+        match self.0 {
+            Value::Number(n) => {
+                if let Some(n) = n.as_u64() {
+                    serializer.serialize_u64(n)
+                } else if let Some(n) = n.as_i64() {
+                    serializer.serialize_i64(n)
+                } else {
+                    serializer.serialize_f64(n.as_f64().unwrap_or(f64::NAN))
+                }
+            }
+            Value::Array(values) => {
+                let mut seq = serializer.serialize_seq(Some(values.len()))?;
+                for value in values {
+                    seq.serialize_element(&JmesInput(value))?;
+                }
+                seq.end()
+            }
+            Value::Object(values) => {
+                let mut map = serializer.serialize_map(Some(values.len()))?;
+                for (key, value) in values {
+                    map.serialize_entry(key, &JmesInput(value))?;
+                }
+                map.end()
+            }
+            value => value.serialize(serializer),
+        }
+    }
+}
+
+fn build_regex(pattern: &str, ignore_case: Option<bool>) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(ignore_case.unwrap_or(false))
+        .build()
+}
+
+// 支持 \t 等转义写法，默认使用逗号，其余只能是单个 ASCII 字符
+fn parse_delimiter(delimiter: &Option<String>) -> Result<u8, String> {
+    match delimiter.as_deref() {
+        None | Some("") => Ok(b','),
+        Some("\\t") | Some("\t") => Ok(b'\t'),
+        Some(delimiter) if delimiter.len() == 1 && delimiter.is_ascii() => {
+            Ok(delimiter.as_bytes()[0])
+        }
+        Some(delimiter) => Err(format!(
+            "Delimiter must be a single ASCII character, got {:?}",
+            delimiter
+        )),
+    }
+}
+
+fn parse_date(
+    date: &str,
+    format: Option<&str>,
+    timezone: &Option<Timezone>,
+) -> Option<DateTime<Utc>> {
+    let date = date.trim();
+    // 不含时区的时间按所选时区解释
+    let from_naive = |naive: NaiveDateTime| match timezone {
+        Some(Timezone::Utc) => Some(naive.and_utc()),
+        _ => Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|date| date.with_timezone(&Utc)),
+    };
+    let from_date = |date: NaiveDate| date.and_hms_opt(0, 0, 0).and_then(from_naive);
+    if let Some(format) = format.filter(|format| !format.is_empty()) {
+        return DateTime::parse_from_str(date, format)
+            .map(|date| date.with_timezone(&Utc))
+            .ok()
+            .or_else(|| {
+                NaiveDateTime::parse_from_str(date, format)
+                    .ok()
+                    .and_then(from_naive)
+            })
+            .or_else(|| {
+                NaiveDate::parse_from_str(date, format)
+                    .ok()
+                    .and_then(from_date)
+            });
+    }
+    if date.is_empty() {
+        return Some(Utc::now());
+    }
+    // 纯数字视为时间戳，12 位及以上按毫秒处理
+    if let Ok(timestamp) = date.parse::<i64>() {
+        return if timestamp.abs() >= 100_000_000_000 {
+            DateTime::from_timestamp_millis(timestamp)
+        } else {
+            DateTime::from_timestamp(timestamp, 0)
+        };
+    }
+    DateTime::parse_from_rfc3339(date)
+        .or_else(|_| DateTime::parse_from_rfc2822(date))
+        .map(|date| date.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y/%m/%d %H:%M:%S"]
+                .iter()
+                .find_map(|format| NaiveDateTime::parse_from_str(date, format).ok())
+                .and_then(from_naive)
+        })
+        .or_else(|| {
+            ["%Y-%m-%d", "%Y/%m/%d"]
+                .iter()
+                .find_map(|format| NaiveDate::parse_from_str(date, format).ok())
+                .and_then(from_date)
+        })
+}
+
+#[action(zh_cn = "查询 JSON", en = "Query JSON")]
+#[entry(data, query, option { syntax, first })]
+#[description(
+    zh_cn = "使用 JSONPath 或 JMESPath 从 JSON 中取值",
+    en = "Select values from JSON with JSONPath or JMESPath"
+)]
+pub fn query_action(
+    #[name(zh_cn = "数据", en = "Data")]
+    #[description(zh_cn = "要查询的 JSON", en = "JSON to query")]
+    data: Text,
+    #[name(zh_cn = "查询语句", en = "Query")]
+    #[description(
+        zh_cn = "如 $.items[*].name 或 items[].name",
+        en = "Such as $.items[*].name or items[].name"
+    )]
+    query: String,
+    #[name(zh_cn = "语法", en = "Syntax")]
+    #[description(zh_cn = "默认为 JSONPath", en = "JSONPath by default")]
+    syntax: Option<Syntax>,
+    #[name(zh_cn = "仅第一个", en = "First only")]
+    #[description(
+        zh_cn = "只输出第一个结果，而不是结果列表",
+        en = "Output the first result instead of a list"
+    )]
+    first: Option<bool>,
+) -> QueryResult {
+    let data = parse_json!(QueryResult, data);
+    let found = match syntax {
+        Some(Syntax::JmesPath) => {
+            let found = jmespath::compile(&query)?.search(JmesInput(&data))?;
+            serde_json::to_value(&*found)?
+        }
+        _ => {
+            let path = JsonPath::parse(&query)?;
+            Value::Array(path.query(&data).all().into_iter().cloned().collect())
+        }
+    };
+    // JMESPath 的结果不一定是列表，非列表时原样输出
+    let found = match found {
+        Value::Array(list) if first.unwrap_or(false) => list.into_iter().next(),
+        Value::Array(list) if list.is_empty() => None,
+        Value::Null => None,
+        found => Some(found),
+    };
+    match found {
+        Some(found) => QueryResult::Found(found),
+        None => QueryResult::Empty,
+    }
+}
+
+#[action(zh_cn = "正则匹配", en = "Regex match")]
+#[description(
+    zh_cn = "匹配第一处并输出其中的分组",
+    en = "Match the first occurrence and output its groups"
+)]
+pub fn regex_match_action(
+    #[name(zh_cn = "文本", en = "Text")]
+    #[description(zh_cn = "要匹配的文本", en = "Text to match")]
+    text: Text,
+    #[name(zh_cn = "正则表达式", en = "Pattern")]
+    #[description(
+        zh_cn = "可使用 (?<name>...) 声明命名分组",
+        en = "Use (?<name>...) to declare named groups"
+    )]
+    pattern: String,
+    #[name(zh_cn = "忽略大小写", en = "Ignore case")]
+    #[description(zh_cn = "匹配时不区分大小写", en = "Match case-insensitively")]
+    ignore_case: Option<bool>,
+) -> MatchResult {
+    let regex = build_regex(&pattern, ignore_case)?;
+    let Some(captures) = regex.captures(&text) else {
+        return MatchResult::NoMatch;
+    };
+    let groups = captures
+        .iter()
+        .skip(1)
+        .map(|group| group.map_or(Value::Null, |group| json!(group.as_str())))
+        .collect::<Vec<_>>();
+    let named = regex
+        .capture_names()
+        .flatten()
+        .map(|name| {
+            let group = captures.name(name).map(|group| group.as_str());
+            (name.to_string(), json!(group))
+        })
+        .collect::<Map<_, _>>();
+    MatchResult::Matched(json!({
+        "text": &captures[0],
+        "groups": groups,
+        "named": named,
+    }))
+}
+
+#[action(zh_cn = "正则提取", en = "Regex extract")]
+#[description(
+    zh_cn = "提取全部匹配的文本",
+    en = "Extract the text of every match"
+)]
+pub fn regex_extract_action(
+    #[name(zh_cn = "文本", en = "Text")]
+    #[description(zh_cn = "要提取的文本", en = "Text to extract from")]
+    text: Text,
+    #[name(zh_cn = "正则表达式", en = "Pattern")]
+    #[description(zh_cn = "要匹配的正则表达式", en = "Regular expression to match")]
+    pattern: String,
+    #[name(zh_cn = "分组", en = "Group")]
+    #[description(
+        zh_cn = "输出的分组序号或名称，默认为整个匹配",
+        en = "Index or name of the group to output, the whole match by default"
+    )]
+    group: Option<String>,
+    #[name(zh_cn = "忽略大小写", en = "Ignore case")]
+    #[description(zh_cn = "匹配时不区分大小写", en = "Match case-insensitively")]
+    ignore_case: Option<bool>,
+) -> ExtractResult {
+    let regex = build_regex(&pattern, ignore_case)?;
+    let group = group.filter(|group| !group.is_empty());
+    let matches = regex
+        .captures_iter(&text)
+        .filter_map(|captures| {
+            let matched = match &group {
+                Some(group) => match group.parse::<usize>() {
+                    Ok(index) => captures.get(index),
+                    Err(_) => captures.name(group),
+                },
+                None => captures.get(0),
+            };
+            matched.map(|matched| matched.as_str().to_string())
+        })
+        .collect::<Vec<_>>();
+    if matches.is_empty() {
+        return ExtractResult::NoMatch;
+    }
+    ExtractResult::Matched(json!({ "count": matches.len(), "matches": matches }))
+}
+
+#[action(zh_cn = "正则替换", en = "Regex replace")]
+#[description(
+    zh_cn = "替换全部匹配的文本",
+    en = "Replace every match in the text"
+)]
+pub fn regex_replace_action(
+    #[name(zh_cn = "文本", en = "Text")]
+    #[description(zh_cn = "要替换的文本", en = "Text to replace in")]
+    text: Text,
+    #[name(zh_cn = "正则表达式", en = "Pattern")]
+    #[description(zh_cn = "要匹配的正则表达式", en = "Regular expression to match")]
+    pattern: String,
+    #[name(zh_cn = "替换为", en = "Replacement")]
+    #[description(
+        zh_cn = "可使用 $1 或 ${name} 引用分组",
+        en = "Reference groups with $1 or ${name}"
+    )]
+    replacement: String,
+    #[name(zh_cn = "忽略大小写", en = "Ignore case")]
+    #[description(zh_cn = "匹配时不区分大小写", en = "Match case-insensitively")]
+    ignore_case: Option<bool>,
+) -> ReplaceResult {
+    let regex = build_regex(&pattern, ignore_case)?;
+    let count = regex.find_iter(&text).count();
+    let replaced = regex.replace_all(&text, replacement.as_str());
+    ReplaceResult::Success(json!({ "text": replaced, "count": count }))
+}
+
+#[action(zh_cn = "CSV 转 JSON", en = "CSV to JSON")]
+#[description(
+    zh_cn = "将 CSV 解析为行的列表",
+    en = "Parse CSV into a list of rows"
+)]
+pub fn csv_to_json_action(
+    #[name(zh_cn = "CSV", en = "CSV")]
+    #[description(zh_cn = "要解析的 CSV 文本", en = "CSV text to parse")]
+    csv: Text,
+    #[name(zh_cn = "分隔符", en = "Delimiter")]
+    #[description(
+        zh_cn = "默认为逗号，制表符可写作 \\t",
+        en = "Comma by default, write \\t for tab"
+    )]
+    delimiter: Option<String>,
+    #[name(zh_cn = "无表头", en = "No header")]
+    #[description(
+        zh_cn = "第一行是数据而不是表头，每行输出为数组",
+        en = "The first line is data instead of a header, rows become arrays"
+    )]
+    no_header: Option<bool>,
+) -> TableResult {
+    let no_header = no_header.unwrap_or(false);
+    let delimiter = match parse_delimiter(&delimiter) {
+        Ok(delimiter) => delimiter,
+        Err(e) => return TableResult::Invalid(e),
+    };
+    let mut reader = ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(!no_header)
+        .from_reader(csv.as_bytes());
+    let headers = match reader.headers() {
+        Ok(headers) => headers.clone(),
+        Err(e) => return TableResult::Invalid(e.to_string()),
+    };
+    let mut rows = vec![];
+    for record in reader.records() {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => return TableResult::Invalid(e.to_string()),
+        };
+        rows.push(if no_header {
+            json!(record.iter().collect::<Vec<_>>())
+        } else {
+            Value::Object(
+                headers
+                    .iter()
+                    .zip(record.iter())
+                    .map(|(header, field)| (header.to_string(), json!(field)))
+                    .collect(),
+            )
+        });
+    }
+    TableResult::Success(json!({ "count": rows.len(), "rows": rows }))
+}
+
+#[action(zh_cn = "JSON 转 CSV", en = "JSON to CSV")]
+#[description(
+    zh_cn = "将对象或数组的列表写为 CSV",
+    en = "Write a list of objects or arrays as CSV"
+)]
+pub fn json_to_csv_action(
+    #[name(zh_cn = "数据", en = "Data")]
+    #[description(
+        zh_cn = "对象列表时以全部键作为表头",
+        en = "For a list of objects, all keys become the header"
+    )]
+    data: Text,
+    #[name(zh_cn = "分隔符", en = "Delimiter")]
+    #[description(
+        zh_cn = "默认为逗号，制表符可写作 \\t",
+        en = "Comma by default, write \\t for tab"
+    )]
+    delimiter: Option<String>,
+) -> TextResult {
+    let Value::Array(rows) = parse_json!(TextResult, data) else {
+        return TextResult::Invalid("Expected a JSON array".to_string());
+    };
+    let delimiter = match parse_delimiter(&delimiter) {
+        Ok(delimiter) => delimiter,
+        Err(e) => return TextResult::Invalid(e),
+    };
+    let mut headers: Vec<String> = vec![];
+    for row in &rows {
+        if let Value::Object(row) = row {
+            for key in row.keys() {
+                if !headers.contains(key) {
+                    headers.push(key.clone());
+                }
+            }
+        }
+    }
+    let mut writer = WriterBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_writer(vec![]);
+    if !headers.is_empty() {
+        writer.write_record(&headers)?;
+    }
+    for row in &rows {
+        let record = match row {
+            Value::Object(row) => headers
+                .iter()
+                .map(|header| row.get(header).map(display).unwrap_or_default())
+                .collect::<Vec<_>>(),
+            Value::Array(row) => row.iter().map(display).collect(),
+            row => vec![display(row)],
+        };
+        writer.write_record(&record)?;
+    }
+    let bytes = writer.into_inner().map_err(|e| e.into_error())?;
+    TextResult::Success(String::from_utf8(bytes)?)
+}
+
+#[action(zh_cn = "文本模板", en = "Template")]
+#[description(
+    zh_cn = "使用 JSON 数据填充 {{ path }} 占位符",
+    en = "Fill {{ path }} placeholders with JSON data"
+)]
+pub fn template_action(
+    #[name(zh_cn = "模板", en = "Template")]
+    #[description(
+        zh_cn = "如 Hello {{ user.name }}，不存在的路径输出为空",
+        en = "Such as Hello {{ user.name }}, missing paths render as empty"
+    )]
+    template: Text,
+    #[name(zh_cn = "数据", en = "Data")]
+    #[description(zh_cn = "用于填充的 JSON", en = "JSON used to fill the template")]
+    data: Text,
+) -> TextResult {
+    let data = parse_json!(TextResult, data);
+    let placeholder = Regex::new(r"\{\{\s*([^{}]*?)\s*\}\}")?;
+    let rendered = placeholder.replace_all(&template, |captures: &regex::Captures| {
+        lookup(&data, &captures[1]).map(display).unwrap_or_default()
+    });
+    TextResult::Success(rendered.to_string())
+}
+
+#[action(zh_cn = "日期格式化", en = "Format date")]
+#[entry(date, input_format, output_format, option { timezone })]
+#[description(
+    zh_cn = "解析日期并按格式输出",
+    en = "Parse a date and output it in a format"
+)]
+pub fn date_action(
+    #[name(zh_cn = "日期", en = "Date")]
+    #[description(
+        zh_cn = "RFC 3339、RFC 2822、常见日期格式或时间戳，留空为当前时间",
+        en = "RFC 3339, RFC 2822, common date formats or a timestamp, empty for now"
+    )]
+    date: Option<String>,
+    #[name(zh_cn = "输入格式", en = "Input format")]
+    #[description(
+        zh_cn = "如 %d/%m/%Y %H:%M，留空时自动识别",
+        en = "Such as %d/%m/%Y %H:%M, detected automatically when empty"
+    )]
+    input_format: Option<String>,
+    #[name(zh_cn = "输出格式", en = "Output format")]
+    #[description(
+        zh_cn = "默认为 %Y-%m-%d %H:%M:%S",
+        en = "%Y-%m-%d %H:%M:%S by default"
+    )]
+    output_format: Option<String>,
+    #[name(zh_cn = "时区", en = "Timezone")]
+    #[description(
+        zh_cn = "解释与输出日期使用的时区，默认为本地",
+        en = "Timezone used to read and output the date, local by default"
+    )]
+    timezone: Option<Timezone>,
+) -> DateResult {
+    let date = date.unwrap_or_default();
+    let Some(parsed) = parse_date(&date, input_format.as_deref(), &timezone) else {
+        return DateResult::Invalid(format!("Unable to parse date {}", date));
+    };
+    let output_format = output_format
+        .filter(|format| !format.is_empty())
+        .unwrap_or("%Y-%m-%d %H:%M:%S".to_string());
+    // 格式中含有无法识别的占位符时 write! 返回错误，避免 to_string 直接 panic
+    let mut text = String::new();
+    let iso = match timezone {
+        Some(Timezone::Utc) => {
+            write!(text, "{}", parsed.format(&output_format))?;
+            parsed.to_rfc3339()
+        }
+        _ => {
+            let parsed = parsed.with_timezone(&Local);
+            write!(text, "{}", parsed.format(&output_format))?;
+            parsed.to_rfc3339()
+        }
+    };
+    DateResult::Success(json!({
+        "text": text,
+        "timestamp": parsed.timestamp_millis(),
+        "iso": iso,
+    }))
+}
+
+#[action(zh_cn = "合并 JSON", en = "Merge JSON")]
+#[description(
+    zh_cn = "将第二个对象合并到第一个中，同名键以后者为准",
+    en = "Merge the second object into the first, later keys win"
+)]
+pub fn merge_json_action(
+    #[name(zh_cn = "基础对象", en = "Base")]
+    #[description(zh_cn = "被合并的 JSON", en = "JSON to merge into")]
+    base: Text,
+    #[name(zh_cn = "合并对象", en = "Other")]
+    #[description(zh_cn = "要合并进来的 JSON", en = "JSON to merge in")]
+    other: Text,
+    #[name(zh_cn = "深度合并", en = "Deep")]
+    #[description(
+        zh_cn = "递归合并嵌套的对象，而不是整体替换",
+        en = "Merge nested objects recursively instead of replacing them"
+    )]
+    deep: Option<bool>,
+) -> ValueResult {
+    let mut base = parse_json!(ValueResult, base);
+    let other = parse_json!(ValueResult, other);
+    merge(&mut base, other, deep.unwrap_or(false));
+    ValueResult::Success(base)
+}
+
+#[action(zh_cn = "选取字段", en = "Pick keys")]
+#[description(
+    zh_cn = "只保留对象中指定的字段",
+    en = "Keep only the given keys of an object"
+)]
+pub fn pick_keys_action(
+    #[name(zh_cn = "数据", en = "Data")]
+    #[description(zh_cn = "要选取的 JSON 对象", en = "JSON object to pick from")]
+    data: Text,
+    #[name(zh_cn = "字段", en = "Keys")]
+    #[description(
+        zh_cn = "每行一个路径，如 user.name",
+        en = "One path per line, such as user.name"
+    )]
+    keys: Text,
+) -> ValueResult {
+    let data = parse_json!(ValueResult, data);
+    let mut picked = Value::Object(Map::new());
+    for key in lines(&keys) {
+        if let Some(value) = lookup(&data, key) {
+            insert_path(&mut picked, key, value.clone());
+        }
+    }
+    ValueResult::Success(picked)
+}
+
+#[action(zh_cn = "重命名字段", en = "Rename keys")]
+#[description(
+    zh_cn = "按映射重命名对象中的字段",
+    en = "Rename keys of an object by a mapping"
+)]
+pub fn rename_keys_action(
+    #[name(zh_cn = "数据", en = "Data")]
+    #[description(zh_cn = "要重命名的 JSON 对象", en = "JSON object to rename keys in")]
+    data: Text,
+    #[name(zh_cn = "映射", en = "Mapping")]
+    #[description(
+        zh_cn = "每行一个 old=new，可使用 user.name 形式的路径",
+        en = "One old=new per line, paths such as user.name are allowed"
+    )]
+    mapping: Text,
+) -> ValueResult {
+    let mut data = parse_json!(ValueResult, data);
+    for line in lines(&mapping) {
+        let Some((from, to)) = line.split_once('=') else {
+            return ValueResult::Invalid(format!("Invalid line {}, expected old=new", line));
+        };
+        if let Some(value) = remove_path(&mut data, from.trim()) {
+            insert_path(&mut data, to.trim(), value);
+        }
+    }
+    ValueResult::Success(data)
+}
+
+aster_macro::export_plugin!(
+    transform,
+    [
+        query_action,
+        regex_match_action,
+        regex_extract_action,
+        regex_replace_action,
+        csv_to_json_action,
+        json_to_csv_action,
+        template_action,
+        date_action,
+        merge_json_action,
+        pick_keys_action,
+        rename_keys_action
+    ]
+);
@@ -1,29 +1,41 @@
-use std::{str::FromStr, time::Duration};
+use std::{path::Path, str::FromStr, time::Duration};
 
 use aster_macro::action;
-use common::{action::error::ActionError, ty::Data};
-use reqwest::{ClientBuilder, Proxy};
-use serde_json::Value;
+use common::{action::error::ActionError, secret::resolve_secret, tokio::fs::read};
+use reqwest::{
+    ClientBuilder, Method as HttpMethod, Proxy, RequestBuilder, Response,
+    header::{CONTENT_TYPE, HeaderMap},
+    multipart::{Form, Part},
+    redirect::Policy,
+};
+use serde_json::{Map, Value, json};
+
+pub type Text = String;
 
 /// 声明返回分支
 /// ```
-///    ---------
-/// -> + fetch +-- Success
-///    ----+----
-///        | timeout
+///     Redirect  Timeout
+///    -----+--------+-----
+/// -> +      fetch       +-- Success
+///    -----+--------+-----
+///   ClientError  ServerError
 /// ```
-
 #[result]
 enum Result {
-    /// Success分支位于右侧
+    /// 2xx，Success分支位于右侧
     #[right(zh_cn = "成功", en = "Success")]
-    Success(plug!(Value as nesting! { a: nesting! { hello: String } })),
-    // / Error分支位于底部
-    #[bottom]
-    // #[branch(error)]
-    Error {
-        timeout: nesting! { hello: plug! (String as number) },
-    },
+    Success(plug!(Value as nesting! { status: i64, headers: Value, body: Value })),
+    /// 3xx，仅在不跟随重定向时出现
+    #[top(zh_cn = "重定向", en = "Redirect")]
+    Redirect(plug!(Value as nesting! { status: i64, headers: Value, body: Value })),
+    /// 4xx
+    #[bottom(zh_cn = "客户端错误", en = "Client error")]
+    ClientError(plug!(Value as nesting! { status: i64, headers: Value, body: Value })),
+    /// 5xx
+    #[bottom(zh_cn = "服务端错误", en = "Server error")]
+    ServerError(plug!(Value as nesting! { status: i64, headers: Value, body: Value })),
+    #[top(zh_cn = "超时", en = "Timeout")]
+    Timeout,
 }
 
 /// 声明下拉菜单
@@ -34,18 +46,150 @@ pub enum Method {
     Post, // 不指定则使用默认label
     Delete,
     Put,
+    Patch,
+    Head,
+    Options,
+}
+
+#[options]
+pub enum BodyType {
+    Json,
+    #[label(zh_cn = "表单", en = "Form")]
+    Form,
+    Multipart,
+    #[label(zh_cn = "原始文本", en = "Raw")]
+    Raw,
+}
+
+#[options]
+pub enum Auth {
+    Basic,
+    Bearer,
+}
+
+#[options]
+pub enum Redirect {
+    #[label(zh_cn = "跟随", en = "Follow")]
+    Follow,
+    #[label(zh_cn = "不跟随", en = "Manual")]
+    Manual,
+}
+
+// 多行文本按行拆分为键值对，忽略空行
+fn pairs(
+    text: &Option<Text>,
+    separator: char,
+) -> std::result::Result<Vec<(String, String)>, ActionError> {
+    text.as_deref()
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| match line.split_once(separator) {
+            Some((key, value)) => Ok((key.trim().to_string(), value.trim().to_string())),
+            None => Err(ActionError::RunActionCardError(format!(
+                "Invalid line {}, expected key{}value",
+                line, separator
+            ))),
+        })
+        .collect()
+}
+
+fn secret(value: &str) -> std::result::Result<String, ActionError> {
+    resolve_secret(value).map_err(|e| ActionError::RunActionCardError(e.to_string()))
+}
+
+async fn with_body(
+    request: RequestBuilder,
+    body_type: Option<BodyType>,
+    body: Option<Text>,
+) -> std::result::Result<RequestBuilder, Box<dyn std::error::Error>> {
+    let Some(body_type) = body_type else {
+        return Ok(request);
+    };
+    Ok(match body_type {
+        BodyType::Json => {
+            let value: Value = serde_json::from_str(body.as_deref().unwrap_or("null"))?;
+            request.json(&value)
+        }
+        BodyType::Form => request.form(&pairs(&body, '=')?),
+        BodyType::Multipart => {
+            let mut form = Form::new();
+            for (key, value) in pairs(&body, '=')? {
+                // 以 @ 开头的值视为文件路径
+                form = match value.strip_prefix('@') {
+                    Some(path) => {
+                        let file_name = Path::new(path)
+                            .file_name()
+                            .map(|name| name.to_string_lossy().to_string())
+                            .unwrap_or_default();
+                        form.part(key, Part::bytes(read(path).await?).file_name(file_name))
+                    }
+                    None => form.text(key, value),
+                };
+            }
+            request.multipart(form)
+        }
+        BodyType::Raw => request.body(body.unwrap_or_default()),
+    })
+}
+
+fn headers_to_value(headers: &HeaderMap) -> Value {
+    let mut map = Map::new();
+    for (name, value) in headers {
+        let value = String::from_utf8_lossy(value.as_bytes()).to_string();
+        // 同名响应头合并为一个值
+        match map.get_mut(name.as_str()) {
+            Some(Value::String(exist)) => {
+                exist.push_str(", ");
+                exist.push_str(&value);
+            }
+            _ => {
+                map.insert(name.to_string(), Value::String(value));
+            }
+        }
+    }
+    Value::Object(map)
+}
+
+async fn response_to_value(response: Response) -> std::result::Result<Value, reqwest::Error> {
+    let status = response.status().as_u16();
+    let headers = headers_to_value(response.headers());
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|content_type| content_type.contains("json"));
+
+    // 根据 Content-Type 处理不同类型的响应，JSON 解析失败时保留原文
+    let text = response.text().await?;
+    let body = if text.is_empty() {
+        Value::Null
+    } else if is_json {
+        serde_json::from_str(&text).unwrap_or(Value::String(text))
+    } else {
+        Value::String(text)
+    };
+    Ok(json!({ "status": status, "headers": headers, "body": body }))
 }
 
 // 声明 action，并提供i18n支持
 #[action(zh_cn = "网络请求", en = "Network request")]
 // 多入口支持，用于可编程系统的设计
-#[entry(url, method, option { proxy, http2, timeout })]
+#[entry(
+    url,
+    method,
+    request { query, headers, body_type, body },
+    auth { auth, username, credential },
+    option { proxy, http2, timeout, redirect }
+)]
 // action的描述
 #[description(zh_cn = "发送一个网络请求", en = "Send a network request")]
 pub async fn fetch_action(
     // 参数的名称与描述，用于构建表单
     #[name(zh_cn = "请求地址", en = "Request URL")]
     #[description(zh_cn = "请求的目标地址", en = "The target address of the request")]
+    #[validate(url)]
     url: String,
     #[name(zh_cn = "请求方法", en = "Request Method")]
     #[description(
@@ -53,13 +197,44 @@ pub async fn fetch_action(
         en = "Method used when sending network requests"
     )]
     method: Method,
+    #[name(zh_cn = "查询参数", en = "Query")]
+    #[description(zh_cn = "每行一个 key=value", en = "One key=value per line")]
+    query: Option<Text>,
+    #[name(zh_cn = "请求头", en = "Headers")]
+    #[description(
+        zh_cn = "每行一个 Name: value，值可使用 env: 或 secret: 引用密钥",
+        en = "One Name: value per line, values may reference env: or secret: secrets"
+    )]
+    headers: Option<Text>,
+    #[name(zh_cn = "请求体类型", en = "Body type")]
+    #[description(zh_cn = "请求体的编码方式", en = "How the request body is encoded")]
+    body_type: Option<BodyType>,
+    #[name(zh_cn = "请求体", en = "Body")]
+    #[description(
+        zh_cn = "JSON 或原始文本；表单每行一个 key=value，文件字段使用 key=@路径",
+        en = "JSON or raw text; forms take one key=value per line, use key=@path for files"
+    )]
+    body: Option<Text>,
+    #[name(zh_cn = "认证方式", en = "Authentication")]
+    #[description(zh_cn = "请求使用的认证方式", en = "Authentication used for the request")]
+    auth: Option<Auth>,
+    #[name(zh_cn = "用户名", en = "Username")]
+    #[description(zh_cn = "Basic 认证的用户名", en = "Username for basic authentication")]
+    username: Option<String>,
+    #[name(zh_cn = "密码或令牌", en = "Password or token")]
+    #[description(
+        zh_cn = "可使用 env:NAME 或 secret:NAME 引用密钥",
+        en = "Reference secrets with env:NAME or secret:NAME"
+    )]
+    credential: Option<String>,
     #[name(zh_cn = "超时时间", en = "Timeout")]
-    #[description(zh_cn = "请求超时时间", en = "Request timeout")]
-    timeout: u64,
+    #[description(zh_cn = "请求超时时间（毫秒）", en = "Request timeout (ms)")]
+    #[validate(min = 1)]
+    timeout: Option<u64>,
     #[name(zh_cn = "网络代理", en = "Network Proxy")]
     #[description(
-        zh_cn = "请求时使用的代理服务器",
-        en = "Proxy server to use for the request"
+        zh_cn = "请求时使用的代理服务器，支持 http、https 与 socks5",
+        en = "Proxy server to use for the request, supports http, https and socks5"
     )]
     proxy: Option<String>,
     #[name(zh_cn = "启用http2", en = "Enable http2")]
@@ -68,18 +243,22 @@ pub async fn fetch_action(
         en = "Use the more efficient and secure http/2"
     )]
     http2: Option<bool>,
+    #[name(zh_cn = "重定向", en = "Redirect")]
+    #[description(
+        zh_cn = "是否自动跟随重定向，最多 10 次",
+        en = "Whether to follow redirects automatically, up to 10 times"
+    )]
+    redirect: Option<Redirect>,
 ) -> Result {
-    use reqwest::Method as HttpMethod;
-    let method = method.to_string();
     let mut client_builder = ClientBuilder::new();
-    client_builder = client_builder.timeout(Duration::from_millis(timeout));
+    if let Some(timeout) = timeout {
+        client_builder = client_builder.timeout(Duration::from_millis(timeout));
+    }
 
-    if let Some(proxy) = proxy {
-        if !proxy.is_empty() {
-            client_builder = client_builder.proxy(
-                Proxy::http(proxy).map_err(|e| ActionError::RunActionCardError(e.to_string()))?,
-            );
-        }
+    if let Some(proxy) = proxy.filter(|proxy| !proxy.is_empty()) {
+        client_builder = client_builder.proxy(
+            Proxy::all(proxy).map_err(|e| ActionError::RunActionCardError(e.to_string()))?,
+        );
     }
 
     if let Some(http2) = http2 {
@@ -87,30 +266,47 @@ pub async fn fetch_action(
             client_builder = client_builder.http1_only();
         }
     }
+    client_builder = client_builder.redirect(match redirect {
+        Some(Redirect::Manual) => Policy::none(),
+        _ => Policy::limited(10),
+    });
     let client = client_builder
         .build()
         .map_err(|e| ActionError::RunActionCardError(e.to_string()))?;
-    let res = client
-        .request(HttpMethod::from_str(&method)?, url)
-        .send()
-        .await?;
-    let content_type = res
-        .headers()
-        .get(reqwest::header::CONTENT_TYPE)
-        .and_then(|v| v.to_str().ok())
-        .unwrap_or("");
-
-    // 根据 Content-Type 处理不同类型的响应
-    let _ = if content_type.contains("application/json") {
-        // JSON 数据
-        let json = res.json::<serde_json::Map<String, Value>>().await.unwrap();
-        Data::Json(json)
-    } else if content_type.contains("text/") {
-        // 文本数据
-        let text = res.text().await?;
-        Data::String(text)
-    } else {
-        Data::Null
+
+    let method = HttpMethod::from_str(&method.to_string().to_uppercase())?;
+    let mut request = client.request(method, url).query(&pairs(&query, '=')?);
+    for (name, value) in pairs(&headers, ':')? {
+        request = request.header(name, secret(&value)?);
+    }
+    request = match auth {
+        Some(Auth::Basic) => {
+            let password = credential.as_deref().map(secret).transpose()?;
+            request.basic_auth(username.unwrap_or_default(), password)
+        }
+        Some(Auth::Bearer) => request.bearer_auth(secret(&credential.unwrap_or_default())?),
+        None => request,
+    };
+    let request = with_body(request, body_type, body).await?;
+
+    let response = match request.send().await {
+        Ok(response) => response,
+        Err(e) if e.is_timeout() => return Result::Timeout,
+        Err(e) => return Err(Box::new(e)),
     };
-    Result::Success(Value::Null)
+    let status = response.status();
+    let result = match response_to_value(response).await {
+        Ok(result) => result,
+        Err(e) if e.is_timeout() => return Result::Timeout,
+        Err(e) => return Err(Box::new(e)),
+    };
+    if status.is_redirection() {
+        Result::Redirect(result)
+    } else if status.is_client_error() {
+        Result::ClientError(result)
+    } else if status.is_server_error() {
+        Result::ServerError(result)
+    } else {
+        Result::Success(result)
+    }
 }
@@ -0,0 +1,533 @@
+//! 卡片目录：以 JSON Schema 描述所有 action 与触发器的参数、分支与插头
+//!
+//! 供前端以外的工具使用，输出的键有序且格式固定，提交到仓库后可以直接比较差异
+
+use std::{collections::HashMap, fs, path::Path};
+
+use aster_common::{
+    action::{
+        form::{FormData, FormType},
+        result::PlugType,
+    },
+    i18n::{I18nConfig, ParamI18n, ParsedI18nMapTrait},
+};
+use common::action::rule::ValidateRule;
+use serde_json::{Map, Value, json};
+
+use crate::{
+    CardInfo, GenerateCodeContext,
+    error::{Error, Result},
+    extract::trigger::TriggerInfo,
+    output::Output,
+    utils::FromType,
+};
+
+/// 目录格式的版本，结构发生不兼容的变化时递增
+pub const CATALOG_VERSION: u32 = 1;
+
+const SCHEMA: &str = "https://json-schema.org/draft/2020-12/schema";
+
+fn i18n(map: HashMap<String, String>) -> Value {
+    json!(map)
+}
+
+// JSON Schema 的 title 与 description 只能是字符串，优先使用默认语言
+fn pick(map: &HashMap<String, String>) -> Option<&String> {
+    let config = I18nConfig::current();
+    config
+        .resolve(&config.default, |language| map.get(language))
+        .or_else(|| config.languages.iter().find_map(|language| map.get(language)))
+}
+
+/// 插头中的类型描述转换为 JSON Schema，与 normalize_type 的输出对应
+fn plug_value_schema(value: &Value) -> Value {
+    match value {
+        Value::String(ty) if ["string", "number", "boolean"].contains(&ty.as_str()) => {
+            json!({ "type": ty })
+        }
+        // 触发器的 context 中以 `string[]` 的形式声明数组
+        Value::String(ty) if ty.ends_with("[]") => json!({
+            "type": "array",
+            "items": plug_value_schema(&json!(ty.trim_end_matches("[]"))),
+        }),
+        Value::Object(map) => {
+            if map.get("\0type").and_then(Value::as_str) == Some("tuple") {
+                let mut items = map
+                    .iter()
+                    .filter_map(|(key, value)| {
+                        let index = key.strip_prefix('[')?.strip_suffix(']')?;
+                        Some((index.parse::<usize>().ok()?, plug_value_schema(value)))
+                    })
+                    .collect::<Vec<_>>();
+                items.sort_by_key(|(index, _)| *index);
+                let items = items.into_iter().map(|(_, item)| item).collect::<Vec<_>>();
+                json!({ "type": "array", "prefixItems": items, "items": false })
+            } else {
+                let properties = map
+                    .iter()
+                    .filter(|(key, _)| !key.starts_with('\0'))
+                    .map(|(key, value)| (key.clone(), plug_value_schema(value)))
+                    .collect::<Map<_, _>>();
+                json!({ "type": "object", "properties": properties })
+            }
+        }
+        _ => json!({}),
+    }
+}
+
+fn plug_schema(plug: &PlugType) -> Option<Value> {
+    match plug {
+        PlugType::None => None,
+        PlugType::Unknown => Some(json!({})),
+        PlugType::Error => Some(json!({ "$ref": "#/$defs/error" })),
+        PlugType::Base(ty) => Some(plug_value_schema(&Value::String(ty.clone()))),
+        PlugType::Value(value) => Some(plug_value_schema(value)),
+    }
+}
+
+fn apply_rule(schema: &mut Map<String, Value>, rule: &ValidateRule) {
+    if let Some(min) = rule.min {
+        schema.insert("minimum".into(), json!(min));
+    }
+    if let Some(max) = rule.max {
+        schema.insert("maximum".into(), json!(max));
+    }
+    if let Some(len) = &rule.len {
+        if let Some(min) = len.min {
+            schema.insert("minLength".into(), json!(min));
+        }
+        if let Some(max) = len.max {
+            schema.insert("maxLength".into(), json!(max));
+        }
+    }
+    if let Some(regex) = &rule.regex {
+        schema.insert("pattern".into(), json!(regex));
+    }
+    if rule.url {
+        schema.insert("format".into(), json!("uri"));
+    }
+    if let Some(one_of) = &rule.one_of {
+        schema.insert("enum".into(), json!(one_of));
+    }
+}
+
+/// 单个参数的 JSON Schema，返回值的第二项表示参数是否可选
+fn param_schema(param: &ParamI18n, card: &CardInfo, ctx: &GenerateCodeContext) -> (Value, bool) {
+    let mut form_type = FormType::from_type(param.r#type.clone());
+    let optional = form_type.is_optional();
+    let ty = param.r#type.replace(' ', "");
+    let inner = FormType::get_inner_type(&ty).unwrap_or(ty);
+
+    let mut schema = match inner.as_str() {
+        "String" | "Text" | "Code" | "File" | "Directory" => json!({ "type": "string" }),
+        "bool" => json!({ "type": "boolean" }),
+        "f32" | "f64" => json!({ "type": "number" }),
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => {
+            json!({ "type": "integer", "minimum": 0 })
+        }
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" => json!({ "type": "integer" }),
+        _ => json!({}),
+    };
+    let schema_map = schema.as_object_mut().unwrap();
+
+    // #[options] 枚举，优先使用同一文件中的声明
+    let options = ctx
+        .form_data_list
+        .iter()
+        .filter(|form| form.name == inner)
+        .min_by_key(|form| form.file != card.file);
+    if let Some(options) = options {
+        form_type.fix_with(&options.data);
+        if let FormData::Option(labels) | FormData::AutoComplete(labels) = &options.data {
+            schema_map.insert("type".into(), json!("string"));
+            schema_map.insert(
+                "enum".into(),
+                json!(labels.iter().map(|label| &label.value).collect::<Vec<_>>()),
+            );
+            let labels = labels
+                .iter()
+                .filter_map(|label| {
+                    let i18n = label.label.clone()?.to_filter_value();
+                    Some((label.value.clone(), json!(i18n)))
+                })
+                .collect::<Map<_, _>>();
+            if !labels.is_empty() {
+                schema_map.insert("x-labels".into(), Value::Object(labels));
+            }
+        }
+    }
+    if let Some(rule) = &param.validate {
+        apply_rule(schema_map, rule);
+    }
+    if let Some(title) = pick(&param.name) {
+        schema_map.insert("title".into(), json!(title));
+    }
+    if let Some(description) = pick(&param.description) {
+        schema_map.insert("description".into(), json!(description));
+    }
+    schema_map.insert("x-type".into(), json!(param.r#type.replace(' ', "")));
+    schema_map.insert("x-form".into(), json!(form_type.get_form_type()));
+    schema_map.insert(
+        "x-i18n".into(),
+        json!({
+            "name": i18n(param.name.clone()),
+            "description": i18n(param.description.clone()),
+        }),
+    );
+    (schema, optional)
+}
+
+fn action_schema(card: &CardInfo, ctx: &GenerateCodeContext) -> Value {
+    let mut properties = Map::new();
+    let mut required = vec![];
+    for param in card.params.iter() {
+        let (schema, optional) = param_schema(param, card, ctx);
+        if !optional {
+            required.push(param.key.clone());
+        }
+        properties.insert(param.key.clone(), schema);
+    }
+    let order = card
+        .params
+        .iter()
+        .map(|param| &param.key)
+        .collect::<Vec<_>>();
+
+    let branch_types = ctx
+        .result_branch_list
+        .iter()
+        .find(|e| e.name == card.result && e.file == card.file)
+        .map(|collect| collect.data.as_slice())
+        .unwrap_or_default();
+    let mut source = json!("left");
+    let mut branches = Map::new();
+    for branch in branch_types {
+        if branch.r#type == "source" {
+            source = json!(branch.position);
+            continue;
+        }
+        let mut schema = json!({
+            "type": branch.r#type,
+            "position": branch.position,
+            "label": i18n(branch.get_i18n()),
+        });
+        if let Some(plug) = plug_schema(&branch.plug) {
+            schema["plug"] = plug;
+        }
+        branches.insert(branch.branch.clone(), schema);
+    }
+
+    let title = card.title.to_filter_value();
+    let description = card.description.to_filter_value();
+    json!({
+        "group": card.parent.strip_prefix("action.").unwrap_or(&card.parent),
+        "file": card.file,
+        "version": card.version,
+        "title": i18n(title),
+        "description": i18n(description),
+        "entries": card.entries,
+        "args": {
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+            "x-order": order,
+        },
+        "result": card.result,
+        "source": source,
+        "branches": branches,
+    })
+}
+
+fn trigger_schema(trigger: &TriggerInfo) -> Value {
+    let properties = trigger
+        .args
+        .iter()
+        .map(|(key, ty)| {
+            let schema = match ty.as_str() {
+                "String" | "Text" | "Code" | "File" => json!({ "type": "string" }),
+                "Int" => json!({ "type": "integer" }),
+                "Float" => json!({ "type": "number" }),
+                "Bool" => json!({ "type": "boolean" }),
+                "Json" => json!({ "type": "object" }),
+                _ => json!({}),
+            };
+            (key.clone(), schema)
+        })
+        .collect::<Map<_, _>>();
+    let context = trigger
+        .context
+        .iter()
+        .map(|(key, plug)| (key.clone(), plug_value_schema(plug)))
+        .collect::<Map<_, _>>();
+    json!({
+        "group": trigger.parent.strip_prefix("trigger.").unwrap_or(&trigger.parent),
+        "title": trigger.title,
+        "description": trigger.description,
+        "args": {
+            "type": "object",
+            "properties": properties,
+            "required": trigger.args.keys().collect::<Vec<_>>(),
+        },
+        "context": context,
+    })
+}
+
+/// 生成包含所有 action 与触发器的目录
+pub fn create_catalog(ctx: &GenerateCodeContext, triggers: &[TriggerInfo]) -> Value {
+    let actions = ctx
+        .card_info_list
+        .iter()
+        .map(|card| (card.action_type.clone(), action_schema(card, ctx)))
+        .collect::<Map<_, _>>();
+    let triggers = triggers
+        .iter()
+        .map(|trigger| (trigger.name.clone(), trigger_schema(trigger)))
+        .collect::<Map<_, _>>();
+    json!({
+        "$schema": SCHEMA,
+        "version": CATALOG_VERSION,
+        "$defs": {
+            "error": { "type": "string", "description": "Error message of the failed branch" },
+        },
+        "actions": actions,
+        "triggers": triggers,
+    })
+}
+
+pub fn read_catalog(path: &Path) -> Option<Value> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+pub fn write_catalog(output: &Output, path: &Path, catalog: &Value) -> Result<()> {
+    let content = serde_json::to_string_pretty(catalog)
+        .map_err(|e| Error::WriteFileError(path.to_path_buf(), e.to_string()))?;
+    output.write(path, content + "\n")
+}
+
+fn object<'a>(value: &'a Value, key: &str) -> Option<&'a Map<String, Value>> {
+    value.get(key).and_then(Value::as_object)
+}
+
+fn required(args: &Value) -> Vec<&str> {
+    args.get("required")
+        .and_then(Value::as_array)
+        .map(|required| required.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+// 参数的取值范围，类型或可选值变化时视为不兼容
+fn value_shape(schema: &Value) -> (Option<&Value>, Option<&Value>) {
+    (schema.get("type"), schema.get("enum"))
+}
+
+fn compare_args(kind: &str, name: &str, old: &Value, new: &Value, changes: &mut Vec<String>) {
+    let old_props = old.get("properties").and_then(Value::as_object);
+    let new_props = new.get("properties").and_then(Value::as_object);
+    let (Some(old_props), Some(new_props)) = (old_props, new_props) else {
+        return;
+    };
+    let old_required = required(old);
+    for (key, old_schema) in old_props {
+        match new_props.get(key) {
+            None => changes.push(format!(
+                "parameter `{}` of {} `{}` was removed",
+                key, kind, name
+            )),
+            Some(new_schema) if value_shape(old_schema) != value_shape(new_schema) => {
+                changes.push(format!(
+                    "parameter `{}` of {} `{}` changed its type",
+                    key, kind, name
+                ))
+            }
+            _ => (),
+        }
+    }
+    for key in required(new) {
+        if !old_required.contains(&key) {
+            changes.push(format!(
+                "{} `{}` now requires parameter `{}`",
+                kind, name, key
+            ));
+        }
+    }
+}
+
+// context 中的每一项本身就是插头
+fn plug_of<'a>(field: &str, item: &'a Value) -> Option<&'a Value> {
+    if field == "context" {
+        Some(item)
+    } else {
+        item.get("plug")
+    }
+}
+
+/// 比较新旧目录，列出会使已点亮的卡片或已有工作流失效的变化
+pub fn breaking_changes(old: &Value, new: &Value) -> Vec<String> {
+    let mut changes = vec![];
+    if old.get("version") != new.get("version") {
+        changes.push(format!(
+            "catalog version changed from {} to {}",
+            old.get("version").unwrap_or(&Value::Null),
+            new.get("version").unwrap_or(&Value::Null)
+        ));
+    }
+    for (kind, key) in [("action", "actions"), ("trigger", "triggers")] {
+        let (Some(old_cards), Some(new_cards)) = (object(old, key), object(new, key)) else {
+            continue;
+        };
+        for (name, old_card) in old_cards {
+            let Some(new_card) = new_cards.get(name) else {
+                changes.push(format!("{} `{}` was removed", kind, name));
+                continue;
+            };
+            // 升级了版本的 action 由迁移函数处理参数的变化
+            let version = |card: &Value| card.get("version").and_then(Value::as_u64).unwrap_or(1);
+            let migrated = version(new_card) > version(old_card);
+            if let (Some(old_args), Some(new_args), false) =
+                (old_card.get("args"), new_card.get("args"), migrated)
+            {
+                compare_args(kind, name, old_args, new_args, &mut changes);
+            }
+            for (field, what) in [("branches", "branch"), ("context", "context")] {
+                let (Some(old_items), Some(new_items)) =
+                    (object(old_card, field), object(new_card, field))
+                else {
+                    continue;
+                };
+                for (item, old_item) in old_items {
+                    match new_items.get(item) {
+                        None => changes.push(format!(
+                            "{} `{}` of {} `{}` was removed",
+                            what, item, kind, name
+                        )),
+                        Some(new_item) if plug_of(field, old_item) != plug_of(field, new_item) => {
+                            changes.push(format!(
+                                "plug of {} `{}` of {} `{}` changed",
+                                what, item, kind, name
+                            ))
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog(version: u32, args: Value) -> Value {
+        json!({
+            "version": CATALOG_VERSION,
+            "actions": {
+                "fetch_action": {
+                    "version": version,
+                    "args": args,
+                    "branches": {
+                        "Success": { "type": "primary", "plug": { "type": "string" } },
+                    },
+                },
+            },
+        })
+    }
+
+    fn args(properties: Value, required: &[&str]) -> Value {
+        json!({ "type": "object", "properties": properties, "required": required })
+    }
+
+    #[test]
+    fn allows_compatible_changes() {
+        let old = catalog(1, args(json!({ "url": { "type": "string" } }), &["url"]));
+        let new = catalog(
+            1,
+            args(
+                json!({ "url": { "type": "string" }, "timeout": { "type": "integer" } }),
+                &["url"],
+            ),
+        );
+        assert!(breaking_changes(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn detects_removed_param() {
+        let old = catalog(
+            1,
+            args(
+                json!({ "url": { "type": "string" }, "timeout": { "type": "integer" } }),
+                &["url"],
+            ),
+        );
+        let new = catalog(1, args(json!({ "url": { "type": "string" } }), &["url"]));
+        assert_eq!(
+            breaking_changes(&old, &new),
+            ["parameter `timeout` of action `fetch_action` was removed"]
+        );
+    }
+
+    #[test]
+    fn detects_changed_type() {
+        let old = catalog(1, args(json!({ "timeout": { "type": "integer" } }), &[]));
+        let new = catalog(1, args(json!({ "timeout": { "type": "string" } }), &[]));
+        assert_eq!(
+            breaking_changes(&old, &new),
+            ["parameter `timeout` of action `fetch_action` changed its type"]
+        );
+
+        // 可选值减少同样视为不兼容
+        let old = catalog(
+            1,
+            args(json!({ "method": { "enum": ["Get", "Post"] } }), &[]),
+        );
+        let new = catalog(1, args(json!({ "method": { "enum": ["Get"] } }), &[]));
+        assert_eq!(breaking_changes(&old, &new).len(), 1);
+    }
+
+    #[test]
+    fn detects_new_required_param() {
+        let old = catalog(1, args(json!({ "url": { "type": "string" } }), &["url"]));
+        let new = catalog(
+            1,
+            args(
+                json!({ "url": { "type": "string" }, "method": { "type": "string" } }),
+                &["url", "method"],
+            ),
+        );
+        assert_eq!(
+            breaking_changes(&old, &new),
+            ["action `fetch_action` now requires parameter `method`"]
+        );
+    }
+
+    #[test]
+    fn skips_args_of_migrated_actions() {
+        let old = catalog(1, args(json!({ "timeout_ms": { "type": "integer" } }), &[]));
+        let new = catalog(
+            2,
+            args(json!({ "timeout": { "type": "string" } }), &["timeout"]),
+        );
+        assert!(breaking_changes(&old, &new).is_empty());
+
+        // 迁移只处理参数，分支的变化仍然报告
+        let mut new = new;
+        new["actions"]["fetch_action"]["branches"]["Success"]["plug"] = json!({ "type": "number" });
+        assert_eq!(
+            breaking_changes(&old, &new),
+            ["plug of branch `Success` of action `fetch_action` changed"]
+        );
+    }
+
+    #[test]
+    fn detects_removed_cards() {
+        let old = catalog(1, args(json!({}), &[]));
+        let new = json!({ "version": CATALOG_VERSION, "actions": {} });
+        assert_eq!(
+            breaking_changes(&old, &new),
+            ["action `fetch_action` was removed"]
+        );
+    }
+}
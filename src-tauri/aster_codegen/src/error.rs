@@ -10,6 +10,27 @@ pub enum Error {
     WriteFileError(PathBuf, String),
     #[error("Failed to emit TypeScript code: {0}")]
     EmitTsCodeError(String),
+    #[error("Found {0} error(s) in action and trigger sources")]
+    ValidateError(usize),
+    #[error("Found {0} breaking change(s) in the card catalog")]
+    BreakingChangeError(usize),
+    #[error("{0} generated file(s) are out of date, run aster_codegen to update them")]
+    StaleOutputError(usize),
+    #[error("Unknown locale format `{0}`, expected one of `json`, `po`, `ftl`")]
+    UnknownLocaleFormat(String),
+    #[error("Keys `{0}` and `{1}` map to the same Fluent identifier `{2}`")]
+    FluentIdCollision(String, String, String),
+}
+
+/// 按 `文件:行:列: error: 信息` 的格式输出每一条错误
+pub fn format_syn_error(path: &str, error: syn::Error) -> Vec<String> {
+    error
+        .into_iter()
+        .map(|e| {
+            let start = e.span().start();
+            format!("{}:{}:{}: error: {}", path, start.line, start.column + 1, e)
+        })
+        .collect()
 }
 
 pub(crate) type Result<T> = std::result::Result<T, Error>;
@@ -1,3 +1,4 @@
 pub mod i18n;
 pub mod cargo_metadata;
-pub mod error;
\ No newline at end of file
+pub mod error;
+pub mod trigger;
\ No newline at end of file
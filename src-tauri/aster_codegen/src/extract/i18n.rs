@@ -1,3 +1,2 @@
 pub mod action;
 pub mod options;
-pub mod result;
\ No newline at end of file
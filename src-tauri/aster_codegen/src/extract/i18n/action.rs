@@ -1,27 +1,43 @@
-use aster_common::action::param::{ParamInfo, parse_param_attributes};
-use aster_common::attr::parse_attr;
+use aster_common::action::param::ParamInfo;
 use aster_common::card::CardAttr;
-use aster_common::i18n::{ParamI18n, ParsedI18nMap};
+use aster_common::i18n::ParamI18n;
 use aster_common::utils::IntoString;
-use darling;
+use aster_common::validate::{
+    check_action, parse_i18n, parse_i18n_tokens, parse_param_attrs, split_action_attr,
+};
 use proc_macro2::TokenStream;
 use syn::{self, ItemFn, Meta, ReturnType, parse_quote};
 
 use crate::CardInfo;
 
-pub fn extract_i18n_from_action(action: &ItemFn) -> CardInfo {
+/// 提取 action 的多语言信息，`options` 为同一文件中声明的 #[options] 枚举
+pub fn extract_i18n_from_action(
+    action: &ItemFn,
+    languages: Option<&[String]>,
+    options: &[String],
+) -> syn::Result<CardInfo> {
+    let title = action
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("action"))
+        .and_then(|attr| attr.meta.require_list().ok())
+        .map(|list| list.tokens.clone())
+        .unwrap_or_default();
+    check_action(&title, action, languages, Some(options))?;
+    let (version, title) = split_action_attr(title)?;
+
     let action_name = action.sig.ident.clone();
     let action_name_str = action_name.to_string();
     let mut func_description_attrs = CardAttr::default();
 
-    let result_return_type;
-
-    match &action.sig.output {
-        ReturnType::Type(_, ty) => {
-            let ty = ty.as_ref();
-            result_return_type = ty.into_string();
+    let result_return_type = match &action.sig.output {
+        ReturnType::Type(_, ty) => ty.as_ref().into_string(),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &action.sig.ident,
+                "Return type is required",
+            ));
         }
-        _ => panic!("Return type is required"),
     };
 
     for attr in &action.attrs {
@@ -33,91 +49,53 @@ pub fn extract_i18n_from_action(action: &ItemFn) -> CardInfo {
                     func_description_attrs.entries.push(token_stream);
                 }
             }
-            ty @ ("description" | "action") => {
-                // let desc = vec![NestedMeta::Meta(attr.meta.clone())];
-                // match parse_param_attributes(&desc) {
-                //     Ok(parsed) => {
-                //         if ty == "description" {
-                //             func_description_attrs.description = parsed.description;
-                //         } else {
-                //             func_description_attrs.title = parsed.action;
-                //         }
-                //     }
-                //     Err(e) => {
-                //         eprintln!("解析函数 description 属性失败: {}", e);
-                //     }
-                // };
-                match parse_attr::<ParsedI18nMap>(attr) {
-                    Ok((i18n, _)) => {
-                        if !i18n.is_empty() {
-                            if ty == "description" {
-                                func_description_attrs.description = Some(i18n);
-                            } else {
-                                func_description_attrs.title = Some(i18n);
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("解析函数 description 属性失败: {}", e);
-                    }
-                };
+            "description" => {
+                let i18n = parse_i18n(attr)?;
+                if !i18n.is_empty() {
+                    func_description_attrs.description = Some(i18n);
+                }
+            }
+            // 标题中可能带有 version，已经在上面取出
+            "action" => {
+                let i18n = parse_i18n_tokens(title.clone())?;
+                if !i18n.is_empty() {
+                    func_description_attrs.title = Some(i18n);
+                }
             }
             _ => (),
         }
     }
     let mut all_param_info: Vec<ParamInfo> = Vec::new();
 
-    // 遍历函数的所有参数，提取参数名和类型
+    // 遍历函数的所有参数，提取参数名和类型，参数已经由 check_action 校验过
     for arg in action.sig.inputs.iter() {
-        match arg {
-            // 不允许使用 self 参数，因为这是静态函数
-            syn::FnArg::Receiver(_) => panic!("'self' is not allowed!"),
-            syn::FnArg::Typed(typed_param) => {
-                if let syn::Pat::Ident(pat_ident) = *typed_param.pat.clone() {
-                    let param_name = &pat_ident.ident;
-                    let param_type = &typed_param.ty;
-                    let param_name_str = param_name.to_string();
-                    let param_type_str: TokenStream = parse_quote! { #param_type };
-                    let param_type_str = param_type_str.to_string();
-
-                    // 使用 Darling 解析参数属性
-                    match parse_param_attributes(
-                        &typed_param
-                            .attrs
-                            .iter()
-                            .map(|e| darling::ast::NestedMeta::Meta(e.meta.clone()))
-                            .collect::<Vec<_>>(),
-                    ) {
-                        Ok(param_attr) => {
-                            // 创建参数信息并存储
-                            let param_info =
-                                ParamInfo::new(param_name_str.clone(), param_type_str, param_attr);
-                            all_param_info.push(param_info);
-                        }
-                        Err(e) => {
-                            // 如果解析失败，创建一个空的属性配置
-                            eprintln!("解析参数 '{}' 的属性失败: {}", param_name_str, e);
-                            let param_info = ParamInfo::new(
-                                param_name_str.clone(),
-                                param_type_str,
-                                Default::default(),
-                            );
-                            all_param_info.push(param_info);
-                        }
-                    }
-                } else {
-                    panic!("Unsupported pattern")
-                }
-            }
+        let syn::FnArg::Typed(typed_param) = arg else {
+            continue;
+        };
+        let syn::Pat::Ident(pat_ident) = typed_param.pat.as_ref() else {
+            continue;
+        };
+        let param_name_str = pat_ident.ident.to_string();
+        let param_type = &typed_param.ty;
+        let param_type_str: TokenStream = parse_quote! { #param_type };
+        let param_type_str = param_type_str.to_string();
+
+        // Context 参数由运行时注入，不生成表单
+        if param_type_str == "Context" {
+            continue;
         }
+
+        let param_attr = parse_param_attrs(&typed_param.attrs, &[])?;
+        all_param_info.push(ParamInfo::new(param_name_str, param_type_str, param_attr));
     }
 
     let mut card_info =
         generate_action_processing(&action_name_str, &func_description_attrs, &all_param_info);
 
     card_info.result = result_return_type;
+    card_info.version = version;
 
-    card_info
+    Ok(card_info)
 }
 
 // 生成完整的 action 信息，包括 action 本身的多语言信息
@@ -138,6 +116,7 @@ fn generate_action_processing(
                 key: param.name.clone(),
                 name: name_info,
                 r#type: param.r#type.clone(),
+                validate: param.attributes.get_validate_rule(),
             }
         })
         .collect();
@@ -158,5 +137,6 @@ fn generate_action_processing(
         entries: func_description_attrs.entries.clone(),
         keys: func_description_attrs.keys.clone(),
         params: params_i18n,
+        version: 1,
     }
 }
@@ -1,28 +1,25 @@
-use aster_common::action::{form::FormData, param::parse_param_attributes};
+use aster_common::action::form::FormData;
 use aster_common::i18n::Label;
-use darling::ast::NestedMeta;
+use aster_common::validate::{check_options, parse_i18n};
 use syn::ItemEnum;
 
-pub fn extract_i18n_from_options(item: &ItemEnum) -> FormData {
+pub fn extract_i18n_from_options(
+    item: &ItemEnum,
+    languages: Option<&[String]>,
+) -> syn::Result<FormData> {
+    check_options(item, languages)?;
     let mut options: Vec<_> = Vec::new();
-    for (idx, variant) in item.variants.iter().enumerate() {
-        options.push(Label {
-            label: None,
-            value: variant.ident.to_string(),
-        });
+    for variant in item.variants.iter() {
+        let mut label = None;
         for attr in variant.attrs.iter() {
-            match parse_param_attributes(&[NestedMeta::Meta(attr.meta.clone())]) {
-                Ok(parsed) => {
-                    options[idx] = Label {
-                        label: parsed.label,
-                        value: variant.ident.to_string(),
-                    };
-                }
-                Err(e) => {
-                    eprintln!("解析选项属性失败: {}", e);
-                }
+            if attr.path().is_ident("label") {
+                label = Some(parse_i18n(attr)?);
             }
         }
+        options.push(Label {
+            label,
+            value: variant.ident.to_string(),
+        });
     }
-    FormData::Option(options)
+    Ok(FormData::Option(options))
 }
@@ -0,0 +1,244 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use swc_common::{BytePos, Spanned};
+use swc_ecma_ast::{CallExpr, Callee, Expr, Lit, ObjectLit, Prop, PropName, PropOrSpread, Str};
+use swc_ecma_parser::{Parser, StringInput, Syntax, TsSyntax, lexer::Lexer};
+use swc_ecma_visit::{Visit, VisitWith};
+
+/// 触发器卡片的信息，来自前端 `defineCard({ ... })` 中的字面量
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct TriggerInfo {
+    pub name: String,
+    pub parent: String,
+    /// 参数名与参数类型，如 `{ value: "String" }`
+    pub args: BTreeMap<String, String>,
+    pub title: BTreeMap<String, String>,
+    pub description: BTreeMap<String, String>,
+    /// 触发时放入 context 的数据，与 action 的插头使用相同的格式
+    pub context: BTreeMap<String, Value>,
+}
+
+// 字符串字面量的值，从原始文本中取出以兼容单双引号
+fn str_value(str: &Str) -> Option<String> {
+    let raw = str.raw.as_ref()?.to_string();
+    if raw.starts_with('"') {
+        return serde_json::from_str(&raw).ok();
+    }
+    let inner = raw.strip_prefix('\'')?.strip_suffix('\'')?;
+    Some(inner.replace("\\'", "'").replace("\\\\", "\\"))
+}
+
+fn prop_name(name: &PropName) -> Option<String> {
+    match name {
+        PropName::Ident(ident) => Some(ident.sym.to_string()),
+        PropName::Str(str) => str_value(str),
+        _ => None,
+    }
+}
+
+fn props(object: &ObjectLit) -> impl Iterator<Item = (String, &Expr)> {
+    object.props.iter().filter_map(|prop| match prop {
+        PropOrSpread::Prop(prop) => match prop.as_ref() {
+            Prop::KeyValue(kv) => Some((prop_name(&kv.key)?, kv.value.as_ref())),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+// 只处理由字面量组成的部分，函数等其余表达式忽略
+fn to_value(expr: &Expr) -> Option<Value> {
+    match expr {
+        Expr::Lit(Lit::Str(str)) => str_value(str).map(Value::String),
+        Expr::Lit(Lit::Num(num)) => serde_json::Number::from_f64(num.value).map(Value::Number),
+        Expr::Lit(Lit::Bool(bool)) => Some(Value::Bool(bool.value)),
+        Expr::Lit(Lit::Null(_)) => Some(Value::Null),
+        Expr::Object(object) => Some(Value::Object(
+            props(object)
+                .filter_map(|(key, value)| Some((key, to_value(value)?)))
+                .collect(),
+        )),
+        Expr::Array(array) => Some(Value::Array(
+            array
+                .elems
+                .iter()
+                .flatten()
+                .filter_map(|elem| to_value(&elem.expr))
+                .collect(),
+        )),
+        Expr::Paren(paren) => to_value(&paren.expr),
+        Expr::TsAs(ts_as) => to_value(&ts_as.expr),
+        Expr::TsConstAssertion(assertion) => to_value(&assertion.expr),
+        _ => None,
+    }
+}
+
+fn to_string_map(value: Option<&Value>) -> BTreeMap<String, String> {
+    value
+        .and_then(Value::as_object)
+        .map(|object| {
+            object
+                .iter()
+                .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[derive(Default)]
+struct DefineCardVisitor {
+    triggers: Vec<TriggerInfo>,
+}
+
+impl Visit for DefineCardVisitor {
+    fn visit_call_expr(&mut self, node: &CallExpr) {
+        node.visit_children_with(self);
+        let Callee::Expr(callee) = &node.callee else {
+            return;
+        };
+        if !matches!(callee.as_ref(), Expr::Ident(ident) if &*ident.sym == "defineCard") {
+            return;
+        }
+        let Some(Expr::Object(object)) = node.args.first().map(|arg| arg.expr.as_ref()) else {
+            return;
+        };
+        let Some(Value::Object(card)) = to_value(&Expr::Object(object.clone())) else {
+            return;
+        };
+        let mut info = TriggerInfo {
+            name: card
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            parent: card
+                .get("parent")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            args: to_string_map(card.get("args")),
+            context: card
+                .get("context")
+                .and_then(Value::as_object)
+                .map(|context| context.clone().into_iter().collect())
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+        // i18n 的键为前端使用的语言名，如 zh-CN，统一为 zh_cn
+        if let Some(Value::Object(i18n)) = card.get("i18n") {
+            for (lang, messages) in i18n {
+                let lang = lang.replace('-', "_").to_lowercase();
+                if let Some(title) = messages.get("title").and_then(Value::as_str) {
+                    info.title.insert(lang.clone(), title.to_string());
+                }
+                if let Some(description) = messages.get("description").and_then(Value::as_str) {
+                    info.description.insert(lang, description.to_string());
+                }
+            }
+        }
+        if !info.name.is_empty() {
+            self.triggers.push(info);
+        }
+    }
+}
+
+// 与 action 源码的诊断使用相同的 `文件:行:列: error: 信息` 格式
+fn format_parse_error(path: &Path, src: &str, error: swc_ecma_parser::error::Error) -> String {
+    // 源码从 BytePos(1) 开始
+    let offset = error.span().lo.0.saturating_sub(1) as usize;
+    let before = src.get(..offset).unwrap_or(src);
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or_default().chars().count() + 1;
+    format!(
+        "{}:{}:{}: error: {}",
+        path.display(),
+        line,
+        column,
+        error.kind().msg()
+    )
+}
+
+/// 提取目录下所有触发器卡片的信息，按名称排序，无法解析的文件返回全部诊断
+pub fn extract_triggers(dir: &Path) -> Result<Vec<TriggerInfo>, Vec<String>> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(vec![]);
+    };
+    let mut visitor = DefineCardVisitor::default();
+    let mut diagnostics = vec![];
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        let tsx = path.extension().is_some_and(|ext| ext == "tsx");
+        if !tsx && !path.extension().is_some_and(|ext| ext == "ts") {
+            continue;
+        }
+        let src = match fs::read_to_string(&path) {
+            Ok(src) => src,
+            Err(e) => {
+                diagnostics.push(format!("{}: error: {}", path.display(), e));
+                continue;
+            }
+        };
+        let lexer = Lexer::new(
+            Syntax::Typescript(TsSyntax {
+                tsx,
+                ..Default::default()
+            }),
+            swc_ecma_ast::EsVersion::Es2022,
+            StringInput::new(&src, BytePos(1), BytePos(1 + src.len() as u32)),
+            None,
+        );
+        let mut parser = Parser::new_from(lexer);
+        let module = parser.parse_module();
+        let mut errors = parser.take_errors();
+        match module {
+            Ok(module) if errors.is_empty() => module.visit_with(&mut visitor),
+            Ok(_) => (),
+            Err(e) => errors.push(e),
+        }
+        diagnostics.extend(
+            errors
+                .into_iter()
+                .map(|e| format_parse_error(&path, &src, e)),
+        );
+    }
+    if !diagnostics.is_empty() {
+        return Err(diagnostics);
+    }
+    visitor.triggers.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(visitor.triggers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(name: &str, src: &str) -> Result<Vec<TriggerInfo>, Vec<String>> {
+        let dir = std::env::temp_dir().join(format!("aster-codegen-triggers-{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("trigger.ts"), src).unwrap();
+        let result = extract_triggers(&dir);
+        let _ = fs::remove_dir_all(&dir);
+        result
+    }
+
+    #[test]
+    fn extracts_defined_cards() {
+        let triggers = extract(
+            "ok",
+            "export default defineCard({ name: 'timer', parent: 'trigger' });",
+        )
+        .unwrap();
+        assert_eq!(triggers.len(), 1);
+        assert_eq!(triggers[0].name, "timer");
+    }
+
+    #[test]
+    fn reports_unparsable_files() {
+        let diagnostics = extract("broken", "const a = ;\n").unwrap_err();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].contains("trigger.ts:1:11: error:"), "{}", diagnostics[0]);
+    }
+}
@@ -1,11 +1,14 @@
+pub mod catalog;
 pub mod error;
+pub mod locale;
+pub mod output;
 pub mod utils;
 use std::{collections::HashMap, fs::read_to_string, path::PathBuf};
 pub mod extract;
 
 use aster_common::{
     action::{
-        form::{FormItem, FormType},
+        form::{FormData, FormItem, FormType},
         stat::Stat,
     },
     collect::{FormDataCollect, ResultBranchTypeCollect},
@@ -18,7 +21,7 @@ use aster_common::{
 use serde::{Deserialize, Serialize};
 use serde_json::Map;
 use swc_atoms::Atom;
-use swc_common::{SourceMap, Spanned};
+use swc_common::{BytePos, SourceMap, Spanned};
 use swc_ecma_ast::{Expr, Ident};
 use swc_ecma_codegen::{
     Emitter,
@@ -29,10 +32,11 @@ use swc_ecma_visit::{VisitMut, VisitMutWith};
 
 use crate::{
     error::Result,
+    output::Output,
     utils::{FromType, IntoI18nValueList},
 };
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CardInfo {
     #[serde(default)]
     pub title: ParsedI18nMap,
@@ -48,6 +52,9 @@ pub struct CardInfo {
     pub entries: Vec<String>,
     pub keys: Vec<String>,
     pub params: Vec<ParamI18n>,
+    /// `#[action(version = N)]` 声明的参数版本
+    #[serde(default)]
+    pub version: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -93,6 +100,7 @@ pub struct GenerateCodeContext<'a> {
     pub card_info_list: &'a mut Vec<CardInfo>,
     pub form_data_list: &'a mut Vec<FormDataCollect>,
     pub result_branch_list: &'a mut Vec<ResultBranchTypeCollect>,
+    pub output: &'a Output,
 }
 
 pub fn generate_code(ctx: &GenerateCodeContext) -> Result<()> {
@@ -114,18 +122,27 @@ pub fn create_replacement(
             card_info
                 .params
                 .iter()
-                .map(|ParamI18n { key, r#type, .. }| {
+                .map(|ParamI18n { key, r#type, validate, .. }| {
                     let form_type = FormType::from_type(r#type.clone());
                     let args_type = form_type.get_args_type();
                     let _effect = {
                         let width = form_type.get_width();
+                        let args_type =
+                            FormType::get_inner_type(r#type).unwrap_or(r#type.clone());
+                        // Directory 同样使用文件选择器，但只允许选择目录
+                        let data = match args_type.as_str() {
+                            "File" => Some(FormData::File { directory: false }),
+                            "Directory" => Some(FormData::File { directory: true }),
+                            _ => None,
+                        };
                         form.push(FormItem {
-                            args_type: FormType::get_inner_type(r#type).unwrap_or(r#type.clone()),
+                            args_type,
                             name: key.clone(),
                             optional: form_type.is_optional(),
                             r#type: form_type,
                             default: None,
-                            data: None,
+                            data,
+                            validate: validate.clone(),
                         });
 
                         stat.push(Stat {
@@ -283,7 +300,7 @@ fn meta_data_to_typescript(ctx: &GenerateCodeContext) -> Result<()> {
             ..Default::default()
         }),
         swc_ecma_ast::EsVersion::Es2022,
-        StringInput::new(&src, Default::default(), Default::default()),
+        StringInput::new(&src, BytePos(1), BytePos(1 + src.len() as u32)),
         None,
     );
     let mut parser = Parser::new_from(lexer);
@@ -335,13 +352,10 @@ fn meta_data_to_typescript(ctx: &GenerateCodeContext) -> Result<()> {
         }
 
         let output = String::from_utf8(buffer).expect("Invalid UTF-8");
-        std::fs::write(
-            target.clone(),
-            [output, split_tag, extend_part.trim_start().to_string()]
-                .join("\n")
-                .to_string(),
-        )
-        .map_err(|e| error::Error::WriteFileError(target, e.to_string()))?;
+        ctx.output.write(
+            &target,
+            [output, split_tag, extend_part.trim_start().to_string()].join("\n"),
+        )?;
     }
     Ok(())
 }
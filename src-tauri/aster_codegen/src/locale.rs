@@ -0,0 +1,441 @@
+//! 多语言提取：将卡片标题、参数名称与描述、选项与分支的标签按语言输出为翻译目录，
+//! 并统计每种语言缺失的翻译
+//!
+//! 键按命名空间组织，如 `action.fetch_action.title`、`options.Method.Get`、`result.FetchResult.Success`
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+    str::FromStr,
+};
+
+use aster_common::{
+    action::form::FormData,
+    i18n::{I18nConfig, ParsedI18nMapTrait, locale_tag},
+};
+use serde_json::json;
+
+use crate::{
+    GenerateCodeContext,
+    error::{Error, Result},
+    extract::trigger::TriggerInfo,
+    output::Output,
+};
+
+/// 键与各语言的翻译
+pub type Messages = BTreeMap<String, BTreeMap<String, String>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocaleFormat {
+    Json,
+    Po,
+    Fluent,
+}
+
+impl FromStr for LocaleFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "json" => Ok(LocaleFormat::Json),
+            "po" => Ok(LocaleFormat::Po),
+            "ftl" | "fluent" => Ok(LocaleFormat::Fluent),
+            _ => Err(Error::UnknownLocaleFormat(s.to_string())),
+        }
+    }
+}
+
+impl LocaleFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            LocaleFormat::Json => "json",
+            LocaleFormat::Po => "po",
+            LocaleFormat::Fluent => "ftl",
+        }
+    }
+}
+
+// 相同的键只保留第一次出现的内容，如多个 action 返回同一个 #[result] 枚举
+fn insert(messages: &mut Messages, key: String, i18n: HashMap<String, String>) {
+    messages
+        .entry(key)
+        .or_insert_with(|| i18n.into_iter().collect());
+}
+
+/// 收集所有 action 与触发器中需要翻译的内容
+pub fn collect_messages(ctx: &GenerateCodeContext, triggers: &[TriggerInfo]) -> Messages {
+    let mut messages = Messages::new();
+    for card in ctx.card_info_list.iter() {
+        let ns = format!("action.{}", card.action_type);
+        insert(
+            &mut messages,
+            format!("{}.title", ns),
+            card.title.to_filter_value(),
+        );
+        insert(
+            &mut messages,
+            format!("{}.description", ns),
+            card.description.to_filter_value(),
+        );
+        for param in card.params.iter() {
+            let ns = format!("{}.params.{}", ns, param.key);
+            insert(&mut messages, format!("{}.name", ns), param.name.clone());
+            insert(
+                &mut messages,
+                format!("{}.description", ns),
+                param.description.clone(),
+            );
+        }
+    }
+    for trigger in triggers {
+        let ns = format!("trigger.{}", trigger.name);
+        insert(
+            &mut messages,
+            format!("{}.title", ns),
+            trigger.title.clone().into_iter().collect(),
+        );
+        insert(
+            &mut messages,
+            format!("{}.description", ns),
+            trigger.description.clone().into_iter().collect(),
+        );
+    }
+    for form in ctx.form_data_list.iter() {
+        let ns = format!("options.{}", form.name);
+        match &form.data {
+            FormData::Option(labels) | FormData::AutoComplete(labels) => {
+                for label in labels {
+                    let i18n = label
+                        .label
+                        .as_ref()
+                        .map(|label| label.to_filter_value())
+                        .unwrap_or_default();
+                    insert(&mut messages, format!("{}.{}", ns, label.value), i18n);
+                }
+            }
+            FormData::Input { placeholder } => {
+                insert(
+                    &mut messages,
+                    format!("{}.placeholder", ns),
+                    placeholder.to_filter_value(),
+                );
+            }
+            _ => (),
+        }
+    }
+    for result in ctx.result_branch_list.iter() {
+        for branch in result.data.iter() {
+            // 只有枚举自身的分支带有标签，嵌套的 #[result] 在各自的枚举中收集
+            if branch.id != result.name {
+                continue;
+            }
+            insert(
+                &mut messages,
+                format!("result.{}.{}", result.name, branch.branch),
+                branch.get_i18n(),
+            );
+        }
+    }
+    // 没有任何翻译的内容不需要翻译，如未声明标签的选项
+    messages.retain(|_, i18n| !i18n.is_empty());
+    messages
+}
+
+/// 每种语言缺失翻译的键，使用回退链中的语言不算作已翻译
+pub fn missing_keys<'a>(
+    messages: &'a Messages,
+    config: &I18nConfig,
+) -> BTreeMap<String, Vec<&'a str>> {
+    config
+        .languages
+        .iter()
+        .map(|language| {
+            let missing = messages
+                .iter()
+                .filter(|(_, i18n)| !i18n.contains_key(language))
+                .map(|(key, _)| key.as_str())
+                .collect();
+            (language.clone(), missing)
+        })
+        .collect()
+}
+
+/// 输出每种语言的覆盖率与缺失的键
+pub fn coverage_report(messages: &Messages, config: &I18nConfig) -> String {
+    let mut report = vec![];
+    for (language, missing) in missing_keys(messages, config) {
+        let total = messages.len();
+        let translated = total - missing.len();
+        let percent = match total {
+            0 => 100.0,
+            _ => translated as f64 * 100.0 / total as f64,
+        };
+        report.push(format!(
+            "{}: {}/{} ({:.1}%)",
+            language, translated, total, percent
+        ));
+        for key in missing {
+            // 标明实际显示时使用的语言
+            let fallback = config
+                .fallback_chain(&language)
+                .into_iter()
+                .find(|fallback| messages[key].contains_key(*fallback));
+            match fallback {
+                Some(fallback) => {
+                    report.push(format!("  missing {} (falls back to {})", key, fallback))
+                }
+                None => report.push(format!("  missing {}", key)),
+            }
+        }
+    }
+    report.join("\n")
+}
+
+fn po_string(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
+// Fluent 的标识符以字母开头，只能包含字母、数字、`-` 与 `_`
+fn fluent_id(key: &str) -> String {
+    let id = key
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => c,
+            _ => '-',
+        })
+        .collect::<String>();
+    match id.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        true => id,
+        false => format!("m-{}", id),
+    }
+}
+
+/// 为每个键分配 Fluent 标识符，替换字符后相同的键无法区分，视为错误
+fn fluent_ids(messages: &Messages) -> Result<BTreeMap<&str, String>> {
+    let mut keys = HashMap::new();
+    let mut ids = BTreeMap::new();
+    for key in messages.keys() {
+        let id = fluent_id(key);
+        if let Some(other) = keys.insert(id.clone(), key) {
+            return Err(Error::FluentIdCollision(other.clone(), key.clone(), id));
+        }
+        ids.insert(key.as_str(), id);
+    }
+    Ok(ids)
+}
+
+// 文本中的花括号与行首的 `[`、`*`、`.` 是 Fluent 的语法，以字符串字面量的形式写出
+fn fluent_text(line: &str) -> String {
+    let mut text = String::new();
+    for (index, c) in line.chars().enumerate() {
+        match c {
+            '{' | '}' => text.push_str(&format!("{{\"{}\"}}", c)),
+            '[' | '*' | '.' if index == 0 => text.push_str(&format!("{{\"{}\"}}", c)),
+            _ => text.push(c),
+        }
+    }
+    text
+}
+
+fn render(
+    messages: &Messages,
+    config: &I18nConfig,
+    language: &str,
+    format: LocaleFormat,
+) -> Result<String> {
+    let content = match format {
+        LocaleFormat::Json => {
+            let catalog = messages
+                .iter()
+                .filter_map(|(key, i18n)| Some((key, i18n.get(language)?)))
+                .collect::<BTreeMap<_, _>>();
+            let mut content = serde_json::to_string_pretty(&catalog).unwrap_or_default();
+            content.push('\n');
+            content
+        }
+        LocaleFormat::Po => {
+            let mut content = vec![
+                "msgid \"\"".to_string(),
+                "msgstr \"\"".to_string(),
+                po_string("Content-Type: text/plain; charset=UTF-8\n"),
+                po_string(&format!("Language: {}\n", locale_tag(language))),
+            ];
+            for (key, i18n) in messages {
+                // msgid 为默认语言的内容，缺失时按回退链取
+                let source = config
+                    .resolve(&config.default, |language| i18n.get(language))
+                    .or_else(|| i18n.values().next())
+                    .map(String::as_str)
+                    .unwrap_or(key.as_str());
+                content.push(String::new());
+                content.push(format!("msgctxt {}", po_string(key)));
+                content.push(format!("msgid {}", po_string(source)));
+                content.push(format!(
+                    "msgstr {}",
+                    po_string(i18n.get(language).map(String::as_str).unwrap_or_default())
+                ));
+            }
+            content.push(String::new());
+            content.join("\n")
+        }
+        LocaleFormat::Fluent => {
+            let ids = fluent_ids(messages)?;
+            let mut content = vec![];
+            for (key, i18n) in messages {
+                let id = &ids[key.as_str()];
+                match i18n.get(language) {
+                    Some(value) if value.contains('\n') => {
+                        content.push(format!("{} =", id));
+                        for line in value.lines() {
+                            content.push(format!("    {}", fluent_text(line)));
+                        }
+                    }
+                    Some(value) => content.push(format!("{} = {}", id, fluent_text(value))),
+                    None => content.push(format!("# missing: {}", id)),
+                }
+            }
+            content.push(String::new());
+            content.join("\n")
+        }
+    };
+    Ok(content)
+}
+
+/// 按语言写入翻译目录与覆盖率 `coverage.json`
+pub fn write_locales(
+    output: &Output,
+    dir: &Path,
+    messages: &Messages,
+    config: &I18nConfig,
+    format: LocaleFormat,
+) -> Result<()> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| Error::WriteFileError(dir.to_path_buf(), e.to_string()))?;
+    for language in config.languages.iter() {
+        let path = dir.join(format!("{}.{}", language, format.extension()));
+        output.write(&path, render(messages, config, language, format)?)?;
+    }
+    let coverage = missing_keys(messages, config)
+        .into_iter()
+        .map(|(language, missing)| {
+            let coverage = json!({
+                "total": messages.len(),
+                "translated": messages.len() - missing.len(),
+                "missing": missing,
+            });
+            (language, coverage)
+        })
+        .collect::<BTreeMap<_, _>>();
+    let mut content = serde_json::to_string_pretty(&coverage).unwrap_or_default();
+    content.push('\n');
+    output.write(&dir.join("coverage.json"), content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> I18nConfig {
+        I18nConfig {
+            languages: ["zh_cn", "zh_tw", "en"]
+                .iter()
+                .map(|language| language.to_string())
+                .collect(),
+            fallback: [("zh_tw".to_string(), vec!["zh_cn".to_string()])]
+                .into_iter()
+                .collect(),
+            default: "en".to_string(),
+        }
+    }
+
+    fn messages(entries: &[(&str, &[(&str, &str)])]) -> Messages {
+        entries
+            .iter()
+            .map(|(key, i18n)| {
+                let i18n = i18n
+                    .iter()
+                    .map(|(language, text)| (language.to_string(), text.to_string()))
+                    .collect();
+                (key.to_string(), i18n)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reports_missing_keys_with_fallback() {
+        let messages = messages(&[
+            ("action.a.title", &[("en", "A"), ("zh_cn", "甲")]),
+            ("action.b.title", &[("en", "B")]),
+        ]);
+        let missing = missing_keys(&messages, &config());
+        assert_eq!(missing["en"], Vec::<&str>::new());
+        assert_eq!(missing["zh_cn"], ["action.b.title"]);
+        assert_eq!(missing["zh_tw"], ["action.a.title", "action.b.title"]);
+
+        let report = coverage_report(&messages, &config());
+        assert!(report.contains("en: 2/2 (100.0%)"));
+        assert!(report.contains("zh_cn: 1/2 (50.0%)"));
+        assert!(report.contains("zh_tw: 0/2 (0.0%)"));
+        assert!(report.contains("  missing action.a.title (falls back to zh_cn)"));
+        assert!(report.contains("  missing action.b.title (falls back to en)"));
+    }
+
+    #[test]
+    fn renders_po() {
+        let messages = messages(&[(
+            "action.a.title",
+            &[("en", "Say \"hi\"\n"), ("zh_cn", "问候")],
+        )]);
+        let content = render(&messages, &config(), "zh_tw", LocaleFormat::Po).unwrap();
+        assert!(content.contains("\"Language: zh-TW\\n\""));
+        assert!(
+            content
+                .contains("msgctxt \"action.a.title\"\nmsgid \"Say \\\"hi\\\"\\n\"\nmsgstr \"\"\n")
+        );
+        let content = render(&messages, &config(), "zh_cn", LocaleFormat::Po).unwrap();
+        assert!(content.contains("msgstr \"问候\""));
+    }
+
+    #[test]
+    fn renders_fluent() {
+        let messages = messages(&[
+            ("action.a.title", &[("en", "Use {name}")]),
+            ("action.b.title", &[("en", "* first\n.second\nthird")]),
+            ("1st.title", &[("zh_cn", "第一")]),
+        ]);
+        let content = render(&messages, &config(), "en", LocaleFormat::Fluent).unwrap();
+        assert_eq!(
+            content,
+            [
+                "# missing: m-1st-title",
+                "action-a-title = Use {\"{\"}name{\"}\"}",
+                "action-b-title =",
+                "    {\"*\"} first",
+                "    {\".\"}second",
+                "    third",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn rejects_fluent_id_collisions() {
+        let messages = messages(&[
+            ("action.a_b", &[("en", "A")]),
+            ("action.a.b", &[("en", "B")]),
+            ("action-a-b", &[("en", "C")]),
+        ]);
+        let result = render(&messages, &config(), "en", LocaleFormat::Fluent);
+        assert!(matches!(
+            result,
+            Err(Error::FluentIdCollision(first, second, id))
+                if first == "action-a-b" && second == "action.a.b" && id == "action-a-b"
+        ));
+    }
+}
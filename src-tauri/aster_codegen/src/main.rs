@@ -1,29 +1,38 @@
 use std::{
-    env::current_dir,
+    collections::{BTreeMap, HashMap},
+    env,
     error::Error,
-    fs::{self, read_dir, read_to_string, write},
-    path::PathBuf,
+    fs::{self, read_dir, read_to_string},
+    path::{Path, PathBuf},
     str::FromStr,
+    thread,
+    time::Duration,
 };
 
 use aster_codegen::{
-    GenerateCodeContext,
+    CardInfo, GenerateCodeContext,
+    catalog::{breaking_changes, create_catalog, read_catalog, write_catalog},
+    error::format_syn_error,
     extract::{
         cargo_metadata::extract_cargo_matedata,
-        i18n::{
-            action::extract_i18n_from_action, options::extract_i18n_from_options,
-            result::extract_i18n_and_position_from_result,
-        },
+        i18n::{action::extract_i18n_from_action, options::extract_i18n_from_options},
+        trigger::extract_triggers,
     },
+    locale::{LocaleFormat, collect_messages, coverage_report, write_locales},
+    output::Output,
 };
 use aster_common::{
+    action::branch::extract_i18n_and_position_from_result,
     collect::{FormDataCollect, ResultBranchTypeCollect},
+    i18n::{I18nConfig, locale_tag},
     utils::IntoIdent,
+    validate::{Diagnostics, check_migrate, check_migrations, configured_languages},
 };
 use proc_macro2;
 use quote::quote;
-use syn::{self, Item, parse_quote, punctuated::Punctuated, token::Comma};
+use syn::{self, Item, punctuated::Punctuated, token::Comma};
 use toml_edit::{DocumentMut, Table, value};
+use vase_scan::index::{FileIndex, update_index};
 
 const ACTIONS_DIR: &str = "actions";
 
@@ -34,24 +43,220 @@ const PATH_CARGO_TOML: &str = "Cargo.toml";
 const PATH_SRC: &str = "src";
 const PATH_LIB_RS: &str = "lib.rs";
 
-struct ParseFileContext<'a, 'b: 'a> {
+const DIST_DIR: &str = "../src/invoke/actions";
+const TRIGGERS_DIR: &str = "../src/invoke/triggers";
+const PATH_CATALOG: &str = "catalog.json";
+const LOCALES_DIR: &str = "locales";
+/// 前端 vue-i18n 使用的回退链
+const PATH_FALLBACK: &str = "../src/i18n/fallback.json";
+
+/// 监听模式下检查文件变化的间隔
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 命令行参数
+/// - `--watch` 监听动作包的变化，只重新解析发生变化的动作包
+/// - `--check` 只检查生成的文件是否过期，不写入，可在提交前使用
+/// - `--deny-breaking` 卡片目录中存在不兼容的变化时失败
+/// - `--extract-i18n[=json|po|ftl]` 按语言输出翻译目录与覆盖率，默认为 json
+#[derive(Debug, Default)]
+struct Options {
+    watch: bool,
+    check: bool,
+    deny_breaking: bool,
+    extract_i18n: Option<LocaleFormat>,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, Box<dyn Error>> {
+        let mut options = Options::default();
+        for arg in env::args().skip(1) {
+            match arg.as_str() {
+                "--watch" => options.watch = true,
+                "--check" => options.check = true,
+                "--deny-breaking" => options.deny_breaking = true,
+                "--extract-i18n" => options.extract_i18n = Some(LocaleFormat::Json),
+                _ => match arg.strip_prefix("--extract-i18n=") {
+                    Some(format) => options.extract_i18n = Some(format.parse()?),
+                    None => eprintln!("Unknown argument: {}", arg),
+                },
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// 单个动作包的解析结果，监听模式下只重新解析发生变化的动作包
+#[derive(Debug, Default)]
+struct CrateOutput {
+    crate_name: String,
+    stmts: Vec<String>,
+    card_info_list: Vec<CardInfo>,
+    form_data_list: Vec<FormDataCollect>,
+    result_branch_list: Vec<ResultBranchTypeCollect>,
+    diagnostics: Vec<String>,
+}
+
+struct ParseFileContext<'a> {
     crate_name: &'a str,
     file: &'a syn::File,
     path: &'a str,
-    stmts: &'a mut Vec<String>,
-    generate_ctx: &'a mut GenerateCodeContext<'b>,
+    languages: Option<&'a [String]>,
+    output: &'a mut CrateOutput,
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let current_dir = current_dir()?;
-    let dir = PathBuf::from(ACTIONS_DIR);
-    let dir = read_dir(dir)?;
+    let options = Options::from_args()?;
+    if !options.watch {
+        let crates = read_crate_dirs()?
+            .into_iter()
+            .map(|dir| Ok((dir.clone(), parse_crate(&dir)?)))
+            .collect::<Result<BTreeMap<_, _>, Box<dyn Error>>>()?;
+        return generate(&crates, &options);
+    }
+
+    // 监听模式下的错误只输出，修改后重新生成
+    let mut index_map = HashMap::new();
+    let mut crates = BTreeMap::new();
+    loop {
+        if let Err(e) = watch(&mut index_map, &mut crates, &options) {
+            eprintln!("{}", e);
+        }
+        thread::sleep(WATCH_INTERVAL);
+    }
+}
+
+fn read_crate_dirs() -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut dirs = read_dir(ACTIONS_DIR)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect::<Vec<_>>();
+    // 保持输出的顺序稳定
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// 检查一次文件变化，重新解析发生变化的动作包，存在变化时重新生成
+fn watch(
+    index_map: &mut HashMap<PathBuf, FileIndex>,
+    crates: &mut BTreeMap<PathBuf, CrateOutput>,
+    options: &Options,
+) -> Result<(), Box<dyn Error>> {
+    let dirs = read_crate_dirs()?;
+    let removed = crates.len();
+    crates.retain(|dir, _| dirs.contains(dir));
+    let mut changed = removed != crates.len();
+    for dir in dirs {
+        let mut crate_changed = !crates.contains_key(&dir);
+        for file in [
+            dir.join(PATH_CARGO_TOML),
+            dir.join(PATH_SRC).join(PATH_LIB_RS),
+        ] {
+            crate_changed |= update_index(index_map, &file)?;
+        }
+        if crate_changed {
+            println!("Parsing {}", dir.display());
+            crates.insert(dir.clone(), parse_crate(&dir)?);
+            changed = true;
+        }
+    }
+    // 触发器只影响卡片目录
+    if let Ok(entries) = read_dir(TRIGGERS_DIR) {
+        for entry in entries.filter_map(Result::ok) {
+            changed |= update_index(index_map, &entry.path())?;
+        }
+    }
+    if changed {
+        generate(crates, options)?;
+    }
+    Ok(())
+}
 
-    let manifest_rs = current_dir
-        .join(PATH_ASTER_LOADER)
+/// vue-i18n 的 fallbackLocale，前端的语言名如 zh-CN 先回退到生成代码中的 zh_cn，再按回退链回退
+fn fallback_locales(config: &I18nConfig) -> Result<String, Box<dyn Error>> {
+    let mut fallback = BTreeMap::new();
+    for language in config.languages.iter() {
+        let tag = locale_tag(language);
+        let chain = std::iter::once(language.as_str())
+            .chain(config.fallback_chain(language))
+            .filter(|locale| *locale != tag)
+            .collect::<Vec<_>>();
+        fallback.insert(tag, chain);
+    }
+    fallback.insert("default".to_string(), vec![config.default.as_str()]);
+    let mut content = serde_json::to_string_pretty(&fallback)?;
+    content.push('\n');
+    Ok(content)
+}
+
+/// 解析动作包中的 action、#[options] 与 #[result]，源码中的错误记录在 diagnostics 中
+fn parse_crate(dir: &Path) -> Result<CrateOutput, Box<dyn Error>> {
+    let cargo_metadata = extract_cargo_matedata(&dir.join(PATH_CARGO_TOML))?;
+    let crate_name = cargo_metadata.package.name;
+
+    let src_dir = dir.join(PATH_SRC);
+    if !src_dir.exists() {
+        return Err(aster_codegen::error::Error::PathNotExist(src_dir.clone()).into());
+    }
+    let lib_rs = fs::read_to_string(src_dir.join(PATH_LIB_RS))?;
+    let lib_rs_path = format!(
+        "{}/{}/{}/{}",
+        ACTIONS_DIR, &crate_name, PATH_SRC, PATH_LIB_RS
+    );
+
+    let mut output = CrateOutput {
+        crate_name: crate_name.clone(),
+        ..Default::default()
+    };
+    // 监听模式下源码可能正在编辑，无法解析时同样作为错误输出
+    let file = proc_macro2::TokenStream::from_str(&lib_rs)
+        .map_err(syn::Error::from)
+        .and_then(syn::parse2::<syn::File>);
+    let file = match file {
+        Ok(file) => file,
+        Err(e) => {
+            output.diagnostics = format_syn_error(&lib_rs_path, e);
+            return Ok(output);
+        }
+    };
+    let languages = configured_languages(dir);
+
+    let result = parse_action(ParseFileContext {
+        crate_name: &crate_name,
+        file: &file,
+        path: &lib_rs_path,
+        languages: languages.as_deref(),
+        output: &mut output,
+    });
+    if let Err(e) = result {
+        output.diagnostics = format_syn_error(&lib_rs_path, e);
+    }
+    Ok(output)
+}
+
+/// 根据所有动作包的解析结果生成 TypeScript、manifest.rs 与卡片目录
+fn generate(
+    crates: &BTreeMap<PathBuf, CrateOutput>,
+    options: &Options,
+) -> Result<(), Box<dyn Error>> {
+    // 与编译器一致，存在错误时不生成任何文件
+    let triggers = extract_triggers(Path::new(TRIGGERS_DIR));
+    let diagnostics = crates
+        .values()
+        .flat_map(|output| output.diagnostics.iter())
+        .chain(triggers.as_ref().err().into_iter().flatten())
+        .collect::<Vec<_>>();
+    if !diagnostics.is_empty() {
+        for diagnostic in diagnostics.iter() {
+            eprintln!("{}", diagnostic);
+        }
+        return Err(aster_codegen::error::Error::ValidateError(diagnostics.len()).into());
+    }
+
+    let loader_cargo_metadata_path = PathBuf::from(PATH_ASTER_LOADER).join(PATH_CARGO_TOML);
+    let manifest_rs = PathBuf::from(PATH_ASTER_LOADER)
         .join(PATH_SRC)
         .join(PATH_MANIFEST_RS);
-    let loader_cargo_metadata_path = PathBuf::from(PATH_ASTER_LOADER).join(PATH_CARGO_TOML);
 
     let mut loader_cargo_metadata =
         read_to_string(&loader_cargo_metadata_path)?.parse::<DocumentMut>()?;
@@ -64,64 +269,91 @@ fn main() -> Result<(), Box<dyn Error>> {
         }
         None => Table::default(),
     };
-    println!("{:?}", deps_table);
+    for output in crates.values() {
+        let mut dep_details = Table::default();
+        dep_details.insert(
+            "path",
+            value(format!("../{}/{}", ACTIONS_DIR, output.crate_name)),
+        );
+        deps_table.insert(&output.crate_name, dep_details.into());
+    }
 
     let mut card_info_list = vec![];
     let mut form_data_list = vec![];
     let mut result_branch_list = vec![];
+    for output in crates.values() {
+        card_info_list.extend(output.card_info_list.iter().cloned());
+        form_data_list.extend(output.form_data_list.iter().cloned());
+        result_branch_list.extend(output.result_branch_list.iter().cloned());
+    }
 
-    let mut ctx = GenerateCodeContext {
-        dist: PathBuf::from("../src/invoke/actions"),
+    let output = Output::new(options.check);
+    let ctx = GenerateCodeContext {
+        dist: PathBuf::from(DIST_DIR),
         card_info_list: &mut card_info_list,
         form_data_list: &mut form_data_list,
         result_branch_list: &mut result_branch_list,
+        output: &output,
     };
-    let mut stmts: Vec<String> = vec![];
-    for entry in dir.into_iter().filter_map(Result::ok) {
-        let path = entry.path();
-        let cargo_metadata_path = path.join(PATH_CARGO_TOML);
-        let cargo_metadata = extract_cargo_matedata(&cargo_metadata_path)?;
-        let crate_name = cargo_metadata.package.name;
 
-        let mut dep_details = Table::default();
-        dep_details.insert("path", value(format!("../{}/{}", ACTIONS_DIR, crate_name)));
-        deps_table.insert(&crate_name, dep_details.into());
+    aster_codegen::generate_code(&ctx)?;
 
-        let src_dir = path.join(PATH_SRC);
-        if !src_dir.exists() {
-            return Err(aster_codegen::error::Error::PathNotExist(src_dir.clone()).into());
+    // 与上次提交的目录比较，CI 中传入 --deny-breaking 时存在不兼容的变化则失败
+    let triggers = triggers.unwrap_or_default();
+    let catalog = create_catalog(&ctx, &triggers);
+    let catalog_path = Path::new(PATH_CATALOG);
+    if let Some(old_catalog) = read_catalog(catalog_path) {
+        let changes = breaking_changes(&old_catalog, &catalog);
+        for change in changes.iter() {
+            eprintln!("breaking change: {}", change);
+        }
+        if !changes.is_empty() && options.deny_breaking {
+            return Err(aster_codegen::error::Error::BreakingChangeError(changes.len()).into());
         }
-        let lib_rs_path = src_dir.join(PATH_LIB_RS);
-        let lib_rs = fs::read_to_string(lib_rs_path)?;
-        let lib_rs_path = format!(
-            "{}/{}/{}/{}",
-            ACTIONS_DIR, &crate_name, PATH_SRC, PATH_LIB_RS
-        );
-
-        let tokens = proc_macro2::TokenStream::from_str(&lib_rs)?;
-        let file: syn::File = parse_quote!(#tokens);
-
-        parse_action(ParseFileContext {
-            crate_name: &crate_name,
-            file: &file,
-            path: &lib_rs_path,
-            generate_ctx: &mut ctx,
-            stmts: &mut stmts,
-        });
     }
+    write_catalog(&output, catalog_path, &catalog)?;
 
-    aster_codegen::generate_code(&ctx)?;
+    let i18n_config = I18nConfig::current();
+    output.write(Path::new(PATH_FALLBACK), fallback_locales(i18n_config)?)?;
+    if let Some(format) = options.extract_i18n {
+        let messages = collect_messages(&ctx, &triggers);
+        write_locales(
+            &output,
+            Path::new(LOCALES_DIR),
+            &messages,
+            i18n_config,
+            format,
+        )?;
+        println!("{}", coverage_report(&messages, i18n_config));
+    }
 
-    let loader_contents = stmts.join("\n\n");
+    let loader_contents = crates
+        .values()
+        .flat_map(|output| output.stmts.iter().cloned())
+        .collect::<Vec<_>>()
+        .join("\n\n");
 
     loader_cargo_metadata["dependencies"] = deps_table.into();
 
-    write(
-        loader_cargo_metadata_path,
+    output.write(
+        &loader_cargo_metadata_path,
         loader_cargo_metadata.to_string(),
     )?;
-    write(manifest_rs, loader_contents)?;
+    output.write(&manifest_rs, loader_contents)?;
 
+    let changed = output.take_changed();
+    if options.check {
+        for path in changed.iter() {
+            eprintln!("stale: {}", path.display());
+        }
+        if !changed.is_empty() {
+            return Err(aster_codegen::error::Error::StaleOutputError(changed.len()).into());
+        }
+    } else {
+        for path in changed.iter() {
+            println!("Updated {}", path.display());
+        }
+    }
     Ok(())
 }
 
@@ -136,26 +368,58 @@ fn parse_action(
         crate_name,
         file,
         path,
-        generate_ctx,
-        stmts,
+        languages,
+        output,
     }: ParseFileContext,
-) {
-    let mut actions: Punctuated<proc_macro2::TokenStream, Comma> = Punctuated::new();
+) -> syn::Result<()> {
+    let mut diagnostics = Diagnostics::default();
+    // (函数名, 是否异步, 参数版本)
+    let mut action_fns = vec![];
+    let mut migrations = vec![];
+    // 参数类型需要是同一文件中声明的 #[options] 枚举
+    let options = file
+        .items
+        .iter()
+        .filter_map(|e| match e {
+            Item::Enum(item) if item.attrs.iter().any(|attr| attr.path().is_ident("options")) => {
+                Some(item.ident.to_string())
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
     for e in file.items.iter() {
         let path = path.to_string();
         match e {
-            Item::Fn(item) => {
-                let mut card_info = extract_i18n_from_action(&item);
-                let action_name = &card_info.action_type.into_ident();
-                if item.sig.asyncness.is_some() {
-                    actions.push(quote! { async #action_name })
-                } else {
-                    actions.push(quote! { #action_name });
-                }
+            // 只有 #[action] 修饰的函数才是 action，其余为辅助函数
+            Item::Fn(item) if item.attrs.iter().any(|attr| attr.path().is_ident("action")) => {
+                let Some(mut card_info) =
+                    diagnostics.take(extract_i18n_from_action(&item, languages, &options))
+                else {
+                    continue;
+                };
+                action_fns.push((
+                    &item.sig.ident,
+                    item.sig.asyncness.is_some(),
+                    card_info.version,
+                ));
 
                 card_info.parent = format!("action.{}", crate_name);
                 card_info.file = path;
-                generate_ctx.card_info_list.push(card_info);
+                output.card_info_list.push(card_info);
+            }
+            Item::Fn(item) => {
+                let Some(attr) = item.attrs.iter().find(|attr| attr.path().is_ident("migrate"))
+                else {
+                    continue;
+                };
+                let tokens = attr
+                    .meta
+                    .require_list()
+                    .map(|list| list.tokens.clone())
+                    .unwrap_or_default();
+                if let Some(migrate) = diagnostics.take(check_migrate(&tokens, item)) {
+                    migrations.push((migrate, &item.sig.ident));
+                }
             }
             Item::Enum(item) => {
                 let item_name = item.ident.to_string();
@@ -174,22 +438,28 @@ fn parse_action(
 
                 match enum_type {
                     EnumType::Options => {
-                        let data = extract_i18n_from_options(&item);
-                        generate_ctx.form_data_list.push(FormDataCollect {
+                        let Some(data) =
+                            diagnostics.take(extract_i18n_from_options(&item, languages))
+                        else {
+                            continue;
+                        };
+                        output.form_data_list.push(FormDataCollect {
                             file: path,
                             name: item_name,
                             data,
                         })
                     }
                     EnumType::Result => {
-                        let data = extract_i18n_and_position_from_result(&item);
-                        generate_ctx
-                            .result_branch_list
-                            .push(ResultBranchTypeCollect {
-                                file: path,
-                                name: item_name,
-                                data,
-                            });
+                        let Some(data) = diagnostics
+                            .take(extract_i18n_and_position_from_result(&item, languages))
+                        else {
+                            continue;
+                        };
+                        output.result_branch_list.push(ResultBranchTypeCollect {
+                            file: path,
+                            name: item_name,
+                            data,
+                        });
                     }
                     EnumType::Others => (),
                 }
@@ -197,8 +467,35 @@ fn parse_action(
             _ => (),
         }
     }
+    let versions = action_fns
+        .iter()
+        .map(|(ident, _, version)| (*ident, *version))
+        .collect::<Vec<_>>();
+    diagnostics.take(check_migrations(
+        &versions,
+        &migrations.iter().map(|(migrate, _)| migrate).collect::<Vec<_>>(),
+    ));
+
+    let mut actions: Punctuated<proc_macro2::TokenStream, Comma> = Punctuated::new();
+    for (action_name, is_async, _) in action_fns {
+        let asyncness = is_async.then(|| quote! { async });
+        let arms = migrations
+            .iter()
+            .filter(|(migrate, _)| &migrate.action == action_name)
+            .map(|(migrate, func)| {
+                let from = proc_macro2::Literal::u32_unsuffixed(migrate.from);
+                quote! { #from => #func }
+            })
+            .collect::<Vec<_>>();
+        if arms.is_empty() {
+            actions.push(quote! { #asyncness #action_name });
+        } else {
+            actions.push(quote! { #asyncness #action_name { #(#arms),* } });
+        }
+    }
     let crate_name = crate_name.into_ident();
 
     let tokens = quote! { ::aster_macro::load_action!(#crate_name, [#actions]); }.to_string();
-    stmts.push(tokens);
+    output.stmts.push(tokens);
+    diagnostics.finish()
 }
@@ -0,0 +1,44 @@
+//! 生成文件的写入，内容没有变化时不写入，避免触发 Vite 重新加载
+
+use std::{
+    cell::RefCell,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::error::{Error, Result};
+
+#[derive(Debug, Default)]
+pub struct Output {
+    /// 只检查生成的文件是否过期，不写入
+    check: bool,
+    changed: RefCell<Vec<PathBuf>>,
+}
+
+impl Output {
+    pub fn new(check: bool) -> Self {
+        Output {
+            check,
+            changed: RefCell::default(),
+        }
+    }
+
+    /// 写入生成的内容，与现有内容相同时跳过
+    pub fn write(&self, path: &Path, content: impl AsRef<[u8]>) -> Result<()> {
+        let content = content.as_ref();
+        if fs::read(path).is_ok_and(|current| current == content) {
+            return Ok(());
+        }
+        self.changed.borrow_mut().push(path.to_path_buf());
+        if self.check {
+            return Ok(());
+        }
+        fs::write(path, content)
+            .map_err(|e| Error::WriteFileError(path.to_path_buf(), e.to_string()))
+    }
+
+    /// 取出内容发生变化的文件，检查模式下即为过期的文件
+    pub fn take_changed(&self) -> Vec<PathBuf> {
+        self.changed.take()
+    }
+}
@@ -48,7 +48,7 @@ impl FromType for FormType {
                 min: None,
                 max: None,
             }
-        } else if t == "File" {
+        } else if t == "File" || t == "Directory" {
             FormType::File(optional)
         } else {
             FormType::Unknown(optional)
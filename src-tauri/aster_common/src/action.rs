@@ -1,3 +1,4 @@
+pub mod branch;
 pub mod form;
 pub mod param;
 pub mod result;
@@ -1,14 +1,16 @@
-use aster_common::{
+//! 从 `#[result]` 枚举中提取分支的位置、插头类型与多语言标签，
+//! 代码生成与插件清单使用相同的结果
+
+use crate::{
     action::result::{PlugType, ResultBranchType},
-    attr::parse_attr,
     i18n::ParsedI18nMap,
     nesting::parse_nesting,
-    utils::{IntoIdent, IntoString, normalize_type},
+    utils::{IntoString, normalize_type},
+    validate::{POSITIONS, check_result, is_error_branch, parse_i18n, source_position},
 };
 use serde_json::{Map, Value, json};
 use syn::{
-    Attribute, ExprCast, Field, Fields, FieldsNamed, FieldsUnnamed, Ident, ItemEnum, Meta, Type,
-    Variant, parse_quote,
+    Attribute, ExprCast, Field, Fields, FieldsNamed, FieldsUnnamed, Ident, ItemEnum, Type, Variant,
 };
 
 pub(crate) struct ParsedAttribute {
@@ -26,7 +28,7 @@ struct Context<'a> {
 
 type PlugValue = Value;
 
-fn extract_plug_value_from_arg(field: &Field) -> PlugValue {
+fn extract_plug_value_from_arg(field: &Field) -> syn::Result<PlugValue> {
     let mut plug_value = Value::Null;
     // nesting
     loop {
@@ -37,7 +39,7 @@ fn extract_plug_value_from_arg(field: &Field) -> PlugValue {
             let mut stmt = vec![];
             let mut nesting_plug = Map::new();
             nesting_plug.insert("\0type".to_string(), json!("object"));
-            parse_nesting(&field, &m.mac.tokens, "_", (&mut stmt, &mut nesting_plug)).unwrap();
+            parse_nesting(field, &m.mac.tokens, "_", (&mut stmt, &mut nesting_plug))?;
         }
         break;
     }
@@ -46,8 +48,7 @@ fn extract_plug_value_from_arg(field: &Field) -> PlugValue {
             if !m.mac.path.is_ident("plug") {
                 break;
             }
-            let token = &m.mac.tokens;
-            let cast: ExprCast = parse_quote!(#token);
+            let cast: ExprCast = syn::parse2(m.mac.tokens.clone())?;
             let (_, ty) = (cast.expr.as_ref(), cast.ty.as_ref());
             plug_value = match ty {
                 Type::Macro(m) => {
@@ -62,59 +63,43 @@ fn extract_plug_value_from_arg(field: &Field) -> PlugValue {
         };
         break;
     }
-    plug_value
+    Ok(plug_value)
 }
 
-pub fn extract_i18n_and_position_from_result(item: &ItemEnum) -> Vec<ResultBranchType> {
+pub fn extract_i18n_and_position_from_result(
+    item: &ItemEnum,
+    languages: Option<&[String]>,
+) -> syn::Result<Vec<ResultBranchType>> {
+    check_result(item, languages)?;
     let result_ident = &item.ident;
+    // 输入端口的位置，由 #[source(...)] 指定
     let mut result_branch_types = vec![ResultBranchType {
         branch: "source".to_string(),
         id: result_ident.clone().to_string(),
         r#type: String::from("source"),
-        position: String::from("left"),
+        position: source_position(item)?,
         plug: PlugType::None,
         i18n: None,
     }];
 
-    for e in item.attrs.iter() {
-        if e.path().is_ident("source") {
-            if let Meta::List(list) = &e.meta {
-                let default = &"left".into_ident();
-                let ident = list.path.get_ident().unwrap_or_else(|| default);
-                match ident.to_string().as_str() {
-                    p @ ("left" | "right" | "top" | "bottom") => {
-                        result_branch_types.clear();
-                        result_branch_types.push(ResultBranchType {
-                            branch: "source".to_string(),
-                            id: result_ident.to_string(),
-                            r#type: String::from("source"),
-                            position: p.to_string(),
-                            plug: PlugType::None,
-                            i18n: None,
-                        });
-                    }
-                    _ => (),
-                }
-            }
-        };
-    }
-
     let mut context = Context {
         result_branch_types: &mut result_branch_types,
         result_ident,
     };
 
     for variant in &item.variants {
-        let attribute = parse_result_attr(&variant.attrs);
+        let attribute = parse_result_attr(&variant.attrs)?;
         match &variant.fields {
             Fields::Unnamed(fields) => {
-                handle_unnamed_variant(variant, fields, attribute, &mut context)
+                handle_unnamed_variant(variant, fields, attribute, &mut context)?
+            }
+            Fields::Named(fields) => {
+                handle_named_variant(variant, fields, attribute, &mut context)?
             }
-            Fields::Named(fields) => handle_named_variant(variant, fields, attribute, &mut context),
             Fields::Unit => handle_unit_variant(variant, attribute, &mut context),
         };
     }
-    result_branch_types
+    Ok(result_branch_types)
 }
 
 fn handle_unnamed_variant(
@@ -122,7 +107,7 @@ fn handle_unnamed_variant(
     unname: &FieldsUnnamed,
     ParsedAttribute { position, i18n, .. }: ParsedAttribute,
     context: &mut Context,
-) {
+) -> syn::Result<()> {
     let ident = variant.ident.clone();
     let ident_name = ident.to_string();
 
@@ -130,7 +115,7 @@ fn handle_unnamed_variant(
     let len: usize = members.len();
     let mut plug = PlugType::None;
     for (idx, f) in members.iter_mut().enumerate() {
-        let p = extract_plug_value_from_arg(f);
+        let p = extract_plug_value_from_arg(f)?;
         if len == 1 {
             plug = match p {
                 Value::String(str) => PlugType::Base(str),
@@ -163,6 +148,7 @@ fn handle_unnamed_variant(
         plug,
         i18n,
     });
+    Ok(())
 }
 
 fn handle_named_variant(
@@ -175,7 +161,7 @@ fn handle_named_variant(
         into_branch,
     }: ParsedAttribute,
     context: &mut Context,
-) {
+) -> syn::Result<()> {
     let ident = variant.ident.clone();
     let ident_name = ident.to_string();
     if !into_branch {
@@ -188,7 +174,7 @@ fn handle_named_variant(
             } else {
                 String::from("unknown")
             };
-            let plug = extract_plug_value_from_arg(f);
+            let plug = extract_plug_value_from_arg(f)?;
             plug_type.insert(ident_name, plug);
         }
 
@@ -212,7 +198,7 @@ fn handle_named_variant(
             plug: PlugType::Value(plug),
             i18n: g_i18n.clone(),
         });
-        return;
+        return Ok(());
     }
     for Field {
         attrs,
@@ -223,7 +209,10 @@ fn handle_named_variant(
     {
         // 实现子分支的fn
         let Some(sub_branch_ident) = id else {
-            panic!("Named branch in {} has no ident", &variant.ident);
+            return Err(syn::Error::new_spanned(
+                ty,
+                format!("Named branch in {} has no ident", &variant.ident),
+            ));
         };
         let variant_name = format!("{}_{}", &ident.to_string(), sub_branch_ident.to_string());
 
@@ -231,7 +220,7 @@ fn handle_named_variant(
             position: pos,
             i18n,
             ..
-        } = parse_result_attr(&attrs);
+        } = parse_result_attr(attrs)?;
 
         let Context { result_ident, .. } = context;
 
@@ -251,7 +240,14 @@ fn handle_named_variant(
                 let type_name = type_path.into_string();
                 plug = normalize_type(&type_name);
             }
-            _ => todo!(),
+            // 错误分支的类型会被忽略
+            Type::Infer(_) if into_error => plug = Value::Null,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    "unsupported branch type, expected a type, a tuple or `_`",
+                ));
+            }
         };
         // 添加分支结果类型
         context.result_branch_types.push(ResultBranchType {
@@ -275,6 +271,7 @@ fn handle_named_variant(
             i18n: i18n.or(g_i18n.clone()),
         });
     }
+    Ok(())
 }
 
 fn handle_unit_variant(
@@ -305,7 +302,7 @@ fn handle_unit_variant(
     });
 }
 
-fn parse_result_attr(attrs: &Vec<Attribute>) -> ParsedAttribute {
+fn parse_result_attr(attrs: &Vec<Attribute>) -> syn::Result<ParsedAttribute> {
     let mut pa = ParsedAttribute {
         position: String::new(),
         into_error: false,
@@ -315,40 +312,32 @@ fn parse_result_attr(attrs: &Vec<Attribute>) -> ParsedAttribute {
 
     let mut label_i18n = false;
     for attr in attrs {
-        let attr_ident = attr.path().require_ident().unwrap();
+        let Some(attr_ident) = attr.path().get_ident() else {
+            continue;
+        };
         let attr_str = attr_ident.to_string();
-        let attr_str: &str = Box::leak(Box::new(attr_str));
-        match attr_str {
-            p @ ("right" | "left" | "top" | "bottom") => {
-                if let Ok((i18n, _)) = parse_attr::<ParsedI18nMap>(attr) {
-                    if !label_i18n {
-                        pa.i18n = Some(i18n);
-                    }
+        match attr_str.as_str() {
+            p if POSITIONS.contains(&p) => {
+                let i18n = parse_i18n(attr)?;
+                // 仅作位置标记时没有翻译，不覆盖 label
+                if !label_i18n && !i18n.is_empty() {
+                    pa.i18n = Some(i18n);
                 }
                 if pa.position.is_empty() {
                     pa.position.push_str(p);
                 }
             }
             "label" => {
-                if let Ok((i18n, _)) = parse_attr::<ParsedI18nMap>(attr) {
-                    label_i18n = true;
-                    pa.i18n = Some(i18n);
-                }
+                label_i18n = true;
+                pa.i18n = Some(parse_i18n(attr)?);
             }
             "branch" => {
-                match &attr.meta {
-                    Meta::List(list) => {
-                        if &list.tokens.to_string() == "error" {
-                            pa.into_error = true;
-                        }
-                    }
-                    _ => panic!("only receive error"),
-                }
+                pa.into_error = is_error_branch(attr)?;
                 pa.into_branch = true;
             }
             _ => (),
         }
     }
 
-    pa
+    Ok(pa)
 }
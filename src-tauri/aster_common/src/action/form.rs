@@ -4,6 +4,7 @@ use crate::{
     i18n::{I18nValue, Label, ParsedI18nMap, ParsedI18nMapTrait},
     typescript::DefaultValue,
 };
+use common::action::rule::ValidateRule;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +13,7 @@ pub enum FormData {
     AutoComplete(Vec<Label>),
     Range { min: f64, max: f64, step: f64 },
     Input { placeholder: ParsedI18nMap },
+    File { directory: bool },
 }
 
 impl FormData {
@@ -165,4 +167,5 @@ pub struct FormItem {
     pub default: Option<DefaultValue>,
     pub data: Option<FormData>,
     pub optional: bool,
+    pub validate: Option<ValidateRule>,
 }
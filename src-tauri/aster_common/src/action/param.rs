@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 
-use darling::{FromMeta, ast::NestedMeta};
+use common::action::rule::{Len, ValidateRule, check_pattern};
+use darling::{Error, FromMeta, ast::NestedMeta};
+use serde_json::{Value, json};
+use syn::{Expr, ExprLit, ExprUnary, Lit, Meta, UnOp};
 
 use crate::i18n::{ParsedI18nMap, ParsedI18nMapTrait};
 
@@ -34,6 +37,158 @@ pub struct ParamAttr {
     pub label: Option<ParsedI18nMap>,
     #[darling(default)]
     pub default: Option<String>,
+    #[darling(default)]
+    pub validate: Option<ValidateAttr>,
+}
+
+/// `#[validate(min = 1, max = 10, len(max = 64), regex = "...", url, one_of("a", "b"))]`
+#[derive(Debug, Clone, Default)]
+pub struct ValidateAttr(pub ValidateRule);
+
+const VALIDATE_KEYS: [&str; 6] = ["min", "max", "regex", "len", "url", "one_of"];
+
+// 数字字面量，允许负数
+fn parse_number(expr: &Expr) -> darling::Result<f64> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse().map_err(Error::from),
+        Expr::Lit(ExprLit {
+            lit: Lit::Float(float),
+            ..
+        }) => float.base10_parse().map_err(Error::from),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => parse_number(expr).map(|number| -number),
+        _ => Err(Error::custom("expected a number").with_span(expr)),
+    }
+}
+
+fn parse_len(expr: &Expr) -> darling::Result<usize> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse().map_err(Error::from),
+        _ => Err(Error::custom("expected an unsigned integer").with_span(expr)),
+    }
+}
+
+fn parse_value(lit: &Lit) -> darling::Result<Value> {
+    match lit {
+        Lit::Str(str) => Ok(json!(str.value())),
+        Lit::Int(int) => Ok(json!(int.base10_parse::<i64>()?)),
+        Lit::Float(float) => Ok(json!(float.base10_parse::<f64>()?)),
+        Lit::Bool(bool) => Ok(json!(bool.value)),
+        _ => Err(Error::unexpected_lit_type(lit)),
+    }
+}
+
+impl FromMeta for ValidateAttr {
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        let mut rule = ValidateRule::default();
+        let mut errors = Error::accumulator();
+        for item in items {
+            let NestedMeta::Meta(meta) = item else {
+                errors.push(Error::unsupported_format("literal").with_span(item));
+                continue;
+            };
+            let key = meta
+                .path()
+                .get_ident()
+                .map(ToString::to_string)
+                .unwrap_or_default();
+            match (key.as_str(), meta) {
+                ("min", Meta::NameValue(meta)) => {
+                    rule.min = errors.handle(parse_number(&meta.value))
+                }
+                ("max", Meta::NameValue(meta)) => {
+                    rule.max = errors.handle(parse_number(&meta.value))
+                }
+                ("regex", Meta::NameValue(meta)) => {
+                    let Some(pattern) = errors.handle(String::from_expr(&meta.value)) else {
+                        continue;
+                    };
+                    if let Err(e) = check_pattern(&pattern) {
+                        errors.push(
+                            Error::custom(format!("invalid regex: {}", e)).with_span(&meta.value),
+                        );
+                    }
+                    rule.regex = Some(pattern);
+                }
+                // len = 6 表示固定长度
+                ("len", Meta::NameValue(meta)) => {
+                    let len = errors.handle(parse_len(&meta.value));
+                    rule.len = Some(Len { min: len, max: len });
+                }
+                ("len", Meta::List(list)) => {
+                    let mut len = Len::default();
+                    let metas = errors
+                        .handle(
+                            NestedMeta::parse_meta_list(list.tokens.clone()).map_err(Error::from),
+                        )
+                        .unwrap_or_default();
+                    for meta in metas {
+                        match meta {
+                            NestedMeta::Meta(Meta::NameValue(meta))
+                                if meta.path.is_ident("min") =>
+                            {
+                                len.min = errors.handle(parse_len(&meta.value))
+                            }
+                            NestedMeta::Meta(Meta::NameValue(meta))
+                                if meta.path.is_ident("max") =>
+                            {
+                                len.max = errors.handle(parse_len(&meta.value))
+                            }
+                            meta => errors.push(
+                                Error::custom("expected `min = ...` or `max = ...`")
+                                    .with_span(&meta),
+                            ),
+                        }
+                    }
+                    rule.len = Some(len);
+                }
+                ("url", Meta::Path(_)) => rule.url = true,
+                ("one_of", Meta::List(list)) => {
+                    let values = errors
+                        .handle(
+                            NestedMeta::parse_meta_list(list.tokens.clone()).map_err(Error::from),
+                        )
+                        .unwrap_or_default()
+                        .iter()
+                        .filter_map(|item| match item {
+                            NestedMeta::Lit(lit) => errors.handle(parse_value(lit)),
+                            NestedMeta::Meta(meta) => {
+                                errors.push(Error::custom("expected a literal").with_span(meta));
+                                None
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    rule.one_of = Some(values);
+                }
+                (key, meta) if VALIDATE_KEYS.contains(&key) => {
+                    errors.push(
+                        Error::unsupported_format(match meta {
+                            Meta::Path(_) => "word",
+                            Meta::List(_) => "list",
+                            Meta::NameValue(_) => "name value",
+                        })
+                        .with_span(meta),
+                    );
+                }
+                (key, meta) => {
+                    errors.push(Error::unknown_field_with_alts(key, &VALIDATE_KEYS).with_span(meta))
+                }
+            }
+        }
+        if let (Some(min), Some(max)) = (rule.min, rule.max)
+            && min > max
+        {
+            errors.push(Error::custom("`min` is greater than `max`"));
+        }
+        errors.finish_with(ValidateAttr(rule))
+    }
 }
 
 impl ParamAttr {
@@ -52,6 +207,13 @@ impl ParamAttr {
             .unwrap_or_else(HashMap::new)
             .to_filter_value()
     }
+
+    /// 获取校验规则
+    pub fn get_validate_rule(&self) -> Option<ValidateRule> {
+        self.validate
+            .as_ref()
+            .map(|ValidateAttr(rule)| rule.clone())
+    }
 }
 
 // 新的解析函数，使用 Darling 自动解析
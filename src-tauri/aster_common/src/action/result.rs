@@ -5,7 +5,7 @@ use serde_json::Value;
 
 use crate::i18n::{ParsedI18nMap, ParsedI18nMapTrait};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PlugType {
     Unknown,
     None,
@@ -14,7 +14,7 @@ pub enum PlugType {
     Value(Value),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResultBranchType {
     /// 分支名称
     pub branch: String,
@@ -1,13 +1,13 @@
 use crate::action::{form::FormData, result::ResultBranchType};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FormDataCollect {
     pub name: String,
     pub data: FormData,
     pub file: String,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct ResultBranchTypeCollect {
     pub name: String,
     pub data: Vec<ResultBranchType>,
@@ -227,6 +227,23 @@ mod hot_lib {
 It will then return false until another reload occurred.
 
 
+### `reload_error`
+
+An update that fails to load the library does not stop the reload thread.
+Subscribers still get the reloaded event, the version stays the same and the error can be queried:
+
+```ignore
+#[hot_lib_reloader::hot_module(dylib = "lib")]
+mod hot_lib {
+    /* ... */
+    #[lib_reload_error]
+    pub fn reload_error() -> Option<String> {}
+}
+```
+
+`hot_lib::reload_error()` returns the error of the last update until a later update succeeds.
+
+
 
 # Usage tips
 
@@ -321,6 +321,13 @@ impl LibReloader {
     pub fn log_info(what: impl std::fmt::Display) {
         log::info!("{what}");
     }
+
+    /// Helper to log from the macro without requiring the user to have the log
+    /// crate around
+    #[doc(hidden)]
+    pub fn log_error(what: impl std::fmt::Display) {
+        log::error!("{what}");
+    }
 }
 
 /// Deletes the currently loaded lib file if it exists
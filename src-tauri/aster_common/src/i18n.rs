@@ -1,23 +1,22 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
 
-use darling::FromMeta;
+use common::action::rule::ValidateRule;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, FromMeta, Serialize, Deserialize)]
-pub struct I18n {
-    #[serde(rename = "zh-CN")]
-    pub zh_cn: Option<String>,
-    pub en: Option<String>,
-    pub ja: Option<String>,
-    pub fr: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParamI18n {
     pub description: HashMap<String, String>,
     pub key: String,
     pub name: HashMap<String, String>,
     pub r#type: String,
+    /// `#[validate(...)]` 声明的校验规则
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validate: Option<ValidateRule>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -28,6 +27,16 @@ pub struct ActionI18n {
     pub params: Vec<ParamI18n>,
     pub entries: Vec<String>,
     pub keys: Vec<String>,
+    /// 返回的 #[result] 枚举名
+    #[serde(default)]
+    pub result: String,
+    /// `#[action(version = N)]` 声明的参数版本
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_version() -> u32 {
+    1
 }
 #[derive(Debug, Clone, Deserialize)]
 pub enum I18nValue {
@@ -64,6 +73,117 @@ impl Label {
 
 pub type ParsedI18nMap = HashMap<String, Option<String>>;
 
+/// 工作区未配置时多语言属性中可以使用的语言
+pub const LANGUAGES: [&str; 4] = ["zh_cn", "en", "ja", "fr"];
+
+/// 工作区配置的语言与回退链，在工作区的 Cargo.toml 中配置
+/// ```toml
+/// [workspace.metadata.aster.i18n]
+/// languages = ["zh_cn", "zh_tw", "en", "ja", "fr"]
+/// fallback = { zh_tw = ["zh_cn"] }
+/// default = "en"
+/// ```
+/// 如上 zh_tw 缺失的翻译依次使用 zh_cn、en
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct I18nConfig {
+    pub languages: Vec<String>,
+    #[serde(default)]
+    pub fallback: HashMap<String, Vec<String>>,
+    /// 回退链最后使用的语言
+    #[serde(default = "default_language")]
+    pub default: String,
+}
+
+fn default_language() -> String {
+    "en".to_string()
+}
+
+impl Default for I18nConfig {
+    fn default() -> Self {
+        I18nConfig {
+            languages: LANGUAGES
+                .iter()
+                .map(|language| language.to_string())
+                .collect(),
+            fallback: HashMap::new(),
+            default: default_language(),
+        }
+    }
+}
+
+impl I18nConfig {
+    /// 从 dir 向上查找配置了 [workspace.metadata.aster.i18n] 的 Cargo.toml，未找到时使用默认配置
+    pub fn load(dir: &Path) -> Self {
+        dir.ancestors()
+            .find_map(|dir| {
+                let content = fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+                let manifest = toml::from_str::<toml::Table>(&content).ok()?;
+                let config = manifest
+                    .get("workspace")?
+                    .get("metadata")?
+                    .get("aster")?
+                    .get("i18n")?
+                    .clone();
+                config.try_into::<I18nConfig>().ok()
+            })
+            .unwrap_or_default()
+    }
+
+    /// 过程宏中从正在编译的 crate 向上查找，代码生成时从当前目录查找
+    pub fn current() -> &'static Self {
+        static CONFIG: OnceLock<I18nConfig> = OnceLock::new();
+        CONFIG.get_or_init(|| {
+            let dir = std::env::var("CARGO_MANIFEST_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| PathBuf::from("."));
+            I18nConfig::load(&dir)
+        })
+    }
+
+    pub fn contains(&self, language: &str) -> bool {
+        self.languages.iter().any(|l| l == language)
+    }
+
+    /// 缺失翻译时依次使用的语言，不包含 language 本身
+    pub fn fallback_chain<'a>(&'a self, language: &'a str) -> Vec<&'a str> {
+        let mut chain: Vec<&str> = vec![];
+        let mut pending = vec![language];
+        // 回退链可以传递，如 zh_tw → zh_cn → en
+        while let Some(current) = pending.pop() {
+            for next in self.fallback.get(current).into_iter().flatten().rev() {
+                if next != language && !chain.contains(&next.as_str()) {
+                    pending.push(next);
+                }
+            }
+            if current != language && !chain.contains(&current) {
+                chain.push(current);
+            }
+        }
+        if self.default != language && !chain.contains(&self.default.as_str()) {
+            chain.push(&self.default);
+        }
+        chain
+    }
+
+    /// 按回退链取出翻译，`get` 取出某种语言的翻译
+    pub fn resolve<T>(&self, language: &str, get: impl Fn(&str) -> Option<T>) -> Option<T> {
+        std::iter::once(language)
+            .chain(self.fallback_chain(language))
+            .find_map(get)
+    }
+}
+
+/// 前端使用的语言名，如 zh_cn 为 zh-CN
+pub fn locale_tag(language: &str) -> String {
+    let mut parts = language.split('_');
+    let mut tag = parts.next().unwrap_or_default().to_lowercase();
+    for part in parts {
+        tag.push('-');
+        tag.push_str(&part.to_uppercase());
+    }
+    tag
+}
+
 pub trait ParsedI18nMapTrait {
     fn to_filter_value(&self) -> HashMap<String, String>;
 }
@@ -74,3 +194,64 @@ impl ParsedI18nMapTrait for ParsedI18nMap {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(fallback: &[(&str, &[&str])]) -> I18nConfig {
+        I18nConfig {
+            languages: ["zh_cn", "zh_tw", "en", "ja"]
+                .iter()
+                .map(|language| language.to_string())
+                .collect(),
+            fallback: fallback
+                .iter()
+                .map(|(language, chain)| {
+                    let chain = chain.iter().map(|next| next.to_string()).collect();
+                    (language.to_string(), chain)
+                })
+                .collect(),
+            default: "en".to_string(),
+        }
+    }
+
+    #[test]
+    fn follows_transitive_fallback() {
+        let config = config(&[("zh_tw", &["zh_cn"]), ("zh_cn", &["ja"])]);
+        assert_eq!(config.fallback_chain("zh_tw"), ["zh_cn", "ja", "en"]);
+        assert_eq!(config.fallback_chain("zh_cn"), ["ja", "en"]);
+        assert_eq!(config.fallback_chain("ja"), ["en"]);
+        assert!(config.fallback_chain("en").is_empty());
+    }
+
+    #[test]
+    fn stops_at_fallback_cycles() {
+        let config = config(&[
+            ("zh_tw", &["zh_cn"]),
+            ("zh_cn", &["zh_tw", "ja"]),
+            ("ja", &["zh_cn"]),
+        ]);
+        assert_eq!(config.fallback_chain("zh_tw"), ["zh_cn", "ja", "en"]);
+        assert_eq!(config.fallback_chain("ja"), ["zh_cn", "zh_tw", "en"]);
+    }
+
+    #[test]
+    fn resolves_along_the_chain() {
+        let config = config(&[("zh_tw", &["zh_cn"])]);
+        let i18n = HashMap::from([("zh_cn", "简体"), ("en", "English")]);
+        let get = |language: &str| i18n.get(language).copied();
+        assert_eq!(config.resolve("zh_tw", get), Some("简体"));
+        assert_eq!(config.resolve("ja", get), Some("English"));
+        assert_eq!(config.resolve("en", get), Some("English"));
+        assert_eq!(config.resolve("zh_tw", |_| None::<&str>), None);
+    }
+
+    #[test]
+    fn converts_locale_tags() {
+        assert_eq!(locale_tag("zh_cn"), "zh-CN");
+        assert_eq!(locale_tag("pt_br"), "pt-BR");
+        assert_eq!(locale_tag("en"), "en");
+        assert_eq!(locale_tag("EN"), "en");
+    }
+}
@@ -7,4 +7,5 @@ pub mod i18n;
 pub mod nesting;
 pub mod typescript;
 pub mod utils;
-pub mod rust;
\ No newline at end of file
+pub mod rust;
+pub mod validate;
\ No newline at end of file
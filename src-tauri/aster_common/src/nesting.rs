@@ -42,16 +42,20 @@ pub fn parse_nesting(
     }
     .into_ident();
 
-    let mut st: ItemStruct = parse_quote! {
+    let mut st: ItemStruct = syn::parse2(quote! {
         #[derive(::std::fmt::Debug, ::serde::Deserialize, ::serde::Serialize)]
         struct #actual_ident { #token }
-    };
+    })
+    .map_err(|_| syn::Error::new_spanned(token, "Nested content must use named fields"))?;
     let Fields::Named(FieldsNamed {
         named: mut struct_fields,
         ..
     }) = st.fields
     else {
-        panic!("Nested content must use named fields")
+        return Err(syn::Error::new_spanned(
+            token,
+            "Nested content must use named fields",
+        ));
     };
     for f in struct_fields.iter_mut() {
         f.vis = Visibility::Public(Pub {
@@ -75,8 +79,7 @@ pub fn parse_nesting(
                     f.ty = ty;
                     plug_type = plug.into();
                 } else if mac.path.is_ident("plug") {
-                    let token = &mac.tokens;
-                    let cast: ExprCast = parse_quote!(#token);
+                    let cast: ExprCast = syn::parse2(mac.tokens.clone())?;
                     let (expr, ty) = (cast.expr.as_ref(), cast.ty.as_ref());
                     plug_type = match ty {
                         Type::Macro(m) => {
@@ -194,6 +194,9 @@ impl ToObjectExpr for FormData {
                 ("step", ExprValue::Number(*step)),
             ]
             .to_object_entry(),
+            Self::File { directory } => {
+                vec![("directory", ExprValue::Boolean(*directory))].to_object_entry()
+            }
         }
     }
 }
@@ -252,6 +255,12 @@ impl ToObjectExpr for FormItem {
         if let Some(default) = &self.default {
             entries.push(("defaultValue", ExprValue::Expr(default.get_value_expr())));
         }
+        // 前端按相同的规则校验表单
+        if let Some(validate) = &self.validate
+            && let Ok(value) = serde_json::to_value(validate)
+        {
+            entries.push(("validate", ExprValue::Expr(value.to_expr())));
+        }
         entries.to_object_entry()
     }
 }
@@ -0,0 +1,730 @@
+//! #[action]、#[result]、#[options] 的校验，由宏与代码生成共用
+//!
+//! 所有错误都带有源码位置，宏中转换为 compile_error!，代码生成时输出文件与行列号
+
+use std::{fs, path::Path};
+
+use darling::ast::NestedMeta;
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{
+    Attribute, Expr, ExprLit, Fields, FnArg, GenericArgument, Ident, ItemEnum, ItemFn, Lit, Meta,
+    MetaNameValue, Pat, PathArguments, ReturnType, Token, Type, braced,
+    parse::{Parse, ParseStream, Parser},
+    punctuated::Punctuated,
+    token::Brace,
+};
+
+use crate::{
+    action::param::{ParamAttr, ValidateAttr, parse_param_attributes},
+    i18n::{I18nConfig, ParsedI18nMap},
+};
+
+/// 参数上可用的属性
+pub const PARAM_ATTRS: [&str; 4] = ["name", "description", "default", "validate"];
+
+/// 分支与输入端口可放置的位置
+pub const POSITIONS: [&str; 4] = ["left", "right", "top", "bottom"];
+
+/// #[result] 枚举的分支上可用的属性，文档注释除外
+const BRANCH_ATTRS: [&str; 9] = [
+    "left", "right", "top", "bottom", "label", "branch", "raw", "error", "rename",
+];
+
+/// 数字类型，可以使用 `#[validate(min, max)]`
+const NUMBER_TYPES: [&str; 14] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize", "f32",
+    "f64",
+];
+
+/// 以字符串传递的类型，可以使用 `#[validate(len, regex, url)]`
+const STRING_TYPES: [&str; 5] = ["String", "Text", "Code", "File", "Directory"];
+
+/// 可以直接生成表单的参数类型，与 FormType::from_type 保持一致
+const FORM_TYPES: [&str; 20] = [
+    "String", "Text", "Code", "File", "Directory", "bool", "u8", "u16", "u32", "u64", "u128",
+    "usize", "i8", "i16", "i32", "i64", "i128", "isize", "f32", "f64",
+];
+
+/// 收集多个错误，一次性报告
+#[derive(Debug, Default)]
+pub struct Diagnostics(Option<syn::Error>);
+
+impl Diagnostics {
+    pub fn push(&mut self, error: syn::Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    /// 记录失败的结果，成功时返回其中的值
+    pub fn take<T>(&mut self, result: syn::Result<T>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(e) => {
+                self.push(e);
+                None
+            }
+        }
+    }
+
+    pub fn finish(self) -> syn::Result<()> {
+        match self.0 {
+            Some(errors) => Err(errors),
+            None => Ok(()),
+        }
+    }
+}
+
+/// 将 darling 的错误转换为 syn::Error，保留每个错误的位置
+pub fn from_darling(error: darling::Error) -> syn::Error {
+    let span = error.span();
+    error
+        .into_iter()
+        .map(|e| syn::Error::new(e.span(), e.to_string()))
+        .reduce(|mut errors, e| {
+            errors.combine(e);
+            errors
+        })
+        .unwrap_or_else(|| syn::Error::new(span, "invalid attribute"))
+}
+
+fn quoted(list: &[impl AsRef<str>]) -> String {
+    list.iter()
+        .map(|item| format!("`{}`", item.as_ref()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn type_name(ty: &Type) -> String {
+    ty.to_token_stream().to_string().replace(' ', "")
+}
+
+/// 读取 crate 在 Cargo.toml 中配置的语言，所有多语言属性都需要提供这些语言
+/// ```toml
+/// [package.metadata.aster]
+/// languages = ["zh_cn", "en"]
+/// ```
+pub fn configured_languages(manifest_dir: &Path) -> Option<Vec<String>> {
+    let content = fs::read_to_string(manifest_dir.join("Cargo.toml")).ok()?;
+    let manifest = toml::from_str::<toml::Table>(&content).ok()?;
+    let languages = manifest
+        .get("package")?
+        .get("metadata")?
+        .get("aster")?
+        .get("languages")?
+        .as_array()?;
+    Some(
+        languages
+            .iter()
+            .filter_map(|language| language.as_str())
+            .map(String::from)
+            .collect(),
+    )
+}
+
+/// 在过程宏中读取正在编译的 crate 配置的语言
+pub fn current_languages() -> Option<Vec<String>> {
+    let dir = std::env::var("CARGO_MANIFEST_DIR").ok()?;
+    configured_languages(Path::new(&dir))
+}
+
+/// 解析 `zh_cn = "...", en = "..."` 形式的多语言内容，拒绝工作区未配置的语言
+pub fn parse_i18n_tokens(tokens: TokenStream) -> syn::Result<ParsedI18nMap> {
+    let config = I18nConfig::current();
+    let pairs = Punctuated::<MetaNameValue, Token![,]>::parse_terminated.parse2(tokens)?;
+    let mut i18n = ParsedI18nMap::new();
+    for pair in pairs {
+        let key = pair.path.require_ident()?;
+        let language = key.to_string();
+        if !config.contains(&language) {
+            return Err(syn::Error::new_spanned(
+                key,
+                format!(
+                    "unknown language `{}`, expected one of {}",
+                    language,
+                    quoted(&config.languages)
+                ),
+            ));
+        }
+        let Expr::Lit(ExprLit {
+            lit: Lit::Str(value),
+            ..
+        }) = &pair.value
+        else {
+            return Err(syn::Error::new_spanned(
+                &pair.value,
+                "expected a string literal",
+            ));
+        };
+        if i18n.insert(language, Some(value.value())).is_some() {
+            return Err(syn::Error::new_spanned(
+                key,
+                format!("duplicate translation for `{}`", key),
+            ));
+        }
+    }
+    Ok(i18n)
+}
+
+fn parse_version(expr: &Expr) -> syn::Result<u32> {
+    let Expr::Lit(ExprLit {
+        lit: Lit::Int(int), ..
+    }) = expr
+    else {
+        return Err(syn::Error::new_spanned(
+            expr,
+            "expected an integer literal, such as `version = 2`",
+        ));
+    };
+    let version = int.base10_parse::<u32>()?;
+    if version == 0 {
+        return Err(syn::Error::new_spanned(int, "versions start from 1"));
+    }
+    Ok(version)
+}
+
+/// 取出 `#[action(version = 2, zh_cn = "...")]` 中的参数版本，未声明时为 1，其余部分为多语言标题
+pub fn split_action_attr(tokens: TokenStream) -> syn::Result<(u32, TokenStream)> {
+    let pairs = Punctuated::<MetaNameValue, Token![,]>::parse_terminated.parse2(tokens)?;
+    let mut version = None;
+    let mut title = Punctuated::<MetaNameValue, Token![,]>::new();
+    for pair in pairs {
+        if !pair.path.is_ident("version") {
+            title.push(pair);
+            continue;
+        }
+        if version.is_some() {
+            return Err(syn::Error::new_spanned(&pair.path, "duplicate `version`"));
+        }
+        version = Some(parse_version(&pair.value)?);
+    }
+    Ok((version.unwrap_or(1), title.into_token_stream()))
+}
+
+/// 解析多语言属性，如 `#[name(zh_cn = "名称", en = "Name")]`，仅作标记时没有翻译
+pub fn parse_i18n(attr: &Attribute) -> syn::Result<ParsedI18nMap> {
+    match &attr.meta {
+        Meta::List(list) => parse_i18n_tokens(list.tokens.clone()),
+        Meta::Path(_) => Ok(ParsedI18nMap::new()),
+        Meta::NameValue(meta) => Err(syn::Error::new_spanned(
+            meta,
+            format!(
+                "expected `#[{}(zh_cn = \"...\", en = \"...\")]`",
+                meta.path.to_token_stream()
+            ),
+        )),
+    }
+}
+
+/// 检查多语言内容是否包含所有配置的语言
+pub fn check_translations(
+    i18n: &ParsedI18nMap,
+    languages: &[String],
+    tokens: impl ToTokens,
+) -> syn::Result<()> {
+    let missing = languages
+        .iter()
+        .filter(|language| !matches!(i18n.get(*language), Some(Some(_))))
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    Err(syn::Error::new_spanned(
+        tokens,
+        format!("missing translation for {}", quoted(&missing)),
+    ))
+}
+
+/// 解析多语言属性并检查是否包含所有配置的语言
+pub fn check_i18n(attr: &Attribute, languages: &[String]) -> syn::Result<ParsedI18nMap> {
+    let i18n = parse_i18n(attr)?;
+    if let Meta::List(_) = attr.meta {
+        check_translations(&i18n, languages, attr)?;
+    }
+    Ok(i18n)
+}
+
+/// 解析参数上的属性，拒绝未知的属性与语言
+pub fn parse_param_attrs(attrs: &[Attribute], languages: &[String]) -> syn::Result<ParamAttr> {
+    let mut diagnostics = Diagnostics::default();
+    for attr in attrs {
+        let path = attr.path();
+        if path.is_ident("name") || path.is_ident("description") {
+            diagnostics.take(check_i18n(attr, languages));
+        } else if !path.is_ident("default") && !path.is_ident("validate") {
+            diagnostics.push(syn::Error::new_spanned(
+                path,
+                format!(
+                    "unknown attribute `{}` on action parameter, expected one of {}",
+                    path.to_token_stream(),
+                    quoted(&PARAM_ATTRS)
+                ),
+            ));
+        }
+    }
+    diagnostics.finish()?;
+    let metas = attrs
+        .iter()
+        .map(|attr| NestedMeta::Meta(attr.meta.clone()))
+        .collect::<Vec<_>>();
+    parse_param_attributes(&metas).map_err(from_darling)
+}
+
+// Option<T> 中的 T
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    if path.qself.is_some() || path.path.segments.len() != 1 {
+        return None;
+    }
+    let segment = &path.path.segments[0];
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    if segment.ident != "Option" || args.args.len() != 1 {
+        return None;
+    }
+    match &args.args[0] {
+        GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+/// 检查参数类型能否生成表单：基础类型、#[options] 枚举，或由 Option 包裹的这些类型
+///
+/// `options` 为同一文件中声明的 #[options] 枚举，宏中无法得知时传入 None
+pub fn check_param_type(ty: &Type, options: Option<&[String]>) -> syn::Result<()> {
+    let inner = option_inner(ty).unwrap_or(ty);
+    let name = match inner {
+        Type::Path(path) if path.qself.is_none() && path.path.segments.len() == 1 => {
+            let segment = &path.path.segments[0];
+            segment.arguments.is_none().then(|| segment.ident.to_string())
+        }
+        _ => None,
+    };
+    let Some(name) = name else {
+        return Err(syn::Error::new_spanned(
+            ty,
+            format!(
+                "unsupported parameter type `{}`, expected `String`, `Text`, `Code`, `File`, \
+                 `Directory`, `bool`, a number, an `#[options]` enum or an `Option` of these",
+                type_name(ty)
+            ),
+        ));
+    };
+    match options {
+        Some(options) if !FORM_TYPES.contains(&name.as_str()) && !options.contains(&name) => {
+            Err(syn::Error::new_spanned(
+                inner,
+                format!(
+                    "`{}` is neither a form type nor an `#[options]` enum declared in this file",
+                    name
+                ),
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// 检查 `#[validate(...)]` 中的规则是否适用于参数的类型
+pub fn check_validate(attr: &Attribute, rule: &ValidateAttr, ty: &Type) -> syn::Result<()> {
+    let ValidateAttr(rule) = rule;
+    let inner = option_inner(ty).unwrap_or(ty);
+    let name = type_name(inner);
+    let is_number = NUMBER_TYPES.contains(&name.as_str());
+    let is_string = STRING_TYPES.contains(&name.as_str());
+    let mut unsupported = vec![];
+    if !is_number && (rule.min.is_some() || rule.max.is_some()) {
+        unsupported.push("min, max");
+    }
+    if !is_string && (rule.len.is_some() || rule.regex.is_some() || rule.url) {
+        unsupported.push("len, regex, url");
+    }
+    if !is_number && !is_string && rule.one_of.is_some() {
+        unsupported.push("one_of");
+    }
+    if unsupported.is_empty() {
+        return Ok(());
+    }
+    Err(syn::Error::new_spanned(
+        attr,
+        format!(
+            "`#[validate({})]` cannot be used on a parameter of type `{}`",
+            unsupported.join(", "),
+            name
+        ),
+    ))
+}
+
+/// `#[entry]` 中的一项，可以是单个参数，也可以是一组参数
+enum Entry {
+    Param(Ident),
+    Group(Punctuated<Ident, Token![,]>),
+}
+
+impl Parse for Entry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+        if !input.peek(Brace) {
+            return Ok(Entry::Param(ident));
+        }
+        let content;
+        braced!(content in input);
+        Ok(Entry::Group(content.parse_terminated(Ident::parse, Token![,])?))
+    }
+}
+
+/// 检查 `#[entry]` 引用的参数是否存在，且每个参数只出现一次
+pub fn check_entries(attr: &Attribute, params: &[String]) -> syn::Result<()> {
+    let list = attr.meta.require_list()?;
+    let entries = Punctuated::<Entry, Token![,]>::parse_terminated.parse2(list.tokens.clone())?;
+    let mut diagnostics = Diagnostics::default();
+    let mut seen = vec![];
+    for entry in entries {
+        let idents = match entry {
+            Entry::Param(ident) => vec![ident],
+            Entry::Group(idents) => idents.into_iter().collect(),
+        };
+        for ident in idents {
+            let name = ident.to_string();
+            if !params.contains(&name) {
+                diagnostics.push(syn::Error::new_spanned(
+                    &ident,
+                    format!("`#[entry]` references unknown parameter `{}`", name),
+                ));
+            } else if seen.contains(&name) {
+                diagnostics.push(syn::Error::new_spanned(
+                    &ident,
+                    format!("parameter `{}` is listed in `#[entry]` more than once", name),
+                ));
+            } else {
+                seen.push(name);
+            }
+        }
+    }
+    diagnostics.finish()
+}
+
+/// 校验 #[action] 修饰的函数，`title` 为 `#[action(...)]` 中的内容
+///
+/// 未配置语言时，其余的多语言属性需要提供与标题相同的语言
+pub fn check_action(
+    title: &TokenStream,
+    item: &ItemFn,
+    languages: Option<&[String]>,
+    options: Option<&[String]>,
+) -> syn::Result<()> {
+    let mut diagnostics = Diagnostics::default();
+    let title_i18n = diagnostics
+        .take(split_action_attr(title.clone()).and_then(|(_, title)| parse_i18n_tokens(title)))
+        .unwrap_or_default();
+    let languages = match languages {
+        Some(languages) => languages.to_vec(),
+        None => I18nConfig::current()
+            .languages
+            .iter()
+            .filter(|language| matches!(title_i18n.get(*language), Some(Some(_))))
+            .cloned()
+            .collect(),
+    };
+    if !title.is_empty() {
+        diagnostics.take(check_translations(&title_i18n, &languages, title));
+    }
+
+    if let ReturnType::Default = item.sig.output {
+        diagnostics.push(syn::Error::new_spanned(
+            &item.sig.ident,
+            "Return type is required, an action returns its `#[result]` enum",
+        ));
+    }
+
+    let mut params = vec![];
+    for input in &item.sig.inputs {
+        let typed = match input {
+            FnArg::Receiver(receiver) => {
+                diagnostics.push(syn::Error::new_spanned(
+                    receiver,
+                    "'self' is not allowed in an action",
+                ));
+                continue;
+            }
+            FnArg::Typed(typed) => typed,
+        };
+        let Pat::Ident(pat) = typed.pat.as_ref() else {
+            diagnostics.push(syn::Error::new_spanned(
+                &typed.pat,
+                "Unsupported pattern, action parameters must be plain identifiers",
+            ));
+            continue;
+        };
+        // Context 参数由运行时注入，不属于表单
+        if type_name(&typed.ty) == "Context" {
+            continue;
+        }
+        params.push(pat.ident.to_string());
+        let param_attr = diagnostics.take(parse_param_attrs(&typed.attrs, &languages));
+        diagnostics.take(check_param_type(&typed.ty, options));
+        let validate = typed
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("validate"));
+        if let (Some(attr), Some(ParamAttr {
+            validate: Some(rule),
+            ..
+        })) = (validate, &param_attr)
+        {
+            diagnostics.take(check_validate(attr, rule, &typed.ty));
+        }
+    }
+
+    for attr in &item.attrs {
+        if attr.path().is_ident("description") {
+            diagnostics.take(check_i18n(attr, &languages));
+        } else if attr.path().is_ident("entry") {
+            diagnostics.take(check_entries(attr, &params));
+        }
+    }
+    diagnostics.finish()
+}
+
+/// `#[migrate(fetch_action, from = 1)]`，将 action 的参数从 from 版本迁移到下一个版本
+pub struct MigrateAttr {
+    pub action: Ident,
+    pub from: u32,
+    /// from 的位置，用于报告错误
+    pub from_span: proc_macro2::Span,
+}
+
+impl Parse for MigrateAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let action = input.parse::<Ident>()?;
+        input.parse::<Token![,]>()?;
+        let pair = input.parse::<MetaNameValue>()?;
+        if !pair.path.is_ident("from") {
+            return Err(syn::Error::new_spanned(
+                &pair.path,
+                "expected `from = <version>`",
+            ));
+        }
+        let from = parse_version(&pair.value)?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Self {
+            action,
+            from,
+            from_span: syn::spanned::Spanned::span(&pair.value),
+        })
+    }
+}
+
+/// 检查迁移函数的声明，迁移函数接收旧版本的参数并返回新版本的参数，均为 `serde_json::Value`
+pub fn check_migrate(attr: &TokenStream, item: &ItemFn) -> syn::Result<MigrateAttr> {
+    let mut diagnostics = Diagnostics::default();
+    let migrate = diagnostics.take(syn::parse2::<MigrateAttr>(attr.clone()));
+    if let Some(asyncness) = &item.sig.asyncness {
+        diagnostics.push(syn::Error::new_spanned(
+            asyncness,
+            "a migration cannot be async",
+        ));
+    }
+    let typed = item
+        .sig
+        .inputs
+        .iter()
+        .all(|input| matches!(input, FnArg::Typed(_)));
+    if item.sig.inputs.len() != 1 || !typed {
+        diagnostics.push(syn::Error::new_spanned(
+            &item.sig.ident,
+            "a migration takes exactly one parameter, the arguments of the old version as `Value`",
+        ));
+    }
+    if let ReturnType::Default = item.sig.output {
+        diagnostics.push(syn::Error::new_spanned(
+            &item.sig.ident,
+            "a migration returns the migrated arguments as `Value`",
+        ));
+    }
+    diagnostics.finish()?;
+    Ok(migrate.expect("checked above"))
+}
+
+/// 检查同一文件中的迁移函数，每个版本的 action 都需要从上一个版本迁移而来
+pub fn check_migrations(actions: &[(&Ident, u32)], migrations: &[&MigrateAttr]) -> syn::Result<()> {
+    let mut diagnostics = Diagnostics::default();
+    for (index, migrate) in migrations.iter().enumerate() {
+        let Some((_, version)) = actions.iter().find(|(action, _)| **action == migrate.action)
+        else {
+            diagnostics.push(syn::Error::new_spanned(
+                &migrate.action,
+                format!("no `#[action]` named `{}` in this file", migrate.action),
+            ));
+            continue;
+        };
+        if migrate.from >= *version {
+            diagnostics.push(syn::Error::new(
+                migrate.from_span,
+                format!(
+                    "`{}` is at version {}, there is nothing to migrate from version {}",
+                    migrate.action, version, migrate.from
+                ),
+            ));
+        }
+        let duplicate = migrations[..index]
+            .iter()
+            .any(|other| other.action == migrate.action && other.from == migrate.from);
+        if duplicate {
+            diagnostics.push(syn::Error::new(
+                migrate.from_span,
+                format!(
+                    "duplicate migration of `{}` from version {}",
+                    migrate.action, migrate.from
+                ),
+            ));
+        }
+    }
+    for (action, version) in actions {
+        for from in 1..*version {
+            let declared = migrations
+                .iter()
+                .any(|migrate| migrate.action == **action && migrate.from == from);
+            if !declared {
+                diagnostics.push(syn::Error::new_spanned(
+                    action,
+                    format!(
+                        "missing `#[migrate({}, from = {})]`, cards lit with version {} cannot be upgraded",
+                        action, from, from
+                    ),
+                ));
+            }
+        }
+    }
+    diagnostics.finish()
+}
+
+/// `#[branch]` 将命名分支展开为子分支，`#[branch(error)]` 同时将其转为错误分支
+pub fn is_error_branch(attr: &Attribute) -> syn::Result<bool> {
+    match &attr.meta {
+        Meta::Path(_) => Ok(false),
+        Meta::List(list) if list.tokens.to_string() == "error" => Ok(true),
+        meta => Err(syn::Error::new_spanned(
+            meta,
+            "expected `#[branch]` or `#[branch(error)]`",
+        )),
+    }
+}
+
+/// `#[source(right)]` 指定输入端口的位置，默认在左侧
+pub fn source_position(item: &ItemEnum) -> syn::Result<String> {
+    let Some(attr) = item.attrs.iter().find(|attr| attr.path().is_ident("source")) else {
+        return Ok(String::from("left"));
+    };
+    let ident: Ident = attr.parse_args()?;
+    let position = ident.to_string();
+    if !POSITIONS.contains(&position.as_str()) {
+        return Err(syn::Error::new_spanned(
+            &ident,
+            format!(
+                "unknown position `{}`, expected one of {}",
+                position,
+                quoted(&POSITIONS)
+            ),
+        ));
+    }
+    Ok(position)
+}
+
+// 分支或子分支上的属性，`strict` 时拒绝未知的属性
+fn check_branch_attrs(
+    attrs: &[Attribute],
+    source: &str,
+    languages: &[String],
+    strict: bool,
+) -> syn::Result<()> {
+    let mut diagnostics = Diagnostics::default();
+    let mut position: Option<String> = None;
+    for attr in attrs {
+        let path = attr.path();
+        let name = path.to_token_stream().to_string();
+        if POSITIONS.contains(&name.as_str()) {
+            if let Some(position) = &position {
+                diagnostics.push(syn::Error::new_spanned(
+                    path,
+                    format!(
+                        "duplicate branch position, this branch is already placed at `{}`",
+                        position
+                    ),
+                ));
+            } else if name == source {
+                diagnostics.push(syn::Error::new_spanned(
+                    path,
+                    format!("position `{}` is taken by the source handle", name),
+                ));
+            }
+            position.get_or_insert(name);
+            diagnostics.take(check_i18n(attr, languages));
+        } else if name == "label" {
+            diagnostics.take(check_i18n(attr, languages));
+        } else if name == "branch" {
+            diagnostics.take(is_error_branch(attr));
+        } else if strict && name != "doc" && !BRANCH_ATTRS.contains(&name.as_str()) {
+            diagnostics.push(syn::Error::new_spanned(
+                path,
+                format!(
+                    "unknown attribute `{}` on result branch, expected one of {}",
+                    name,
+                    quoted(&BRANCH_ATTRS)
+                ),
+            ));
+        }
+    }
+    diagnostics.finish()
+}
+
+/// 校验 #[result] 枚举：分支属性、位置以及配置的语言
+pub fn check_result(item: &ItemEnum, languages: Option<&[String]>) -> syn::Result<()> {
+    let mut diagnostics = Diagnostics::default();
+    let languages = languages.unwrap_or_default();
+    let source = diagnostics
+        .take(source_position(item))
+        .unwrap_or_else(|| String::from("left"));
+    for variant in &item.variants {
+        diagnostics.take(check_branch_attrs(
+            &variant.attrs,
+            &source,
+            languages,
+            true,
+        ));
+        // 子分支可以单独指定位置，其余字段会带着属性生成结构体
+        if let Fields::Named(fields) = &variant.fields {
+            for field in &fields.named {
+                diagnostics.take(check_branch_attrs(&field.attrs, &source, languages, false));
+            }
+        }
+    }
+    diagnostics.finish()
+}
+
+/// 校验 #[options] 枚举：选项不能携带字段，标签需要提供配置的语言
+pub fn check_options(item: &ItemEnum, languages: Option<&[String]>) -> syn::Result<()> {
+    let mut diagnostics = Diagnostics::default();
+    let languages = languages.unwrap_or_default();
+    for variant in &item.variants {
+        if !variant.fields.is_empty() {
+            diagnostics.push(syn::Error::new_spanned(
+                &variant.fields,
+                format!(
+                    "options is only for enums with no fields, but found variant `{}` with fields",
+                    variant.ident
+                ),
+            ));
+        }
+        for attr in &variant.attrs {
+            if attr.path().is_ident("label") {
+                diagnostics.take(check_i18n(attr, languages));
+            }
+        }
+    }
+    diagnostics.finish()
+}
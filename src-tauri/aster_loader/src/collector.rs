@@ -1,3 +1,4 @@
+use aster_common::hot_lib_reloader::LibReloadObserver;
 use common::action::ActionTrait;
 
 /// Action 创建器接口
@@ -14,3 +15,17 @@ pub struct ActionCreatorInfo {
 
 // 自动注册的 Action 创建器信息
 inventory::collect!(ActionCreatorInfo);
+
+/// 动作包动态库的热重载信息
+pub struct ActionLibraryInfo {
+    pub group: &'static str,
+    pub subscribe: fn() -> LibReloadObserver,
+    /// 成功重载的次数
+    pub version: fn() -> usize,
+    /// 最近一次重载失败的原因，成功重载后清除
+    pub reload_error: fn() -> Option<String>,
+    /// 关闭当前动态库中复用的运行时，重载前调用
+    pub shutdown: fn(),
+}
+
+inventory::collect!(ActionLibraryInfo);
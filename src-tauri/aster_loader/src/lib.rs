@@ -1,44 +1,125 @@
 use std::collections::HashMap;
 
 use common::{
-    action::{Action, ActionTrait, error::ActionError},
-    ty::{CardResult, Data, type_convert::parse_data},
+    action::{
+        Action, ActionIssue, ActionTrait, LitAction, error::ActionError, manager::ActionManager,
+    },
+    application::Application,
+    ty::{CONTEXT_KEY, CardResult, Data, type_convert::parse_data},
 };
 
 use crate::collector::ActionCreatorInfo;
 
 pub mod collector;
 pub mod manifest;
+pub mod plugin;
+pub mod reload;
+
+/// 获取所有已注册的 action 类型，包括已加载插件中的 action
+pub fn get_action_types() -> Vec<&'static str> {
+    inventory::iter::<ActionCreatorInfo>
+        .into_iter()
+        .map(|creator_info| creator_info.action_type)
+        .chain(plugin::action_types())
+        .collect()
+}
 
 pub trait ActionProvider {
     fn get_action_instance_from_type(
         action_type: &str,
     ) -> Result<Box<dyn ActionTrait>, ActionError>;
+    /// 将参数迁移到当前版本，返回是否发生了迁移
+    fn migrate(&mut self) -> Result<bool, ActionError>;
     fn run(&self, context: &HashMap<String, Data>) -> Result<CardResult, ActionError>;
 }
 
+fn upgrade(action: &mut Action, instance: &dyn ActionTrait) -> Result<bool, ActionError> {
+    let current = instance.version();
+    if action.version > current {
+        return Err(ActionError::UnsupportedVersionError(
+            action.version,
+            current,
+        ));
+    }
+    if action.version == current {
+        return Ok(false);
+    }
+    let mut args = action.data.to_value();
+    for from in action.version..current {
+        args = instance.migrate(from, args)?;
+    }
+    action.data = Data::from_value(args);
+    action.version = current;
+    Ok(true)
+}
+
+/// 加载所有已点亮的 action，旧版本的卡片迁移后写回，无法使用的卡片附带原因
+pub fn load_lit_actions() -> Vec<LitAction> {
+    Application::get_action_list()
+        .into_iter()
+        .map(|mut action| {
+            let Ok(instance) = Action::get_action_instance_from_type(&action.r#type) else {
+                return LitAction {
+                    action,
+                    issue: Some(ActionIssue::Missing),
+                };
+            };
+            let issue = upgrade(&mut action, instance.as_ref())
+                .and_then(|migrated| match migrated {
+                    true => Application::lit_action(action.clone()),
+                    false => Ok(()),
+                })
+                .err()
+                .map(|e| ActionIssue::Migrate {
+                    message: e.to_string(),
+                });
+            LitAction { action, issue }
+        })
+        .collect()
+}
+
 impl ActionProvider for Action {
     fn get_action_instance_from_type(
         action_type: &str,
     ) -> Result<Box<dyn common::action::ActionTrait>, common::action::error::ActionError> {
         for creator_info in inventory::iter::<ActionCreatorInfo>.into_iter() {
-            println!("{:?}", creator_info.action_type);
             if creator_info.action_type == action_type {
                 return Ok((creator_info.creator_fn)());
             }
         }
+        // 内置的动作包优先于插件
+        if let Some(action) = plugin::find_action(action_type) {
+            return Ok(action);
+        }
         Err(ActionError::RunActionCardError(format!(
             "Action type {} not found",
             action_type
         )))
     }
+    fn migrate(&mut self) -> Result<bool, ActionError> {
+        let action = Self::get_action_instance_from_type(&self.r#type)?;
+        upgrade(self, action.as_ref())
+    }
     fn run(&self, context: &HashMap<String, Data>) -> Result<CardResult, ActionError> {
         // info!("Run action {}", &self.id);
         let action_type = self.r#type.as_str();
         // info!("Action type: {}", action_type);
         let action = Self::get_action_instance_from_type(action_type)?;
-        let data = parse_data(context, self.data.clone())
+        // 单独运行的卡片同样不能在运行中途被重载
+        let _run = reload::enter_run();
+        // 尚未加载过的旧卡片在运行前迁移，不写回文件
+        let mut current = self.clone();
+        upgrade(&mut current, action.as_ref())?;
+        let mut data = parse_data(context, current.data)
             .map_err(|e| ActionError::RunActionCardError(e.to_string()))?;
+        // 供声明了 Context 参数的 action 读取上游输出
+        if let Data::Json(map) = &mut data {
+            let context = serde_json::to_value(context)
+                .map_err(|e| ActionError::RunActionCardError(e.to_string()))?;
+            map.insert(CONTEXT_KEY.to_string(), context);
+        }
+        // 上游输出替换后再次校验，点亮时引用的值无法提前校验
+        action.validate(&data)?;
         Ok(action.run(data)?)
     }
 }
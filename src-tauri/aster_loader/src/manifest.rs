@@ -1,2 +1,16 @@
+:: aster_macro :: load_action ! (command , [async command_action]) ;
+
+:: aster_macro :: load_action ! (desktop , [async notify_action , async read_clipboard_action , async write_clipboard_action , async read_clipboard_image_action , async write_clipboard_image_action , async open_action , async prompt_action]) ;
+
+:: aster_macro :: load_action ! (fs , [read_file_action , write_file_action , copy_action , move_action , rename_action , delete_action , list_dir_action , zip_action , unzip_action , hash_action , ensure_dir_action]) ;
+
+:: aster_macro :: load_action ! (llm , [async llm_action]) ;
+
+:: aster_macro :: load_action ! (mail , [async send_mail_action]) ;
+
+:: aster_macro :: load_action ! (program , [program_action]) ;
+
+:: aster_macro :: load_action ! (transform , [query_action , regex_match_action , regex_extract_action , regex_replace_action , csv_to_json_action , json_to_csv_action , template_action , date_action , merge_json_action , pick_keys_action , rename_keys_action]) ;
+
 :: aster_macro :: load_action ! (web , [async fetch_action]) ;
 
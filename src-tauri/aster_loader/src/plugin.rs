@@ -0,0 +1,380 @@
+use std::{
+    collections::HashSet,
+    env::consts::DLL_EXTENSION,
+    ffi::{CStr, CString, c_char},
+    fs,
+    mem::ManuallyDrop,
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock, Mutex, OnceLock, RwLock},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use common::{
+    action::{
+        Action, ActionTrait,
+        error::ActionError,
+        rule::{param_rules, validate_args},
+    },
+    plugin::{
+        ABI_VERSION_SYMBOL, AbiVersionFn, CALL_SYMBOL, CallFn, FREE_SYMBOL, FreeFn,
+        MANIFEST_SYMBOL, ManifestFn, PLUGIN_ABI_VERSION, PluginCallResult, PluginManifest,
+        SHUTDOWN_SYMBOL, ShutdownFn,
+    },
+    ty::{CardResult, Data},
+};
+use libloading::Library;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::collector::ActionCreatorInfo;
+
+/// 加载前复制到该目录，原文件因此可以随时被替换
+const SHADOW_DIR: &str = ".loaded";
+
+/// 当前进程的副本目录，UI 进程、服务与 worker 各自加载插件，互不清理对方的副本
+fn shadow_dir(dir: &Path) -> PathBuf {
+    dir.join(SHADOW_DIR).join(std::process::id().to_string())
+}
+
+#[derive(Debug, Error)]
+pub enum PluginError {
+    #[error("Failed to load plugin {0}: {1}")]
+    LoadError(PathBuf, String),
+    #[error("{0} is not a DaisyTools plugin, missing symbol {1}")]
+    MissingSymbolError(PathBuf, String),
+    #[error("Plugin {0} targets ABI version {1}, but this host supports version {2}")]
+    AbiMismatchError(PathBuf, u32, u32),
+    #[error("Plugin {0} has an invalid manifest: {1}")]
+    ManifestError(PathBuf, String),
+    #[error("Action {1} of plugin {0} is already provided by another action pack")]
+    ConflictError(String, String),
+}
+
+/// 已加载的插件
+///
+/// 调用期间持有 Arc，替换或卸载后等最后一次调用结束才会释放动态库
+pub struct Plugin {
+    pub manifest: PluginManifest,
+    pub path: PathBuf,
+    modified: Option<SystemTime>,
+    shadow: PathBuf,
+    call: CallFn,
+    free: FreeFn,
+    /// 较早构建的插件没有导出
+    shutdown: Option<ShutdownFn>,
+    library: ManuallyDrop<Library>,
+}
+
+impl Drop for Plugin {
+    fn drop(&mut self) {
+        // 最后一次调用已经结束，插件中的线程须在卸载前退出
+        if let Some(shutdown) = self.shutdown {
+            unsafe { shutdown() };
+        }
+        // 函数指针在此之后不再使用
+        unsafe { ManuallyDrop::drop(&mut self.library) };
+        let _ = fs::remove_file(&self.shadow);
+    }
+}
+
+fn symbol<T: Copy>(library: &Library, path: &Path, name: &[u8]) -> Result<T, PluginError> {
+    unsafe { library.get::<T>(name) }
+        .map(|symbol| *symbol)
+        .map_err(|_| {
+            let name = String::from_utf8_lossy(&name[..name.len() - 1]).to_string();
+            PluginError::MissingSymbolError(path.to_path_buf(), name)
+        })
+}
+
+// 读取插件分配的字符串并交还给插件释放
+unsafe fn take_string(ptr: *mut c_char, free: FreeFn) -> String {
+    if ptr.is_null() {
+        return String::new();
+    }
+    let text = unsafe { CStr::from_ptr(ptr) }.to_string_lossy().to_string();
+    unsafe { free(ptr) };
+    text
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+impl Plugin {
+    /// 加载插件，ABI 版本不兼容时在读取清单之前拒绝
+    pub fn load(path: &Path) -> Result<Self, PluginError> {
+        let load_error =
+            |e: &dyn std::fmt::Display| PluginError::LoadError(path.to_path_buf(), e.to_string());
+        let dir = shadow_dir(path.parent().unwrap_or(Path::new(".")));
+        fs::create_dir_all(&dir).map_err(|e| load_error(&e))?;
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let shadow = dir.join(format!("{}-{}.{}", stem, stamp, DLL_EXTENSION));
+        fs::copy(path, &shadow).map_err(|e| load_error(&e))?;
+
+        let library = match unsafe { Library::new(&shadow) } {
+            Ok(library) => library,
+            Err(e) => {
+                let _ = fs::remove_file(&shadow);
+                return Err(load_error(&e));
+            }
+        };
+        // 先构造 Plugin，之后的任何错误都会随 Drop 卸载动态库并删除副本
+        let mut plugin = Plugin {
+            manifest: PluginManifest {
+                name: stem.to_string(),
+                version: String::new(),
+                abi_version: 0,
+                actions: vec![],
+            },
+            path: path.to_path_buf(),
+            modified: modified(path),
+            shadow,
+            call: symbol(&library, path, CALL_SYMBOL)?,
+            free: symbol(&library, path, FREE_SYMBOL)?,
+            shutdown: None,
+            library: ManuallyDrop::new(library),
+        };
+
+        let abi_version_fn: AbiVersionFn = symbol(&plugin.library, path, ABI_VERSION_SYMBOL)?;
+        let abi_version = unsafe { abi_version_fn() };
+        if abi_version != PLUGIN_ABI_VERSION {
+            return Err(PluginError::AbiMismatchError(
+                path.to_path_buf(),
+                abi_version,
+                PLUGIN_ABI_VERSION,
+            ));
+        }
+        let manifest_fn: ManifestFn = symbol(&plugin.library, path, MANIFEST_SYMBOL)?;
+        let manifest = unsafe { take_string(manifest_fn(), plugin.free) };
+        plugin.manifest = serde_json::from_str(&manifest)
+            .map_err(|e| PluginError::ManifestError(path.to_path_buf(), e.to_string()))?;
+        if plugin.manifest.abi_version != PLUGIN_ABI_VERSION {
+            return Err(PluginError::AbiMismatchError(
+                path.to_path_buf(),
+                plugin.manifest.abi_version,
+                PLUGIN_ABI_VERSION,
+            ));
+        }
+        // ABI 版本相同后才信任可选的导出函数
+        plugin.shutdown = symbol(&plugin.library, path, SHUTDOWN_SYMBOL).ok();
+        Ok(plugin)
+    }
+
+    /// 调用插件中的 action，返回插件原样给出的结果
+    pub fn call_raw(&self, action_type: &str, args: Value) -> PluginCallResult {
+        let (Ok(action), Ok(args)) = (CString::new(action_type), CString::new(args.to_string()))
+        else {
+            return PluginCallResult::Err {
+                message: "Arguments contain a NUL character".to_string(),
+            };
+        };
+        let result =
+            unsafe { take_string((self.call)(action.as_ptr(), args.as_ptr()), self.free) };
+        serde_json::from_str(&result).unwrap_or_else(|e| PluginCallResult::Err {
+            message: format!("Invalid result from plugin {}: {}", self.manifest.name, e),
+        })
+    }
+
+    pub fn call(&self, action_type: &str, args: Value) -> Result<CardResult, ActionError> {
+        to_card_result(self.call_raw(action_type, args))
+    }
+}
+
+/// 将插件返回的结果转换为卡片的结果
+pub fn to_card_result(result: PluginCallResult) -> Result<CardResult, ActionError> {
+    match result {
+        PluginCallResult::Ok { variant, data } => Ok(CardResult {
+            variant: intern(variant),
+            data,
+        }),
+        PluginCallResult::Err { message } => Err(ActionError::RunActionCardError(message)),
+    }
+}
+
+// CardResult 的分支名与 action 类型需要 &'static str，同名字符串只分配一次
+fn intern(variant: String) -> &'static str {
+    static VARIANTS: LazyLock<Mutex<HashSet<&'static str>>> = LazyLock::new(Default::default);
+    let mut variants = VARIANTS.lock().unwrap();
+    if let Some(variant) = variants.get(variant.as_str()) {
+        return variant;
+    }
+    let variant: &'static str = Box::leak(variant.into_boxed_str());
+    variants.insert(variant);
+    variant
+}
+
+static PLUGINS: LazyLock<RwLock<Vec<Arc<Plugin>>>> = LazyLock::new(Default::default);
+
+/// 按类型查找运行在 worker 进程中的 action
+pub type IsolatedResolver = fn(&str) -> Option<Box<dyn ActionTrait>>;
+
+/// 隔离模式下由宿主查找运行在 worker 进程中的 action
+static ISOLATED: OnceLock<IsolatedResolver> = OnceLock::new();
+
+/// 注册隔离模式下插件 action 的查找方式
+pub fn set_isolated_resolver(resolver: IsolatedResolver) {
+    let _ = ISOLATED.set(resolver);
+}
+
+/// 插件目录中的动态库
+pub fn plugin_paths(dir: &Path) -> Vec<PathBuf> {
+    let mut paths = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.is_file())
+                .filter(|path| path.extension().is_some_and(|ext| ext == DLL_EXTENSION))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    paths.sort();
+    paths
+}
+
+/// 扫描插件目录并加载其中的动作包，返回被拒绝的插件
+///
+/// 重复调用时只重新加载发生变化的文件，已删除的插件会被卸载
+pub fn load_plugins(dir: &Path) -> Vec<PluginError> {
+    let mut errors = vec![];
+    let previous = PLUGINS.read().unwrap().clone();
+    let mut plugins: Vec<Arc<Plugin>> = vec![];
+    if previous.is_empty() {
+        // 清理相同 pid 的进程异常退出时遗留的副本
+        let _ = fs::remove_dir_all(shadow_dir(dir));
+        // 其他进程正常退出后留下的空目录，仍在使用的目录不为空，删除会失败
+        for entry in fs::read_dir(dir.join(SHADOW_DIR))
+            .into_iter()
+            .flatten()
+            .flatten()
+        {
+            let _ = fs::remove_dir(entry.path());
+        }
+    }
+
+    for path in plugin_paths(dir) {
+        let unchanged = previous
+            .iter()
+            .find(|plugin| plugin.path == path && plugin.modified == modified(&path));
+        let plugin = match unchanged {
+            Some(plugin) => plugin.clone(),
+            None => match Plugin::load(&path) {
+                Ok(plugin) => Arc::new(plugin),
+                Err(e) => {
+                    errors.push(e);
+                    continue;
+                }
+            },
+        };
+        // 不允许覆盖内置的 action 或其他插件的 action
+        let conflict = plugin.manifest.actions.iter().find(|action| {
+            inventory::iter::<ActionCreatorInfo>
+                .into_iter()
+                .any(|info| info.action_type == action.action_type)
+                || plugins.iter().any(|other| {
+                    other
+                        .manifest
+                        .actions
+                        .iter()
+                        .any(|other| other.action_type == action.action_type)
+                })
+        });
+        if let Some(action) = conflict {
+            errors.push(PluginError::ConflictError(
+                plugin.manifest.name.clone(),
+                action.action_type.clone(),
+            ));
+            continue;
+        }
+        plugins.push(plugin);
+    }
+
+    *PLUGINS.write().unwrap() = plugins;
+    errors
+}
+
+/// 卸载插件，正在执行的调用结束后才会释放动态库
+pub fn unload_plugin(name: &str) -> bool {
+    let mut plugins = PLUGINS.write().unwrap();
+    let count = plugins.len();
+    plugins.retain(|plugin| plugin.manifest.name != name);
+    plugins.len() != count
+}
+
+/// 已加载插件的清单
+pub fn get_plugins() -> Vec<PluginManifest> {
+    PLUGINS
+        .read()
+        .unwrap()
+        .iter()
+        .map(|plugin| plugin.manifest.clone())
+        .collect()
+}
+
+/// 插件提供的 action 类型
+pub(crate) fn action_types() -> Vec<&'static str> {
+    PLUGINS
+        .read()
+        .unwrap()
+        .iter()
+        .flat_map(|plugin| plugin.manifest.actions.iter())
+        .map(|action| intern(action.action_type.clone()))
+        .collect()
+}
+
+struct PluginAction {
+    plugin: Arc<Plugin>,
+    action_type: String,
+}
+
+impl ActionTrait for PluginAction {
+    fn get_action(&self, name: String, args: Data) -> Action {
+        self.new_action(&self.action_type, name, args)
+    }
+    fn validate(&self, args: &Data) -> Result<(), ActionError> {
+        let rules = self
+            .plugin
+            .manifest
+            .actions
+            .iter()
+            .find(|action| action.action_type == self.action_type)
+            .map(|action| param_rules(&action.meta))
+            .unwrap_or_default();
+        validate_args(&rules, args)
+    }
+    // 插件中声明的迁移不会被调用，旧版本的卡片会被标记为无法使用
+    fn version(&self) -> u32 {
+        self.plugin
+            .manifest
+            .actions
+            .iter()
+            .find(|action| action.action_type == self.action_type)
+            .and_then(|action| action.meta.get("version")?.as_u64())
+            .map_or(1, |version| version as u32)
+    }
+    fn run(&self, args: Data) -> Result<CardResult, ActionError> {
+        self.plugin.call(&self.action_type, args.to_value())
+    }
+}
+
+pub(crate) fn find_action(action_type: &str) -> Option<Box<dyn ActionTrait>> {
+    let plugins = PLUGINS.read().unwrap();
+    let plugin = plugins.iter().find(|plugin| {
+        plugin
+            .manifest
+            .actions
+            .iter()
+            .any(|action| action.action_type == action_type)
+    });
+    match plugin {
+        Some(plugin) => Some(Box::new(PluginAction {
+            plugin: plugin.clone(),
+            action_type: action_type.to_string(),
+        })),
+        None => ISOLATED.get().and_then(|resolve| resolve(action_type)),
+    }
+}
@@ -0,0 +1,231 @@
+//! 动作包动态库的热重载
+//!
+//! 动态库重新构建后，热重载模块先发出即将重载的事件，此时等待所有运行中的工作流结束再放行，
+//! 重载完成前新的运行会等待，卡片因此不会在运行中途切换到新的动态库
+
+use std::{
+    cell::Cell,
+    marker::PhantomData,
+    sync::{Arc, Condvar, LazyLock, Mutex},
+    path::{Path, PathBuf},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::collector::ActionLibraryInfo;
+
+#[derive(Default)]
+struct GateState {
+    running: usize,
+    /// 正在重载的动态库数量
+    reloading: usize,
+}
+
+#[derive(Default)]
+struct ReloadGate {
+    state: Mutex<GateState>,
+    changed: Condvar,
+}
+
+static GATE: LazyLock<ReloadGate> = LazyLock::new(Default::default);
+
+thread_local! {
+    // 工作流中逐个运行卡片，同一线程中嵌套的运行只计数一次
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// 运行中的标记，全部释放后才允许重载
+pub struct RunGuard {
+    // 嵌套深度记录在线程中，不能跨线程释放
+    _not_send: PhantomData<*const ()>,
+}
+
+/// 开始一次运行，有动态库正在重载时等待重载完成
+pub fn enter_run() -> RunGuard {
+    let depth = DEPTH.get();
+    DEPTH.set(depth + 1);
+    if depth == 0 {
+        let mut state = GATE.state.lock().unwrap();
+        while state.reloading > 0 {
+            state = GATE.changed.wait(state).unwrap();
+        }
+        state.running += 1;
+    }
+    RunGuard {
+        _not_send: PhantomData,
+    }
+}
+
+impl Drop for RunGuard {
+    fn drop(&mut self) {
+        let depth = DEPTH.get() - 1;
+        DEPTH.set(depth);
+        if depth == 0 {
+            GATE.state.lock().unwrap().running -= 1;
+            GATE.changed.notify_all();
+        }
+    }
+}
+
+// 阻止新的运行，并等待运行中的工作流结束
+fn drain() {
+    let mut state = GATE.state.lock().unwrap();
+    state.reloading += 1;
+    while state.running > 0 {
+        state = GATE.changed.wait(state).unwrap();
+    }
+}
+
+fn resume() {
+    GATE.state.lock().unwrap().reloading -= 1;
+    GATE.changed.notify_all();
+}
+
+/// 一次重载的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReloadReport {
+    pub group: String,
+    /// 成功重载的次数，失败时保持不变
+    pub version: usize,
+    pub success: bool,
+    pub error: Option<String>,
+    /// 等待运行中的工作流结束的时间（毫秒）
+    pub drain_time: u64,
+    pub reloaded_at: u64,
+}
+
+/// 监听所有动作包的动态库，在工作流运行之间重新加载，每次重载后报告结果
+pub fn watch_action_libraries(on_report: impl Fn(ReloadReport) + Send + Sync + 'static) {
+    let on_report = Arc::new(on_report);
+    for library in inventory::iter::<ActionLibraryInfo> {
+        let on_report = on_report.clone();
+        // 找不到构建目录时创建加载器会 panic，只影响该线程
+        std::thread::spawn(move || {
+            let observer = (library.subscribe)();
+            loop {
+                let block = observer.wait_for_about_to_reload();
+                let started_at = Instant::now();
+                drain();
+                let drain_time = started_at.elapsed().as_millis() as u64;
+                // 旧动态库卸载后其中的线程不能继续运行
+                (library.shutdown)();
+                drop(block);
+                // 加载失败时同样会发出重载完成的事件
+                observer.wait_for_reload();
+                resume();
+
+                let error = (library.reload_error)();
+                on_report(ReloadReport {
+                    group: library.group.to_string(),
+                    version: (library.version)(),
+                    success: error.is_none(),
+                    error,
+                    drain_time,
+                    reloaded_at: SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .map(|time| time.as_millis() as u64)
+                        .unwrap_or_default(),
+                });
+            }
+        });
+    }
+}
+
+/// 动作包动态库所在的目录，与当前程序位于同一个 cargo 输出目录
+///
+/// 服务位于 `target/<profile>`，测试程序位于其下的 `deps`，
+/// 动作包只作为依赖构建时不会复制到 `target/<profile>`，同样在 `deps` 中查找
+pub fn lib_dir(group: &str) -> PathBuf {
+    let dir = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        .unwrap_or_default();
+    let file = libloading::library_filename(group);
+    let candidates = [
+        Some(dir.clone()),
+        Some(dir.join("deps")),
+        dir.parent().filter(|_| dir.ends_with("deps")).map(Path::to_path_buf),
+    ];
+    candidates
+        .into_iter()
+        .flatten()
+        .find(|dir| dir.join(&file).exists())
+        .unwrap_or(dir)
+}
+
+/// 固定当前加载的动态库，之后的构建不会被加载
+pub fn pin_action_libraries() {
+    for library in inventory::iter::<ActionLibraryInfo> {
+        std::thread::spawn(move || {
+            let observer = (library.subscribe)();
+            // 不释放即将重载的阻塞，重载线程会一直等待
+            std::mem::forget(observer.wait_for_about_to_reload());
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::mpsc, thread, time::Duration};
+
+    use super::*;
+
+    // 所有测试共用 GATE，逐个运行
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    fn running() -> usize {
+        GATE.state.lock().unwrap().running
+    }
+
+    #[test]
+    fn counts_nested_runs_once() {
+        let _lock = LOCK.lock().unwrap();
+        let outer = enter_run();
+        let inner = enter_run();
+        assert_eq!(running(), 1);
+        drop(inner);
+        assert_eq!(running(), 1);
+        drop(outer);
+        assert_eq!(running(), 0);
+    }
+
+    #[test]
+    fn reload_waits_for_running_workflows() {
+        let _lock = LOCK.lock().unwrap();
+        let guard = enter_run();
+        let (tx, rx) = mpsc::channel();
+        let reload = thread::spawn(move || {
+            drain();
+            tx.send(()).unwrap();
+        });
+        assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+        drop(guard);
+        assert!(rx.recv_timeout(Duration::from_secs(1)).is_ok());
+        reload.join().unwrap();
+        resume();
+    }
+
+    #[test]
+    fn runs_wait_for_reload() {
+        let _lock = LOCK.lock().unwrap();
+        drain();
+        let (tx, rx) = mpsc::channel();
+        let run = thread::spawn(move || {
+            let _guard = enter_run();
+            tx.send(()).unwrap();
+        });
+        assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+        resume();
+        assert!(rx.recv_timeout(Duration::from_secs(1)).is_ok());
+        run.join().unwrap();
+        assert_eq!(running(), 0);
+    }
+
+    #[test]
+    fn finds_action_libraries_next_to_the_executable() {
+        let dir = lib_dir("transform");
+        assert!(dir.join(libloading::library_filename("transform")).exists());
+    }
+}
@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use aster_macro::action;
+use common::tokio::time::sleep;
+
+#[result]
+enum TextResult {
+    #[right(zh_cn = "成功", en = "Success")]
+    Success(plug!(String as String)),
+}
+
+#[action(zh_cn = "回显", en = "Echo")]
+#[description(zh_cn = "原样返回文本", en = "Return the text as is")]
+pub fn echo_action(
+    #[name(zh_cn = "文本", en = "Text")]
+    #[description(zh_cn = "返回的文本", en = "Text to return")]
+    text: String,
+) -> TextResult {
+    TextResult::Success(text)
+}
+
+#[action(zh_cn = "等待", en = "Sleep")]
+#[description(zh_cn = "等待后返回", en = "Return after a delay")]
+pub async fn sleep_action(
+    #[name(zh_cn = "时长", en = "Duration")]
+    #[description(zh_cn = "等待的毫秒数", en = "Milliseconds to wait")]
+    ms: u64,
+) -> TextResult {
+    sleep(Duration::from_millis(ms)).await;
+    TextResult::Success(format!("slept {}ms", ms))
+}
+
+#[action(zh_cn = "崩溃", en = "Panic")]
+#[description(zh_cn = "以给定的消息 panic", en = "Panic with the given message")]
+pub fn panic_action(
+    #[name(zh_cn = "消息", en = "Message")]
+    #[description(zh_cn = "panic 的消息", en = "Message to panic with")]
+    message: String,
+) -> TextResult {
+    panic!("{}", message)
+}
+
+/// 与内置的 transform 动作包同名
+#[cfg(feature = "conflict")]
+#[action(zh_cn = "查询", en = "Query")]
+#[description(zh_cn = "与内置 action 冲突", en = "Conflicts with a built-in action")]
+pub fn query_action(
+    #[name(zh_cn = "文本", en = "Text")]
+    #[description(zh_cn = "返回的文本", en = "Text to return")]
+    text: String,
+) -> TextResult {
+    TextResult::Success(text)
+}
+
+#[cfg(not(feature = "conflict"))]
+aster_macro::export_plugin!(
+    plugin_fixture,
+    [echo_action, async sleep_action, panic_action]
+);
+
+#[cfg(feature = "conflict")]
+aster_macro::export_plugin!(
+    plugin_fixture,
+    [echo_action, async sleep_action, panic_action, query_action]
+);
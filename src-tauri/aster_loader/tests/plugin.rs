@@ -0,0 +1,191 @@
+// 保证内置动作包链接进测试，才能检查插件与内置 action 的冲突
+use transform as _;
+
+use std::{
+    env::consts::{DLL_EXTENSION, DLL_PREFIX},
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{Arc, Mutex, MutexGuard},
+    thread,
+};
+
+use aster_loader::{
+    ActionProvider,
+    plugin::{Plugin, PluginError, get_plugins, load_plugins, unload_plugin},
+};
+use common::{
+    action::Action,
+    plugin::PluginCallResult,
+    ty::Data,
+    utils::get_uid,
+};
+use serde_json::json;
+
+// 已加载的插件是全局的，逐个运行
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn lock() -> MutexGuard<'static, ()> {
+    LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 构建 tests/fixtures/plugin，返回复制出的插件，conflict 时额外导出与内置 action 同名的 query_action
+fn fixture(conflict: bool) -> PathBuf {
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/plugin/Cargo.toml");
+    let target = Path::new(env!("CARGO_TARGET_TMPDIR")).join("plugin_fixture");
+    let mut command = Command::new(env!("CARGO"));
+    command
+        .args(["build", "--quiet", "--manifest-path"])
+        .arg(&manifest)
+        .arg("--target-dir")
+        .arg(&target);
+    if conflict {
+        command.args(["--features", "conflict"]);
+    }
+    assert!(command.status().unwrap().success(), "Failed to build plugin fixture");
+
+    // 两种构建产物同名，构建后立即复制
+    let library = format!("{}plugin_fixture.{}", DLL_PREFIX, DLL_EXTENSION);
+    let copy = target.join(format!("plugin_fixture-{}.{}", conflict, DLL_EXTENSION));
+    fs::copy(target.join("debug").join(library), &copy).unwrap();
+    copy
+}
+
+// 每个测试使用独立的插件目录
+fn plugin_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aster-plugin-{}-{}", name, get_uid()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn install(dir: &Path, name: &str, conflict: bool) -> PathBuf {
+    let path = dir.join(format!("{}.{}", name, DLL_EXTENSION));
+    fs::copy(fixture(conflict), &path).unwrap();
+    path
+}
+
+fn shadow_files(dir: &Path) -> usize {
+    let dir = dir.join(".loaded").join(std::process::id().to_string());
+    fs::read_dir(dir).map_or(0, |entries| entries.count())
+}
+
+#[test]
+fn loads_plugins_and_runs_actions() {
+    let _lock = lock();
+    let dir = plugin_dir("load");
+    install(&dir, "echo", false);
+
+    assert!(load_plugins(&dir).is_empty());
+    let plugins = get_plugins();
+    assert_eq!(plugins.len(), 1);
+    assert_eq!(plugins[0].name, "plugin_fixture");
+    assert_eq!(plugins[0].actions.len(), 3);
+
+    // 插件中的 action 与内置 action 一样按类型查找
+    let action = Action::get_action_instance_from_type("echo_action").unwrap();
+    let result = action
+        .run(Data::from_value(json!({ "text": "daisy" })))
+        .unwrap();
+    assert_eq!(result.variant, "Success");
+    assert!(matches!(result.data, Data::String(text) if text == "daisy"));
+
+    assert!(unload_plugin("plugin_fixture"));
+    assert!(Action::get_action_instance_from_type("echo_action").is_err());
+}
+
+#[test]
+fn runs_async_actions_concurrently() {
+    let _lock = lock();
+    let dir = plugin_dir("async");
+    let plugin = Arc::new(Plugin::load(&install(&dir, "sleep", false)).unwrap());
+
+    let calls = (0..4).map(|i| {
+        let plugin = plugin.clone();
+        thread::spawn(move || plugin.call("sleep_action", json!({ "ms": 50 * i })))
+    });
+    for (i, call) in calls.collect::<Vec<_>>().into_iter().enumerate() {
+        let result = call.join().unwrap().unwrap();
+        assert!(matches!(result.data, Data::String(text) if text == format!("slept {}ms", 50 * i)));
+    }
+    // 卸载前关闭插件中复用的运行时
+    drop(plugin);
+    assert_eq!(shadow_files(&dir), 0);
+}
+
+#[test]
+fn catches_panics_in_plugins() {
+    let _lock = lock();
+    let dir = plugin_dir("panic");
+    let plugin = Plugin::load(&install(&dir, "panic", false)).unwrap();
+
+    let result = plugin.call_raw("panic_action", json!({ "message": "boom" }));
+    assert!(matches!(
+        result,
+        PluginCallResult::Err { message } if message == "Action panic_action panicked"
+    ));
+    let result = plugin.call_raw("missing_action", json!({}));
+    assert!(matches!(
+        result,
+        PluginCallResult::Err { message } if message.contains("not found")
+    ));
+
+    // panic 之后插件仍然可用
+    let result = plugin.call("echo_action", json!({ "text": "still here" })).unwrap();
+    assert!(matches!(result.data, Data::String(text) if text == "still here"));
+}
+
+#[test]
+fn cleans_up_shadow_copies() {
+    let _lock = lock();
+    let dir = plugin_dir("shadow");
+    let path = install(&dir, "echo", false);
+    // 异常退出的进程遗留的副本，以及其他进程留下的空目录
+    let own = dir.join(".loaded").join(std::process::id().to_string());
+    fs::create_dir_all(&own).unwrap();
+    fs::write(own.join(format!("stale.{}", DLL_EXTENSION)), "").unwrap();
+    let other = dir.join(".loaded").join("0");
+    fs::create_dir_all(&other).unwrap();
+
+    assert!(load_plugins(&dir).is_empty());
+    assert_eq!(shadow_files(&dir), 1);
+    assert!(!other.exists());
+
+    // 原文件可以在加载期间被替换，重新扫描时加载新的副本并释放旧的
+    fs::remove_file(&path).unwrap();
+    install(&dir, "echo", false);
+    assert!(load_plugins(&dir).is_empty());
+    assert_eq!(shadow_files(&dir), 1);
+
+    // 删除的插件在重新扫描时卸载
+    fs::remove_file(&path).unwrap();
+    assert!(load_plugins(&dir).is_empty());
+    assert!(get_plugins().is_empty());
+    assert_eq!(shadow_files(&dir), 0);
+}
+
+#[test]
+fn rejects_conflicting_actions() {
+    let _lock = lock();
+    let dir = plugin_dir("conflict");
+    install(&dir, "a", false);
+    install(&dir, "b", false);
+
+    // 先加载的插件保留，重复提供同名 action 的插件被拒绝
+    let errors = load_plugins(&dir);
+    assert!(matches!(
+        errors.as_slice(),
+        [PluginError::ConflictError(_, action)] if action == "echo_action"
+    ));
+    assert_eq!(get_plugins().len(), 1);
+    assert!(unload_plugin("plugin_fixture"));
+
+    // 不能覆盖内置的 action
+    let dir = plugin_dir("builtin");
+    install(&dir, "query", true);
+    let errors = load_plugins(&dir);
+    assert!(matches!(
+        errors.as_slice(),
+        [PluginError::ConflictError(_, action)] if action == "query_action"
+    ));
+    assert!(get_plugins().is_empty());
+}
@@ -1,9 +1,15 @@
-use aster_common::action::param::{parse_param_attributes, ParamInfo};
+use aster_common::action::param::ParamInfo;
+use aster_common::attr::{parse_attr, parse_proc_attr};
+use aster_common::i18n::{ActionI18n, ParamI18n, ParsedI18nMap, ParsedI18nMapTrait};
 use aster_common::nesting::NESTING_PRIFIX;
+use aster_common::validate::{
+    check_action, check_migrate, current_languages, parse_param_attrs, split_action_attr,
+    PARAM_ATTRS,
+};
 use common::utils::to_upper_camel_case;
 use proc_macro::TokenStream;
-use proc_macro2::Span;
-use quote::quote;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
 use syn;
 use syn::token::Pub;
 use syn::{
@@ -13,8 +19,28 @@ use syn::{
 
 use crate::utils::{create_destructuring_pattern, create_struct_with_dynamic_fields};
 
-pub fn define_action_impl(_: TokenStream, input: TokenStream) -> TokenStream {
+pub fn define_migrate_impl(attr: TokenStream, input: TokenStream) -> TokenStream {
     let mut impl_fn = parse_macro_input!(input as ItemFn);
+    if let Err(e) = check_migrate(&attr.into(), &impl_fn) {
+        return e.into_compile_error().into();
+    }
+    // 由 load_action! 生成的代码调用
+    impl_fn.vis = syn::Visibility::Public(Pub {
+        span: Span::call_site(),
+    });
+    quote! { #impl_fn }.into()
+}
+
+pub fn define_action_impl(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let impl_fn = parse_macro_input!(input as ItemFn);
+    expand_action(attr.into(), impl_fn)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_action(attr: TokenStream2, mut impl_fn: ItemFn) -> syn::Result<TokenStream2> {
+    // 先完整校验一遍，所有问题一次性报告在对应的位置上
+    check_action(&attr, &impl_fn, current_languages().as_deref(), None)?;
 
     // 获取函数名，这里的名称是snake_case
     // 将用于：
@@ -24,17 +50,27 @@ pub fn define_action_impl(_: TokenStream, input: TokenStream) -> TokenStream {
     let action_name = impl_fn.sig.ident.clone();
     let action_name_str = &action_name.to_string();
 
-    let return_type = match &impl_fn.sig.output {
-        ReturnType::Type(_, ty) => {
-            let ty = ty.as_ref();
+    // 声明的返回类型是 #[result] 生成的标记类型，仅用于关联分支，实际返回 CardResult
+    let result_name = match &impl_fn.sig.output {
+        ReturnType::Type(_, ty) => quote! { #ty }.to_string(),
+        _ => String::new(),
+    };
+    let (return_type, result_type) = match &impl_fn.sig.output {
+        ReturnType::Type(_, ty) => (
             ReturnType::Type(
                 Token![->](Span::call_site()),
                 Box::new(Type::Verbatim(
-                    quote! {::std::result::Result<#ty, ::std::boxed::Box<dyn ::std::error::Error>>},
+                    quote! {::std::result::Result<::common::ty::CardResult, ::std::boxed::Box<dyn ::std::error::Error>>},
                 )),
-            )
+            ),
+            ty.clone(),
+        ),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &impl_fn.sig.ident,
+                "Return type is required",
+            ))
         }
-        _ => panic!("Return type is required"),
     };
 
     impl_fn.sig.output = return_type;
@@ -46,67 +82,51 @@ pub fn define_action_impl(_: TokenStream, input: TokenStream) -> TokenStream {
     let mut all_param_info: Vec<ParamInfo> = Vec::new();
 
     // 遍历函数的所有参数，提取参数名和类型
-    let args = impl_fn
-        .sig
-        .inputs
-        .iter_mut()
-        .enumerate()
-        .map(|(_, arg)| match arg {
+    let mut args = vec![];
+    for arg in impl_fn.sig.inputs.iter_mut() {
+        let typed_param = match arg {
             // 不允许使用 self 参数，因为这是静态函数
-            syn::FnArg::Receiver(_) => panic!("'self' is not allowed!"),
-            syn::FnArg::Typed(typed_param) => {
-                if let syn::Pat::Ident(pat_ident) = *typed_param.pat.clone() {
-                    let param_name = &pat_ident.ident;
-                    let param_type = &typed_param.ty;
-                    let param_name_str = param_name.to_string();
-                    let param_type_str = quote! { #param_type }.to_string();
-
-                    // 使用 Darling 解析参数属性
-                    match parse_param_attributes(
-                        &typed_param
-                            .attrs
-                            .iter()
-                            .map(|e| darling::ast::NestedMeta::Meta(e.meta.clone()))
-                            .collect::<Vec<_>>(),
-                    ) {
-                        Ok(param_attr) => {
-                            // 创建参数信息并存储
-                            let param_info =
-                                ParamInfo::new(param_name_str.clone(), param_type_str, param_attr);
-                            all_param_info.push(param_info);
-                        }
-                        Err(e) => {
-                            // 如果解析失败，创建一个空的属性配置
-                            eprintln!("解析参数 '{}' 的属性失败: {}", param_name_str, e);
-                            let param_info = ParamInfo::new(
-                                param_name_str.clone(),
-                                param_type_str,
-                                Default::default(),
-                            );
-                            all_param_info.push(param_info);
-                        }
-                    }
+            syn::FnArg::Receiver(receiver) => {
+                return Err(syn::Error::new_spanned(receiver, "'self' is not allowed!"))
+            }
+            syn::FnArg::Typed(typed_param) => typed_param,
+        };
+        let syn::Pat::Ident(pat_ident) = typed_param.pat.as_ref() else {
+            return Err(syn::Error::new_spanned(
+                &typed_param.pat,
+                "Unsupported pattern",
+            ));
+        };
+        let param_name_str = pat_ident.ident.to_string();
+        let param_type = &typed_param.ty;
+        let param_type_str = quote! { #param_type }.to_string();
 
-                    // 清理已处理的属性
-                    typed_param.attrs.retain(|attr| {
-                        let path = attr.path();
-                        match path.get_ident() {
-                            Some(ident) => {
-                                let ident_str = ident.to_string();
-                                !["name", "description", "default"].contains(&(&ident_str as &str))
-                            }
-                            _ => true,
-                        }
-                    });
-
-                    // 返回 (参数名字符串, 参数类型)
-                    (param_name_str, param_type)
-                } else {
-                    panic!("Unsupported pattern")
+        // Context 参数由运行时注入，不属于表单
+        if param_type_str != "Context" {
+            // 属性已经校验过，这里只取出其中的内容
+            let param_attr = parse_param_attrs(&typed_param.attrs, &[])?;
+            all_param_info.push(ParamInfo::new(
+                param_name_str.clone(),
+                param_type_str,
+                param_attr,
+            ));
+
+            // 清理已处理的属性
+            typed_param.attrs.retain(|attr| {
+                let path = attr.path();
+                match path.get_ident() {
+                    Some(ident) => {
+                        let ident_str = ident.to_string();
+                        !PARAM_ATTRS.contains(&(&ident_str as &str))
+                    }
+                    _ => true,
                 }
-            }
-        })
-        .collect::<Vec<_>>();
+            });
+        }
+
+        // 记录 (参数名字符串, 参数类型)
+        args.push((param_name_str, typed_param.ty.clone()));
+    }
 
     // 生成 Action 结构体名称（UpperCamelCase）
     let action_struct = Ident::new(&to_upper_camel_case(&action_name_str), Span::call_site());
@@ -116,7 +136,16 @@ pub fn define_action_impl(_: TokenStream, input: TokenStream) -> TokenStream {
     let action_arg_str = format!("{}Arg", &action_struct_str);
 
     // 使用工具函数动态生成参数结构体定义
-    let impl_action_arg = create_struct_with_dynamic_fields(&action_arg_str, args.clone());
+    let mut impl_action_arg = create_struct_with_dynamic_fields(
+        &action_arg_str,
+        args.iter().map(|(name, ty)| (name.clone(), ty)).collect(),
+    );
+    for field in impl_action_arg.fields.iter_mut() {
+        let ty = &field.ty;
+        if quote! { #ty }.to_string() == "Context" {
+            field.attrs.push(parse_quote!(#[serde(rename = "$context", default)]));
+        }
+    }
 
     // 提取所有参数名用于函数调用
     let arg_list = args
@@ -127,6 +156,20 @@ pub fn define_action_impl(_: TokenStream, input: TokenStream) -> TokenStream {
     // 生成参数解构模式，用于从结构体中提取各个字段
     let destructuring_pattern = create_destructuring_pattern(&action_arg_str, arg_list);
 
+    // 插件清单中的 action 信息由插件自身提供，在编译期序列化为常量
+    let version = split_action_attr(attr.clone()).map_or(1, |(version, _)| version);
+    let meta = action_meta(
+        action_name_str,
+        result_name,
+        version,
+        attr,
+        &impl_fn.attrs,
+        &all_param_info,
+    );
+    let meta_ident = format_ident!("{}_META", action_name_str.to_uppercase());
+    let version_ident = format_ident!("{}_VERSION", action_name_str.to_uppercase());
+    let branches_ident = format_ident!("{}_BRANCHES", action_name_str.to_uppercase());
+
     let mut visitor = ActionVisitor::new();
     visitor.visit_item_fn_mut(&mut impl_fn);
 
@@ -143,20 +186,97 @@ pub fn define_action_impl(_: TokenStream, input: TokenStream) -> TokenStream {
 
     impl_fn.sig.inputs = fn_input;
 
+    // 动态库与宿主各自链接 tokio，宿主的运行时无法驱动动态库中的 Future，
+    // 异步 action 在动态库内运行完毕后返回，导出的函数签名因此与同步 action 相同
+    let export = match impl_fn.sig.asyncness {
+        Some(_) => {
+            let blocking_fn = format_ident!("__{}_blocking", action_name);
+            quote! {
+                #impl_fn
+
+                #[doc(hidden)]
+                #[unsafe(export_name = #action_name_str)]
+                pub fn #blocking_fn(
+                    arg: ::serde_json::Value,
+                ) -> ::std::result::Result<::common::ty::CardResult, ::std::boxed::Box<dyn ::std::error::Error>> {
+                    ::common::plugin::block_on(#action_name(arg))
+                }
+            }
+        }
+        None => quote! {
+            #[unsafe(no_mangle)]
+            #impl_fn
+        },
+    };
+
     // 生成最终代码
     let expanded = quote! {
         use ::aster_macro::*;
         // 保留原始函数定义
-        #[unsafe(no_mangle)]
-        #impl_fn
+        #export
 
         // 生成参数结构体，自动实现 Debug 和 Deserialize
         #[derive(Debug, ::serde::Deserialize)]
         #impl_action_arg
+
+        #[doc(hidden)]
+        pub const #meta_ident: &str = #meta;
+
+        #[doc(hidden)]
+        pub const #version_ident: u32 = #version;
+
+        #[doc(hidden)]
+        pub const #branches_ident: &str = <#result_type>::BRANCHES;
     };
 
-    // 将 quote! 生成的代码转换为 TokenStream 返回给编译器
-    TokenStream::from(expanded)
+    Ok(expanded)
+}
+
+fn action_meta(
+    action_type: &str,
+    result: String,
+    version: u32,
+    attr: proc_macro2::TokenStream,
+    fn_attrs: &[syn::Attribute],
+    param_info: &[ParamInfo],
+) -> String {
+    let title = split_action_attr(attr)
+        .ok()
+        .and_then(|(_, title)| parse_proc_attr::<ParsedI18nMap>(&title).ok())
+        .unwrap_or_default();
+    let mut description = ParsedI18nMap::new();
+    let mut entries = vec![];
+    for attr in fn_attrs {
+        if attr.path().is_ident("description") {
+            if let Ok((i18n, _)) = parse_attr::<ParsedI18nMap>(attr) {
+                description = i18n;
+            }
+        } else if attr.path().is_ident("entry") {
+            if let syn::Meta::List(list) = &attr.meta {
+                entries.push(list.tokens.to_string());
+            }
+        }
+    }
+    let meta = ActionI18n {
+        action_type: action_type.to_string(),
+        title: title.to_filter_value(),
+        description: description.to_filter_value(),
+        params: param_info
+            .iter()
+            .map(|param| ParamI18n {
+                description: param.attributes.get_all_descriptions(),
+                key: param.name.clone(),
+                name: param.attributes.get_all_names(),
+                r#type: param.r#type.clone(),
+                validate: param.attributes.get_validate_rule(),
+            })
+            .collect(),
+        entries,
+        keys: vec![],
+        result,
+        version,
+    };
+    serde_json::to_string(&meta).unwrap_or_default()
 }
 
 struct ActionVisitor {
@@ -1,3 +1,4 @@
+use aster_common::action::branch::extract_i18n_and_position_from_result;
 use aster_common::nesting::{parse_nesting, NESTING_PRIFIX};
 use aster_common::utils::{create_string_literal, IntoIdent};
 use proc_macro::TokenStream;
@@ -5,16 +6,17 @@ use proc_macro2::Span;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{quote, ToTokens};
 use serde_json::Map;
-use syn::ItemType;
 use syn::{
     parse_macro_input, parse_quote,
     punctuated::Punctuated,
     token::{Brace, Comma, Pub},
     Attribute, ExprCast, ExprClosure, ExprStruct, Field, Fields, FieldsNamed, FieldsUnnamed, FnArg,
-    Ident, ImplItem, ImplItemFn, ItemEnum, ItemImpl, ItemStruct, ItemTrait, Lit, Meta, TraitItem,
+    Ident, ImplItem, ImplItemFn, ItemEnum, ItemImpl, ItemStruct, ItemTrait, Lit, TraitItem,
     TraitItemFn, Type, Variant, Visibility,
 };
 
+use aster_common::validate::{check_result, current_languages, is_error_branch};
+
 use crate::utils::{any_error, create_vec_expr, data_wrapper, prepend_underscore, result};
 #[derive(Debug)]
 pub(crate) struct Context<'a> {
@@ -38,7 +40,7 @@ fn parse_arg(
     field: &mut Field,
     ArgsContext { nesting_ident }: &ArgsContext,
     _context: &mut Context,
-) -> VariantHandlerOutput {
+) -> syn::Result<VariantHandlerOutput> {
     let (trait_item, impl_item, mut stmt_list) = VariantHandlerOutput::default();
 
     // nesting
@@ -54,8 +56,7 @@ fn parse_arg(
                 &m.mac.tokens,
                 nesting_ident,
                 (&mut stmt, &mut _nesting_plug),
-            )
-            .unwrap();
+            )?;
             field.ty = ty;
             stmt_list.extend(stmt);
         };
@@ -67,8 +68,7 @@ fn parse_arg(
             if !m.mac.path.is_ident("plug") {
                 break;
             }
-            let token = &m.mac.tokens;
-            let cast: ExprCast = parse_quote!(#token);
+            let cast: ExprCast = syn::parse2(m.mac.tokens.clone())?;
             let (expr, _) = (cast.expr.as_ref(), cast.ty.as_ref());
 
             field.ty = parse_quote!(#expr);
@@ -76,14 +76,24 @@ fn parse_arg(
         break;
     }
 
-    (trait_item, impl_item, stmt_list)
+    Ok((trait_item, impl_item, stmt_list))
 }
 
 /// 创建分支Result，根据枚举生成的Result，直接将返回值对应到workflow的下一个分支
 pub fn result_branch_impl(input: TokenStream) -> TokenStream {
     // 需要用到enum上
-    let mut result_item = parse_macro_input!(input as ItemEnum);
+    let result_item = parse_macro_input!(input as ItemEnum);
+    expand_result(result_item)
+        .unwrap_or_else(|e| e.into_compile_error().into())
+}
+
+fn expand_result(mut result_item: ItemEnum) -> syn::Result<TokenStream> {
+    check_result(&result_item, current_languages().as_deref())?;
     let result_ident = &result_item.ident;
+    // 插件清单中的分支信息，与代码生成提取的内容相同
+    let branches =
+        extract_i18n_and_position_from_result(&result_item, current_languages().as_deref())?;
+    let branches = serde_json::to_string(&branches).unwrap_or_default();
 
     let inner_ident = prepend_underscore(&result_ident);
     let mut trait_item: ItemTrait = parse_quote! {
@@ -113,7 +123,7 @@ pub fn result_branch_impl(input: TokenStream) -> TokenStream {
     let mut result_trait_members: Vec<TraitItem> = vec![];
 
     let any_err = any_error();
-    let return_type = &result(parse_quote!(#result_ident), any_err.clone());
+    let return_type = &result(parse_quote!(::common::ty::CardResult), any_err.clone());
 
     let mut context = Context {
         result_ident,
@@ -122,12 +132,10 @@ pub fn result_branch_impl(input: TokenStream) -> TokenStream {
         result_ext_trait,
     };
 
-    let mut result_struct: ItemType = parse_quote! {
-        // pub struct #result_ident {
-        //     pub variant: &'static str,
-        //     pub data: Data,
-        // }
-        pub type #result_ident = ::common::ty::CardResult;
+    // 每个结果枚举对应一个独立的标记类型，分支以关联函数与常量的形式挂在其上，
+    // 同一文件中的多个结果枚举即使分支同名也不会冲突
+    let mut result_struct: ItemStruct = parse_quote! {
+        pub struct #result_ident;
     };
 
     result_item
@@ -136,12 +144,14 @@ pub fn result_branch_impl(input: TokenStream) -> TokenStream {
     result_struct.attrs = result_item.attrs;
 
     for variant in &result_item.variants {
-        let attribute = parse_result_attr(&variant.attrs);
+        let attribute = parse_result_attr(&variant.attrs)?;
         let (trait_items, impl_items, stmt_items) = match &variant.fields {
             Fields::Unnamed(fields) => {
-                handle_unnamed_variant(variant, fields, attribute, &mut context)
+                handle_unnamed_variant(variant, fields, attribute, &mut context)?
+            }
+            Fields::Named(fields) => {
+                handle_named_variant(variant, fields, attribute, &mut context)?
             }
-            Fields::Named(fields) => handle_named_variant(variant, fields, attribute, &mut context),
             Fields::Unit => handle_unit_variant(variant, attribute, &mut context),
         };
         result_trait_members.extend(trait_items);
@@ -159,6 +169,10 @@ pub fn result_branch_impl(input: TokenStream) -> TokenStream {
 
     let mut token: TokenStream = quote! {
         #result_struct
+        impl #result_ident {
+            #[doc(hidden)]
+            pub const BRANCHES: &str = #branches;
+        }
         // impl ::std::convert::Into<::common::ty::CardResult> for #result_ident {
         //     fn into(self) -> ::common::ty::CardResult {
         //         ::common::ty::CardResult {
@@ -176,7 +190,7 @@ pub fn result_branch_impl(input: TokenStream) -> TokenStream {
     let stmts: TokenStream = TokenStream2::from_iter(stmts).into();
 
     token.extend(stmts);
-    token
+    Ok(token)
 }
 
 pub fn handle_unnamed_variant(
@@ -184,12 +198,11 @@ pub fn handle_unnamed_variant(
     unname: &FieldsUnnamed,
     ParsedAttribute { into_error, .. }: ParsedAttribute,
     context: &mut Context,
-) -> VariantHandlerOutput {
+) -> syn::Result<VariantHandlerOutput> {
     let ident = variant.ident.clone();
     let ident_name = ident.to_string();
     let ident_lit = create_string_literal(&ident_name);
 
-    let result_ident = context.result_ident.clone();
     let return_type = context.return_type.clone();
 
     let mut members = unname.unnamed.clone();
@@ -207,7 +220,7 @@ pub fn handle_unnamed_variant(
                 ),
             },
             context,
-        );
+        )?;
         stmt_list.extend(stmt);
     }
 
@@ -255,14 +268,14 @@ pub fn handle_unnamed_variant(
     // 构建ImplItem
     let impl_item: ImplItem = parse_quote! {
         fn #ident(#args) -> #return_type {
-            ::std::result::Result::Ok(#result_ident {
+            ::std::result::Result::Ok(::common::ty::CardResult {
                 variant: #ident_lit,
                 data: #data_expr,
             })
         }
     };
 
-    (vec![TraitItem::Fn(item_fn)], vec![impl_item], stmt_list)
+    Ok((vec![TraitItem::Fn(item_fn)], vec![impl_item], stmt_list))
 }
 
 fn handle_named_variant(
@@ -275,7 +288,7 @@ fn handle_named_variant(
         ..
     }: ParsedAttribute,
     context: &mut Context,
-) -> VariantHandlerOutput {
+) -> syn::Result<VariantHandlerOutput> {
     // named 形似结构体，需要把它转化为一个struct
     // 默认情况下，需要传递一个同名的结构体
     // 如果使用了 #[branch] 标记，则转化为子分支
@@ -325,7 +338,7 @@ fn handle_named_variant(
                     ),
                 },
                 context,
-            );
+            )?;
             stmt.extend(stmt_list);
         }
 
@@ -335,7 +348,6 @@ fn handle_named_variant(
         });
 
         let return_type = context.return_type;
-        let result_ident = context.result_ident;
         // 然后创建trait
         let item_trait = parse_quote! {
             #[allow(non_snake_case)]
@@ -346,7 +358,7 @@ fn handle_named_variant(
         let ident_lit = create_string_literal(&ident.to_string());
         let item_impl = parse_quote! {
             fn #ident(arg_0: #branch_ident) -> #return_type {
-                ::std::result::Result::Ok(#result_ident {
+                ::std::result::Result::Ok(::common::ty::CardResult {
                     variant: #ident_lit,
                     data: ::common::ty::Data::Any(::serde_json::to_value(arg_0)?),
                 })
@@ -354,7 +366,7 @@ fn handle_named_variant(
         };
         stmt.push(quote! {#st}.into());
 
-        return (vec![item_trait], vec![item_impl], stmt);
+        return Ok((vec![item_trait], vec![item_impl], stmt));
     }
     let item_const = parse_quote! {
         #[allow(non_upper_case_globals)]
@@ -388,7 +400,10 @@ fn handle_named_variant(
              }| {
                 // 实现子分支的fn
                 let Some(sub_branch_ident) = id else {
-                    panic!("Named branch in {} has no ident", &variant.ident);
+                    return Err(syn::Error::new_spanned(
+                        ty,
+                        format!("Named branch in {} has no ident", &variant.ident),
+                    ));
                 };
 
                 let mut into_error = into_error_branch;
@@ -397,7 +412,7 @@ fn handle_named_variant(
                     into_error: i_e,
                     raw: r,
                     ..
-                } = parse_result_attr(&attrs);
+                } = parse_result_attr(&attrs)?;
 
                 if i_e {
                     into_error = true
@@ -413,11 +428,7 @@ fn handle_named_variant(
                 if into_error {
                     handle_into_error(sub_branch_ident, &variant_lit, context);
                 };
-                let Context {
-                    result_ident,
-                    return_type,
-                    ..
-                } = context;
+                let Context { return_type, .. } = context;
 
                 let item: ImplItem = match ty {
                     // 元组类型转化为多参数函数
@@ -465,7 +476,7 @@ fn handle_named_variant(
                         let impl_fn: ImplItemFn = parse_quote! {
                             #warning
                             pub fn #sub_branch_ident(self, #args) -> #return_type {
-                                ::std::result::Result::Ok(#result_ident {
+                                ::std::result::Result::Ok(::common::ty::CardResult {
                                     variant: #variant_lit,
                                     data: ::common::ty::Data::Vec(#data_expr),
                                 })
@@ -475,12 +486,15 @@ fn handle_named_variant(
                     }
                     Type::Infer(_) => {
                         if !into_error {
-                            panic!(
-                                "{} {} {}",
-                                "Using inferred type _ requires converting the",
-                                sub_branch_ident.to_string(),
-                                "branch into an error variant using #[error]"
-                            )
+                            return Err(syn::Error::new_spanned(
+                                ty,
+                                format!(
+                                    "{} {} {}",
+                                    "Using inferred type _ requires converting the",
+                                    sub_branch_ident,
+                                    "branch into an error variant using #[branch(error)]"
+                                ),
+                            ));
                         } else {
                             ImplItem::Verbatim(TokenStream::new().into())
                         }
@@ -490,7 +504,7 @@ fn handle_named_variant(
                         let data_expr = data_wrapper(&"arg_0".into_ident(), &type_name);
                         parse_quote! {
                             fn #sub_branch_ident(arg_0: #type_path) -> #return_type {
-                                ::std::result::Result::Ok(#result_ident {
+                                ::std::result::Result::Ok(::common::ty::CardResult {
                                     variant: #variant_lit,
                                     data: #data_expr,
                                 })
@@ -502,25 +516,30 @@ fn handle_named_variant(
                         ImplItem::Verbatim(quote! { struct Token {} }.into());
                         parse_quote! {
                             fn nesting() -> #return_type {
-                                ::std::result::Result::Ok(#result_ident {
+                                ::std::result::Result::Ok(::common::ty::CardResult {
                                     variant: #variant_lit,
                                     data: $::common::ty::Data::Null,
                                 })
                             }
                         }
                     }
-                    _ => todo!(),
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            ty,
+                            "unsupported branch type, expected a type, a tuple or `_`",
+                        ))
+                    }
                 };
-                item
+                Ok(item)
             },
         )
-        .collect();
+        .collect::<syn::Result<_>>()?;
 
     // 倘若转化为错误分支，那么原有的Result成员就不需要注入
     if into_error_branch {
-        VariantHandlerOutput::default()
+        Ok(VariantHandlerOutput::default())
     } else {
-        (vec![item_const], vec![item_const_impl], stmts)
+        Ok((vec![item_const], vec![item_const_impl], stmts))
     }
 }
 
@@ -535,7 +554,6 @@ fn handle_unit_variant(
     let variant_name = variant_ident.to_string();
     let variant_lit = create_string_literal(&variant_name);
 
-    let result_ident = context.result_ident.clone();
     let return_type = &context.return_type;
 
     if into_error {
@@ -555,7 +573,7 @@ fn handle_unit_variant(
     let item_const_impl: ImplItem = parse_quote! {
         const #variant_ident: #return_type =
             ::std::result::Result::Ok(
-            #result_ident {
+            ::common::ty::CardResult {
                 variant: #variant_lit,
                 data: ::common::ty::Data::Null
             }
@@ -571,7 +589,7 @@ pub(crate) struct ParsedAttribute {
     pub raw: bool,
 }
 
-fn parse_result_attr(attrs: &Vec<Attribute>) -> ParsedAttribute {
+fn parse_result_attr(attrs: &Vec<Attribute>) -> syn::Result<ParsedAttribute> {
     let mut pa = ParsedAttribute {
         into_error: false,
         raw: false,
@@ -579,29 +597,16 @@ fn parse_result_attr(attrs: &Vec<Attribute>) -> ParsedAttribute {
     };
 
     for attr in attrs {
-        let attr_ident = attr.path().require_ident().unwrap();
-        let attr_str = attr_ident.to_string();
-        let attr_str: &str = Box::leak(Box::new(attr_str));
-        match attr_str {
-            "branch" => {
-                match &attr.meta {
-                    Meta::List(list) => {
-                        if &list.tokens.to_string() == "error" {
-                            pa.into_error = true;
-                        }
-                    }
-                    _ => panic!("only receive error"),
-                }
-                pa.into_branch = true;
-            }
-            "raw" => {
-                pa.raw = true;
-            }
-            _ => (),
+        let path = attr.path();
+        if path.is_ident("branch") {
+            pa.into_error = is_error_branch(attr)?;
+            pa.into_branch = true;
+        } else if path.is_ident("raw") {
+            pa.raw = true;
         }
     }
 
-    pa
+    Ok(pa)
 }
 
 fn handle_into_error(
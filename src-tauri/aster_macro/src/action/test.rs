@@ -0,0 +1,101 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, FnArg, Ident, ItemFn, LitInt, ReturnType, Token,
+};
+
+/// `#[action_test(merge_json_action, timeout = 30)]`，timeout 的单位为秒
+struct ActionTestAttr {
+    action: Ident,
+    timeout: Option<LitInt>,
+}
+
+impl Parse for ActionTestAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let action = input.parse::<Ident>()?;
+        let mut timeout = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key = input.parse::<Ident>()?;
+            if key != "timeout" {
+                return Err(syn::Error::new_spanned(
+                    key,
+                    "expected `timeout = <seconds>`",
+                ));
+            }
+            input.parse::<Token![=]>()?;
+            let seconds = input.parse::<LitInt>()?;
+            seconds.base10_parse::<u64>()?;
+            timeout = Some(seconds);
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(Self { action, timeout })
+    }
+}
+
+// 测试函数接收 ActionTest 并返回 aster_test::Result<()>
+fn check_action_test(item: &ItemFn) -> syn::Result<()> {
+    if let Some(asyncness) = &item.sig.asyncness {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "an action test cannot be async, the action runs on a blocking thread",
+        ));
+    }
+    let typed = item
+        .sig
+        .inputs
+        .iter()
+        .all(|input| matches!(input, FnArg::Typed(_)));
+    if item.sig.inputs.len() != 1 || !typed {
+        return Err(syn::Error::new_spanned(
+            &item.sig.ident,
+            "an action test takes exactly one parameter, the `ActionTest` to configure and run",
+        ));
+    }
+    if let ReturnType::Default = item.sig.output {
+        return Err(syn::Error::new_spanned(
+            &item.sig.ident,
+            "an action test returns `aster_test::Result<()>`",
+        ));
+    }
+    Ok(())
+}
+
+pub fn define_action_test_impl(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let attr = parse_macro_input!(attr as ActionTestAttr);
+    let item = parse_macro_input!(input as ItemFn);
+    expand_action_test(attr, item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_action_test(attr: ActionTestAttr, item: ItemFn) -> syn::Result<TokenStream2> {
+    check_action_test(&item)?;
+    let name = &item.sig.ident;
+    let action_type = attr.action.to_string();
+    let timeout = match attr.timeout {
+        Some(seconds) => quote! {
+            ::std::option::Option::Some(::std::time::Duration::from_secs(#seconds))
+        },
+        None => quote! { ::std::option::Option::None },
+    };
+    let attrs = &item.attrs;
+    let vis = &item.vis;
+    let sig = &item.sig;
+    let block = &item.block;
+
+    Ok(quote! {
+        #[test]
+        #(#attrs)*
+        #vis fn #name() {
+            #sig #block
+            ::aster_test::run_action_test(
+                stringify!(#name),
+                ::aster_test::ActionTest::new(#action_type),
+                #timeout,
+                #name,
+            );
+        }
+    })
+}
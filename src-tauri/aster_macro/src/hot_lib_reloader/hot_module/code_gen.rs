@@ -51,6 +51,8 @@ pub(crate) fn generate_lib_loader_items(
         static VERSION: ::std::sync::atomic::AtomicUsize = ::std::sync::atomic::AtomicUsize::new(0);
         // for simple queries
         static WAS_UPDATED: ::std::sync::atomic::AtomicBool = ::std::sync::atomic::AtomicBool::new(false);
+        // error of the last update, cleared by the next successful one
+        static RELOAD_ERROR: ::std::sync::Mutex<Option<String>> = ::std::sync::Mutex::new(None);
 
         fn __lib_loader() -> ::std::sync::Arc<::std::sync::RwLock<#crate_name::LibReloader>> {
             LIB_LOADER_INIT.call_once(|| {
@@ -73,26 +75,35 @@ pub(crate) fn generate_lib_loader_items(
 
                             // get lock to lib_loader, make sure to not deadlock on it here
                             let mut first_lock_attempt = None;
-                            loop {
+                            let result = loop {
                                 if let Ok(mut lib_loader) = lib_loader_for_update.try_write() {
                                     if let Some(first_lock_attempt) = first_lock_attempt {
                                         let duration: ::std::time::Duration = first_lock_attempt - ::std::time::Instant::now();
                                         #crate_name::LibReloader::log_info(&format!("...got write lock after {}ms!", duration.as_millis()));
                                     }
-                                    let _ = !lib_loader.update().expect("hot lib update()");
-                                    break;
+                                    break lib_loader.update();
                                 }
                                 if first_lock_attempt.is_none() {
                                     first_lock_attempt = Some(::std::time::Instant::now());
                                     #crate_name::LibReloader::log_info("trying to get a write lock...");
                                 }
                                 ::std::thread::sleep(::std::time::Duration::from_millis(1));
+                            };
+
+                            // a failed update keeps the version, the next change retries
+                            match result {
+                                Ok(_) => {
+                                    *RELOAD_ERROR.lock().expect("lock reload error") = None;
+                                    VERSION.fetch_add(1, ::std::sync::atomic::Ordering::Release);
+                                    WAS_UPDATED.store(true, ::std::sync::atomic::Ordering::Release);
+                                }
+                                Err(err) => {
+                                    #crate_name::LibReloader::log_error(&format!("hot lib update() failed: {}", err));
+                                    *RELOAD_ERROR.lock().expect("lock reload error") = Some(err.to_string());
+                                }
                             }
 
-                            VERSION.fetch_add(1, ::std::sync::atomic::Ordering::Release);
-                            WAS_UPDATED.store(true, ::std::sync::atomic::Ordering::Release);
-
-                            // inform subscribers about lib reloaded
+                            // inform subscribers about lib reloaded, also after a failed update
                             __lib_notifier()
                                 .read()
                                 .expect("read lock notifier")
@@ -245,3 +256,20 @@ pub(crate) fn gen_lib_was_updated_function(f_decl: ForeignItemFn, span: Span) ->
         },
     })
 }
+
+pub(crate) fn gen_lib_reload_error_function(f_decl: ForeignItemFn, span: Span) -> Result<ItemFn> {
+    let ForeignItemFn {
+        sig, vis, attrs, ..
+    } = f_decl;
+
+    Ok(ItemFn {
+        attrs,
+        vis,
+        sig,
+        block: syn::parse_quote_spanned! {span=>
+            {
+                RELOAD_ERROR.lock().expect("lock reload error").clone()
+            }
+        },
+    })
+}
@@ -11,7 +11,9 @@ use super::code_gen::{
 };
 use super::HotModuleAttribute;
 use crate::hot_lib_reloader::util::read_functions_from_file;
-use crate::hot_module::code_gen::{gen_lib_version_function, gen_lib_was_updated_function};
+use crate::hot_module::code_gen::{
+    gen_lib_reload_error_function, gen_lib_version_function, gen_lib_was_updated_function,
+};
 
 pub(crate) struct HotModule {
     pub(crate) vis: Visibility,
@@ -159,6 +161,26 @@ impl syn::parse::Parse for HotModule {
                     items.push(Item::Fn(f));
                 }
 
+                // parses and code gens
+                // #[lib_reload_error]
+                // pub fn reload_error() -> Option<String> {}
+                syn::Item::Fn(func)
+                    if func
+                        .attrs
+                        .iter()
+                        .any(|attr| attr.path().is_ident("lib_reload_error")) =>
+                {
+                    let span = func.span();
+                    let f = ForeignItemFn {
+                        attrs: Vec::new(),
+                        vis: func.vis,
+                        sig: func.sig,
+                        semi_token: token::Semi::default(),
+                    };
+                    let f = gen_lib_reload_error_function(f, span)?;
+                    items.push(Item::Fn(f));
+                }
+
                 // parses and code gens
                 // #[hot_function]
                 // fn do_stuff(arg: &str) -> u32 {}
@@ -7,7 +7,7 @@ mod loader;
 mod utils;
 
 // 导出主要的宏
-use action::define_action_impl;
+use action::{define_action_impl, define_action_test_impl, define_migrate_impl};
 
 use crate::{
     action::{define_options_proc, result_branch_impl, to_value_derive_impl},
@@ -30,6 +30,35 @@ pub fn action(attr: TokenStream, input: TokenStream) -> TokenStream {
     define_action_impl(attr, input)
 }
 
+/// 声明 action 参数从旧版本到下一个版本的迁移，`#[action(version = N)]` 的每个旧版本都需要一个迁移函数
+/// ```ignore
+/// #[migrate(fetch_action, from = 1)]
+/// fn fetch_action_v1(mut args: Value) -> Value {
+///     args["timeout"] = args["timeout_ms"].take();
+///     args
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn migrate(attr: TokenStream, input: TokenStream) -> TokenStream {
+    define_migrate_impl(attr, input)
+}
+
+/// 为 action 生成单元测试，经过与服务相同的运行流程，需要依赖 `aster_test`
+/// ```ignore
+/// #[action_test(merge_json_action)]
+/// fn merges_objects(test: ActionTest) -> aster_test::Result<()> {
+///     test.arg("base", r#"{ "a": 1 }"#)
+///         .arg("other", r#"{ "b": 2 }"#)
+///         .run()?
+///         .assert_data(json!({ "a": 1, "b": 2 }))?;
+///     Ok(())
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn action_test(attr: TokenStream, input: TokenStream) -> TokenStream {
+    define_action_test_impl(attr, input)
+}
+
 #[proc_macro_attribute]
 pub fn options(_attr: TokenStream, input: TokenStream) -> TokenStream {
     define_options_proc(input)
@@ -118,6 +147,15 @@ pub fn load_action(input: TokenStream) -> TokenStream {
     loader::load_action_impl(input)
 }
 
+/// 将动作包导出为可在运行时加载的插件，需要以 cdylib 构建并启用 `plugin` 特性
+/// ```ignore
+/// aster_macro::export_plugin!(transform, [query_action, async fetch_action]);
+/// ```
+#[proc_macro]
+pub fn export_plugin(input: TokenStream) -> TokenStream {
+    loader::export_plugin_impl(input)
+}
+
 /// This macro is the top-level interface for making a dynamic Rust library
 /// hot-reloadable. The attribute macro will insert code into the module it
 /// accompanies that will do several things:
@@ -173,6 +211,12 @@ pub fn load_action(input: TokenStream) -> TokenStream {
 ///   // with a `#[lib_change_subscription]` attribute.
 ///    #[lib_change_subscription]
 ///    pub fn subscribe() -> hot_lib_reloader::LibReloadObserver {}
+///
+///   // An update that fails to load the library keeps the previous version
+///   // number. The error of the last update can be queried with an empty
+///   // function with a `#[lib_reload_error]` attribute.
+///    #[lib_reload_error]
+///    pub fn reload_error() -> Option<String> {}
 /// }
 /// ```
 ///
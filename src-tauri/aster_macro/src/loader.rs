@@ -1,4 +1,6 @@
 mod r#impl;
+mod plugin;
 mod ty;
 
-pub use r#impl::load_action_impl;
\ No newline at end of file
+pub use plugin::export_plugin_impl;
+pub use r#impl::load_action_impl;
@@ -20,18 +20,44 @@ pub fn load_action_impl(input: TokenStream) -> TokenStream {
     let mut token_stream_list = vec![];
 
     let mut expand = quote! {
-        #[::aster_macro::hot_module(dylib = #group_lit)]
+        #[::aster_macro::hot_module(dylib = #group_lit, lib_dir = crate::reload::lib_dir(#group_lit))]
         mod #mod_name {
             hot_functions_from_file!(#file_name);
+
+            #[lib_change_subscription]
+            pub fn subscribe() -> ::aster_common::hot_lib_reloader::LibReloadObserver {}
+
+            #[lib_version]
+            pub fn version() -> usize {}
+
+            #[lib_reload_error]
+            pub fn reload_error() -> ::std::option::Option<::std::string::String> {}
+
+            // 由 common 导出，卸载前关闭动态库中复用的运行时
+            #[hot_function]
+            pub fn daisy_shutdown_runtimes() {}
+
+            // 由 common 导出，使动态库中的桌面请求回到宿主处理
+            #[hot_function]
+            pub fn daisy_set_desktop_handler(handler: ::common::desktop::DesktopHandler) {}
         }
+
+        // 服务在工作流运行之间重新加载动作包
+        ::inventory::submit!(crate::collector::ActionLibraryInfo {
+            group: #group_lit,
+            subscribe: #mod_name::subscribe,
+            version: #mod_name::version,
+            reload_error: #mod_name::reload_error,
+            shutdown: #mod_name::daisy_shutdown_runtimes,
+        });
     };
     // 检查函数是否为异步函数，如果是则包装为 block_on 调用
     for func in action.funcs.iter() {
-        // 生成 Action 结构体名称（UpperCamelCase）
-        let action_struct = &utils::to_upper_camel_case(&group_str).into_ident();
-
         let action_name = &func.name;
         let action_str = action_name.to_string();
+
+        // 生成 Action 结构体名称（UpperCamelCase），以函数命名使同一组内的多个 action 互不冲突
+        let action_struct = &utils::to_upper_camel_case(&action_str).into_ident();
         let action_lit = create_string_literal(&action_str);
 
         let func_call = quote! { #mod_name::#action_name(args) };
@@ -44,6 +70,14 @@ pub fn load_action_impl(input: TokenStream) -> TokenStream {
             func_call
         };
         let creator_name = quote::format_ident!("create_{}", action_name);
+        // 校验规则来自 #[action] 生成的元信息
+        let meta_ident = quote::format_ident!("{}_META", action_str.to_uppercase());
+        let version_ident = quote::format_ident!("{}_VERSION", action_str.to_uppercase());
+        let migrate_arms = func.migrations.iter().map(|migrate| {
+            let from = &migrate.from;
+            let func = &migrate.func;
+            quote! { #from => ::std::result::Result::Ok(::#group::#func(args)), }
+        });
 
         token_stream_list.push(quote! {
             // 生成 Action 结构体
@@ -56,8 +90,30 @@ pub fn load_action_impl(input: TokenStream) -> TokenStream {
                     self.new_action(#action_lit, name, args)
                 }
 
+                fn validate(&self, args: &::common::ty::Data) -> ::std::result::Result<(), ::common::action::error::ActionError> {
+                    static RULES: ::std::sync::LazyLock<::std::vec::Vec<::common::action::rule::ParamRule>> =
+                        ::std::sync::LazyLock::new(|| {
+                            let meta = ::serde_json::from_str(::#group::#meta_ident).unwrap_or_default();
+                            ::common::action::rule::param_rules(&meta)
+                        });
+                    ::common::action::rule::validate_args(&RULES, args)
+                }
+
+                fn version(&self) -> u32 {
+                    ::#group::#version_ident
+                }
+
+                fn migrate(&self, from: u32, args: ::serde_json::Value) -> ::std::result::Result<::serde_json::Value, ::common::action::error::ActionError> {
+                    match from {
+                        #(#migrate_arms)*
+                        _ => ::std::result::Result::Err(::common::action::error::ActionError::MigrateActionError(from, self.version())),
+                    }
+                }
+
                 fn run(&self, args: ::common::ty::Data) -> ::std::result::Result<::common::ty::CardResult, ::common::action::error::ActionError> {
                     let args: ::serde_json::Value = args.to_value();
+                    // 动态库可能刚刚重载，每次运行前重新传入
+                    #mod_name::daisy_set_desktop_handler(::common::desktop::host_handler);
                     // 调用原始函数（可能包装了 block_on）
                     let result = #maybe_block_on
                         .map_err(|e| ::common::action::error::ActionError::RunActionCardError(e.to_string()))?;
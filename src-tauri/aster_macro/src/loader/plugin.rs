@@ -0,0 +1,81 @@
+use aster_common::utils::create_string_literal;
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse_macro_input;
+
+use crate::loader::ty::LoadActionInput;
+
+pub fn export_plugin_impl(input: TokenStream) -> TokenStream {
+    let plugin = parse_macro_input!(input as LoadActionInput);
+    let plugin_lit = create_string_literal(&plugin.name.to_string());
+
+    let mut metas = vec![];
+    let mut arms = vec![];
+    for func in plugin.funcs.iter() {
+        let action_name = &func.name;
+        let action_lit = create_string_literal(&action_name.to_string());
+        let meta_ident = format_ident!("{}_META", action_name.to_string().to_uppercase());
+        let branches_ident = format_ident!("{}_BRANCHES", action_name.to_string().to_uppercase());
+
+        metas.push(quote! {
+            ::common::plugin::PluginActionMeta {
+                action_type: #action_lit.to_string(),
+                meta: ::serde_json::from_str(#meta_ident).unwrap_or_default(),
+                branches: ::serde_json::from_str(#branches_ident).unwrap_or_default(),
+            }
+        });
+        let call = if func.is_async {
+            quote! { ::common::plugin::block_on(#action_name(args)) }
+        } else {
+            quote! { #action_name(args) }
+        };
+        arms.push(quote! { #action_lit => ::std::option::Option::Some(#call), });
+    }
+
+    // 仅在启用 plugin 特性构建 cdylib 时导出，避免静态链接多个动作包时符号冲突
+    quote! {
+        #[cfg(feature = "plugin")]
+        #[unsafe(no_mangle)]
+        pub extern "C" fn daisy_plugin_abi_version() -> u32 {
+            ::common::plugin::PLUGIN_ABI_VERSION
+        }
+
+        #[cfg(feature = "plugin")]
+        #[unsafe(no_mangle)]
+        pub extern "C" fn daisy_plugin_manifest() -> *mut ::std::ffi::c_char {
+            ::common::plugin::to_c_string(&::common::plugin::PluginManifest {
+                name: #plugin_lit.to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                abi_version: ::common::plugin::PLUGIN_ABI_VERSION,
+                actions: vec![#(#metas),*],
+            })
+        }
+
+        #[cfg(feature = "plugin")]
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn daisy_plugin_call(
+            action: *const ::std::ffi::c_char,
+            args: *const ::std::ffi::c_char,
+        ) -> *mut ::std::ffi::c_char {
+            unsafe {
+                ::common::plugin::handle_call(action, args, |action, args| match action {
+                    #(#arms)*
+                    _ => ::std::option::Option::None,
+                })
+            }
+        }
+
+        #[cfg(feature = "plugin")]
+        #[unsafe(no_mangle)]
+        pub extern "C" fn daisy_plugin_shutdown() {
+            ::common::plugin::daisy_shutdown_runtimes()
+        }
+
+        #[cfg(feature = "plugin")]
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn daisy_plugin_free(ptr: *mut ::std::ffi::c_char) {
+            unsafe { ::common::plugin::free_c_string(ptr) }
+        }
+    }
+    .into()
+}
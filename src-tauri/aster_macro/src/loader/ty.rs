@@ -1,33 +1,51 @@
 use syn::{
-    bracketed,
+    braced, bracketed,
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
-    token::Comma,
-    Ident, Result, Token,
+    token::{Brace, Comma},
+    Ident, LitInt, Result, Token,
 };
 
-/// 单个函数入口，可能带 `async`
+/// 迁移函数，`1 => fetch_action_v1` 表示从版本 1 迁移
+pub struct MigrateEntry {
+    pub from: LitInt,
+    pub func: Ident,
+}
+
+impl Parse for MigrateEntry {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let from = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let func = input.parse()?;
+        Ok(MigrateEntry { from, func })
+    }
+}
+
+/// 单个函数入口，可能带 `async`，以及 `{ 1 => fetch_action_v1 }` 形式的迁移函数
 pub struct FuncEntry {
     pub is_async: bool,
     pub name: Ident,
+    pub migrations: Vec<MigrateEntry>,
 }
 
 impl Parse for FuncEntry {
     fn parse(input: ParseStream) -> Result<Self> {
-        if input.peek(Token![async]) {
-            input.parse::<Token![async]>()?;
-            let name: Ident = input.parse()?;
-            Ok(FuncEntry {
-                is_async: true,
-                name,
-            })
-        } else {
-            let name: Ident = input.parse()?;
-            Ok(FuncEntry {
-                is_async: false,
-                name,
-            })
+        let is_async = input.parse::<Option<Token![async]>>()?.is_some();
+        let name: Ident = input.parse()?;
+        let mut migrations = vec![];
+        if input.peek(Brace) {
+            let content;
+            braced!(content in input);
+            migrations = content
+                .parse_terminated(MigrateEntry::parse, Comma)?
+                .into_iter()
+                .collect();
         }
+        Ok(FuncEntry {
+            is_async,
+            name,
+            migrations,
+        })
     }
 }
 
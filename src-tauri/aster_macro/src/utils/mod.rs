@@ -110,6 +110,7 @@ pub fn data_wrapper(ident: &Ident, ty: &str) -> Expr {
         "f16" | "f32" | "f64" | "f128" => parse_quote!(::common::ty::Data::Float(#ident.into())),
         "Code" | "Text" | "String" => parse_quote!(::common::ty::Data::String(#ident)),
         "bool" => parse_quote!(::common::ty::Data::Bool(#ident)),
+        "Data" => parse_quote!(#ident),
         "()" | "None" => parse_quote!(::common::ty::Data::Null(())),
         _ => parse_quote!(::common::ty::Data::Any(#ident.into())),
     }
@@ -0,0 +1,7 @@
+// 宏的诊断信息测试，修改错误信息后使用 TRYBUILD=overwrite 更新 .stderr
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/pass/*.rs");
+    t.compile_fail("tests/ui/fail/*.rs");
+}
@@ -0,0 +1,10 @@
+use aster_macro::result;
+
+#[result]
+enum Output {
+    #[right(zh_cn = "成功", en = "Success")]
+    #[bottom]
+    Success(plug!(String as String)),
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use aster_macro::migrate;
+
+#[migrate(echo_action, from = 0)]
+async fn echo_action_v0(args: serde_json::Value, times: u32) -> serde_json::Value {
+    args
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use aster_macro::action;
+
+#[action(zh_cn = "回显", en = "Echo")]
+pub fn echo_action(
+    #[name(zh_cn = "文本", en = "Text")]
+    text: String,
+) {
+    println!("{}", text);
+}
+
+fn main() {}
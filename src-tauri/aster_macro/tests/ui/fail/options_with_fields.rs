@@ -0,0 +1,9 @@
+use aster_macro::options;
+
+#[options]
+pub enum Mode {
+    Upper,
+    Custom(String),
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use aster_macro::action;
+
+#[action(zh_cn = "回显", en = "Echo")]
+#[entry(text, count)]
+pub fn echo_action(
+    #[name(zh_cn = "文本", en = "Text")]
+    text: String,
+) -> Output {
+    Output::Success(text)
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use aster_macro::action;
+
+#[action(zh_cm = "回显", en = "Echo")]
+pub fn echo_action(text: String) -> Output {
+    Output::Success(text)
+}
+
+fn main() {}
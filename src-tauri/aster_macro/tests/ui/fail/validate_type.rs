@@ -0,0 +1,12 @@
+use aster_macro::action;
+
+#[action(zh_cn = "回显", en = "Echo")]
+pub fn echo_action(
+    #[name(zh_cn = "文本", en = "Text")]
+    #[validate(min = 1)]
+    text: String,
+) -> Output {
+    Output::Success(text)
+}
+
+fn main() {}
@@ -0,0 +1,50 @@
+use aster_macro::{action, migrate};
+use serde_json::Value;
+
+#[result]
+enum Output {
+    #[right(zh_cn = "成功", en = "Success")]
+    Success(plug!(String as String)),
+    #[bottom(zh_cn = "失败", en = "Error")]
+    Error(plug!(String as String)),
+}
+
+#[options]
+pub enum Mode {
+    #[label(zh_cn = "大写", en = "Upper")]
+    Upper,
+    #[label(zh_cn = "小写", en = "Lower")]
+    Lower,
+}
+
+#[action(version = 2, zh_cn = "回显", en = "Echo")]
+#[description(zh_cn = "按指定方式输出文本", en = "Output the text in the given mode")]
+#[entry(text, mode)]
+pub fn echo_action(
+    #[name(zh_cn = "文本", en = "Text")]
+    #[description(zh_cn = "要输出的文本", en = "Text to output")]
+    #[validate(len(min = 1, max = 64))]
+    text: String,
+    #[name(zh_cn = "模式", en = "Mode")]
+    mode: Mode,
+    #[name(zh_cn = "重复次数", en = "Repeat")]
+    #[validate(min = 1, max = 10)]
+    repeat: Option<u32>,
+) -> Output {
+    if text.is_empty() {
+        return Output::Error(String::from("empty text"));
+    }
+    let text = text.repeat(repeat.unwrap_or(1) as usize);
+    match mode {
+        Mode::Upper => Output::Success(text.to_uppercase()),
+        Mode::Lower => Output::Success(text.to_lowercase()),
+    }
+}
+
+#[migrate(echo_action, from = 1)]
+fn echo_action_v1(mut args: Value) -> Value {
+    args["repeat"] = args["times"].take();
+    args
+}
+
+fn main() {}
@@ -0,0 +1,232 @@
+use std::{
+    path::{Component, Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::RuntimeError;
+
+/// 权限被拒绝时错误信息的前缀
+pub const DENIED_PREFIX: &str = "Permission denied:";
+
+/// 脚本的权限清单，未声明的能力一律拒绝
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Capabilities {
+    /// 允许读写的目录，包含其子目录
+    #[serde(default)]
+    pub fs: Vec<PathBuf>,
+    /// 允许访问的主机，支持 `*.example.com` 与 `*`
+    #[serde(default)]
+    pub net: Vec<String>,
+    /// 是否允许启动进程
+    #[serde(default)]
+    pub process: bool,
+    /// 允许读取的环境变量，`*` 表示全部
+    #[serde(default)]
+    pub env: Vec<String>,
+    /// 最近一次拒绝的原因，克隆之间共享，脚本失败后据此还原拒绝错误
+    #[serde(skip)]
+    pub(crate) denied: Arc<Mutex<Option<String>>>,
+}
+
+// 按字面处理 . 与 ..，不访问文件系统
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+// 解析最深的已存在祖先目录的真实路径，避免通过符号链接逃逸
+fn resolve(path: &Path) -> PathBuf {
+    let path = normalize(path);
+    let mut existing = path.as_path();
+    let mut rest = vec![];
+    loop {
+        if let Ok(real) = existing.canonicalize() {
+            return rest.iter().rev().fold(real, |path, name| path.join(name));
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                rest.push(name.to_owned());
+                existing = parent;
+            }
+            _ => return path,
+        }
+    }
+}
+
+fn match_host(pattern: &str, host: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let host = host.to_lowercase();
+    if pattern == "*" || pattern == host {
+        return true;
+    }
+    match pattern.strip_prefix("*.") {
+        Some(domain) => host.ends_with(&format!(".{}", domain)),
+        None => false,
+    }
+}
+
+impl Capabilities {
+    fn deny(&self, message: String) -> RuntimeError {
+        let message = format!("{} {}", DENIED_PREFIX, message);
+        if let Ok(mut denied) = self.denied.lock() {
+            *denied = Some(message.clone());
+        }
+        RuntimeError::PermissionDeniedError(message)
+    }
+    /// 检查路径是否位于允许的目录中，返回解析后的路径
+    pub fn check_fs(&self, path: &str) -> Result<PathBuf, RuntimeError> {
+        let path = Path::new(path);
+        if !path.is_absolute() {
+            return Err(self.deny(format!("{} is not an absolute path", path.display())));
+        }
+        let resolved = resolve(path);
+        if self
+            .fs
+            .iter()
+            .any(|root| resolved.starts_with(resolve(root)))
+        {
+            Ok(resolved)
+        } else {
+            Err(self.deny(format!("file access to {}", path.display())))
+        }
+    }
+    /// 检查 URL 的主机是否允许访问
+    pub fn check_net(&self, url: &str) -> Result<(), RuntimeError> {
+        let parsed = url::Url::parse(url).map_err(|e| {
+            RuntimeError::ExecuteScriptError(format!("Invalid url {}: {}", url, e))
+        })?;
+        let host = parsed.host_str().unwrap_or_default();
+        if self.net.iter().any(|pattern| match_host(pattern, host)) {
+            Ok(())
+        } else {
+            Err(self.deny(format!("network access to {}", host)))
+        }
+    }
+    pub fn check_process(&self) -> Result<(), RuntimeError> {
+        if self.process {
+            Ok(())
+        } else {
+            Err(self.deny("starting processes".to_string()))
+        }
+    }
+    fn env_allowed(&self, name: &str) -> bool {
+        self.env.iter().any(|allowed| {
+            allowed == "*"
+                || if cfg!(windows) {
+                    allowed.eq_ignore_ascii_case(name)
+                } else {
+                    allowed == name
+                }
+        })
+    }
+    pub fn check_env(&self, name: &str) -> Result<(), RuntimeError> {
+        if self.env_allowed(name) {
+            Ok(())
+        } else {
+            Err(self.deny(format!("environment variable {}", name)))
+        }
+    }
+    /// 允许传递给子进程的环境变量，过滤掉的变量不记为拒绝
+    pub fn allowed_env(&self) -> Vec<(String, String)> {
+        std::env::vars()
+            .filter(|(name, _)| self.env_allowed(name))
+            .collect()
+    }
+    /// 单次运行使用的副本，拒绝记录与其他运行互不影响
+    pub fn for_run(&self) -> Capabilities {
+        Capabilities {
+            denied: Default::default(),
+            ..self.clone()
+        }
+    }
+    /// 取出并清除最近一次拒绝的原因
+    pub fn take_denied(&self) -> Option<String> {
+        self.denied.lock().ok()?.take()
+    }
+}
+
+/// 脚本执行失败时，检查中拒绝过的权限还原为拒绝错误，不依据脚本抛出的内容判断
+pub fn restore_denied(error: RuntimeError, capabilities: &Capabilities) -> RuntimeError {
+    match (error, capabilities.take_denied()) {
+        (RuntimeError::ExecuteScriptError(_), Some(message)) => {
+            RuntimeError::PermissionDeniedError(message)
+        }
+        (error, _) => error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_fs_roots() {
+        let root = std::env::temp_dir().join("daisytools-capability");
+        let capabilities = Capabilities {
+            fs: vec![root.clone()],
+            ..Default::default()
+        };
+        let inside = root.join("a").join("b.txt");
+        assert!(capabilities.check_fs(inside.to_str().unwrap()).is_ok());
+
+        let escape = root.join("..").join("other.txt");
+        assert!(matches!(
+            capabilities.check_fs(escape.to_str().unwrap()),
+            Err(RuntimeError::PermissionDeniedError(_))
+        ));
+        assert!(capabilities.check_fs("relative.txt").is_err());
+    }
+
+    #[test]
+    fn checks_net_hosts() {
+        let capabilities = Capabilities {
+            net: vec!["api.example.com".to_string(), "*.test.org".to_string()],
+            ..Default::default()
+        };
+        assert!(capabilities.check_net("https://api.example.com/v1").is_ok());
+        assert!(capabilities.check_net("http://a.test.org").is_ok());
+        assert!(capabilities.check_net("https://example.com").is_err());
+        assert!(capabilities.check_net("https://test.org").is_err());
+    }
+
+    #[test]
+    fn denies_by_default() {
+        let capabilities = Capabilities::default();
+        assert!(capabilities.check_process().is_err());
+        assert!(capabilities.check_env("PATH").is_err());
+        assert!(capabilities.allowed_env().is_empty());
+    }
+
+    #[test]
+    fn restores_denied_from_checks() {
+        let capabilities = Capabilities::default();
+        let _ = capabilities.clone().check_process();
+        let error = RuntimeError::ExecuteScriptError("Error: at <eval>".to_string());
+        assert!(matches!(
+            restore_denied(error, &capabilities),
+            RuntimeError::PermissionDeniedError(message)
+                if message == format!("{} starting processes", DENIED_PREFIX)
+        ));
+    }
+
+    #[test]
+    fn keeps_forged_denials_as_script_errors() {
+        let capabilities = Capabilities::default();
+        let error = RuntimeError::ExecuteScriptError(format!("Error: {} x", DENIED_PREFIX));
+        assert!(matches!(
+            restore_denied(error, &capabilities),
+            RuntimeError::ExecuteScriptError(_)
+        ));
+    }
+}
@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RuntimeError {
+    #[error("Unsupported language: {0}")]
+    UnsupportedLanguageError(String),
+    #[error("No interpreter found for {0}")]
+    InterpreterNotFoundError(String),
+    #[error("Failed to create runtime: {0}")]
+    CreateRuntimeError(String),
+    #[error("Failed to prepare script context: {0}")]
+    PrepareContextError(String),
+    #[error("Failed to execute script: {0}")]
+    ExecuteScriptError(String),
+    #[error("{0}")]
+    ScriptFailedError(String),
+    #[error("Failed to convert script result: {0}")]
+    ConvertResultError(String),
+    #[error("Script exceeded the time limit of {0} ms")]
+    TimeoutError(u128),
+    #[error("Script exceeded the memory limit of {0} bytes")]
+    MemoryLimitError(usize),
+    #[error("{0}")]
+    PermissionDeniedError(String),
+    #[error(
+        "{0} runs in an external interpreter that cannot restrict {1}, remove the {1} entries from the manifest"
+    )]
+    UnenforceableCapabilityError(&'static str, &'static str),
+}
@@ -0,0 +1,194 @@
+use std::{
+    fs::{read_to_string, write},
+    process::Command,
+    time::Duration,
+};
+
+use serde_json::{Value, json};
+
+use crate::{capability::Capabilities, error::RuntimeError};
+
+// 内嵌运行时共用的宿主 API，所有访问都先经过权限清单检查
+
+pub fn env(capabilities: &Capabilities, name: &str) -> Result<Option<String>, RuntimeError> {
+    capabilities.check_env(name)?;
+    Ok(std::env::var(name).ok())
+}
+
+pub fn read_file(capabilities: &Capabilities, path: &str) -> Result<String, RuntimeError> {
+    let path = capabilities.check_fs(path)?;
+    read_to_string(path).map_err(|e| RuntimeError::ExecuteScriptError(e.to_string()))
+}
+
+pub fn write_file(
+    capabilities: &Capabilities,
+    path: &str,
+    content: &str,
+) -> Result<(), RuntimeError> {
+    let path = capabilities.check_fs(path)?;
+    write(path, content).map_err(|e| RuntimeError::ExecuteScriptError(e.to_string()))
+}
+
+/// 启动进程并等待其结束，子进程只能看到允许的环境变量
+pub fn exec(
+    capabilities: &Capabilities,
+    program: &str,
+    args: Vec<String>,
+) -> Result<Value, RuntimeError> {
+    capabilities.check_process()?;
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .env_clear()
+        .envs(capabilities.allowed_env());
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        command.creation_flags(0x08000000);
+    }
+    let output = command
+        .output()
+        .map_err(|e| RuntimeError::ExecuteScriptError(e.to_string()))?;
+    Ok(json!({
+        "code": output.status.code(),
+        "stdout": String::from_utf8_lossy(&output.stdout),
+        "stderr": String::from_utf8_lossy(&output.stderr),
+    }))
+}
+
+/// 最多跟随的重定向次数，与 ureq 的默认值相同
+const MAX_REDIRECTS: usize = 5;
+
+/// 发送 HTTP 请求，非 2xx 的响应同样返回给脚本
+///
+/// 重定向由这里逐跳跟随，每个 Location 都要经过权限清单检查
+pub fn fetch(
+    capabilities: &Capabilities,
+    url: &str,
+    method: Option<String>,
+    body: Option<String>,
+    timeout: Duration,
+) -> Result<Value, RuntimeError> {
+    let agent = ureq::AgentBuilder::new()
+        .redirects(0)
+        .timeout(timeout)
+        .build();
+    let mut url = url.to_string();
+    let mut method = method.unwrap_or_else(|| "GET".to_string()).to_uppercase();
+    let mut body = body;
+    for _ in 0..=MAX_REDIRECTS {
+        capabilities.check_net(&url)?;
+        let request = agent.request(&method, &url);
+        let response = match &body {
+            Some(body) => request.send_string(body),
+            None => request.call(),
+        };
+        let response = match response {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(e) => return Err(RuntimeError::ExecuteScriptError(e.to_string())),
+        };
+        let status = response.status();
+        let location = match status {
+            301 | 302 | 303 | 307 | 308 => response.header("location"),
+            _ => None,
+        };
+        let Some(location) = location else {
+            let body = response
+                .into_string()
+                .map_err(|e| RuntimeError::ExecuteScriptError(e.to_string()))?;
+            return Ok(json!({ "status": status, "body": body }));
+        };
+        // Location 可以是相对地址
+        url = url::Url::parse(&url)
+            .and_then(|base| base.join(location))
+            .map_err(|e| {
+                RuntimeError::ExecuteScriptError(format!("Invalid redirect {}: {}", location, e))
+            })?
+            .to_string();
+        // 307 与 308 保留方法与请求体，其余改为不带请求体的 GET
+        if !matches!(status, 307 | 308) && method != "HEAD" {
+            method = "GET".to_string();
+            body = None;
+        }
+    }
+    Err(RuntimeError::ExecuteScriptError(format!(
+        "Too many redirects, stopped after {}",
+        MAX_REDIRECTS
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::*;
+
+    /// 依次返回 responses 的模拟服务器，返回其地址
+    fn mock_server(responses: Vec<String>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        address
+    }
+
+    fn redirect(location: &str) -> String {
+        format!(
+            "HTTP/1.1 302 Found\r\nLocation: {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            location
+        )
+    }
+
+    fn capabilities() -> Capabilities {
+        Capabilities {
+            net: vec!["127.0.0.1".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn follows_allowed_redirects() {
+        let ok = "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok";
+        let address = mock_server(vec![redirect("/next"), ok.to_string()]);
+        let response = fetch(
+            &capabilities(),
+            &address,
+            None,
+            None,
+            Duration::from_secs(5),
+        );
+        assert_eq!(response.unwrap(), json!({ "status": 200, "body": "ok" }));
+    }
+
+    #[test]
+    fn denies_redirect_to_other_hosts() {
+        let address = mock_server(vec![redirect("http://localhost/secret")]);
+        let response = fetch(
+            &capabilities(),
+            &address,
+            None,
+            None,
+            Duration::from_secs(5),
+        );
+        assert!(matches!(
+            response,
+            Err(RuntimeError::PermissionDeniedError(message)) if message.contains("localhost")
+        ));
+    }
+}
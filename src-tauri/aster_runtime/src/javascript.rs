@@ -0,0 +1,353 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use common::{
+    ty::{Context, Data},
+    utils::get_uid,
+};
+use rquickjs::{
+    CatchResultExt, CaughtError, Context as JsContext, Ctx, Exception, Function, Object,
+    Runtime as JsRuntime, Value,
+    convert::Coerced,
+    function::{Opt, Rest},
+};
+
+use crate::{
+    Limits, Runtime,
+    capability::{Capabilities, restore_denied},
+    context_to_value,
+    error::RuntimeError,
+    host,
+};
+
+/// 基于 QuickJS 的内嵌 JavaScript 运行时，在服务进程内直接执行
+pub struct JavaScriptRuntime;
+
+// 脚本作为函数体执行，通过 return 返回卡片的输出
+fn wrap(code: &str) -> String {
+    format!("(function () {{\n{}\n}})()", code)
+}
+
+// 宿主 API 的错误以异常的形式抛给脚本
+fn throw(ctx: &Ctx, error: RuntimeError) -> rquickjs::Error {
+    Exception::throw_message(ctx, &error.to_string())
+}
+
+// 内存耗尽时 QuickJS 可能连错误对象都无法分配，只能抛出 null
+fn script_error(error: CaughtError, limits: &Limits) -> RuntimeError {
+    match error {
+        CaughtError::Value(value) if value.is_null() => {
+            RuntimeError::MemoryLimitError(limits.memory)
+        }
+        e => RuntimeError::ExecuteScriptError(e.to_string()),
+    }
+}
+
+fn to_js<'js>(ctx: &Ctx<'js>, value: serde_json::Value) -> rquickjs::Result<Value<'js>> {
+    ctx.json_parse(value.to_string())
+}
+
+fn join_args(args: Rest<Coerced<String>>) -> String {
+    args.0
+        .into_iter()
+        .map(|arg| arg.0)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 注入宿主 API：console 输出到日志，daisy 提供受权限清单约束的工具函数
+fn install_host_api<'js>(
+    ctx: &Ctx<'js>,
+    capabilities: &Arc<Capabilities>,
+    timeout: Duration,
+) -> rquickjs::Result<()> {
+    let console = Object::new(ctx.clone())?;
+    console.set(
+        "log",
+        Function::new(ctx.clone(), |args: Rest<Coerced<String>>| {
+            log::info!(target: "script", "{}", join_args(args))
+        })?,
+    )?;
+    console.set(
+        "debug",
+        Function::new(ctx.clone(), |args: Rest<Coerced<String>>| {
+            log::debug!(target: "script", "{}", join_args(args))
+        })?,
+    )?;
+    console.set(
+        "warn",
+        Function::new(ctx.clone(), |args: Rest<Coerced<String>>| {
+            log::warn!(target: "script", "{}", join_args(args))
+        })?,
+    )?;
+    console.set(
+        "error",
+        Function::new(ctx.clone(), |args: Rest<Coerced<String>>| {
+            log::error!(target: "script", "{}", join_args(args))
+        })?,
+    )?;
+    ctx.globals().set("console", console)?;
+
+    let daisy = Object::new(ctx.clone())?;
+    let caps = capabilities.clone();
+    daisy.set(
+        "env",
+        Function::new(ctx.clone(), move |ctx: Ctx<'js>, name: String| {
+            host::env(&caps, &name).map_err(|e| throw(&ctx, e))
+        })?,
+    )?;
+    let caps = capabilities.clone();
+    daisy.set(
+        "readFile",
+        Function::new(ctx.clone(), move |ctx: Ctx<'js>, path: String| {
+            host::read_file(&caps, &path).map_err(|e| throw(&ctx, e))
+        })?,
+    )?;
+    let caps = capabilities.clone();
+    daisy.set(
+        "writeFile",
+        Function::new(
+            ctx.clone(),
+            move |ctx: Ctx<'js>, path: String, content: Coerced<String>| {
+                host::write_file(&caps, &path, &content.0).map_err(|e| throw(&ctx, e))
+            },
+        )?,
+    )?;
+    let caps = capabilities.clone();
+    daisy.set(
+        "exec",
+        Function::new(
+            ctx.clone(),
+            move |ctx: Ctx<'js>, program: String, args: Opt<Vec<String>>| {
+                let output = host::exec(&caps, &program, args.0.unwrap_or_default())
+                    .map_err(|e| throw(&ctx, e))?;
+                to_js(&ctx, output)
+            },
+        )?,
+    )?;
+    let caps = capabilities.clone();
+    daisy.set(
+        "fetch",
+        Function::new(
+            ctx.clone(),
+            move |ctx: Ctx<'js>, url: String, options: Opt<Object<'js>>| {
+                let (method, body) = match options.0 {
+                    Some(options) => (options.get("method")?, options.get("body")?),
+                    None => (None, None),
+                };
+                let response = host::fetch(&caps, &url, method, body, timeout)
+                    .map_err(|e| throw(&ctx, e))?;
+                to_js(&ctx, response)
+            },
+        )?,
+    )?;
+    daisy.set(
+        "now",
+        Function::new(ctx.clone(), || {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_millis() as f64)
+                .unwrap_or_default()
+        })?,
+    )?;
+    daisy.set("uid", Function::new(ctx.clone(), get_uid)?)?;
+    ctx.globals().set("daisy", daisy)?;
+    Ok(())
+}
+
+impl Runtime for JavaScriptRuntime {
+    fn language(&self) -> &'static str {
+        "JavaScript"
+    }
+    fn execute(
+        &self,
+        code: &str,
+        context: &Context,
+        limits: &Limits,
+        capabilities: &Capabilities,
+    ) -> Result<Data, RuntimeError> {
+        let runtime =
+            JsRuntime::new().map_err(|e| RuntimeError::CreateRuntimeError(e.to_string()))?;
+        runtime.set_memory_limit(limits.memory);
+        runtime.set_max_stack_size(limits.stack);
+
+        // 超时后由解释器在下一个检查点中断脚本
+        let timed_out = Arc::new(AtomicBool::new(false));
+        let deadline = Instant::now() + limits.timeout;
+        let flag = timed_out.clone();
+        runtime.set_interrupt_handler(Some(Box::new(move || {
+            let expired = Instant::now() >= deadline;
+            if expired {
+                flag.store(true, Ordering::Relaxed);
+            }
+            expired
+        })));
+
+        let js_context = JsContext::full(&runtime)
+            .map_err(|e| RuntimeError::CreateRuntimeError(e.to_string()))?;
+        let context = serde_json::to_string(&context_to_value(context))
+            .map_err(|e| RuntimeError::PrepareContextError(e.to_string()))?;
+        let capabilities = Arc::new(capabilities.for_run());
+
+        let result = js_context.with(|ctx| -> Result<Option<String>, RuntimeError> {
+            let context = ctx
+                .json_parse(context)
+                .map_err(|e| RuntimeError::PrepareContextError(e.to_string()))?;
+            ctx.globals()
+                .set("context", context)
+                .map_err(|e| RuntimeError::PrepareContextError(e.to_string()))?;
+            install_host_api(&ctx, &capabilities, limits.timeout)
+                .map_err(|e| RuntimeError::PrepareContextError(e.to_string()))?;
+
+            let value: Value = ctx
+                .eval(wrap(code))
+                .catch(&ctx)
+                .map_err(|e| script_error(e, limits))?;
+            // 允许脚本返回 Promise，在此驱动任务队列直到完成
+            let value = if let Some(promise) = value.as_promise() {
+                promise
+                    .finish::<Value>()
+                    .catch(&ctx)
+                    .map_err(|e| script_error(e, limits))?
+            } else {
+                value
+            };
+
+            let json = ctx
+                .json_stringify(value)
+                .map_err(|e| RuntimeError::ConvertResultError(e.to_string()))?;
+            json.map(|json| json.to_string())
+                .transpose()
+                .map_err(|e| RuntimeError::ConvertResultError(e.to_string()))
+        });
+
+        let result = result.map_err(|e| match e {
+            _ if timed_out.load(Ordering::Relaxed) => {
+                RuntimeError::TimeoutError(limits.timeout.as_millis())
+            }
+            e => restore_denied(e, &capabilities),
+        })?;
+
+        // undefined 与函数等无法序列化的值视为没有输出
+        let Some(json) = result else {
+            return Ok(Data::Null);
+        };
+        let value = serde_json::from_str(&json)
+            .map_err(|e| RuntimeError::ConvertResultError(e.to_string()))?;
+        Ok(Data::from_value(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn run(code: &str, limits: Limits) -> Result<Data, RuntimeError> {
+        let context = Context::from([("upstream".to_string(), Data::Int(41))]);
+        JavaScriptRuntime.execute(code, &context, &limits, &Capabilities::default())
+    }
+
+    #[test]
+    fn returns_data_from_context() {
+        let result = run("return context.upstream + 1;", Limits::default()).unwrap();
+        assert!(matches!(result, Data::Int(42)));
+
+        let result = run("return { ok: true };", Limits::default()).unwrap();
+        assert!(matches!(result, Data::Json(map) if map["ok"] == true));
+
+        let result = run("const a = 1;", Limits::default()).unwrap();
+        assert!(matches!(result, Data::Null));
+    }
+
+    #[test]
+    fn resolves_promise() {
+        let result = run("return Promise.resolve('done');", Limits::default()).unwrap();
+        assert!(matches!(result, Data::String(value) if value == "done"));
+    }
+
+    #[test]
+    fn reports_thrown_error() {
+        let result = run("throw new Error('boom');", Limits::default());
+        assert!(matches!(
+            result,
+            Err(RuntimeError::ExecuteScriptError(message)) if message.contains("boom")
+        ));
+    }
+
+    #[test]
+    fn denies_undeclared_capabilities() {
+        let result = run("return daisy.env('PATH');", Limits::default());
+        assert!(matches!(result, Err(RuntimeError::PermissionDeniedError(_))));
+
+        let result = run("return daisy.exec('echo', ['hi']);", Limits::default());
+        assert!(matches!(result, Err(RuntimeError::PermissionDeniedError(_))));
+
+        // 脚本可以捕获拒绝并自行处理
+        let result = run(
+            "try { daisy.readFile('/etc/hosts'); } catch (e) { return 'caught'; }",
+            Limits::default(),
+        )
+        .unwrap();
+        assert!(matches!(result, Data::String(value) if value == "caught"));
+
+        // 脚本自己抛出的同名错误不算拒绝
+        let result = run(
+            "throw new Error('Permission denied: starting processes');",
+            Limits::default(),
+        );
+        assert!(matches!(result, Err(RuntimeError::ExecuteScriptError(_))));
+    }
+
+    #[test]
+    fn allows_declared_capabilities() {
+        let capabilities = Capabilities {
+            env: vec!["PATH".to_string()],
+            ..Default::default()
+        };
+        let result = JavaScriptRuntime
+            .execute(
+                "return daisy.env('PATH');",
+                &Context::new(),
+                &Limits::default(),
+                &capabilities,
+            )
+            .unwrap();
+        assert!(matches!(result, Data::String(_)));
+    }
+
+    #[test]
+    fn interrupts_on_timeout() {
+        let limits = Limits {
+            timeout: Duration::from_millis(100),
+            ..Default::default()
+        };
+        let result = run("while (true) {}", limits);
+        assert!(matches!(result, Err(RuntimeError::TimeoutError(100))));
+    }
+
+    #[test]
+    fn stops_on_memory_limit() {
+        let limits = Limits {
+            memory: 8 * 1024 * 1024,
+            ..Default::default()
+        };
+        let result = run(
+            "const list = []; while (true) { list.push(new Array(1024).fill('x')); }",
+            limits,
+        );
+        assert!(matches!(result, Err(RuntimeError::MemoryLimitError(_))));
+    }
+
+    #[test]
+    fn keeps_thrown_messages_as_script_errors() {
+        let result = run("throw new Error('out of memory');", Limits::default());
+        assert!(matches!(result, Err(RuntimeError::ExecuteScriptError(_))));
+    }
+}
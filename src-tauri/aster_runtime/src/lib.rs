@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use capability::Capabilities;
+use common::ty::{Context, Data};
+use error::RuntimeError;
+
+pub mod capability;
+pub mod error;
+pub mod host;
+pub mod javascript;
+pub mod lua;
+pub mod process;
+pub mod python;
+pub mod shell;
+
+/// 脚本执行时的资源限制
+#[derive(Debug, Clone)]
+pub struct Limits {
+    /// 堆内存上限，单位为字节
+    pub memory: usize,
+    /// 调用栈上限，单位为字节
+    pub stack: usize,
+    /// 执行时间上限
+    pub timeout: Duration,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            memory: 64 * 1024 * 1024,
+            stack: 1024 * 1024,
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// 脚本运行时
+///
+/// 上游卡片的输出以全局变量 `context` 的形式提供给脚本，
+/// 脚本的返回值会转换为 Data 作为卡片的输出，
+/// 文件、网络、进程与环境变量的访问受权限清单约束
+pub trait Runtime {
+    fn language(&self) -> &'static str;
+    /// 运行时所依赖的解释器是否存在，内嵌运行时总是可用
+    fn is_available(&self) -> bool {
+        true
+    }
+    fn execute(
+        &self,
+        code: &str,
+        context: &Context,
+        limits: &Limits,
+        capabilities: &Capabilities,
+    ) -> Result<Data, RuntimeError>;
+}
+
+/// 支持的全部语言
+pub const LANGUAGES: [&str; 5] = ["JavaScript", "Lua", "Python", "PowerShell", "Bash"];
+
+/// 根据语言名称获取运行时
+pub fn get_runtime(language: &str) -> Result<Box<dyn Runtime>, RuntimeError> {
+    match language {
+        "JavaScript" => Ok(Box::new(javascript::JavaScriptRuntime)),
+        "Lua" => Ok(Box::new(lua::LuaRuntime)),
+        "Python" => Ok(Box::new(process::ProcessRuntime(&python::PYTHON))),
+        "PowerShell" => Ok(Box::new(process::ProcessRuntime(&shell::POWERSHELL))),
+        "Bash" => Ok(Box::new(process::ProcessRuntime(&shell::BASH))),
+        _ => Err(RuntimeError::UnsupportedLanguageError(language.to_string())),
+    }
+}
+
+/// 当前主机上可用的语言，外部解释器不存在的语言不会出现
+pub fn available_languages() -> Vec<&'static str> {
+    LANGUAGES
+        .into_iter()
+        .filter(|language| get_runtime(language).is_ok_and(|runtime| runtime.is_available()))
+        .collect()
+}
+
+/// 将上游输出转换为 JSON，供运行时注入脚本
+pub fn context_to_value(context: &Context) -> serde_json::Value {
+    serde_json::Value::Object(
+        context
+            .iter()
+            .map(|(id, data)| (id.clone(), data.to_value()))
+            .collect(),
+    )
+}
@@ -0,0 +1,292 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use common::{
+    ty::{Context, Data},
+    utils::get_uid,
+};
+use mlua::{
+    HookTriggers, Lua, LuaOptions, StdLib, Table, Value as LuaValue, Variadic, VmState,
+};
+use serde_json::{Map, Value, json};
+
+use crate::{
+    Limits, Runtime,
+    capability::{Capabilities, restore_denied},
+    context_to_value,
+    error::RuntimeError,
+    host,
+};
+
+/// 基于 mlua 的内嵌 Lua 运行时
+pub struct LuaRuntime;
+
+fn to_lua(lua: &Lua, value: &Value) -> mlua::Result<LuaValue> {
+    Ok(match value {
+        Value::Null => LuaValue::Nil,
+        Value::Bool(value) => LuaValue::Boolean(*value),
+        Value::Number(value) => match value.as_i64() {
+            Some(value) => LuaValue::Integer(value),
+            None => LuaValue::Number(value.as_f64().unwrap_or_default()),
+        },
+        Value::String(value) => LuaValue::String(lua.create_string(value)?),
+        Value::Array(list) => {
+            let table = lua.create_table()?;
+            for (idx, value) in list.iter().enumerate() {
+                table.raw_set(idx + 1, to_lua(lua, value)?)?;
+            }
+            LuaValue::Table(table)
+        }
+        Value::Object(map) => {
+            let table = lua.create_table()?;
+            for (key, value) in map {
+                table.raw_set(key.as_str(), to_lua(lua, value)?)?;
+            }
+            LuaValue::Table(table)
+        }
+    })
+}
+
+fn from_lua(value: LuaValue) -> mlua::Result<Value> {
+    Ok(match value {
+        LuaValue::Boolean(value) => Value::Bool(value),
+        LuaValue::Integer(value) => json!(value),
+        LuaValue::Number(value) => json!(value),
+        LuaValue::String(value) => Value::String(value.to_string_lossy().to_string()),
+        LuaValue::Table(table) => {
+            // 键为连续整数的表视为数组
+            let len = table.raw_len();
+            let count = table.clone().pairs::<LuaValue, LuaValue>().count();
+            if len > 0 && len == count {
+                let list = table
+                    .clone()
+                    .sequence_values::<LuaValue>()
+                    .map(|value| value.and_then(from_lua))
+                    .collect::<mlua::Result<Vec<_>>>()?;
+                Value::Array(list)
+            } else {
+                let mut map = Map::new();
+                for pair in table.clone().pairs::<LuaValue, LuaValue>() {
+                    let (key, value) = pair?;
+                    let key = match key {
+                        LuaValue::String(key) => key.to_string_lossy().to_string(),
+                        LuaValue::Integer(key) => key.to_string(),
+                        _ => continue,
+                    };
+                    map.insert(key, from_lua(value)?);
+                }
+                Value::Object(map)
+            }
+        }
+        // nil 与函数等无法转换的值视为没有输出
+        _ => Value::Null,
+    })
+}
+
+fn join_args(args: Variadic<LuaValue>) -> String {
+    args.iter()
+        .map(|arg| arg.to_string().unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// 宿主 API 的错误以 Lua 错误的形式抛给脚本
+fn throw(error: RuntimeError) -> mlua::Error {
+    mlua::Error::runtime(error.to_string())
+}
+
+/// 注入宿主 API：print 输出到日志，daisy 提供受权限清单约束的工具函数
+fn install_host_api(
+    lua: &Lua,
+    capabilities: &Arc<Capabilities>,
+    timeout: Duration,
+) -> mlua::Result<()> {
+    let globals = lua.globals();
+    // 基础库中可直接读取文件的函数一并移除
+    globals.set("dofile", LuaValue::Nil)?;
+    globals.set("loadfile", LuaValue::Nil)?;
+    globals.set(
+        "print",
+        lua.create_function(|_, args: Variadic<LuaValue>| {
+            log::info!(target: "script", "{}", join_args(args));
+            Ok(())
+        })?,
+    )?;
+
+    let daisy = lua.create_table()?;
+    let caps = capabilities.clone();
+    daisy.set(
+        "env",
+        lua.create_function(move |_, name: String| host::env(&caps, &name).map_err(throw))?,
+    )?;
+    let caps = capabilities.clone();
+    daisy.set(
+        "read_file",
+        lua.create_function(move |_, path: String| {
+            host::read_file(&caps, &path).map_err(throw)
+        })?,
+    )?;
+    let caps = capabilities.clone();
+    daisy.set(
+        "write_file",
+        lua.create_function(move |_, (path, content): (String, String)| {
+            host::write_file(&caps, &path, &content).map_err(throw)
+        })?,
+    )?;
+    let caps = capabilities.clone();
+    daisy.set(
+        "exec",
+        lua.create_function(move |lua, (program, args): (String, Option<Vec<String>>)| {
+            let output =
+                host::exec(&caps, &program, args.unwrap_or_default()).map_err(throw)?;
+            to_lua(lua, &output)
+        })?,
+    )?;
+    let caps = capabilities.clone();
+    daisy.set(
+        "fetch",
+        lua.create_function(move |lua, (url, options): (String, Option<Table>)| {
+            let (method, body) = match options {
+                Some(options) => (options.get("method")?, options.get("body")?),
+                None => (None, None),
+            };
+            let response = host::fetch(&caps, &url, method, body, timeout).map_err(throw)?;
+            to_lua(lua, &response)
+        })?,
+    )?;
+    daisy.set(
+        "now",
+        lua.create_function(|_, ()| {
+            Ok(SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_millis() as i64)
+                .unwrap_or_default())
+        })?,
+    )?;
+    daisy.set("uid", lua.create_function(|_, ()| Ok(get_uid()))?)?;
+    globals.set("daisy", daisy)?;
+    Ok(())
+}
+
+impl Runtime for LuaRuntime {
+    fn language(&self) -> &'static str {
+        "Lua"
+    }
+    fn execute(
+        &self,
+        code: &str,
+        context: &Context,
+        limits: &Limits,
+        capabilities: &Capabilities,
+    ) -> Result<Data, RuntimeError> {
+        // 不加载 io、os、package 等可直接访问宿主的标准库
+        let lua = Lua::new_with(
+            StdLib::TABLE | StdLib::STRING | StdLib::MATH | StdLib::UTF8 | StdLib::COROUTINE,
+            LuaOptions::default(),
+        )
+        .map_err(|e| RuntimeError::CreateRuntimeError(e.to_string()))?;
+        lua.set_memory_limit(limits.memory)
+            .map_err(|e| RuntimeError::CreateRuntimeError(e.to_string()))?;
+
+        // 每执行一定数量的指令检查一次是否超时
+        let timed_out = Arc::new(AtomicBool::new(false));
+        let deadline = Instant::now() + limits.timeout;
+        let flag = timed_out.clone();
+        lua.set_hook(
+            HookTriggers::new().every_nth_instruction(1000),
+            move |_, _| {
+                if Instant::now() >= deadline {
+                    flag.store(true, Ordering::Relaxed);
+                    Err(mlua::Error::runtime("script timed out"))
+                } else {
+                    Ok(VmState::Continue)
+                }
+            },
+        );
+
+        let capabilities = Arc::new(capabilities.for_run());
+        install_host_api(&lua, &capabilities, limits.timeout)
+            .map_err(|e| RuntimeError::PrepareContextError(e.to_string()))?;
+        let context = to_lua(&lua, &context_to_value(context))
+            .map_err(|e| RuntimeError::PrepareContextError(e.to_string()))?;
+        lua.globals()
+            .set("context", context)
+            .map_err(|e| RuntimeError::PrepareContextError(e.to_string()))?;
+
+        let value: LuaValue = lua
+            .load(code)
+            .set_name("script")
+            .eval()
+            .map_err(|e| match e {
+                _ if timed_out.load(Ordering::Relaxed) => {
+                    RuntimeError::TimeoutError(limits.timeout.as_millis())
+                }
+                mlua::Error::MemoryError(_) => RuntimeError::MemoryLimitError(limits.memory),
+                e => restore_denied(RuntimeError::ExecuteScriptError(e.to_string()), &capabilities),
+            })?;
+        let value =
+            from_lua(value).map_err(|e| RuntimeError::ConvertResultError(e.to_string()))?;
+        Ok(Data::from_value(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn run(code: &str, limits: Limits) -> Result<Data, RuntimeError> {
+        let context = Context::from([("upstream".to_string(), Data::Int(41))]);
+        LuaRuntime.execute(code, &context, &limits, &Capabilities::default())
+    }
+
+    #[test]
+    fn returns_data_from_context() {
+        let result = run("return context.upstream + 1", Limits::default()).unwrap();
+        assert!(matches!(result, Data::Int(42)));
+
+        let result = run("return { 1, 2, 3 }", Limits::default()).unwrap();
+        assert!(matches!(result, Data::Vec(list) if list.len() == 3));
+
+        let result = run("return { ok = true }", Limits::default()).unwrap();
+        assert!(matches!(result, Data::Json(map) if map["ok"] == true));
+    }
+
+    #[test]
+    fn denies_undeclared_capabilities() {
+        let result = run("return daisy.env('PATH')", Limits::default());
+        assert!(matches!(result, Err(RuntimeError::PermissionDeniedError(_))));
+
+        let result = run("return io == nil and os == nil and dofile == nil", Limits::default());
+        assert!(matches!(result, Ok(Data::Bool(true))));
+    }
+
+    #[test]
+    fn interrupts_on_timeout() {
+        let limits = Limits {
+            timeout: Duration::from_millis(100),
+            ..Default::default()
+        };
+        let result = run("while true do end", limits);
+        assert!(matches!(result, Err(RuntimeError::TimeoutError(100))));
+    }
+
+    #[test]
+    fn stops_on_memory_limit() {
+        let limits = Limits {
+            memory: 8 * 1024 * 1024,
+            ..Default::default()
+        };
+        let result = run(
+            "local list = {} while true do list[#list + 1] = string.rep('x', 1024) .. #list end",
+            limits,
+        );
+        assert!(matches!(result, Err(RuntimeError::MemoryLimitError(_))));
+    }
+}
@@ -0,0 +1,307 @@
+use std::{
+    env,
+    fs::{create_dir_all, read_to_string, remove_file, write},
+    io::{Read, Write},
+    path::PathBuf,
+    process::{Command, Stdio},
+    thread::{self, JoinHandle, sleep},
+    time::{Duration, Instant},
+};
+
+use common::{
+    ty::{Context, Data},
+    utils::get_uid,
+};
+
+use crate::{Limits, Runtime, capability::Capabilities, context_to_value, error::RuntimeError};
+
+/// 解释器正常运行所需的环境变量，不受权限清单限制
+const BASE_ENV: [&str; 5] = ["PATH", "SystemRoot", "SYSTEMROOT", "TEMP", "TMP"];
+
+/// 外部解释器的描述
+///
+/// 统一约定：上游输出以 JSON 写入标准输入，脚本结果以 JSON 写入环境变量
+/// `DAISY_RESULT` 指向的文件，非零退出码视为失败并以标准错误作为错误信息
+pub struct Interpreter {
+    pub language: &'static str,
+    /// 按顺序查找的可执行文件
+    pub programs: &'static [&'static str],
+    /// 位于脚本路径之前的参数
+    pub args: &'static [&'static str],
+    pub envs: &'static [(&'static str, &'static str)],
+    pub ext: &'static str,
+    /// 脚本文件是否需要写入 BOM，Windows PowerShell 5 依赖它识别 UTF-8
+    pub bom: bool,
+    /// 将用户代码包装为符合约定的完整脚本
+    pub wrap: fn(&str) -> String,
+}
+
+/// 在 PATH 中查找可执行文件
+pub fn which(program: &str) -> Option<PathBuf> {
+    let paths = env::var_os("PATH")?;
+    let exts: Vec<String> = if cfg!(windows) {
+        env::var("PATHEXT")
+            .unwrap_or_else(|_| ".EXE;.CMD;.BAT".to_string())
+            .split(';')
+            .map(|ext| ext.to_lowercase())
+            .collect()
+    } else {
+        vec![String::new()]
+    };
+    env::split_paths(&paths)
+        .flat_map(|dir| {
+            exts.iter()
+                .map(move |ext| dir.join(format!("{}{}", program, ext)))
+        })
+        .find(|path| path.is_file())
+}
+
+/// 为每行代码增加缩进，用于嵌入到包装函数中
+pub(crate) fn indent(code: &str, prefix: &str) -> String {
+    code.lines()
+        .map(|line| format!("{}{}", prefix, line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 解析脚本结果，无法解析为 JSON 时作为字符串
+pub(crate) fn parse_result(content: &str) -> Data {
+    let content = content.trim_start_matches('\u{feff}').trim();
+    if content.is_empty() {
+        return Data::Null;
+    }
+    match serde_json::from_str(content) {
+        Ok(value) => Data::from_value(value),
+        Err(_) => Data::String(content.to_string()),
+    }
+}
+
+fn read_pipe(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut content = vec![];
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut content);
+        }
+        String::from_utf8_lossy(&content).to_string()
+    })
+}
+
+// 执行结束后清理临时文件
+struct TempFiles(Vec<PathBuf>);
+
+impl Drop for TempFiles {
+    fn drop(&mut self) {
+        for path in &self.0 {
+            let _ = remove_file(path);
+        }
+    }
+}
+
+impl Interpreter {
+    pub fn find(&self) -> Option<PathBuf> {
+        self.programs.iter().find_map(|program| which(program))
+    }
+}
+
+/// 通过外部解释器执行脚本的运行时，内存上限仅对内嵌运行时生效
+///
+/// 外部解释器无法限制文件与网络访问，因此要求权限清单允许启动进程，
+/// 子进程只能看到清单中声明的环境变量
+///
+/// 清单中声明了 `fs` 或 `net` 时拒绝执行，避免误以为脚本只能访问这些目录与主机；
+/// 需要这些限制的脚本应使用 JavaScript 或 Lua
+pub struct ProcessRuntime(pub &'static Interpreter);
+
+impl Runtime for ProcessRuntime {
+    fn language(&self) -> &'static str {
+        self.0.language
+    }
+    fn is_available(&self) -> bool {
+        self.0.find().is_some()
+    }
+    fn execute(
+        &self,
+        code: &str,
+        context: &Context,
+        limits: &Limits,
+        capabilities: &Capabilities,
+    ) -> Result<Data, RuntimeError> {
+        capabilities.check_process()?;
+        let interpreter = self.0;
+        if !capabilities.fs.is_empty() {
+            return Err(RuntimeError::UnenforceableCapabilityError(
+                interpreter.language,
+                "fs",
+            ));
+        }
+        if !capabilities.net.is_empty() {
+            return Err(RuntimeError::UnenforceableCapabilityError(
+                interpreter.language,
+                "net",
+            ));
+        }
+        let program = interpreter.find().ok_or_else(|| {
+            RuntimeError::InterpreterNotFoundError(interpreter.language.to_string())
+        })?;
+
+        let dir = env::temp_dir().join("daisytools-script");
+        create_dir_all(&dir).map_err(|e| RuntimeError::PrepareContextError(e.to_string()))?;
+        let id = get_uid();
+        let script_path = dir.join(format!("{}.{}", id, interpreter.ext));
+        let result_path = dir.join(format!("{}.result", id));
+        let _temp_files = TempFiles(vec![script_path.clone(), result_path.clone()]);
+
+        let mut script = (interpreter.wrap)(code);
+        if interpreter.bom {
+            script.insert(0, '\u{feff}');
+        }
+        write(&script_path, script)
+            .map_err(|e| RuntimeError::PrepareContextError(e.to_string()))?;
+        let context = serde_json::to_vec(&context_to_value(context))
+            .map_err(|e| RuntimeError::PrepareContextError(e.to_string()))?;
+
+        let mut command = Command::new(program);
+        command
+            .args(interpreter.args)
+            .arg(&script_path)
+            .env_clear()
+            .envs(BASE_ENV.into_iter().filter_map(|name| Some((name, env::var_os(name)?))))
+            .envs(capabilities.allowed_env())
+            .envs(interpreter.envs.iter().copied())
+            .env("DAISY_RESULT", &result_path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        // 服务进程中运行时不弹出控制台窗口
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            command.creation_flags(0x08000000);
+        }
+        let mut child = command
+            .spawn()
+            .map_err(|e| RuntimeError::ExecuteScriptError(e.to_string()))?;
+
+        let stdin = child.stdin.take();
+        let writer = thread::spawn(move || {
+            if let Some(mut stdin) = stdin {
+                let _ = stdin.write_all(&context);
+            }
+        });
+        let stdout = read_pipe(child.stdout.take());
+        let stderr = read_pipe(child.stderr.take());
+
+        let deadline = Instant::now() + limits.timeout;
+        let status = loop {
+            match child
+                .try_wait()
+                .map_err(|e| RuntimeError::ExecuteScriptError(e.to_string()))?
+            {
+                Some(status) => break status,
+                None if Instant::now() >= deadline => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(RuntimeError::TimeoutError(limits.timeout.as_millis()));
+                }
+                None => sleep(Duration::from_millis(10)),
+            }
+        };
+        let _ = writer.join();
+        let stdout = stdout.join().unwrap_or_default();
+        let stderr = stderr.join().unwrap_or_default();
+
+        if !stdout.trim().is_empty() {
+            log::info!(target: "script", "{}", stdout.trim_end());
+        }
+        if !status.success() {
+            let message = if stderr.trim().is_empty() {
+                format!("{} exited with {}", interpreter.language, status)
+            } else {
+                stderr.trim().to_string()
+            };
+            return Err(RuntimeError::ScriptFailedError(message));
+        }
+        if !stderr.trim().is_empty() {
+            log::warn!(target: "script", "{}", stderr.trim_end());
+        }
+
+        // 脚本没有写入结果时视为没有输出
+        let result = read_to_string(&result_path).unwrap_or_default();
+        Ok(parse_result(&result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shell::BASH;
+
+    #[test]
+    fn parses_result_content() {
+        assert!(matches!(parse_result(""), Data::Null));
+        assert!(matches!(parse_result("\u{feff}42\n"), Data::Int(42)));
+        assert!(matches!(parse_result("{\"a\":1}"), Data::Json(_)));
+        assert!(matches!(parse_result("hello"), Data::String(value) if value == "hello"));
+    }
+
+    #[test]
+    fn runs_bash_when_available() {
+        let runtime = ProcessRuntime(&BASH);
+        if !runtime.is_available() {
+            return;
+        }
+        let context = Context::from([("upstream".to_string(), Data::Int(41))]);
+        let result = runtime.execute(
+            "echo hi",
+            &context,
+            &Limits::default(),
+            &Capabilities::default(),
+        );
+        assert!(matches!(result, Err(RuntimeError::PermissionDeniedError(_))));
+
+        let capabilities = Capabilities {
+            process: true,
+            ..Default::default()
+        };
+        let result = runtime
+            .execute("echo \"$context\"", &context, &Limits::default(), &capabilities)
+            .unwrap();
+        assert!(matches!(result, Data::Json(map) if map["upstream"] == 41));
+
+        // 无法在子进程中生效的限制直接拒绝
+        let restricted = Capabilities {
+            process: true,
+            net: vec!["api.example.com".to_string()],
+            ..Default::default()
+        };
+        let result = runtime.execute("echo hi", &context, &Limits::default(), &restricted);
+        assert!(matches!(
+            result,
+            Err(RuntimeError::UnenforceableCapabilityError("Bash", "net"))
+        ));
+
+        // 未声明的环境变量不会传递给解释器
+        let result = runtime
+            .execute("echo \"${HOME:-none}\"", &context, &Limits::default(), &capabilities)
+            .unwrap();
+        assert!(matches!(result, Data::String(value) if value == "none"));
+
+        let result = runtime.execute(
+            "echo boom >&2; exit 3",
+            &context,
+            &Limits::default(),
+            &capabilities,
+        );
+        assert!(matches!(
+            result,
+            Err(RuntimeError::ScriptFailedError(message)) if message == "boom"
+        ));
+
+        let limits = Limits {
+            timeout: Duration::from_millis(200),
+            ..Default::default()
+        };
+        let result = runtime.execute("sleep 5", &context, &limits, &capabilities);
+        assert!(matches!(result, Err(RuntimeError::TimeoutError(200))));
+    }
+}
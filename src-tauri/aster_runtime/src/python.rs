@@ -0,0 +1,38 @@
+use crate::process::{Interpreter, indent};
+
+// 脚本作为函数体执行，通过 return 返回卡片的输出
+fn wrap(code: &str) -> String {
+    format!(
+        r#"import json
+import os
+import sys
+
+context = json.load(sys.stdin)
+
+
+def __daisy_main(context):
+{}
+
+
+__daisy_result = __daisy_main(context)
+with open(os.environ["DAISY_RESULT"], "w", encoding="utf-8") as __daisy_file:
+    json.dump(__daisy_result, __daisy_file, ensure_ascii=False, default=str)
+"#,
+        // 末尾的 pass 保证空代码也是合法的函数体
+        indent(&format!("{}\npass", code), "    ")
+    )
+}
+
+pub static PYTHON: Interpreter = Interpreter {
+    language: "Python",
+    programs: if cfg!(windows) {
+        &["python", "py"]
+    } else {
+        &["python3", "python"]
+    },
+    args: &[],
+    envs: &[("PYTHONUTF8", "1"), ("PYTHONIOENCODING", "utf-8")],
+    ext: "py",
+    bom: false,
+    wrap,
+};
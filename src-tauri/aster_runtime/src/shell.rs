@@ -0,0 +1,59 @@
+use crate::process::{Interpreter, indent};
+
+// 脚本块的输出即为卡片的输出，多个输出会组成数组
+fn wrap_powershell(code: &str) -> String {
+    format!(
+        r#"$ErrorActionPreference = 'Stop'
+$context = [Console]::In.ReadToEnd() | ConvertFrom-Json
+$__daisy_result = & {{
+    param($context)
+{}
+}} $context
+ConvertTo-Json -InputObject $__daisy_result -Depth 32 -Compress |
+    Set-Content -LiteralPath $env:DAISY_RESULT -Encoding UTF8
+"#,
+        indent(code, "    ")
+    )
+}
+
+// 函数的标准输出即为卡片的输出，可以是 JSON 或普通文本
+fn wrap_bash(code: &str) -> String {
+    format!(
+        r#"set -e
+context="$(cat)"
+__daisy_main() {{
+{}
+:
+}}
+__daisy_main "$context" > "$DAISY_RESULT"
+"#,
+        indent(code, "    ")
+    )
+}
+
+pub static POWERSHELL: Interpreter = Interpreter {
+    language: "PowerShell",
+    programs: &["pwsh", "powershell"],
+    args: &[
+        "-NoLogo",
+        "-NoProfile",
+        "-NonInteractive",
+        "-ExecutionPolicy",
+        "Bypass",
+        "-File",
+    ],
+    envs: &[],
+    ext: "ps1",
+    bom: true,
+    wrap: wrap_powershell,
+};
+
+pub static BASH: Interpreter = Interpreter {
+    language: "Bash",
+    programs: &["bash"],
+    args: &[],
+    envs: &[],
+    ext: "sh",
+    bom: false,
+    wrap: wrap_bash,
+};
@@ -0,0 +1,190 @@
+//! action 的单元测试工具，以服务使用的 `ActionProvider::run` 运行 action
+//!
+//! 声明上游卡片的输出、插头与参数，运行时同样经过迁移、插头替换与校验，再断言返回的分支与数据
+//! ```ignore
+//! use aster_test::{ActionTest, action_test};
+//! use serde_json::json;
+//!
+//! #[action_test(merge_json_action)]
+//! fn merges_upstream_output(test: ActionTest) -> aster_test::Result<()> {
+//!     test.upstream("fetch", json!({ "body": r#"{ "a": 1 }"# }))
+//!         .plug("base", "fetch", &["body"])
+//!         .arg("other", r#"{ "b": 2 }"#)
+//!         .run()?
+//!         .assert_variant("Success")?
+//!         .assert_data(json!({ "a": 1, "b": 2 }))?;
+//!     Ok(())
+//! }
+//! ```
+
+use std::time::Duration;
+
+use anyhow::{Context as _, ensure};
+use aster_loader::ActionProvider;
+use common::{
+    action::Action,
+    ty::{CardResult, Context, Data},
+};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+use vasing::Stage;
+
+pub use anyhow::Result;
+pub use aster_macro::action_test;
+
+/// 测试中上游卡片的输出与当前卡片的参数
+#[derive(Debug, Clone)]
+pub struct ActionTest {
+    action_type: String,
+    args: Map<String, Value>,
+    context: Context,
+    version: Option<u32>,
+}
+
+impl ActionTest {
+    pub fn new(action_type: impl Into<String>) -> Self {
+        ActionTest {
+            action_type: action_type.into(),
+            args: Map::new(),
+            context: Context::new(),
+            version: None,
+        }
+    }
+
+    /// 参数的值，与点亮时表单提交的值相同
+    pub fn arg(mut self, key: &str, value: impl Serialize) -> Self {
+        let value = serde_json::to_value(value).unwrap_or_default();
+        self.args.insert(key.to_string(), value);
+        self
+    }
+
+    /// 上游卡片 id 的输出
+    pub fn upstream(mut self, id: &str, output: impl Serialize) -> Self {
+        let output = serde_json::to_value(output).unwrap_or_default();
+        self.context
+            .insert(id.to_string(), Data::from_value(output));
+        self
+    }
+
+    /// 参数连接到上游卡片 id 的输出，path 为输出中依次读取的键
+    pub fn plug(mut self, key: &str, id: &str, path: &[&str]) -> Self {
+        let value = std::iter::once(id)
+            .chain(path.iter().copied())
+            .collect::<Vec<_>>();
+        self.args
+            .insert(key.to_string(), json!({ "type": "Plug", "value": value }));
+        self
+    }
+
+    /// 以旧版本点亮的参数运行，用于测试 `#[migrate]` 声明的迁移
+    pub fn version(mut self, version: u32) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// 与服务相同，迁移、替换插头并校验参数后运行
+    pub fn run(&self) -> Result<ActionOutcome> {
+        let instance = Action::get_action_instance_from_type(&self.action_type)?;
+        let mut action = instance.get_action(
+            format!("{} test", self.action_type),
+            Data::Json(self.args.clone()),
+        );
+        if let Some(version) = self.version {
+            action.version = version;
+        }
+        let result = action
+            .run(&self.context)
+            .with_context(|| format!("Failed to run {}", self.action_type))?;
+        Ok(ActionOutcome(result))
+    }
+}
+
+/// action 返回的分支与数据
+#[derive(Debug)]
+pub struct ActionOutcome(CardResult);
+
+impl ActionOutcome {
+    pub fn variant(&self) -> &str {
+        self.0.variant
+    }
+
+    pub fn data(&self) -> &Data {
+        &self.0.data
+    }
+
+    pub fn into_inner(self) -> CardResult {
+        self.0
+    }
+
+    /// 断言进入的分支
+    pub fn assert_variant(self, variant: &str) -> Result<Self> {
+        ensure!(
+            self.0.variant == variant,
+            "Expected variant {}, but got {} with {}",
+            variant,
+            self.0.variant,
+            self.0.data.to_value()
+        );
+        Ok(self)
+    }
+
+    /// 断言输出的数据与 expected 相同
+    pub fn assert_data(self, expected: impl Serialize) -> Result<Self> {
+        let expected = serde_json::to_value(expected)?;
+        let actual = self.0.data.to_value();
+        ensure!(
+            actual == expected,
+            "Expected data {}, but got {}",
+            expected,
+            actual
+        );
+        Ok(self)
+    }
+
+    /// 断言输出的数据包含 expected，对象只比较 expected 中出现的键
+    pub fn assert_contains(self, expected: impl Serialize) -> Result<Self> {
+        let expected = serde_json::to_value(expected)?;
+        let actual = self.0.data.to_value();
+        ensure!(
+            contains(&actual, &expected),
+            "Expected data containing {}, but got {}",
+            expected,
+            actual
+        );
+        Ok(self)
+    }
+}
+
+fn contains(actual: &Value, expected: &Value) -> bool {
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => expected.iter().all(|(key, value)| {
+            actual
+                .get(key)
+                .is_some_and(|actual| contains(actual, value))
+        }),
+        _ => actual == expected,
+    }
+}
+
+/// 在 vasing 的 Stage 中运行测试，由 `#[action_test]` 生成的测试函数调用
+pub fn run_action_test<F>(name: &str, test: ActionTest, timeout: Option<Duration>, body: F)
+where
+    F: FnOnce(ActionTest) -> Result<()> + Send + 'static,
+{
+    let runtime = tokio::runtime::Runtime::new().expect("Failed to create the test runtime");
+    runtime.block_on(async move {
+        let mut stage = Stage::new();
+        if let Some(timeout) = timeout {
+            stage = stage.with_timeout(timeout);
+        }
+        stage.spawn(name, move || async move {
+            // action 以 block_on 运行异步函数，与服务一样在阻塞线程中运行
+            match tokio::task::spawn_blocking(move || body(test)).await {
+                Ok(result) => result,
+                Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+                Err(e) => Err(e.into()),
+            }
+        });
+        stage.run().await;
+    });
+}
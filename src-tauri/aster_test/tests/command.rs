@@ -0,0 +1,55 @@
+// 测试依赖 sh、cat 与 sleep
+#![cfg(unix)]
+
+// 保证动作包链接进测试，其中的 action 才会被注册
+use command as _;
+
+use aster_test::{ActionTest, action_test};
+use serde_json::json;
+
+#[action_test(command_action)]
+fn parses_stdout_on_success(test: ActionTest) -> aster_test::Result<()> {
+    test.arg("program", "sh")
+        .arg("args", "-c\necho '{ \"a\": 1 }'")
+        .arg("parse_json", true)
+        .run()?
+        .assert_variant("Success")?
+        .assert_contains(json!({ "code": 0, "json": { "a": 1 } }))?;
+    Ok(())
+}
+
+#[action_test(command_action)]
+fn keeps_output_on_failure(test: ActionTest) -> aster_test::Result<()> {
+    test.arg("program", "sh")
+        .arg("args", "-c\necho 'not json'; echo oops >&2; exit 3")
+        .arg("parse_json", true)
+        .run()?
+        .assert_variant("Failure")?
+        .assert_data(json!({
+            "stdout": "not json\n",
+            "stderr": "oops\n",
+            "code": 3,
+            "json": null,
+        }))?;
+    Ok(())
+}
+
+#[action_test(command_action)]
+fn writes_stdin(test: ActionTest) -> aster_test::Result<()> {
+    test.arg("program", "cat")
+        .arg("stdin", "daisy")
+        .run()?
+        .assert_variant("Success")?
+        .assert_contains(json!({ "stdout": "daisy" }))?;
+    Ok(())
+}
+
+#[action_test(command_action)]
+fn kills_on_timeout(test: ActionTest) -> aster_test::Result<()> {
+    test.arg("program", "sleep")
+        .arg("args", "5")
+        .arg("timeout", 100)
+        .run()?
+        .assert_variant("Timeout")?;
+    Ok(())
+}
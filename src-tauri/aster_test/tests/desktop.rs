@@ -0,0 +1,84 @@
+// 保证动作包链接进测试，其中的 action 才会被注册
+use desktop as _;
+
+use std::{
+    sync::{Mutex, Once},
+    time::Duration,
+};
+
+use aster_test::{ActionTest, action_test};
+use common::desktop::{DesktopFuture, DesktopRequest, set_desktop_handler};
+use serde_json::{Value, json};
+
+static NOTIFIED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// 代替 UI 进程处理请求，Prompt 的默认值为 slow 时不响应
+fn fake_desktop(request: DesktopRequest) -> DesktopFuture {
+    Box::pin(async move {
+        match request {
+            DesktopRequest::Notify { title, .. } => NOTIFIED.lock().unwrap().push(title),
+            DesktopRequest::ReadClipboardText => return Ok(json!("copied")),
+            DesktopRequest::Prompt { default, .. } if default.as_deref() == Some("slow") => {
+                common::tokio::time::sleep(Duration::from_secs(60)).await;
+            }
+            DesktopRequest::Prompt { default, .. } => return Ok(json!(default)),
+            _ => {}
+        }
+        Ok(Value::Null)
+    })
+}
+
+// 宿主注册的处理方式只能设置一次
+fn setup() {
+    static SETUP: Once = Once::new();
+    SETUP.call_once(|| set_desktop_handler(fake_desktop));
+}
+
+#[action_test(notify_action)]
+fn routes_requests_from_the_library_to_the_host(test: ActionTest) -> aster_test::Result<()> {
+    setup();
+    test.arg("title", "routed")
+        .arg("body", "hello")
+        .run()?
+        .assert_variant("Success")?;
+    assert!(NOTIFIED.lock().unwrap().contains(&"routed".to_string()));
+    Ok(())
+}
+
+#[action_test(read_clipboard_action)]
+fn returns_results_from_the_host(test: ActionTest) -> aster_test::Result<()> {
+    setup();
+    test.run()?
+        .assert_variant("Success")?
+        .assert_data(json!("copied"))?;
+    Ok(())
+}
+
+#[action_test(prompt_action)]
+fn distinguishes_cancelled_prompts(test: ActionTest) -> aster_test::Result<()> {
+    setup();
+    test.clone()
+        .arg("title", "Name")
+        .arg("message", "Who are you")
+        .arg("default", "daisy")
+        .run()?
+        .assert_variant("Submitted")?
+        .assert_data(json!("daisy"))?;
+    test.arg("title", "Name")
+        .arg("message", "Who are you")
+        .run()?
+        .assert_variant("Cancelled")?;
+    Ok(())
+}
+
+#[action_test(prompt_action)]
+fn times_out_when_the_host_does_not_respond(test: ActionTest) -> aster_test::Result<()> {
+    setup();
+    test.arg("title", "Name")
+        .arg("message", "Who are you")
+        .arg("default", "slow")
+        .arg("timeout", 100)
+        .run()?
+        .assert_variant("Timeout")?;
+    Ok(())
+}
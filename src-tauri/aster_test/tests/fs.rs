@@ -0,0 +1,112 @@
+// 保证动作包链接进测试，其中的 action 才会被注册
+use fs as _;
+
+use std::path::PathBuf;
+
+use aster_test::{ActionTest, action_test};
+use common::utils::get_uid;
+use serde_json::json;
+
+// 每个测试使用独立的临时目录
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aster-fs-{}-{}", name, get_uid()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn path(path: PathBuf) -> String {
+    path.to_string_lossy().to_string()
+}
+
+#[action_test(read_file_action)]
+fn reports_missing_files(test: ActionTest) -> aster_test::Result<()> {
+    let dir = temp_dir("missing");
+    test.arg("path", path(dir.join("missing.txt")))
+        .run()?
+        .assert_variant("NotFound")?;
+    Ok(())
+}
+
+#[action_test(copy_action)]
+fn keeps_existing_targets(test: ActionTest) -> aster_test::Result<()> {
+    let dir = temp_dir("exists");
+    std::fs::write(dir.join("a.txt"), "a")?;
+    std::fs::write(dir.join("b.txt"), "b")?;
+    test.arg("source", path(dir.join("a.txt")))
+        .arg("target", path(dir.join("b.txt")))
+        .run()?
+        .assert_variant("Exists")?;
+    assert_eq!(std::fs::read_to_string(dir.join("b.txt"))?, "b");
+    Ok(())
+}
+
+#[action_test(copy_action)]
+fn overwrites_existing_targets(test: ActionTest) -> aster_test::Result<()> {
+    let dir = temp_dir("overwrite");
+    std::fs::write(dir.join("a.txt"), "a")?;
+    std::fs::write(dir.join("b.txt"), "b")?;
+    test.arg("source", path(dir.join("a.txt")))
+        .arg("target", path(dir.join("b.txt")))
+        .arg("overwrite", true)
+        .run()?
+        .assert_variant("Success")?
+        .assert_data(json!(path(dir.join("b.txt"))))?;
+    assert_eq!(std::fs::read_to_string(dir.join("b.txt"))?, "a");
+    Ok(())
+}
+
+#[action_test(copy_action)]
+fn rejects_copying_into_itself(test: ActionTest) -> aster_test::Result<()> {
+    let dir = temp_dir("nested");
+    std::fs::create_dir_all(dir.join("a"))?;
+    let result = test
+        .arg("source", path(dir.join("a")))
+        .arg("target", path(dir.join("a").join("b")))
+        .run();
+    assert!(result.is_err());
+    assert!(!dir.join("a").join("b").exists());
+    Ok(())
+}
+
+#[cfg(unix)]
+#[action_test(copy_action)]
+fn copies_links_as_links(test: ActionTest) -> aster_test::Result<()> {
+    let dir = temp_dir("links");
+    std::fs::create_dir_all(dir.join("a"))?;
+    // 指向上级目录的链接形成循环
+    std::os::unix::fs::symlink("..", dir.join("a").join("loop"))?;
+    test.arg("source", path(dir.join("a")))
+        .arg("target", path(dir.join("b")))
+        .run()?
+        .assert_variant("Success")?;
+    assert_eq!(std::fs::read_link(dir.join("b").join("loop"))?, PathBuf::from(".."));
+    Ok(())
+}
+
+#[test]
+fn zips_and_unzips_a_directory() -> aster_test::Result<()> {
+    let dir = temp_dir("zip");
+    let source = dir.join("source");
+    std::fs::create_dir_all(source.join("logs"))?;
+    std::fs::write(source.join("a.txt"), "a")?;
+    std::fs::write(source.join("logs").join("b.log"), "b")?;
+
+    // 压缩包位于源目录中时不包含自身
+    let archive = source.join("source.zip");
+    ActionTest::new("zip_action")
+        .arg("source", path(source.clone()))
+        .arg("target", path(archive.clone()))
+        .run()?
+        .assert_variant("Success")?;
+    ActionTest::new("unzip_action")
+        .arg("archive", path(archive))
+        .arg("target", path(dir.join("target")))
+        .run()?
+        .assert_variant("Success")?;
+
+    let target = dir.join("target");
+    assert_eq!(std::fs::read_to_string(target.join("a.txt"))?, "a");
+    assert_eq!(std::fs::read_to_string(target.join("logs").join("b.log"))?, "b");
+    assert!(!target.join("source.zip").exists());
+    Ok(())
+}
@@ -0,0 +1,28 @@
+// 测试依赖 sh
+#![cfg(unix)]
+
+// 保证动作包链接进测试，其中的 action 才会被注册
+use command as _;
+
+use aster_test::ActionTest;
+use serde_json::json;
+
+// 服务在运行时的阻塞线程中调用 action，宿主以 Handle::block_on 等待，
+// 动态库导出的函数再以自己的运行时运行异步 action，两层 block_on 不能冲突
+#[tokio::test(flavor = "multi_thread")]
+async fn runs_async_action_inside_a_running_runtime() {
+    let runs = (0..4).map(|i| {
+        tokio::task::spawn_blocking(move || -> aster_test::Result<()> {
+            ActionTest::new("command_action")
+                .arg("program", "sh")
+                .arg("args", format!("-c\necho {i}"))
+                .run()?
+                .assert_variant("Success")?
+                .assert_contains(json!({ "stdout": format!("{i}\n") }))?;
+            Ok(())
+        })
+    });
+    for run in runs.collect::<Vec<_>>() {
+        run.await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,81 @@
+// 保证动作包链接进测试，其中的 action 才会被注册
+use mail as _;
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    sync::mpsc::{Receiver, channel},
+    thread,
+};
+
+use aster_test::{ActionTest, action_test};
+use serde_json::json;
+
+/// 只处理一次连接的模拟 SMTP 服务器，拒绝收件人时以 550 响应 RCPT，返回端口与收到的邮件
+fn mock_server(reject: bool) -> (u16, Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (sender, receiver) = channel();
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        write!(stream, "220 localhost ESMTP\r\n").unwrap();
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap_or(0) > 0 {
+            let command = line.trim_end().to_ascii_uppercase();
+            line.clear();
+            let reply = if command.starts_with("RCPT") && reject {
+                "550 5.1.1 No such user"
+            } else if command == "DATA" {
+                write!(stream, "354 End data with <CR><LF>.<CR><LF>\r\n").unwrap();
+                let mut data = String::new();
+                while reader.read_line(&mut line).unwrap_or(0) > 0 && line != ".\r\n" {
+                    data.push_str(&line);
+                    line.clear();
+                }
+                line.clear();
+                sender.send(data).unwrap();
+                "250 2.0.0 Queued as 1"
+            } else if command == "QUIT" {
+                write!(stream, "221 Bye\r\n").unwrap();
+                break;
+            } else {
+                "250 OK"
+            };
+            write!(stream, "{}\r\n", reply).unwrap();
+        }
+    });
+    (port, receiver)
+}
+
+fn send_mail(test: ActionTest, port: u16) -> ActionTest {
+    test.arg("host", "127.0.0.1")
+        .arg("port", port)
+        .arg("security", "Plain")
+        .arg("from", "Daisy <daisy@example.com>")
+        .arg("to", "ops@example.com")
+        .arg("subject", "Disk usage")
+        .arg("body", "Disk is 91% full")
+}
+
+#[action_test(send_mail_action)]
+fn delivers_mail(test: ActionTest) -> aster_test::Result<()> {
+    let (port, receiver) = mock_server(false);
+    send_mail(test, port)
+        .run()?
+        .assert_variant("Success")?
+        .assert_data(json!({ "code": 250, "message": "2.0.0 Queued as 1" }))?;
+    let data = receiver.recv()?;
+    assert!(data.contains("Subject: Disk usage\r\n"));
+    assert!(data.contains("Disk is 91% full"));
+    Ok(())
+}
+
+#[action_test(send_mail_action)]
+fn reports_rejection(test: ActionTest) -> aster_test::Result<()> {
+    let (port, receiver) = mock_server(true);
+    send_mail(test, port).run()?.assert_variant("Rejected")?;
+    // 被拒绝的邮件不会发送内容
+    assert!(receiver.try_recv().is_err());
+    Ok(())
+}
@@ -0,0 +1,156 @@
+// 保证动作包链接进测试，其中的 action 才会被注册
+use transform as _;
+
+use aster_test::{ActionTest, action_test};
+use common::action::error::ActionError;
+use serde_json::json;
+
+#[action_test(merge_json_action)]
+fn merges_upstream_output(test: ActionTest) -> aster_test::Result<()> {
+    test.upstream("fetch", json!({ "body": r#"{ "a": 1, "b": { "c": 1 } }"# }))
+        .plug("base", "fetch", &["body"])
+        .arg("other", r#"{ "b": { "d": 2 } }"#)
+        .arg("deep", true)
+        .run()?
+        .assert_variant("Success")?
+        .assert_data(json!({ "a": 1, "b": { "c": 1, "d": 2 } }))?;
+    Ok(())
+}
+
+#[action_test(pick_keys_action)]
+fn picks_nested_keys(test: ActionTest) -> aster_test::Result<()> {
+    test.arg(
+        "data",
+        r#"{ "user": { "name": "daisy", "age": 3 }, "id": 1 }"#,
+    )
+    .arg("keys", "user.name\nid")
+    .run()?
+    .assert_variant("Success")?
+    .assert_contains(json!({ "user": { "name": "daisy" }, "id": 1 }))?;
+    Ok(())
+}
+
+#[action_test(merge_json_action)]
+fn rejects_invalid_json(test: ActionTest) -> aster_test::Result<()> {
+    test.arg("base", "not json")
+        .arg("other", "{}")
+        .run()?
+        .assert_variant("Invalid")?;
+    Ok(())
+}
+
+#[action_test(merge_json_action)]
+fn fails_on_missing_upstream(test: ActionTest) -> aster_test::Result<()> {
+    let error = test
+        .plug("base", "fetch", &["body"])
+        .arg("other", "{}")
+        .run()
+        .unwrap_err();
+    // 上游输出中找不到插头的值，运行前替换插头时失败
+    match error.downcast_ref::<ActionError>() {
+        Some(ActionError::RunActionCardError(message)) => assert_eq!(
+            message,
+            "Failed to parse plug: The value for plug was not found in the context"
+        ),
+        _ => panic!("Expected a plug resolution error, but got {:#}", error),
+    }
+    Ok(())
+}
+
+#[action_test(query_action)]
+fn queries_with_jmespath(test: ActionTest) -> aster_test::Result<()> {
+    test.arg("data", r#"{ "items": [{ "id": 1 }, { "id": 2 }] }"#)
+        .arg("query", "items[].id")
+        .arg("syntax", "JmesPath")
+        .run()?
+        .assert_variant("Found")?
+        .assert_data(json!([1, 2]))?;
+    Ok(())
+}
+
+#[action_test(query_action)]
+fn reports_empty_query(test: ActionTest) -> aster_test::Result<()> {
+    test.arg("data", r#"{ "items": [] }"#)
+        .arg("query", "$.items[*].id")
+        .run()?
+        .assert_variant("Empty")?;
+    Ok(())
+}
+
+#[action_test(regex_extract_action)]
+fn extracts_named_group(test: ActionTest) -> aster_test::Result<()> {
+    test.arg("text", "a=1, b=22")
+        .arg("pattern", r"\w=(?<n>\d+)")
+        .arg("group", "n")
+        .run()?
+        .assert_variant("Matched")?
+        .assert_data(json!({ "count": 2, "matches": ["1", "22"] }))?;
+    Ok(())
+}
+
+#[action_test(template_action)]
+fn renders_template(test: ActionTest) -> aster_test::Result<()> {
+    test.arg("template", "Hi {{ user.name }}{{missing}}!")
+        .arg("data", r#"{ "user": { "name": "daisy" } }"#)
+        .run()?
+        .assert_variant("Success")?
+        .assert_data(json!("Hi daisy!"))?;
+    Ok(())
+}
+
+#[action_test(csv_to_json_action)]
+fn parses_tab_separated_csv(test: ActionTest) -> aster_test::Result<()> {
+    test.arg("csv", "name\tage\ndaisy\t3\n")
+        .arg("delimiter", "\\t")
+        .run()?
+        .assert_variant("Success")?
+        .assert_data(json!({ "count": 1, "rows": [{ "name": "daisy", "age": "3" }] }))?;
+    Ok(())
+}
+
+#[action_test(csv_to_json_action)]
+fn rejects_multi_character_delimiter(test: ActionTest) -> aster_test::Result<()> {
+    test.arg("csv", "a;;b")
+        .arg("delimiter", ";;")
+        .run()?
+        .assert_variant("Invalid")?;
+    Ok(())
+}
+
+#[action_test(json_to_csv_action)]
+fn rejects_non_ascii_delimiter(test: ActionTest) -> aster_test::Result<()> {
+    test.arg("data", r#"[{ "a": 1 }]"#)
+        .arg("delimiter", "，")
+        .run()?
+        .assert_variant("Invalid")?;
+    Ok(())
+}
+
+#[action_test(json_to_csv_action)]
+fn writes_csv_with_union_header(test: ActionTest) -> aster_test::Result<()> {
+    test.arg("data", r#"[{ "a": 1 }, { "b": "x;y" }]"#)
+        .arg("delimiter", ";")
+        .run()?
+        .assert_variant("Success")?
+        .assert_data(json!("a;b\n1;\n;\"x;y\"\n"))?;
+    Ok(())
+}
+
+#[action_test(rename_keys_action)]
+fn renames_nested_keys(test: ActionTest) -> aster_test::Result<()> {
+    test.arg("data", r#"{ "user": { "name": "daisy" } }"#)
+        .arg("mapping", "user.name = title")
+        .run()?
+        .assert_variant("Success")?
+        .assert_data(json!({ "user": {}, "title": "daisy" }))?;
+    Ok(())
+}
+
+#[action_test(rename_keys_action)]
+fn rejects_invalid_mapping(test: ActionTest) -> aster_test::Result<()> {
+    test.arg("data", "{}")
+        .arg("mapping", "name")
+        .run()?
+        .assert_variant("Invalid")?;
+    Ok(())
+}
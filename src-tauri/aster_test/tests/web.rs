@@ -0,0 +1,191 @@
+// 保证动作包链接进测试，其中的 action 才会被注册
+use web as _;
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    sync::mpsc::{Receiver, channel},
+    thread,
+    time::Duration,
+};
+
+use aster_test::{ActionTest, action_test};
+use common::{secret::ALLOWED_ENV, utils::get_uid};
+use serde_json::json;
+
+fn response(status: &str, headers: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        headers,
+        body.len(),
+        body
+    )
+}
+
+/// 依次以给定的响应处理每个连接的模拟 HTTP 服务器，返回地址与收到的请求
+fn mock_server(responses: Vec<String>, delay: Duration) -> (String, Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (sender, receiver) = channel();
+    thread::spawn(move || {
+        for reply in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            let mut length = 0;
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap_or(0) > 0 && line != "\r\n" {
+                if let Some((name, value)) = line.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                line.clear();
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str("\r\n");
+            request.push_str(&String::from_utf8_lossy(&body));
+            // 不关心请求的测试会丢弃接收端
+            let _ = sender.send(request);
+            thread::sleep(delay);
+            // 超时的客户端已断开连接，写入失败可以忽略
+            let _ = stream.write_all(reply.as_bytes());
+        }
+    });
+    (url, receiver)
+}
+
+fn serve(reply: String) -> (String, Receiver<String>) {
+    mock_server(vec![reply], Duration::ZERO)
+}
+
+#[action_test(fetch_action)]
+fn parses_json_on_success(test: ActionTest) -> aster_test::Result<()> {
+    let (url, _) = serve(response(
+        "200 OK",
+        "Content-Type: application/json\r\n",
+        r#"{ "ok": true }"#,
+    ));
+    test.arg("url", format!("{}/status", url))
+        .arg("method", "Get")
+        .run()?
+        .assert_variant("Success")?
+        .assert_contains(json!({ "status": 200, "body": { "ok": true } }))?;
+    Ok(())
+}
+
+#[action_test(fetch_action)]
+fn splits_client_errors(test: ActionTest) -> aster_test::Result<()> {
+    let (url, _) = serve(response("404 Not Found", "", "missing"));
+    test.arg("url", url)
+        .arg("method", "Get")
+        .run()?
+        .assert_variant("ClientError")?
+        .assert_contains(json!({ "status": 404, "body": "missing" }))?;
+    Ok(())
+}
+
+#[action_test(fetch_action)]
+fn splits_server_errors(test: ActionTest) -> aster_test::Result<()> {
+    let (url, _) = serve(response("503 Service Unavailable", "", ""));
+    test.arg("url", url)
+        .arg("method", "Get")
+        .run()?
+        .assert_variant("ServerError")?
+        .assert_contains(json!({ "status": 503, "body": null }))?;
+    Ok(())
+}
+
+#[action_test(fetch_action)]
+fn follows_redirects(test: ActionTest) -> aster_test::Result<()> {
+    let (url, receiver) = mock_server(
+        vec![
+            response("302 Found", "Location: /next\r\n", ""),
+            response("200 OK", "", "done"),
+        ],
+        Duration::ZERO,
+    );
+    test.arg("url", format!("{}/start", url))
+        .arg("method", "Get")
+        .run()?
+        .assert_variant("Success")?
+        .assert_contains(json!({ "body": "done" }))?;
+    receiver.recv()?;
+    assert!(receiver.recv()?.starts_with("GET /next "));
+    Ok(())
+}
+
+#[action_test(fetch_action)]
+fn keeps_redirects_when_manual(test: ActionTest) -> aster_test::Result<()> {
+    let (url, _) = serve(response("302 Found", "Location: /next\r\n", ""));
+    test.arg("url", url)
+        .arg("method", "Get")
+        .arg("redirect", "Manual")
+        .run()?
+        .assert_variant("Redirect")?
+        .assert_contains(json!({ "status": 302, "headers": { "location": "/next" } }))?;
+    Ok(())
+}
+
+#[action_test(fetch_action)]
+fn reports_timeout(test: ActionTest) -> aster_test::Result<()> {
+    let (url, _) = mock_server(vec![response("200 OK", "", "")], Duration::from_millis(500));
+    test.arg("url", url)
+        .arg("method", "Get")
+        .arg("timeout", 100)
+        .run()?
+        .assert_variant("Timeout")?;
+    Ok(())
+}
+
+#[action_test(fetch_action)]
+fn uploads_files_in_multipart(test: ActionTest) -> aster_test::Result<()> {
+    let file = std::env::temp_dir().join(format!("aster-web-{}.txt", get_uid()));
+    std::fs::write(&file, "daisy notes")?;
+    let (url, receiver) = serve(response("200 OK", "", ""));
+    test.arg("url", url)
+        .arg("method", "Post")
+        .arg("body_type", "Multipart")
+        .arg("body", format!("name=daisy\nfile=@{}", file.display()))
+        .run()?
+        .assert_variant("Success")?;
+    let request = receiver.recv()?;
+    let file_name = file.file_name().unwrap().to_string_lossy();
+    assert!(request.contains("name=\"name\"\r\n\r\ndaisy\r\n"));
+    assert!(request.contains(&format!("name=\"file\"; filename=\"{}\"", file_name)));
+    assert!(request.contains("daisy notes"));
+    Ok(())
+}
+
+#[action_test(fetch_action)]
+fn resolves_allowed_env_in_headers(test: ActionTest) -> aster_test::Result<()> {
+    // 这些变量只在本测试中使用
+    unsafe {
+        std::env::set_var(ALLOWED_ENV, "DAISY_WEB_TOKEN");
+        std::env::set_var("DAISY_WEB_TOKEN", "token");
+        std::env::set_var("DAISY_WEB_HIDDEN", "hidden");
+    }
+    let (url, receiver) = serve(response("200 OK", "", ""));
+    test.clone()
+        .arg("url", url.clone())
+        .arg("method", "Get")
+        .arg("headers", "X-Token: env:DAISY_WEB_TOKEN\nX-Plain: plain")
+        .run()?
+        .assert_variant("Success")?;
+    let request = receiver.recv()?.to_ascii_lowercase();
+    assert!(request.contains("x-token: token\r\n"));
+    assert!(request.contains("x-plain: plain\r\n"));
+
+    // 未列出的环境变量在发送请求前失败
+    let error = test
+        .arg("url", url)
+        .arg("method", "Get")
+        .arg("headers", "X-Hidden: env:DAISY_WEB_HIDDEN")
+        .run()
+        .unwrap_err();
+    assert!(format!("{:#}", error).contains("DAISY_WEB_HIDDEN is not listed"));
+    Ok(())
+}
@@ -2,6 +2,7 @@ pub mod entry;
 pub mod error;
 pub mod r#impl;
 pub mod manager; 
+pub mod rule;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -21,6 +22,32 @@ pub struct Action {
     pub data: Data,
     #[serde(default)]
     pub plug: Value,
+    /// 点亮时参数的版本，旧版本的卡片在加载时迁移
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+/// 已点亮的卡片无法使用的原因
+#[derive(Serialize, Clone, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ActionIssue {
+    /// 卡片的类型已不存在，例如动作包被移除
+    Missing,
+    /// 参数无法迁移到当前版本
+    Migrate { message: String },
+}
+
+/// 已点亮的卡片及其状态，供界面标记无法使用的卡片
+#[derive(Serialize, Clone, Debug)]
+pub struct LitAction {
+    #[serde(flatten)]
+    pub action: Action,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue: Option<ActionIssue>,
 }
 
 pub trait ActionTrait {
@@ -32,9 +59,23 @@ pub trait ActionTrait {
             r#type: r#type.to_string(),
             data: args,
             plug: Value::Null,
+            version: self.version(),
         }
     }
+    /// 参数的版本，由 `#[action(version = N)]` 声明
+    fn version(&self) -> u32 {
+        1
+    }
+    /// 将参数从 from 版本迁移到下一个版本，由 `#[migrate]` 声明的函数实现
+    fn migrate(&self, from: u32, _args: Value) -> Result<Value, ActionError> {
+        Err(ActionError::MigrateActionError(from, self.version()))
+    }
+    /// 校验卡片参数，点亮与运行前调用，默认不做校验
+    fn validate(&self, _args: &Data) -> Result<(), ActionError> {
+        Ok(())
+    }
     fn lit(&self, name: String, args: Data) -> Result<String, ActionError> {
+        self.validate(&args)?;
         let action = self.get_action(name, args);
         Application::lit_action(action.clone())?;
         Ok(action.id)
@@ -12,5 +12,8 @@ pub enum ActionEntry {
         uid: String,
         r#type: String,
         data: Data,
+        /// 保存工作流时参数的版本，未记录的旧工作流视为版本 1
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        version: Option<u32>,
     },
 }
\ No newline at end of file
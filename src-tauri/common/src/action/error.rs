@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::action::rule::FieldError;
+
 #[derive(Debug, Error)]
 pub enum ActionError {
     #[error("Failed to light up action card: {0}")]
@@ -8,4 +10,10 @@ pub enum ActionError {
     RunActionCardError(String),
     #[error("Failed to remove action {0}: {1}")]
     RemoveActionError(String,String),
+    #[error("Invalid arguments: {}", .0.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; "))]
+    InvalidArgsError(Vec<FieldError>),
+    #[error("No migration declared from version {0}, the current version is {1}")]
+    MigrateActionError(u32, u32),
+    #[error("Card was lit with version {0}, newer than the installed version {1}")]
+    UnsupportedVersionError(u32, u32),
 }
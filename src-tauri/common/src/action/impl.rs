@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fs::write};
+use std::{collections::HashMap, io};
 
 use serde_json::Value;
 
@@ -8,6 +8,7 @@ use crate::{
         manager::ActionManager,
     },
     application::Application,
+    utils::update_file,
 };
 
 impl Action {
@@ -26,6 +27,7 @@ impl Action {
                     uid,
                     r#type: ty,
                     data,
+                    version,
                 } => Some((
                     key.clone(),
                     Action {
@@ -34,6 +36,7 @@ impl Action {
                         r#type: ty.to_string(),
                         data: data.clone(),
                         plug: Value::Null,
+                        version: version.unwrap_or(1),
                     },
                 )),
             })
@@ -74,22 +77,23 @@ impl Action {
     //         action_type
     //     )))
     // }
+    /// 删除卡片，与点亮一样按原始 JSON 改写，不会丢失无法解析的卡片
     pub fn remove(id: &str) -> Result<(), ActionError> {
-        let mut action_list = Application::get_action_list();
-        let index = action_list.iter().position(|trigger| trigger.id == id);
-        if index.is_none() {
-            return Err(ActionError::RemoveActionError(
-                id.to_string(),
-                "not found".to_string(),
-            ));
-        }
-        let index = index.unwrap();
-        action_list.remove(index);
-        let content = serde_json::to_string(&action_list)
-            .map_err(|e| ActionError::RemoveActionError(id.to_string(), e.to_string()))?;
         let path = Application::get_action_file();
-        write(path, content)
-            .map_err(|e| ActionError::RemoveActionError(id.to_string(), e.to_string()))?;
-        Ok(())
+        let error = |e: io::Error| ActionError::RemoveActionError(id.to_string(), e.to_string());
+        update_file(&path, |content| {
+            let mut action_list: Vec<Value> = match content.trim() {
+                "" => vec![],
+                content => serde_json::from_str(content)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            };
+            let index = action_list
+                .iter()
+                .position(|action| action.get("id").and_then(Value::as_str) == Some(id))
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "not found"))?;
+            action_list.remove(index);
+            Ok((serde_json::to_string(&action_list)?, ()))
+        })
+        .map_err(error)
     }
 }
@@ -1,6 +1,8 @@
-use std::{fs::{exists, read_to_string, write}, path::PathBuf};
+use std::{fs::{exists, read_to_string, write}, io, path::PathBuf};
 
-use crate::{action::{error::ActionError, Action}, application::Application};
+use serde_json::Value;
+
+use crate::{action::{error::ActionError, Action}, application::Application, utils::update_file};
 
 pub trait ActionManager {
     fn get_action_file() -> PathBuf;
@@ -19,25 +21,44 @@ impl ActionManager for Application {
             write(path, "[]").unwrap();
             return vec![];
         }
-        let result = read_to_string(path).unwrap();
-        let result: Vec<Action> = serde_json::from_str(&result).unwrap();
+        let Ok(result) = read_to_string(path) else {
+            return vec![];
+        };
+        let result: Vec<Value> = serde_json::from_str(&result).unwrap_or_default();
+        // 单张卡片损坏时跳过，不影响其余卡片
         result
+            .into_iter()
+            .filter_map(|action| match serde_json::from_value::<Action>(action) {
+                Ok(action) => Some(action),
+                Err(e) => {
+                    log::error!("Failed to load lit action: {}", e);
+                    None
+                }
+            })
+            .collect()
     }
     /// 点亮一个action，如果action已经存在，则更新
+    ///
+    /// 按原始 JSON 改写，无法解析的卡片原样保留，文件本身无法解析时不写入
     fn lit_action(action: Action) -> Result<(), ActionError> {
         let path = Self::get_action_file();
-        let mut action_list = Application::get_action_list();
-        let index = action_list
-            .iter()
-            .position(|current_action| current_action.id == action.id);
-        if index.is_some() {
-            action_list[index.unwrap()] = action;
-        } else {
-            action_list.push(action);
-        }
-        let content = serde_json::to_string(&action_list)
+        let value = serde_json::to_value(&action)
             .map_err(|e| ActionError::LitActionCardError(e.to_string()))?;
-        write(path, content).map_err(|e| ActionError::LitActionCardError(e.to_string()))?;
-        Ok(())
+        update_file(&path, |content| {
+            let mut action_list: Vec<Value> = match content.trim() {
+                "" => vec![],
+                content => serde_json::from_str(content)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            };
+            match action_list
+                .iter_mut()
+                .find(|current| current.get("id").and_then(Value::as_str) == Some(&action.id))
+            {
+                Some(current) => *current = value,
+                None => action_list.push(value),
+            }
+            Ok((serde_json::to_string(&action_list)?, ()))
+        })
+        .map_err(|e| ActionError::LitActionCardError(e.to_string()))
     }
 }
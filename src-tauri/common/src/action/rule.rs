@@ -0,0 +1,278 @@
+//! 参数的校验规则，由 `#[validate(...)]` 生成，在点亮与运行 action 时校验
+
+use std::fmt::Display;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+
+use crate::{
+    action::error::ActionError,
+    ty::{Data, type_convert::Plug},
+};
+
+/// 单个参数的校验规则，与前端生成的校验器保持一致
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValidateRule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub len: Option<Len>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub url: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub one_of: Option<Vec<Value>>,
+}
+
+/// 字符串的字符数或列表的长度范围
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Len {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<usize>,
+}
+
+/// 某个参数未通过校验的原因
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// 检查正则表达式能否编译，供宏在编译期报告
+pub fn check_pattern(pattern: &str) -> Result<(), String> {
+    Regex::new(pattern).map(|_| ()).map_err(|e| e.to_string())
+}
+
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(str) => str.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn same(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::String(a), Value::String(b)) => a == b,
+        _ => match (number(a), number(b)) {
+            (Some(a), Some(b)) => a == b,
+            _ => a == b,
+        },
+    }
+}
+
+impl ValidateRule {
+    /// 校验单个值，空值与引用上游输出的值不做校验
+    pub fn check(&self, value: &Value) -> Result<(), String> {
+        if value.is_null() || serde_json::from_value::<Plug>(value.clone()).is_ok() {
+            return Ok(());
+        }
+        if self.min.is_some() || self.max.is_some() {
+            let Some(number) = number(value) else {
+                return Err(String::from("expected a number"));
+            };
+            if let Some(min) = self.min.filter(|min| number < *min) {
+                return Err(format!("must be at least {}", min));
+            }
+            if let Some(max) = self.max.filter(|max| number > *max) {
+                return Err(format!("must be at most {}", max));
+            }
+        }
+        if let Some(Len { min, max }) = &self.len {
+            let len = match value {
+                Value::String(str) => str.chars().count(),
+                Value::Array(array) => array.len(),
+                _ => return Err(String::from("expected a string or a list")),
+            };
+            if let Some(min) = min.filter(|min| len < *min) {
+                return Err(format!("length must be at least {}", min));
+            }
+            if let Some(max) = max.filter(|max| len > *max) {
+                return Err(format!("length must be at most {}", max));
+            }
+        }
+        if self.regex.is_some() || self.url {
+            let Value::String(str) = value else {
+                return Err(String::from("expected a string"));
+            };
+            if let Some(pattern) = &self.regex {
+                let regex = Regex::new(pattern).map_err(|e| e.to_string())?;
+                if !regex.is_match(str) {
+                    return Err(format!("does not match `{}`", pattern));
+                }
+            }
+            if self.url && Url::parse(str).is_err() {
+                return Err(String::from("must be a valid URL"));
+            }
+        }
+        if let Some(one_of) = &self.one_of
+            && !one_of.iter().any(|item| same(item, value))
+        {
+            let items = one_of
+                .iter()
+                .map(|item| item.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(format!("must be one of {}", items));
+        }
+        Ok(())
+    }
+}
+
+/// action 元信息中参数的校验规则
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParamRule {
+    pub key: String,
+    #[serde(default)]
+    pub validate: Option<ValidateRule>,
+}
+
+/// 从 `#[action]` 生成的元信息中取出参数的校验规则
+pub fn param_rules(meta: &Value) -> Vec<ParamRule> {
+    meta.get("params")
+        .and_then(|params| serde_json::from_value(params.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// 按规则校验卡片参数，收集所有字段的错误
+pub fn validate_args(rules: &[ParamRule], args: &Data) -> Result<(), ActionError> {
+    let Data::Json(args) = args else {
+        return Ok(());
+    };
+    let errors = rules
+        .iter()
+        .filter_map(|ParamRule { key, validate }| {
+            let value = args.get(key)?;
+            let message = validate.as_ref()?.check(value).err()?;
+            Some(FieldError {
+                field: key.clone(),
+                message,
+            })
+        })
+        .collect::<Vec<_>>();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(ActionError::InvalidArgsError(errors))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn rule(rule: Value) -> ValidateRule {
+        serde_json::from_value(rule).unwrap()
+    }
+
+    #[test]
+    fn checks_number_range() {
+        let rule = rule(json!({ "min": 1, "max": 10 }));
+        assert!(rule.check(&json!(1)).is_ok());
+        assert!(rule.check(&json!("10")).is_ok());
+        assert_eq!(rule.check(&json!(0)).unwrap_err(), "must be at least 1");
+        assert_eq!(rule.check(&json!(10.5)).unwrap_err(), "must be at most 10");
+        assert_eq!(rule.check(&json!("ten")).unwrap_err(), "expected a number");
+    }
+
+    #[test]
+    fn checks_length() {
+        let rule = rule(json!({ "len": { "min": 2, "max": 3 } }));
+        assert!(rule.check(&json!("雏菊")).is_ok());
+        assert!(rule.check(&json!([1, 2, 3])).is_ok());
+        assert_eq!(
+            rule.check(&json!("a")).unwrap_err(),
+            "length must be at least 2"
+        );
+        assert_eq!(
+            rule.check(&json!([1, 2, 3, 4])).unwrap_err(),
+            "length must be at most 3"
+        );
+        assert_eq!(
+            rule.check(&json!(12)).unwrap_err(),
+            "expected a string or a list"
+        );
+    }
+
+    #[test]
+    fn checks_regex_and_url() {
+        let both = rule(json!({ "regex": "^[a-z]+$", "url": true }));
+        assert!(both.check(&json!("daisy")).is_err());
+        assert_eq!(
+            both.check(&json!("https://example.com")).unwrap_err(),
+            "does not match `^[a-z]+$`"
+        );
+        let url = rule(json!({ "url": true }));
+        assert!(url.check(&json!("https://example.com/a?b=c")).is_ok());
+        assert_eq!(
+            url.check(&json!("example")).unwrap_err(),
+            "must be a valid URL"
+        );
+        assert_eq!(url.check(&json!(1)).unwrap_err(), "expected a string");
+    }
+
+    #[test]
+    fn checks_one_of() {
+        let rule = rule(json!({ "one_of": ["GET", 2] }));
+        assert!(rule.check(&json!("GET")).is_ok());
+        assert!(rule.check(&json!("2")).is_ok());
+        assert_eq!(
+            rule.check(&json!("PUT")).unwrap_err(),
+            "must be one of \"GET\", 2"
+        );
+    }
+
+    #[test]
+    fn skips_empty_and_plugged_values() {
+        let rule = rule(json!({ "min": 1, "regex": "^a" }));
+        assert!(rule.check(&Value::Null).is_ok());
+        assert!(
+            rule.check(&json!({ "type": "Plug", "value": ["fetch", "body"] }))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn collects_errors_of_all_fields() {
+        let meta = json!({
+            "params": [
+                { "key": "count", "validate": { "min": 1 } },
+                { "key": "name", "validate": { "len": { "max": 2 } } },
+                { "key": "body" },
+            ]
+        });
+        let rules = param_rules(&meta);
+        let args = Data::Json(
+            json!({ "count": 0, "name": "daisy", "body": "" })
+                .as_object()
+                .unwrap()
+                .clone(),
+        );
+        let Err(ActionError::InvalidArgsError(errors)) = validate_args(&rules, &args) else {
+            panic!("expected invalid args");
+        };
+        let fields = errors
+            .iter()
+            .map(|error| error.field.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(fields, ["count", "name"]);
+
+        let args = Data::Json(json!({ "count": 3 }).as_object().unwrap().clone());
+        assert!(validate_args(&rules, &args).is_ok());
+    }
+}
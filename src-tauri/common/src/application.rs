@@ -12,7 +12,7 @@ impl Application {
     pub fn get_path(path: &str) -> PathBuf {
         let is_dir = path.ends_with(".d") || !path.contains(".");
         let path = PathBuf::from("C:\\ProgramData").join(APP_NAME).join(path);
-        println!("{}", path.display());
+        log::debug!("{}", path.display());
         if !exists(&path).unwrap() {
             if is_dir {
                 create_dir_all(&path).unwrap();
@@ -0,0 +1,55 @@
+use std::fs::read_to_string;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::application::Application;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MaxToken {
+    Default,
+    Value(u32),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiConfig {
+    pub api_key: String,
+    pub model: String,
+    /// OpenAI 兼容接口的地址，请求发送到其下的 /chat/completions
+    #[serde(default = "default_base_url")]
+    pub base_url: String,
+    pub temperature: f32,
+    pub max_tokens: MaxToken,
+    pub top_p: f32,
+    pub frequency_penalty: f32,
+}
+
+fn default_base_url() -> String {
+    "https://api.openai.com/v1".to_string()
+}
+
+impl Default for AiConfig {
+    fn default() -> Self {
+        AiConfig {
+            api_key: "".to_string(),
+            model: "".to_string(),
+            base_url: default_base_url(),
+            temperature: 0.7,
+            max_tokens: MaxToken::Default,
+            top_p: 1.0,
+            frequency_penalty: 0.0,
+        }
+    }
+}
+
+impl AiConfig {
+    /// 从 config.json 中读取 AI 配置，action 无法访问应用的 Config，因此单独读取
+    pub fn load() -> Self {
+        read_to_string(Application::get_path("config.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+            .and_then(|mut config| serde_json::from_value(config["aiConfig"].take()).ok())
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,93 @@
+use std::{future::Future, pin::Pin, sync::OnceLock, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+/// 需要由 UI 进程完成的桌面交互，服务进程没有桌面会话
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DesktopRequest {
+    Notify {
+        title: String,
+        body: String,
+    },
+    /// 剪贴板中没有文本时返回 null
+    ReadClipboardText,
+    WriteClipboardText {
+        text: String,
+    },
+    /// 返回 ClipboardImage，剪贴板中没有图片时返回 null
+    ReadClipboardImage,
+    WriteClipboardImage {
+        image: ClipboardImage,
+    },
+    /// 网址使用默认浏览器打开，其余视为文件路径
+    Open {
+        target: String,
+    },
+    /// 弹出输入框并等待用户输入，取消时返回 null
+    Prompt {
+        title: String,
+        message: String,
+        default: Option<String>,
+        /// 超时后由 UI 进程关闭输入框（毫秒）
+        timeout: Option<u64>,
+    },
+}
+
+/// 剪贴板中的图片，rgba 为 Base64 编码的像素
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardImage {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: String,
+}
+
+#[derive(Debug, Error)]
+pub enum DesktopError {
+    #[error("No UI process is connected")]
+    NotConnectedError,
+    #[error("Timed out waiting for the UI process")]
+    TimeoutError,
+    #[error("Desktop request failed: {0}")]
+    RequestError(String),
+}
+
+pub type DesktopFuture = Pin<Box<dyn Future<Output = Result<Value, DesktopError>> + Send>>;
+pub type DesktopHandler = fn(DesktopRequest) -> DesktopFuture;
+
+static HANDLER: OnceLock<DesktopHandler> = OnceLock::new();
+
+/// 注册桌面请求的处理方式
+///
+/// UI 进程直接处理，服务进程通过 IPC 转发给 UI 进程
+pub fn set_desktop_handler(handler: DesktopHandler) {
+    let _ = HANDLER.set(handler);
+}
+
+/// 交给宿主注册的处理方式，宿主尚未注册时返回 NotConnectedError
+///
+/// 动作包动态库中的 common 是独立的副本，宿主将该函数传给动态库，请求因此回到宿主中处理
+pub fn host_handler(request: DesktopRequest) -> DesktopFuture {
+    match HANDLER.get() {
+        Some(handler) => handler(request),
+        None => Box::pin(async { Err(DesktopError::NotConnectedError) }),
+    }
+}
+
+/// 由宿主在调用动作包前传入 [`host_handler`]，动态库重载后需要重新传入
+///
+/// 每个链接了 common 的动态库都导出自己的这一函数
+#[unsafe(no_mangle)]
+pub fn daisy_set_desktop_handler(handler: DesktopHandler) {
+    set_desktop_handler(handler);
+}
+
+/// 发送桌面请求并等待结果
+pub async fn request(request: DesktopRequest, timeout: Duration) -> Result<Value, DesktopError> {
+    let handler = HANDLER.get().ok_or(DesktopError::NotConnectedError)?;
+    tokio::time::timeout(timeout, handler(request))
+        .await
+        .map_err(|_| DesktopError::TimeoutError)?
+}
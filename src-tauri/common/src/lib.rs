@@ -1,6 +1,10 @@
 pub mod action;
 pub mod application;
+pub mod config;
+pub mod desktop;
 pub mod executor;
+pub mod plugin;
+pub mod secret;
 pub mod ty;
 pub mod utils;
 
@@ -0,0 +1,201 @@
+//! 以动态库形式分发的动作包与宿主之间的 C ABI 约定
+//!
+//! 所有数据均以 JSON 字符串跨越边界，字符串由插件分配并由插件释放，
+//! 因此插件与宿主可以使用不同版本的编译器构建
+
+use std::{
+    error::Error,
+    ffi::{CStr, CString, c_char},
+    panic::{AssertUnwindSafe, catch_unwind},
+    sync::Mutex,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::runtime::{Builder, Runtime};
+
+use crate::ty::{CardResult, Data};
+
+/// 宿主支持的插件 ABI 版本，导出函数或数据格式发生不兼容的变化时递增
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+pub const ABI_VERSION_SYMBOL: &[u8] = b"daisy_plugin_abi_version\0";
+pub const MANIFEST_SYMBOL: &[u8] = b"daisy_plugin_manifest\0";
+pub const CALL_SYMBOL: &[u8] = b"daisy_plugin_call\0";
+pub const FREE_SYMBOL: &[u8] = b"daisy_plugin_free\0";
+/// 可选，卸载前关闭插件中复用的运行时
+pub const SHUTDOWN_SYMBOL: &[u8] = b"daisy_plugin_shutdown\0";
+
+pub type AbiVersionFn = unsafe extern "C" fn() -> u32;
+pub type ManifestFn = unsafe extern "C" fn() -> *mut c_char;
+pub type CallFn = unsafe extern "C" fn(action: *const c_char, args: *const c_char) -> *mut c_char;
+pub type FreeFn = unsafe extern "C" fn(ptr: *mut c_char);
+pub type ShutdownFn = unsafe extern "C" fn();
+
+/// 插件自述的清单
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginManifest {
+    pub name: String,
+    pub version: String,
+    /// 构建插件时使用的 ABI 版本
+    pub abi_version: u32,
+    pub actions: Vec<PluginActionMeta>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginActionMeta {
+    pub action_type: String,
+    /// 由 `#[action]` 生成的标题、描述与参数信息
+    pub meta: Value,
+    /// 返回的 `#[result]` 枚举的分支，编辑器据此绘制卡片的输出端口
+    #[serde(default)]
+    pub branches: Value,
+}
+
+/// 调用 action 的结果
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum PluginCallResult {
+    Ok { variant: String, data: Data },
+    Err { message: String },
+}
+
+impl From<Result<CardResult, Box<dyn Error>>> for PluginCallResult {
+    fn from(result: Result<CardResult, Box<dyn Error>>) -> Self {
+        match result {
+            Ok(CardResult { variant, data }) => PluginCallResult::Ok {
+                variant: variant.to_string(),
+                data,
+            },
+            Err(e) => PluginCallResult::Err {
+                message: e.to_string(),
+            },
+        }
+    }
+}
+
+/// 序列化为由插件持有的 C 字符串，需要通过 `daisy_plugin_free` 释放
+pub fn to_c_string<T: Serialize>(value: &T) -> *mut c_char {
+    let json = serde_json::to_string(value).unwrap_or_else(|e| {
+        serde_json::to_string(&PluginCallResult::Err {
+            message: e.to_string(),
+        })
+        .unwrap_or_default()
+    });
+    // JSON 中的 NUL 只会以 \u0000 的形式出现
+    CString::new(json).unwrap_or_default().into_raw()
+}
+
+/// 释放由 `to_c_string` 分配的字符串
+///
+/// # Safety
+/// `ptr` 必须来自同一个插件的 `to_c_string`，且只能释放一次
+pub unsafe fn free_c_string(ptr: *mut c_char) {
+    if !ptr.is_null() {
+        drop(unsafe { CString::from_raw(ptr) });
+    }
+}
+
+/// 空闲的运行时，调用结束后放回供之后的调用复用
+static RUNTIMES: Mutex<Vec<Runtime>> = Mutex::new(Vec::new());
+
+/// 在插件内执行异步 action，宿主的运行时无法跨越动态库边界
+///
+/// 每个调用独占一个单线程运行时，结束后放回复用，并发的调用各自使用一个。
+/// 空闲的运行时仍可能留有阻塞线程池中的线程，卸载动态库前需调用 [`daisy_shutdown_runtimes`]
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let runtime = RUNTIMES.lock().unwrap().pop().unwrap_or_else(|| {
+        Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to create runtime for plugin")
+    });
+    let output = runtime.block_on(future);
+    RUNTIMES.lock().unwrap().push(runtime);
+    output
+}
+
+/// 关闭复用的运行时并等待其中的线程退出，之后的调用会创建新的运行时
+///
+/// 由宿主在卸载动态库前调用，此时不能有正在执行的调用。
+/// 每个链接了 common 的动态库都导出自己的这一函数
+#[unsafe(no_mangle)]
+pub fn daisy_shutdown_runtimes() {
+    let runtimes = std::mem::take(&mut *RUNTIMES.lock().unwrap());
+    drop(runtimes);
+}
+
+/// 处理 `daisy_plugin_call`，`dispatch` 找不到 action 时返回 None
+///
+/// # Safety
+/// `action` 与 `args` 必须是有效的 C 字符串
+pub unsafe fn handle_call(
+    action: *const c_char,
+    args: *const c_char,
+    dispatch: impl FnOnce(&str, Value) -> Option<Result<CardResult, Box<dyn Error>>>,
+) -> *mut c_char {
+    let action = unsafe { CStr::from_ptr(action) }.to_string_lossy();
+    let args = unsafe { CStr::from_ptr(args) }.to_string_lossy();
+    let result = match serde_json::from_str::<Value>(&args) {
+        Ok(args) => {
+            // panic 不能跨越 extern "C" 函数展开
+            match catch_unwind(AssertUnwindSafe(|| dispatch(&action, args))) {
+                Ok(Some(result)) => result.into(),
+                Ok(None) => PluginCallResult::Err {
+                    message: format!("Action {} not found in plugin", action),
+                },
+                Err(_) => PluginCallResult::Err {
+                    message: format!("Action {} panicked", action),
+                },
+            }
+        }
+        Err(e) => PluginCallResult::Err {
+            message: e.to_string(),
+        },
+    };
+    to_c_string(&result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(
+        action: &str,
+        args: &str,
+        dispatch: impl FnOnce(&str, Value) -> Option<Result<CardResult, Box<dyn Error>>>,
+    ) -> PluginCallResult {
+        let (action, args) = (CString::new(action).unwrap(), CString::new(args).unwrap());
+        let result = unsafe { handle_call(action.as_ptr(), args.as_ptr(), dispatch) };
+        let json = unsafe { CStr::from_ptr(result) }.to_string_lossy().to_string();
+        unsafe { free_c_string(result) };
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn catches_panics_in_actions() {
+        let result = call("boom_action", "{}", |_, _| panic!("boom"));
+        assert!(matches!(
+            result,
+            PluginCallResult::Err { message } if message == "Action boom_action panicked"
+        ));
+
+        let result = call("missing_action", "{}", |_, _| None);
+        assert!(matches!(
+            result,
+            PluginCallResult::Err { message } if message.contains("not found")
+        ));
+
+        let result = call("echo_action", "not json", |_, _| unreachable!());
+        assert!(matches!(result, PluginCallResult::Err { .. }));
+    }
+
+    #[test]
+    fn reuses_runtimes_until_shutdown() {
+        assert_eq!(block_on(async { 1 }), 1);
+        assert_eq!(block_on(async { 2 }), 2);
+        assert_eq!(RUNTIMES.lock().unwrap().len(), 1);
+        daisy_shutdown_runtimes();
+        assert!(RUNTIMES.lock().unwrap().is_empty());
+    }
+}
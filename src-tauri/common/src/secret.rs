@@ -0,0 +1,84 @@
+use std::{collections::HashMap, fs::read_to_string};
+
+use thiserror::Error;
+
+use crate::application::Application;
+
+/// 引用环境变量，如 `env:GITHUB_TOKEN`
+const ENV_PREFIX: &str = "env:";
+/// 引用数据目录下 secrets.json 中的条目，如 `secret:smtp_password`
+const SECRET_PREFIX: &str = "secret:";
+/// 允许以 `env:` 引用的环境变量，以逗号分隔，由启动服务的用户设置
+pub const ALLOWED_ENV: &str = "DAISY_SECRET_ENV";
+
+#[derive(Debug, Error)]
+pub enum SecretError {
+    #[error("Environment variable {0} is not set")]
+    EnvNotFoundError(String),
+    #[error("Environment variable {0} is not listed in DAISY_SECRET_ENV")]
+    EnvNotAllowedError(String),
+    #[error("Secret {0} is not defined")]
+    SecretNotFoundError(String),
+    #[error("Failed to read secrets: {0}")]
+    ReadSecretsError(String),
+}
+
+fn env_allowed(name: &str) -> bool {
+    std::env::var(ALLOWED_ENV)
+        .is_ok_and(|allowed| allowed.split(',').any(|allowed| allowed.trim() == name))
+}
+
+/// 解析密钥引用，使工作流中不必保存明文密码与令牌
+///
+/// 不带前缀的值按原样返回，`env:` 只能引用 `DAISY_SECRET_ENV` 中列出的环境变量
+pub fn resolve_secret(value: &str) -> Result<String, SecretError> {
+    if let Some(name) = value.strip_prefix(ENV_PREFIX) {
+        let name = name.trim();
+        // 工作流不能借此读取服务的任意环境变量
+        if !env_allowed(name) {
+            return Err(SecretError::EnvNotAllowedError(name.to_string()));
+        }
+        return std::env::var(name).map_err(|_| SecretError::EnvNotFoundError(name.to_string()));
+    }
+    if let Some(name) = value.strip_prefix(SECRET_PREFIX) {
+        let path = Application::get_path("secrets.json");
+        let content =
+            read_to_string(path).map_err(|e| SecretError::ReadSecretsError(e.to_string()))?;
+        let secrets: HashMap<String, String> = if content.trim().is_empty() {
+            HashMap::new()
+        } else {
+            serde_json::from_str(&content)
+                .map_err(|e| SecretError::ReadSecretsError(e.to_string()))?
+        };
+        return secrets
+            .get(name.trim())
+            .cloned()
+            .ok_or_else(|| SecretError::SecretNotFoundError(name.trim().to_string()));
+    }
+    Ok(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_allowed_env_only() {
+        // 这些变量只在本测试中使用
+        unsafe {
+            std::env::set_var(ALLOWED_ENV, "DAISY_TEST_TOKEN, DAISY_TEST_MISSING");
+            std::env::set_var("DAISY_TEST_TOKEN", "token");
+            std::env::set_var("DAISY_TEST_HIDDEN", "hidden");
+        }
+        assert_eq!(resolve_secret("env: DAISY_TEST_TOKEN").unwrap(), "token");
+        assert!(matches!(
+            resolve_secret("env:DAISY_TEST_HIDDEN"),
+            Err(SecretError::EnvNotAllowedError(name)) if name == "DAISY_TEST_HIDDEN"
+        ));
+        assert!(matches!(
+            resolve_secret("env:DAISY_TEST_MISSING"),
+            Err(SecretError::EnvNotFoundError(_))
+        ));
+        assert_eq!(resolve_secret("plain").unwrap(), "plain");
+    }
+}
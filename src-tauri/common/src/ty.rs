@@ -20,7 +20,7 @@ impl Display for Data {
     }
 }
 
-use std::fmt::Display;
+use std::{collections::HashMap, fmt::Display};
 
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
@@ -38,6 +38,45 @@ impl std::fmt::Display for ErrorWrap {
 }
 impl std::error::Error for ErrorWrap {}
 
+/// 上游卡片的输出，以卡片 id 为键
+pub type Context = HashMap<String, Data>;
+
+/// action 参数中用于传递上游输出的保留键，声明为 `Context` 类型的参数会从该键读取
+pub const CONTEXT_KEY: &str = "$context";
+
+/// 卡片运行失败时进入的分支，例如插件 worker 崩溃，未连接时工作流结束
+pub const ERROR_VARIANT: &str = "error";
+
+/// action 输出中记录 token 用量的保留键，运行时会将其取出并累加到运行记录中
+pub const USAGE_KEY: &str = "\0usage";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub struct TokenUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+}
+
+impl TokenUsage {
+    pub fn add(&mut self, other: &TokenUsage) {
+        self.prompt_tokens += other.prompt_tokens;
+        self.completion_tokens += other.completion_tokens;
+        self.total_tokens += other.total_tokens;
+    }
+}
+
+impl Data {
+    /// 取出输出中的 token 用量
+    pub fn take_usage(&mut self) -> Option<TokenUsage> {
+        let usage = match self {
+            Data::Json(map) => map.remove(USAGE_KEY)?,
+            Data::Any(Value::Object(map)) => map.remove(USAGE_KEY)?,
+            _ => return None,
+        };
+        serde_json::from_value(usage).ok()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CardResult {
     pub variant: &'static str,
@@ -10,6 +10,20 @@ use serde_json::{Map, Value, json};
 use crate::ty::{Data, error::TypeConvertError};
 
 impl Data {
+    /// 将 JSON 值按其形状转换为对应的 Data
+    pub fn from_value(value: Value) -> Self {
+        match value {
+            Value::Null => Data::Null,
+            Value::Bool(val) => Data::Bool(val),
+            Value::Number(val) => match val.as_i64() {
+                Some(val) => Data::Int(val),
+                None => Data::Float(val.as_f64().unwrap_or_default()),
+            },
+            Value::String(val) => Data::String(val),
+            Value::Array(val) => Data::Vec(val),
+            Value::Object(val) => Data::Json(val),
+        }
+    }
     pub fn value(self) -> Value {
         match self {
             Data::Any(val) => val,
@@ -123,10 +137,10 @@ pub fn parse_data(
     context: &HashMap<String, Data>,
     card_data: Data,
 ) -> Result<Data, TypeConvertError> {
-    let Ok(data) = card_data.r#as::<serde_json::Map<String, Value>>() else {
+    let Ok(mut data) = card_data.r#as::<serde_json::Map<String, Value>>() else {
         return Ok(card_data);
     };
-    for ref mut val in data.values() {
+    for val in data.values_mut() {
         let res = serde_json::from_value::<Plug>(val.clone());
         let Ok(plug) = res else {
             continue;
@@ -153,7 +167,7 @@ pub fn parse_data(
                 )
             })?;
         }
-        *val = &current_value;
+        *val = current_value.clone();
     }
     Ok(Data::Json(data))
 }
@@ -5,7 +5,12 @@ macro_rules! this_file {
     };
 }
 
-use std::path::PathBuf;
+use std::{
+    ffi::OsString,
+    fs::{self, File, OpenOptions},
+    io,
+    path::{Path, PathBuf},
+};
 
 pub fn get_current_binary() -> PathBuf {
     std::env::current_exe().unwrap()
@@ -29,3 +34,101 @@ pub fn to_upper_camel_case(s: &str) -> String {
         })
         .collect::<String>()
 }
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = OsString::from(path.as_os_str());
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// 进程间互斥地访问 path，释放返回的文件即解锁
+///
+/// 锁加在同目录的 `.lock` 文件上，数据文件被替换后锁依然有效
+pub fn lock_file(path: &Path) -> io::Result<File> {
+    let lock = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(with_suffix(path, ".lock"))?;
+    lock.lock()?;
+    Ok(lock)
+}
+
+/// 在文件锁中读取并改写文件，文件不存在时读到空字符串
+///
+/// 新内容先写入临时文件再替换原文件，不加锁的读取方不会读到写了一半的内容
+pub fn update_file<R>(
+    path: &Path,
+    update: impl FnOnce(String) -> io::Result<(String, R)>,
+) -> io::Result<R> {
+    let _lock = lock_file(path)?;
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+    let (content, result) = update(content)?;
+    let temp = with_suffix(path, ".tmp");
+    fs::write(&temp, content)?;
+    fs::rename(&temp, path)?;
+    Ok(result)
+}
+
+/// 按 `a.b.0.c` 形式的路径读取值，数字段同时可作为数组下标
+pub fn lookup<'a>(value: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    path.split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(value, |value, segment| match value {
+            serde_json::Value::Object(map) => map.get(segment),
+            serde_json::Value::Array(list) => {
+                segment.parse::<usize>().ok().and_then(|i| list.get(i))
+            }
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn looks_up_dotted_paths() {
+        let value = json!({ "a": { "b": [{ "c": 1 }] } });
+        assert_eq!(lookup(&value, "a.b.0.c"), Some(&json!(1)));
+        assert_eq!(lookup(&value, ""), Some(&value));
+        assert_eq!(lookup(&value, "a.b.1"), None);
+        assert_eq!(lookup(&value, "a.x"), None);
+    }
+
+    #[test]
+    fn concurrent_updates_are_not_lost() {
+        let dir = std::env::temp_dir().join(format!("daisy-utils-{}", get_uid()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("counter");
+
+        let handles = (0..8)
+            .map(|_| {
+                let path = path.clone();
+                thread::spawn(move || {
+                    for _ in 0..25 {
+                        update_file(&path, |content| {
+                            let count = content.parse::<u32>().unwrap_or(0);
+                            Ok(((count + 1).to_string(), ()))
+                        })
+                        .unwrap();
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "200");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
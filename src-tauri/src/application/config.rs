@@ -1,50 +1,110 @@
 use std::{
+    collections::HashMap,
     fs::{self, read_to_string},
     path::PathBuf,
 };
 
+pub use common::config::{AiConfig, MaxToken};
 use common::{application::Application, ty::error::TypeConvertError};
 use serde::{Deserialize, Serialize};
 
+impl Default for AppConfig {
+    fn default() -> Self {
+        AppConfig {}
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum MaxToken {
-    Default,
-    Value(u32),
+pub struct AppConfig {}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum LogRotation {
+    Never,
+    Hourly,
+    Daily,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct AiConfig {
-    pub api_key: String,
-    pub model: String,
-    pub temperature: f32,
-    pub max_tokens: MaxToken,
-    pub top_p: f32,
-    pub frequency_penalty: f32,
+pub struct LogConfig {
+    pub level: LogLevel,
+    /// 按模块覆盖日志等级，键为 log target 的前缀
+    #[serde(default)]
+    pub modules: HashMap<String, LogLevel>,
+    /// 单个日志文件的最大体积（字节），超出后滚动
+    pub max_file_size: u64,
+    pub rotation: LogRotation,
+    /// 滚动后的日志文件保留天数
+    pub retention_days: u32,
 }
 
-impl Default for AppConfig {
+impl Default for LogConfig {
     fn default() -> Self {
-        AppConfig {}
+        LogConfig {
+            level: LogLevel::Info,
+            modules: HashMap::new(),
+            max_file_size: 10 * 1024 * 1024,
+            rotation: LogRotation::Daily,
+            retention_days: 7,
+        }
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AppConfig {}
+#[serde(rename_all = "camelCase")]
+pub struct MetricsConfig {
+    /// 是否在本机提供 Prometheus 文本格式的抓取接口
+    pub prometheus: bool,
+    pub port: u16,
+}
 
-impl Default for AiConfig {
+impl Default for MetricsConfig {
     fn default() -> Self {
-        AiConfig {
-            api_key: "".to_string(),
-            model: "".to_string(),
-            temperature: 0.7,
-            max_tokens: MaxToken::Default,
-            top_p: 1.0,
-            frequency_penalty: 0.0,
+        MetricsConfig {
+            prometheus: false,
+            port: 9464,
         }
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginConfig {
+    /// 在独立的 worker 进程中运行插件，插件崩溃时只有正在运行的卡片失败
+    pub isolated: bool,
+    /// 单次调用的超时时间（毫秒），超时后 worker 会被重启
+    pub call_timeout: u64,
+    /// 检查 worker 是否存活的间隔（毫秒）
+    pub ping_interval: u64,
+}
+
+impl Default for PluginConfig {
+    fn default() -> Self {
+        PluginConfig {
+            isolated: false,
+            call_timeout: 60_000,
+            ping_interval: 10_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DevConfig {
+    /// 动作包重新构建后，服务在两次工作流运行之间重新加载
+    pub hot_reload: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Config {
@@ -52,6 +112,14 @@ pub struct Config {
     pub ai_config: AiConfig,
     #[serde(default)]
     pub app_config: AppConfig,
+    #[serde(default)]
+    pub log_config: LogConfig,
+    #[serde(default)]
+    pub metrics_config: MetricsConfig,
+    #[serde(default)]
+    pub plugin_config: PluginConfig,
+    #[serde(default)]
+    pub dev_config: DevConfig,
 }
 
 impl Default for Config {
@@ -59,6 +127,10 @@ impl Default for Config {
         Config {
             ai_config: AiConfig::default(),
             app_config: AppConfig::default(),
+            log_config: LogConfig::default(),
+            metrics_config: MetricsConfig::default(),
+            plugin_config: PluginConfig::default(),
+            dev_config: DevConfig::default(),
         }
     }
 }
@@ -0,0 +1,177 @@
+use std::{collections::HashSet, fs::read_to_string, path::Path};
+
+use aster_loader::get_action_types;
+use common::{
+    action::{entry::ActionEntry, manager::ActionManager, Action},
+    application::Application,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::service::{
+    task::{Setup, SetupManager, Task, TaskManager},
+    trigger::{Trigger, TriggerManager, TRIGGER_TYPES},
+};
+
+use super::error::CliError;
+
+/// 可在不同机器之间迁移的任务包，包含任务及其依赖的 action 与触发器
+#[derive(Deserialize, Serialize, Default)]
+pub struct Bundle {
+    #[serde(default)]
+    pub tasks: Vec<Task>,
+    #[serde(default)]
+    pub actions: Vec<Action>,
+    #[serde(default)]
+    pub triggers: Vec<Trigger>,
+}
+
+#[derive(Serialize)]
+pub struct ImportSummary {
+    pub tasks: usize,
+    pub actions: usize,
+    pub triggers: usize,
+}
+
+fn find_duplicate<'a>(ids: impl Iterator<Item = &'a String>) -> Option<&'a String> {
+    let mut seen = HashSet::new();
+    ids.into_iter().find(|id| !seen.insert(*id))
+}
+
+impl Bundle {
+    pub fn read(path: &Path) -> Result<Self, CliError> {
+        let content = read_to_string(path)
+            .map_err(|e| CliError::ReadBundleError(path.display().to_string(), e.to_string()))?;
+        serde_json::from_str(&content)
+            .map_err(|e| CliError::ReadBundleError(path.display().to_string(), e.to_string()))
+    }
+
+    /// 检查任务包，返回发现的所有问题
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = vec![];
+        let action_types = get_action_types();
+
+        if let Some(id) = find_duplicate(self.tasks.iter().map(|task| &task.id)) {
+            problems.push(format!("duplicate task id {}", id));
+        }
+        if let Some(id) = find_duplicate(self.actions.iter().map(|action| &action.id)) {
+            problems.push(format!("duplicate action id {}", id));
+        }
+        if let Some(id) = find_duplicate(self.triggers.iter().map(|trigger| &trigger.id)) {
+            problems.push(format!("duplicate trigger id {}", id));
+        }
+        for action in &self.actions {
+            if !action_types.contains(&action.r#type.as_str()) {
+                problems.push(format!(
+                    "action {} has unknown type {}",
+                    &action.id, &action.r#type
+                ));
+            }
+        }
+        for trigger in &self.triggers {
+            if !TRIGGER_TYPES.contains(&trigger.r#type.as_str()) {
+                problems.push(format!(
+                    "trigger {} has unknown type {}",
+                    &trigger.id, &trigger.r#type
+                ));
+            }
+        }
+
+        let action_ids: HashSet<String> = self
+            .actions
+            .iter()
+            .map(|action| action.id.clone())
+            .chain(Application::get_action_list().into_iter().map(|action| action.id))
+            .collect();
+        let trigger_ids: HashSet<String> = self
+            .triggers
+            .iter()
+            .map(|trigger| trigger.id.clone())
+            .chain(
+                Application::get_trigger_list()
+                    .into_iter()
+                    .map(|trigger| trigger.id),
+            )
+            .collect();
+
+        for task in &self.tasks {
+            if !task.workflow.contains_key("trigger") {
+                problems.push(format!("task {} has no entry for \"trigger\"", &task.id));
+            }
+            for trigger_id in &task.info.trigger {
+                if !trigger_ids.contains(trigger_id) {
+                    problems.push(format!(
+                        "task {} references missing trigger {}",
+                        &task.id, trigger_id
+                    ));
+                }
+            }
+            for entry in task.workflow.values() {
+                match entry {
+                    ActionEntry::LitRef { id, .. } if !action_ids.contains(id) => {
+                        problems.push(format!(
+                            "task {} references missing action {}",
+                            &task.id, id
+                        ));
+                    }
+                    ActionEntry::Inline { uid, r#type, .. }
+                        if !action_types.contains(&r#type.as_str()) =>
+                    {
+                        problems.push(format!(
+                            "task {} has inline action {} with unknown type {}",
+                            &task.id, uid, r#type
+                        ));
+                    }
+                    _ => (),
+                }
+            }
+        }
+        problems
+    }
+
+    /// 导入任务包，已存在的同 id 条目会被覆盖
+    pub fn import(self) -> Result<ImportSummary, CliError> {
+        let problems = self.validate();
+        if !problems.is_empty() {
+            return Err(CliError::InvalidBundleError(problems.join("\n")));
+        }
+        let summary = ImportSummary {
+            tasks: self.tasks.len(),
+            actions: self.actions.len(),
+            triggers: self.triggers.len(),
+        };
+
+        for action in self.actions {
+            Application::lit_action(action).map_err(|e| CliError::ActionError(e.to_string()))?;
+        }
+
+        let mut trigger_list = Application::get_trigger_list();
+        for trigger in self.triggers {
+            trigger_list.retain(|current| current.id != trigger.id);
+            trigger_list.push(trigger);
+        }
+        Application::update_trigger_list(&trigger_list)?;
+
+        let mut task_list = Application::get_task_list()?;
+        let mut setup_list = Application::get_setup_list()?;
+        for task in self.tasks {
+            // 为每个触发器登记启动项，调度器据此装配任务
+            for trigger_id in &task.info.trigger {
+                let exists = setup_list
+                    .iter()
+                    .any(|setup| &setup.trigger == trigger_id && setup.task.contains(&task.id));
+                if !exists {
+                    setup_list.push(Setup {
+                        trigger: trigger_id.clone(),
+                        task: vec![task.id.clone()],
+                    });
+                }
+            }
+            task_list.retain(|current| current.id != task.id);
+            task_list.push(task);
+        }
+        Application::update_task_list(&task_list)?;
+        Application::update_setup_list(&setup_list)?;
+
+        Ok(summary)
+    }
+}
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+use crate::service::{error::ServiceError, task::error::TaskError, trigger::error::TriggerError};
+
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error(transparent)]
+    TaskError(#[from] TaskError),
+    #[error(transparent)]
+    TriggerError(#[from] TriggerError),
+    #[error(transparent)]
+    ServiceError(#[from] ServiceError),
+    #[error("{0}")]
+    ActionError(String),
+    #[error("Invalid argument {0}: {1}")]
+    InvalidArgumentError(String, String),
+    #[error("Failed to read bundle {0}: {1}")]
+    ReadBundleError(String, String),
+    #[error("Invalid bundle:\n{0}")]
+    InvalidBundleError(String),
+}
@@ -0,0 +1,490 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs::read_to_string,
+    path::PathBuf,
+    thread,
+    time::Duration,
+};
+
+use aster_loader::{get_action_types, load_lit_actions, ActionProvider};
+use bundle::Bundle;
+use chrono::Local;
+use common::{
+    action::{entry::ActionEntry, Action, ActionIssue, LitAction},
+    application::Application,
+    ty::{type_convert::ToString as _, Data},
+};
+use error::CliError;
+use output::{format_time, print, print_table};
+use serde::Serialize;
+use serde_json::Value;
+use tauri::async_runtime::block_on;
+
+use crate::service::{
+    metrics::MetricsSnapshot,
+    status::{query_service_metrics, query_service_state},
+    task::{
+        error::TaskError,
+        history::{RunHistoryManager, RunRecord},
+        lifecycle::{TaskLifecycleManager, TaskState},
+        Setup, SetupManager, Task, TaskInfo, TaskManager,
+    },
+    trigger::{Trigger, TriggerManager, TRIGGER_TYPES},
+};
+
+pub mod bundle;
+pub mod error;
+pub mod output;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum TaskCommand {
+    /// 列出所有任务
+    List,
+    /// 查看任务详情
+    Show { id: String },
+    /// 创建任务
+    Create {
+        #[arg(long)]
+        name: String,
+        /// 触发器 id，可重复
+        #[arg(long = "trigger", required = true)]
+        triggers: Vec<String>,
+        /// 工作流 JSON 文件
+        #[arg(long)]
+        workflow: PathBuf,
+        #[arg(long, default_value = "")]
+        description: String,
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+    },
+    /// 删除任务
+    Remove { id: String },
+    /// 启用任务
+    Enable { id: String },
+    /// 停用任务
+    Disable { id: String },
+    /// 暂停任务一段时间
+    Pause {
+        id: String,
+        #[arg(long)]
+        minutes: i64,
+    },
+    /// 归档任务
+    Archive { id: String },
+    /// 立即运行任务，不受状态与运行窗口限制
+    Run {
+        id: String,
+        /// 以 `input` 放入 context 的 JSON
+        #[arg(long)]
+        input: Option<String>,
+    },
+    /// 查看运行历史
+    History {
+        id: String,
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// 持续输出新的运行记录
+        #[arg(long, short)]
+        follow: bool,
+    },
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ActionCommand {
+    /// 列出已点亮的 action
+    List,
+    /// 列出可用的 action 类型
+    Types,
+    /// 点亮 action
+    Lit {
+        action_type: String,
+        name: String,
+        /// Data 格式的参数，如 {"type":"String","value":"..."}
+        #[arg(long)]
+        args: String,
+    },
+    /// 删除 action
+    Remove { id: String },
+    /// 运行 action
+    Run { id: String },
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum TriggerCommand {
+    /// 列出已点亮的触发器
+    List,
+    /// 列出可用的触发器类型
+    Types,
+    /// 点亮触发器
+    Lit {
+        trigger_type: String,
+        name: String,
+        /// Data 格式的参数，如 {"type":"String","value":"0 * * * * *"}
+        #[arg(long)]
+        args: String,
+    },
+    /// 删除触发器
+    Remove { id: String },
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum BundleCommand {
+    /// 检查任务包
+    Validate { path: PathBuf },
+    /// 导入任务包
+    Import { path: PathBuf },
+}
+
+#[derive(Serialize)]
+struct Message {
+    message: String,
+}
+
+fn print_message(json: bool, message: String) {
+    print(json, &Message { message }, |m| println!("{}", m.message));
+}
+
+fn parse_json<T: serde::de::DeserializeOwned>(name: &str, content: &str) -> Result<T, CliError> {
+    serde_json::from_str(content)
+        .map_err(|e| CliError::InvalidArgumentError(name.to_string(), e.to_string()))
+}
+
+fn print_records(records: &[RunRecord]) {
+    print_table(
+        &["RUN", "STARTED", "STATUS", "FAILED NODE", "ERROR"],
+        records
+            .iter()
+            .map(|record| {
+                vec![
+                    record.run_id.clone(),
+                    format_time(record.started_at),
+                    record
+                        .status
+                        .map(|status| format!("{:?}", status))
+                        .unwrap_or("Running".to_string()),
+                    record.failed_node.clone().unwrap_or_default(),
+                    record.error.clone().unwrap_or_default(),
+                ]
+            })
+            .collect(),
+    );
+}
+
+fn set_state(json: bool, id: &str, state: TaskState) -> Result<(), CliError> {
+    let lifecycle = Application::update_task_lifecycle(id, |lifecycle| lifecycle.state = state)?;
+    print(json, &lifecycle, |lifecycle| {
+        println!("Task {} is now {:?}", id, lifecycle.state)
+    });
+    Ok(())
+}
+
+pub fn run_task_command(command: TaskCommand, json: bool) -> Result<(), CliError> {
+    match command {
+        TaskCommand::List => {
+            let task_list = Application::get_task_list()?;
+            let states: HashMap<String, TaskState> = task_list
+                .iter()
+                .map(|task| (task.id.clone(), task.lifecycle().state))
+                .collect();
+            print(json, &task_list, |task_list| {
+                print_table(
+                    &["ID", "NAME", "STATE", "TRIGGERS"],
+                    task_list
+                        .iter()
+                        .map(|task| {
+                            vec![
+                                task.id.clone(),
+                                task.info.name.clone(),
+                                format!("{:?}", states[&task.id]),
+                                task.info.trigger.join(","),
+                            ]
+                        })
+                        .collect(),
+                )
+            });
+        }
+        TaskCommand::Show { id } => {
+            let task =
+                Task::find_from_id(&id).ok_or(TaskError::TaskNotFoundError(id.clone()))?;
+            print(json, &task, |task| {
+                println!("{}", serde_json::to_string_pretty(task).unwrap_or_default());
+                println!("lifecycle: {:?}", task.lifecycle());
+            });
+        }
+        TaskCommand::Create {
+            name,
+            triggers,
+            workflow,
+            description,
+            tags,
+        } => {
+            for trigger_id in &triggers {
+                Trigger::from_id(trigger_id)?;
+            }
+            let content = read_to_string(&workflow).map_err(|e| {
+                CliError::InvalidArgumentError(workflow.display().to_string(), e.to_string())
+            })?;
+            let workflow: HashMap<String, ActionEntry> = parse_json("workflow", &content)?;
+            let task_info = TaskInfo {
+                tag: tags,
+                name,
+                setup: Setup {
+                    trigger: triggers[0].clone(),
+                    task: vec![],
+                },
+                trigger: triggers.clone(),
+                description,
+                enabled: true,
+            };
+            let task_id = Application::add_task(task_info, workflow)?;
+            for trigger_id in triggers {
+                Application::add_setup(Setup {
+                    trigger: trigger_id,
+                    task: vec![task_id.clone()],
+                })?;
+            }
+            print_message(json, task_id);
+        }
+        TaskCommand::Remove { id } => {
+            Task::remove(&id)?;
+            print_message(json, format!("Task {} removed", id));
+        }
+        TaskCommand::Enable { id } => set_state(json, &id, TaskState::Enabled)?,
+        TaskCommand::Disable { id } => set_state(json, &id, TaskState::Disabled)?,
+        TaskCommand::Archive { id } => set_state(json, &id, TaskState::Archived)?,
+        TaskCommand::Pause { id, minutes } => {
+            let until = Local::now().timestamp_millis() + minutes * 60 * 1000;
+            set_state(json, &id, TaskState::Paused { until })?
+        }
+        TaskCommand::Run { id, input } => {
+            let task =
+                Task::find_from_id(&id).ok_or(TaskError::TaskNotFoundError(id.clone()))?;
+            let mut instance = task.create_task_instance()?;
+            if let Some(input) = input {
+                let input = match parse_json::<Value>("input", &input)? {
+                    Value::Object(map) => Data::Json(map),
+                    value => Data::Any(value),
+                };
+                instance = instance.with_context("input", input);
+            }
+            instance.run()?;
+            // 运行结果以历史记录为准
+            let record = Application::get_run_history(&id)?.pop();
+            print(json, &record, |record| match record {
+                Some(record) => print_records(std::slice::from_ref(record)),
+                None => println!("Task {} finished", id),
+            });
+        }
+        TaskCommand::History { id, limit, follow } => {
+            let records = Application::get_run_history(&id)?;
+            let skip = records.len().saturating_sub(limit);
+            let records = &records[skip..];
+            print(json, &records, |records| print_records(records));
+            if !follow {
+                return Ok(());
+            }
+            let mut seen: HashSet<String> = records
+                .iter()
+                .filter(|record| record.status.is_some())
+                .map(|record| record.run_id.clone())
+                .collect();
+            loop {
+                thread::sleep(Duration::from_secs(1));
+                for record in Application::get_run_history(&id)? {
+                    if record.status.is_none() || !seen.insert(record.run_id.clone()) {
+                        continue;
+                    }
+                    if json {
+                        println!("{}", serde_json::to_string(&record).unwrap_or_default());
+                    } else {
+                        print_records(std::slice::from_ref(&record));
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn run_action_command(command: ActionCommand, json: bool) -> Result<(), CliError> {
+    match command {
+        ActionCommand::List => {
+            let action_list = load_lit_actions();
+            print(json, &action_list, |action_list| {
+                print_table(
+                    &["ID", "LABEL", "TYPE", "STATUS"],
+                    action_list
+                        .iter()
+                        .map(|LitAction { action, issue }| {
+                            vec![
+                                action.id.clone(),
+                                action.label.clone(),
+                                action.r#type.clone(),
+                                match issue {
+                                    None => "ok".to_string(),
+                                    Some(ActionIssue::Missing) => "missing".to_string(),
+                                    Some(ActionIssue::Migrate { message }) => message.clone(),
+                                },
+                            ]
+                        })
+                        .collect(),
+                )
+            });
+        }
+        ActionCommand::Types => {
+            let types = get_action_types();
+            print(json, &types, |types| types.iter().for_each(|ty| println!("{}", ty)));
+        }
+        ActionCommand::Lit {
+            action_type,
+            name,
+            args,
+        } => {
+            let args: Data = parse_json("args", &args)?;
+            let action = Action::get_action_instance_from_type(&action_type)
+                .map_err(|e| CliError::ActionError(e.to_string()))?;
+            let id = action
+                .lit(name, args)
+                .map_err(|e| CliError::ActionError(e.to_string()))?;
+            print_message(json, id);
+        }
+        ActionCommand::Remove { id } => {
+            Action::remove(&id).map_err(|e| CliError::ActionError(e.to_string()))?;
+            print_message(json, format!("Action {} removed", id));
+        }
+        ActionCommand::Run { id } => {
+            let action = Action::find_from_id(&id).map_err(|e| CliError::ActionError(e.to_string()))?;
+            let result = action
+                .run(&HashMap::new())
+                .map_err(|e| CliError::ActionError(e.to_string()))?;
+            #[derive(Serialize)]
+            struct RunOutput {
+                variant: String,
+                data: Data,
+            }
+            let output = RunOutput {
+                variant: result.variant.to_string(),
+                data: result.data,
+            };
+            print(json, &output, |output| {
+                println!("{}: {}", output.variant, output.data)
+            });
+        }
+    }
+    Ok(())
+}
+
+pub fn run_trigger_command(command: TriggerCommand, json: bool) -> Result<(), CliError> {
+    match command {
+        TriggerCommand::List => {
+            let trigger_list = Application::get_trigger_list();
+            print(json, &trigger_list, |trigger_list| {
+                print_table(
+                    &["ID", "LABEL", "TYPE", "DATA"],
+                    trigger_list
+                        .iter()
+                        .map(|trigger| {
+                            vec![
+                                trigger.id.clone(),
+                                trigger.label.clone(),
+                                trigger.r#type.clone(),
+                                trigger.data.to_string(),
+                            ]
+                        })
+                        .collect(),
+                )
+            });
+        }
+        TriggerCommand::Types => {
+            print(json, &TRIGGER_TYPES, |types| {
+                types.iter().for_each(|ty| println!("{}", ty))
+            });
+        }
+        TriggerCommand::Lit {
+            trigger_type,
+            name,
+            args,
+        } => {
+            let args: Data = parse_json("args", &args)?;
+            let id = Trigger::try_find(&trigger_type)?.lit(name, args)?;
+            print_message(json, id);
+        }
+        TriggerCommand::Remove { id } => {
+            Trigger::remove(&id)?;
+            print_message(json, format!("Trigger {} removed", id));
+        }
+    }
+    Ok(())
+}
+
+pub fn run_bundle_command(command: BundleCommand, json: bool) -> Result<(), CliError> {
+    match command {
+        BundleCommand::Validate { path } => {
+            let problems = Bundle::read(&path)?.validate();
+            print(json, &problems, |problems| {
+                if problems.is_empty() {
+                    println!("Bundle is valid");
+                } else {
+                    problems.iter().for_each(|problem| println!("- {}", problem));
+                }
+            });
+            if !problems.is_empty() {
+                return Err(CliError::InvalidBundleError(problems.join("\n")));
+            }
+        }
+        BundleCommand::Import { path } => {
+            let summary = Bundle::read(&path)?.import()?;
+            print(json, &summary, |summary| {
+                println!(
+                    "Imported {} tasks, {} actions, {} triggers",
+                    summary.tasks, summary.actions, summary.triggers
+                )
+            });
+        }
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct StatusOutput {
+    state: String,
+    metrics: Option<MetricsSnapshot>,
+}
+
+pub fn run_status_command(json: bool) -> Result<(), CliError> {
+    let state = query_service_state()?.to_string();
+    // 服务未运行时无法获取运行指标
+    let metrics = if state == "Running" {
+        block_on(query_service_metrics()).ok()
+    } else {
+        None
+    };
+    let output = StatusOutput { state, metrics };
+    print(json, &output, |output| {
+        println!("Service: {}", output.state);
+        if let Some(metrics) = &output.metrics {
+            println!("Uptime: {}s", metrics.uptime);
+            println!("Armed triggers: {}", metrics.armed_triggers.len());
+            println!(
+                "Runs: {} running, {} queued",
+                metrics.running_runs, metrics.queued_runs
+            );
+            print_table(
+                &["TASK", "SUCCESS", "FAILURE", "AVG(ms)"],
+                metrics
+                    .tasks
+                    .iter()
+                    .map(|(task_id, task)| {
+                        vec![
+                            task_id.clone(),
+                            task.success.to_string(),
+                            task.failure.to_string(),
+                            (task.latency.sum / task.latency.count.max(1)).to_string(),
+                        ]
+                    })
+                    .collect(),
+            );
+        }
+    });
+    Ok(())
+}
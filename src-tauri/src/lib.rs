@@ -1,11 +1,26 @@
 use application::command::{get_config, open_window, save_config};
 // use pipe::client::communicate_with_service;
+use logger::get_task_logs;
 use service::{
-    action::command::{
-        get_lit_action, register_action, remove_action, run_action_by_id, update_action_plug,
+    action::{
+        command::{
+            get_available_languages, get_lit_action, register_action, remove_action,
+            run_action_by_id, update_action_plug,
+        },
+        plugin::{get_plugins, reload_plugins, setup_plugins},
+    },
+    desktop::{get_prompt, serve_desktop_requests, submit_prompt},
+    reload::serve_reload_reports,
+    status::{
+        get_service_metrics, get_service_metrics_text, get_service_state,
+        get_service_state_file, launch_service,
+    },
+    task::{
+        alert::{get_task_notify_rules, set_task_notify_rules, watch_notification_outbox},
+        create_task,
+        history::get_run_history,
+        lifecycle::{get_task_lifecycle, set_task_state, set_task_windows},
     },
-    status::{get_service_state, get_service_state_file, launch_service},
-    task::create_task,
     trigger::{
         command::{get_lit_trigger, register_trigger, remove_trigger},
         time::is_cron_expression_vaild,
@@ -16,9 +31,10 @@ use tauri::{webview, LogicalPosition, LogicalSize};
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 
 pub mod application;
+pub mod cli;
 pub mod elevation;
 // pub mod ipc;
-pub mod runtime;
+pub mod logger;
 pub mod service;
 pub mod utils;
 // #[tauri::command]
@@ -31,6 +47,7 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_http::init())
         .plugin(tauri_plugin_fs::init())
+        .plugin(tauri_plugin_dialog::init())
         .setup(|app| {
             let width = 800.;
             let height = 600.;
@@ -44,27 +61,50 @@ pub fn run() {
                 LogicalPosition::new(0., 0.),
                 LogicalSize::new(width, height),
             )?;
+            // 弹出服务进程投递的桌面通知
+            watch_notification_outbox(app.handle().clone());
+            // 处理桌面类动作的请求
+            serve_desktop_requests(app.handle().clone());
+            // 显示服务进程重载动作包的结果
+            serve_reload_reports(app.handle().clone());
+            // 加载插件目录中的动作包
+            setup_plugins();
             Ok(())
         })
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
         .invoke_handler(tauri::generate_handler![
             register_action,
             run_action_by_id,
             get_lit_trigger,
             register_trigger,
             get_lit_action,
+            get_available_languages,
             get_service_state,
             launch_service,
             get_service_state_file,
+            get_service_metrics,
+            get_service_metrics_text,
             create_task,
+            get_task_lifecycle,
+            set_task_state,
+            set_task_windows,
+            get_run_history,
+            get_task_logs,
+            get_task_notify_rules,
+            set_task_notify_rules,
             remove_action,
             remove_trigger,
             is_cron_expression_vaild,
             update_action_plug,
             save_config,
             get_config,
-            open_window
+            open_window,
+            get_prompt,
+            submit_prompt,
+            get_plugins,
+            reload_plugins
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,44 @@
+use std::cell::RefCell;
+
+/// 当前线程正在执行的任务信息，会被附加到每条日志记录上
+#[derive(Debug, Clone, Default)]
+pub struct LogContext {
+    pub task_id: Option<String>,
+    pub run_id: Option<String>,
+    pub action_id: Option<String>,
+}
+
+thread_local! {
+    static CONTEXT: RefCell<LogContext> = RefCell::new(LogContext::default());
+}
+
+pub fn current() -> LogContext {
+    CONTEXT.with(|context| context.borrow().clone())
+}
+
+/// 进入一次任务运行，返回的守卫被释放时清空上下文
+pub fn enter_run(task_id: &str, run_id: &str) -> RunScope {
+    let previous = CONTEXT.with(|context| {
+        context.replace(LogContext {
+            task_id: Some(task_id.to_string()),
+            run_id: Some(run_id.to_string()),
+            action_id: None,
+        })
+    });
+    RunScope { previous }
+}
+
+pub fn set_action(action_id: Option<&str>) {
+    CONTEXT.with(|context| context.borrow_mut().action_id = action_id.map(str::to_string));
+}
+
+pub struct RunScope {
+    previous: LogContext,
+}
+
+impl Drop for RunScope {
+    fn drop(&mut self) {
+        let previous = std::mem::take(&mut self.previous);
+        CONTEXT.with(|context| context.replace(previous));
+    }
+}
@@ -0,0 +1,13 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum LogError {
+    #[error("Failed to init logger: {0}")]
+    InitLoggerError(String),
+    #[error("Failed to write log file {0}: {1}")]
+    WriteLogError(PathBuf, String),
+    #[error("Failed to read log file {0}: {1}")]
+    ReadLogError(PathBuf, String),
+}
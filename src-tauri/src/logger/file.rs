@@ -0,0 +1,142 @@
+use std::{
+    fs::{read_dir, remove_file, rename, File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use chrono::{DateTime, Local};
+
+use crate::application::config::{LogConfig, LogRotation};
+
+use super::error::LogError;
+
+/// 按体积与时间滚动的日志文件，滚动后的文件名为 `{name}.{时间戳}.log`
+pub struct RollingFile {
+    dir: PathBuf,
+    name: String,
+    file: Option<File>,
+    size: u64,
+    period: Option<String>,
+    max_size: u64,
+    rotation: LogRotation,
+    retention: Duration,
+}
+
+impl RollingFile {
+    pub fn new(dir: &Path, name: &str, config: &LogConfig) -> Self {
+        RollingFile {
+            dir: dir.to_path_buf(),
+            name: name.to_string(),
+            file: None,
+            size: 0,
+            period: None,
+            max_size: config.max_file_size,
+            rotation: config.rotation,
+            retention: Duration::from_secs(config.retention_days as u64 * 24 * 60 * 60),
+        }
+    }
+
+    pub fn path(&self) -> PathBuf {
+        self.dir.join(format!("{}.log", &self.name))
+    }
+
+    fn period_of(&self, time: &DateTime<Local>) -> Option<String> {
+        match self.rotation {
+            LogRotation::Never => None,
+            LogRotation::Hourly => Some(time.format("%Y%m%d%H").to_string()),
+            LogRotation::Daily => Some(time.format("%Y%m%d").to_string()),
+        }
+    }
+
+    fn open(&mut self) -> Result<(), LogError> {
+        let path = self.path();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| LogError::WriteLogError(path.clone(), e.to_string()))?;
+        let metadata = file
+            .metadata()
+            .map_err(|e| LogError::WriteLogError(path.clone(), e.to_string()))?;
+        // 已存在的文件以其修改时间所在的周期为准
+        let modified: DateTime<Local> = metadata
+            .modified()
+            .map(DateTime::from)
+            .unwrap_or_else(|_| Local::now());
+        self.size = metadata.len();
+        self.period = self.period_of(&modified);
+        self.file = Some(file);
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> Result<(), LogError> {
+        self.file = None;
+        let path = self.path();
+        let target = self.dir.join(format!(
+            "{}.{}.log",
+            &self.name,
+            Local::now().format("%Y%m%d%H%M%S%3f")
+        ));
+        rename(&path, &target).map_err(|e| LogError::WriteLogError(path.clone(), e.to_string()))?;
+        self.cleanup();
+        self.open()
+    }
+
+    /// 删除超出保留期限的滚动文件
+    fn cleanup(&self) {
+        let now = SystemTime::now();
+        for path in rotated_files(&self.dir, &self.name) {
+            let expired = path
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .is_some_and(|age| age > self.retention);
+            if expired {
+                let _ = remove_file(path);
+            }
+        }
+    }
+
+    pub fn write_line(&mut self, line: &str) -> Result<(), LogError> {
+        if self.file.is_none() {
+            self.open()?;
+        }
+        let period = self.period_of(&Local::now());
+        let len = line.len() as u64 + 1;
+        if self.size > 0 && (self.size + len > self.max_size || period != self.period) {
+            self.rotate()?;
+            self.period = period;
+        }
+        let path = self.path();
+        let file = self.file.as_mut().unwrap();
+        writeln!(file, "{}", line).map_err(|e| LogError::WriteLogError(path, e.to_string()))?;
+        self.size += len;
+        Ok(())
+    }
+}
+
+/// 按时间先后列出滚动后的日志文件
+pub fn rotated_files(dir: &Path, name: &str) -> Vec<PathBuf> {
+    let prefix = format!("{}.", name);
+    let current = format!("{}.log", name);
+    let Ok(entries) = read_dir(dir) else {
+        return vec![];
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|file_name| {
+                    file_name != current
+                        && file_name.starts_with(&prefix)
+                        && file_name.ends_with(".log")
+                })
+        })
+        .collect();
+    files.sort();
+    files
+}
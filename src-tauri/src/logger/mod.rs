@@ -0,0 +1,187 @@
+use std::{
+    collections::HashMap,
+    fs::read_to_string,
+    path::PathBuf,
+    sync::Mutex,
+};
+
+use chrono::Local;
+use common::application::Application;
+use error::LogError;
+use file::{rotated_files, RollingFile};
+use log::{LevelFilter, Log, Metadata, Record};
+use serde::{Deserialize, Serialize};
+
+use crate::application::config::{ConfigManager, LogConfig, LogLevel};
+
+pub mod context;
+pub mod error;
+pub mod file;
+
+/// 写入日志文件的结构化记录，每行一条 JSON
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct LogRecord {
+    pub time: String,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action_id: Option<String>,
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Off => LevelFilter::Off,
+            LogLevel::Error => LevelFilter::Error,
+            LogLevel::Warn => LevelFilter::Warn,
+            LogLevel::Info => LevelFilter::Info,
+            LogLevel::Debug => LevelFilter::Debug,
+            LogLevel::Trace => LevelFilter::Trace,
+        }
+    }
+}
+
+pub fn get_log_dir() -> PathBuf {
+    Application::get_path("logs")
+}
+
+pub fn get_task_log_dir() -> PathBuf {
+    Application::get_path("logs/tasks")
+}
+
+pub struct Logger {
+    config: LogConfig,
+    level: LevelFilter,
+    modules: Vec<(String, LevelFilter)>,
+    main: Mutex<RollingFile>,
+    tasks: Mutex<HashMap<String, RollingFile>>,
+}
+
+impl Logger {
+    pub fn new(name: &str, config: LogConfig) -> Self {
+        // 前缀越长越优先
+        let mut modules: Vec<(String, LevelFilter)> = config
+            .modules
+            .iter()
+            .map(|(target, level)| (target.clone(), (*level).into()))
+            .collect();
+        modules.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+        Logger {
+            level: config.level.into(),
+            modules,
+            main: Mutex::new(RollingFile::new(&get_log_dir(), name, &config)),
+            tasks: Mutex::new(HashMap::new()),
+            config,
+        }
+    }
+
+    fn level_of(&self, target: &str) -> LevelFilter {
+        self.modules
+            .iter()
+            .find(|(prefix, _)| target.starts_with(prefix.as_str()))
+            .map(|(_, level)| *level)
+            .unwrap_or(self.level)
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        self.modules
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.level, |a, b| a.max(b))
+    }
+
+    fn write(&self, record: &LogRecord) -> Result<(), LogError> {
+        let line = serde_json::to_string(record)
+            .map_err(|e| LogError::WriteLogError(get_log_dir(), e.to_string()))?;
+        self.main.lock().unwrap().write_line(&line)?;
+        // 属于任务的日志额外写入该任务自己的日志流
+        if let Some(task_id) = &record.task_id {
+            let mut tasks = self.tasks.lock().unwrap();
+            tasks
+                .entry(task_id.clone())
+                .or_insert_with(|| RollingFile::new(&get_task_log_dir(), task_id, &self.config))
+                .write_line(&line)?;
+        }
+        Ok(())
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level_of(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let context = context::current();
+        let record = LogRecord {
+            time: Local::now().to_rfc3339(),
+            level: record.level().to_string(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+            task_id: context.task_id,
+            run_id: context.run_id,
+            action_id: context.action_id,
+        };
+        if let Err(e) = self.write(&record) {
+            eprintln!("{}", e);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// 按配置初始化日志，name 区分不同进程的日志文件（如 client、service）
+pub fn init(name: &str) -> Result<(), LogError> {
+    let config = Application::get_config().log_config;
+    let logger = Logger::new(name, config);
+    let max_level = logger.max_level();
+    log::set_boxed_logger(Box::new(logger))
+        .map_err(|e| LogError::InitLoggerError(e.to_string()))?;
+    log::set_max_level(max_level);
+    Ok(())
+}
+
+/// 读取任务的日志，可按运行 id 过滤，返回最近的 limit 条
+pub fn read_task_logs(
+    task_id: &str,
+    run_id: Option<&str>,
+    limit: Option<usize>,
+) -> Result<Vec<LogRecord>, LogError> {
+    let dir = get_task_log_dir();
+    let mut files = rotated_files(&dir, task_id);
+    files.push(dir.join(format!("{}.log", task_id)));
+
+    let mut records = vec![];
+    for path in files.iter().filter(|path| path.exists()) {
+        let content =
+            read_to_string(path).map_err(|e| LogError::ReadLogError(path.clone(), e.to_string()))?;
+        records.extend(
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str::<LogRecord>(line).ok())
+                .filter(|record| run_id.is_none() || record.run_id.as_deref() == run_id),
+        );
+    }
+    if let Some(limit) = limit {
+        let skip = records.len().saturating_sub(limit);
+        records.drain(..skip);
+    }
+    Ok(records)
+}
+
+#[tauri::command]
+pub fn get_task_logs(
+    task_id: String,
+    run_id: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<LogRecord>, String> {
+    read_task_logs(&task_id, run_id.as_deref(), limit).map_err(|e| e.to_string())
+}
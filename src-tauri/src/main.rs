@@ -1,22 +1,21 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use std::{
-    env::set_var,
-    fs::{create_dir, exists},
-};
+use std::{env::set_var, path::PathBuf};
 
 use clap::Parser;
 use daisytools_lib::{
-    elevation,
-    runtime::javascript::execute_javascript_from_tauri,
+    cli::{
+        error::CliError, run_action_command, run_bundle_command, run_status_command,
+        run_task_command, run_trigger_command, ActionCommand, BundleCommand, TaskCommand,
+        TriggerCommand,
+    },
+    elevation, logger,
     service::{
         install_service, launch_service, start_service, status::query_service_status,
-        unintall_service,
+        unintall_service, worker::run_worker,
     },
 };
-use ftail::Ftail;
-use log::LevelFilter;
 /// Simple program to greet a person
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
@@ -24,6 +23,9 @@ struct Args {
     /// Name of the person to greet
     #[command(subcommand)]
     command: Option<Command>,
+    /// 以 JSON 格式输出
+    #[arg(long, global = true)]
+    json: bool,
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -36,6 +38,27 @@ enum Command {
         #[command(subcommand)]
         action: TaskCommand,
     },
+    Action {
+        #[command(subcommand)]
+        action: ActionCommand,
+    },
+    Trigger {
+        #[command(subcommand)]
+        action: TriggerCommand,
+    },
+    Bundle {
+        #[command(subcommand)]
+        action: BundleCommand,
+    },
+    /// 查看服务状态与运行指标
+    Status,
+    /// 在独立进程中运行插件，由服务进程启动
+    #[command(hide = true)]
+    Worker {
+        plugin: PathBuf,
+        #[arg(long)]
+        address: String,
+    },
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -45,26 +68,39 @@ enum ServiceCommand {
     Uninstall,
 }
 
-#[derive(Debug, clap::Subcommand)]
-enum TaskCommand {
-    Run { code: String },
+// release 下为 windows 子系统，需要附加到父进程的控制台才能输出
+fn attach_console() {
+    #[cfg(all(windows, not(debug_assertions)))]
+    unsafe {
+        use windows::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+fn exit_on_error(res: Result<(), CliError>) {
+    if let Err(e) = res {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+// 日志无法初始化时报告原因并退出
+fn init_logger(name: &str) {
+    if let Err(e) = logger::init(name) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
 }
 
 fn main() {
     #[cfg(debug_assertions)]
     set_var("RUST_BACKTRACE", "1");
     let cli = Args::parse();
-    if !exists("logs").unwrap() {
-        create_dir("logs").unwrap()
-    }
     let Some(cmd) = cli.command else {
         // 如果可以查询到状态，说明已安装服务
         // 如果没有安装服务，则判断是否授予管理员权限
         if query_service_status().is_ok() || privilege::user::privileged() {
-            Ftail::new()
-                .single_file("logs/client.log", true, LevelFilter::Off)
-                .init()
-                .unwrap();
+            init_logger("client");
             // 启动UI
             return daisytools_lib::run();
         };
@@ -73,14 +109,16 @@ fn main() {
         return;
     };
     // cli
-    Ftail::new()
-        .single_file(
-            "D:\\codes\\daisyTools\\src-tauri\\logs\\service.log",
-            true,
-            LevelFilter::Off,
-        )
-        .init()
-        .unwrap();
+    if matches!(cmd, Command::Service { .. }) {
+        init_logger("service");
+    } else if let Command::Worker { plugin, .. } = &cmd {
+        let stem = plugin.file_stem().unwrap_or_default().to_string_lossy();
+        init_logger(&format!("worker-{}", stem));
+    } else {
+        // 管理命令与服务分开记录，避免两个进程同时滚动同一个文件
+        attach_console();
+        init_logger("cli");
+    }
     let res = match cmd {
         // 服务相关
         Command::Service {
@@ -108,12 +146,26 @@ fn main() {
             log::info!("start service");
             start_service()
         }
-        Command::Task { action } => match action {
-            TaskCommand::Run { code: action_id } => {
-                execute_javascript_from_tauri(&action_id).unwrap();
-                return;
+        // 管理命令直接读写数据目录，不依赖 UI
+        Command::Task { action } => return exit_on_error(run_task_command(action, cli.json)),
+        Command::Action { action } => {
+            return exit_on_error(run_action_command(action, cli.json))
+        }
+        Command::Trigger { action } => {
+            return exit_on_error(run_trigger_command(action, cli.json))
+        }
+        Command::Bundle { action } => {
+            return exit_on_error(run_bundle_command(action, cli.json))
+        }
+        Command::Status => return exit_on_error(run_status_command(cli.json)),
+        Command::Worker { plugin, address } => {
+            // 错误写入标准错误，由服务进程读取
+            if let Err(e) = run_worker(plugin, address) {
+                eprintln!("{}", e);
+                std::process::exit(1);
             }
-        },
+            return;
+        }
     };
     match res {
         Err(e) => panic!("{} ", e),
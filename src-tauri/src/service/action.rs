@@ -1,4 +1,5 @@
 pub mod command;
+pub mod plugin;
 pub use aster_loader;
 
 /// 一些用于标记表单类型的辅助类型
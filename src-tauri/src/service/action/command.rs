@@ -1,8 +1,8 @@
 use std::collections::HashMap;
 
-use aster_loader::ActionProvider;
+use aster_loader::{load_lit_actions, ActionProvider};
 use common::{
-    action::{manager::ActionManager, Action},
+    action::{manager::ActionManager, Action, LitAction},
     application::Application,
     ty::Data,
 };
@@ -25,9 +25,9 @@ pub fn register_action(action_type: String, name: String, args: Data) -> Result<
     Ok(id)
 }
 #[tauri::command]
-/// 获取已点亮action
-pub fn get_lit_action() -> Result<Vec<Action>, String> {
-    Ok(Application::get_action_list())
+/// 获取已点亮action，旧版本的卡片会先迁移到当前版本
+pub fn get_lit_action() -> Result<Vec<LitAction>, String> {
+    Ok(load_lit_actions())
 }
 
 #[tauri::command]
@@ -54,3 +54,12 @@ pub fn run_action(action_type: String, args: Data) -> Result<(), String> {
     action.run(args).map_err(|e| e.to_string())?;
     Ok(())
 }
+
+#[tauri::command]
+/// 获取当前主机可用的脚本语言
+pub fn get_available_languages() -> Result<Vec<String>, String> {
+    Ok(aster_runtime::available_languages()
+        .into_iter()
+        .map(String::from)
+        .collect())
+}
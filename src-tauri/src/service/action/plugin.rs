@@ -0,0 +1,31 @@
+use aster_loader::plugin::{get_plugins as get_loaded_plugins, load_plugins};
+use common::{application::Application, plugin::PluginManifest};
+
+/// 插件目录
+pub fn get_plugin_dir() -> std::path::PathBuf {
+    Application::get_path("plugins")
+}
+
+/// 加载插件目录中的动作包，返回被拒绝的插件及原因
+pub fn setup_plugins() -> Vec<String> {
+    let dir = get_plugin_dir();
+    load_plugins(&dir)
+        .into_iter()
+        .map(|e| {
+            log::error!("{}", e);
+            e.to_string()
+        })
+        .collect()
+}
+
+#[tauri::command]
+/// 获取已加载的插件
+pub fn get_plugins() -> Vec<PluginManifest> {
+    get_loaded_plugins()
+}
+
+#[tauri::command]
+/// 重新扫描插件目录，返回被拒绝的插件及原因
+pub fn reload_plugins() -> Vec<String> {
+    setup_plugins()
+}
@@ -0,0 +1,268 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{LazyLock, Mutex, OnceLock},
+    time::Duration,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use common::{
+    desktop::{
+        set_desktop_handler, ClipboardImage, DesktopError, DesktopFuture, DesktopRequest,
+    },
+    utils::get_uid,
+};
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{image::Image, AppHandle, Manager, WebviewUrl, WebviewWindowBuilder, WindowEvent};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_opener::OpenerExt;
+use tokio::sync::oneshot;
+use vase_macro::expose;
+
+use super::device::{connect_device, ServiceDevice, UI_PACKAGE};
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+static PROMPTS: LazyLock<Mutex<HashMap<String, Prompt>>> = LazyLock::new(Default::default);
+
+/// 输入框中显示的内容
+#[derive(Serialize, Clone)]
+pub struct PromptInfo {
+    title: String,
+    message: String,
+    default: Option<String>,
+}
+
+struct Prompt {
+    info: PromptInfo,
+    sender: oneshot::Sender<Option<String>>,
+}
+
+fn request_error(e: impl ToString) -> DesktopError {
+    DesktopError::RequestError(e.to_string())
+}
+
+/// 在 UI 进程中处理桌面请求
+async fn handle_request(request: DesktopRequest) -> Result<Value, DesktopError> {
+    let app = APP.get().ok_or(DesktopError::NotConnectedError)?;
+    match request {
+        DesktopRequest::Notify { title, body } => {
+            app.notification()
+                .builder()
+                .title(title)
+                .body(body)
+                .show()
+                .map_err(request_error)?;
+        }
+        DesktopRequest::ReadClipboardText => {
+            return Ok(app
+                .clipboard()
+                .read_text()
+                .map(Value::String)
+                .unwrap_or_default());
+        }
+        DesktopRequest::WriteClipboardText { text } => {
+            app.clipboard().write_text(text).map_err(request_error)?;
+        }
+        DesktopRequest::ReadClipboardImage => {
+            let Ok(image) = app.clipboard().read_image() else {
+                return Ok(Value::Null);
+            };
+            let image = ClipboardImage {
+                width: image.width(),
+                height: image.height(),
+                rgba: STANDARD.encode(image.rgba()),
+            };
+            return serde_json::to_value(image).map_err(request_error);
+        }
+        DesktopRequest::WriteClipboardImage { image } => {
+            let rgba = STANDARD.decode(image.rgba).map_err(request_error)?;
+            app.clipboard()
+                .write_image(&Image::new_owned(rgba, image.width, image.height))
+                .map_err(request_error)?;
+        }
+        DesktopRequest::Open { target } => {
+            if target.contains("://") || target.starts_with("mailto:") {
+                app.opener().open_url(target, None::<&str>)
+            } else {
+                app.opener().open_path(target, None::<&str>)
+            }
+            .map_err(request_error)?;
+        }
+        DesktopRequest::Prompt {
+            title,
+            message,
+            default,
+            timeout,
+        } => {
+            let info = PromptInfo {
+                title,
+                message,
+                default,
+            };
+            return prompt(app, info, timeout).await;
+        }
+    }
+    Ok(Value::Null)
+}
+
+async fn prompt(
+    app: &AppHandle,
+    info: PromptInfo,
+    timeout: Option<u64>,
+) -> Result<Value, DesktopError> {
+    let id = get_uid();
+    let (sender, receiver) = oneshot::channel();
+    PROMPTS
+        .lock()
+        .unwrap()
+        .insert(id.clone(), Prompt { info, sender });
+
+    let window = WebviewWindowBuilder::new(
+        app,
+        format!("prompt-{}", id),
+        WebviewUrl::App(PathBuf::from(format!("/#/prompt/{}", id))),
+    )
+    .title("DaisyTools")
+    .inner_size(360., 220.)
+    .decorations(false)
+    .always_on_top(true)
+    .center()
+    .build()
+    .map_err(|e| {
+        PROMPTS.lock().unwrap().remove(&id);
+        request_error(e)
+    })?;
+    // 直接关闭窗口视为取消
+    let prompt_id = id.clone();
+    window.on_window_event(move |event| {
+        if let WindowEvent::Destroyed = event {
+            finish_prompt(&prompt_id, None);
+        }
+    });
+
+    let value = match timeout {
+        Some(timeout) => {
+            match tokio::time::timeout(Duration::from_millis(timeout), receiver).await {
+                Ok(value) => value,
+                Err(_) => {
+                    let _ = window.close();
+                    return Err(DesktopError::TimeoutError);
+                }
+            }
+        }
+        None => receiver.await,
+    };
+    Ok(value.ok().flatten().map(Value::String).unwrap_or_default())
+}
+
+fn finish_prompt(id: &str, value: Option<String>) {
+    if let Some(prompt) = PROMPTS.lock().unwrap().remove(id) {
+        let _ = prompt.sender.send(value);
+    }
+}
+
+#[tauri::command]
+pub fn get_prompt(id: String) -> Option<PromptInfo> {
+    PROMPTS
+        .lock()
+        .unwrap()
+        .get(&id)
+        .map(|prompt| prompt.info.clone())
+}
+
+/// value 为空表示用户取消了输入
+#[tauri::command]
+pub fn submit_prompt(app: AppHandle, id: String, value: Option<String>) {
+    finish_prompt(&id, value);
+    if let Some(window) = app.get_webview_window(&format!("prompt-{}", id)) {
+        let _ = window.close();
+    }
+}
+
+// 服务进程中运行的动作通过 IPC 调用
+#[expose(ServiceDevice::Desktop)]
+async fn handle_desktop(request: DesktopRequest) -> anyhow::Result<Value> {
+    Ok(handle_request(request).await?)
+}
+
+fn handle_local(request: DesktopRequest) -> DesktopFuture {
+    Box::pin(handle_request(request))
+}
+
+pub(super) fn forward_request(request: DesktopRequest) -> DesktopFuture {
+    Box::pin(async move {
+        ServiceDevice::Desktop::call(UI_PACKAGE, "handle_desktop", request)
+            .await
+            .map_err(|e| match e {
+                vase::ipc::Error::UnknownPackage(_) | vase::ipc::Error::SessionNotFound => {
+                    DesktopError::NotConnectedError
+                }
+                e => request_error(e),
+            })
+    })
+}
+
+/// 在服务进程中将桌面请求转发给已连接的 UI 进程
+pub fn forward_desktop_requests() {
+    set_desktop_handler(forward_request);
+}
+
+/// 在 UI 进程中处理桌面请求，并连接服务以接收转发的请求
+pub fn serve_desktop_requests(app: AppHandle) {
+    let _ = APP.set(app);
+    set_desktop_handler(handle_local);
+    tauri::async_runtime::spawn(async {
+        // 服务可能晚于 UI 启动
+        while let Err(e) = connect_device().await {
+            log::debug!("Service is not available: {}", e);
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending_prompt(title: &str) -> (String, oneshot::Receiver<Option<String>>) {
+        let id = get_uid();
+        let (sender, receiver) = oneshot::channel();
+        let info = PromptInfo {
+            title: title.to_string(),
+            message: "message".to_string(),
+            default: None,
+        };
+        PROMPTS
+            .lock()
+            .unwrap()
+            .insert(id.clone(), Prompt { info, sender });
+        (id, receiver)
+    }
+
+    #[tokio::test]
+    async fn rejects_requests_without_app() {
+        let result = handle_request(DesktopRequest::ReadClipboardText).await;
+        assert!(matches!(result, Err(DesktopError::NotConnectedError)));
+    }
+
+    #[tokio::test]
+    async fn finishes_prompts_once() {
+        let (id, receiver) = pending_prompt("submitted");
+        assert_eq!(get_prompt(id.clone()).unwrap().title, "submitted");
+
+        finish_prompt(&id, Some("daisy".to_string()));
+        assert_eq!(receiver.await.unwrap(), Some("daisy".to_string()));
+        assert!(get_prompt(id.clone()).is_none());
+        // 关闭窗口时会再次结束，已完成的输入框被忽略
+        finish_prompt(&id, None);
+    }
+
+    #[tokio::test]
+    async fn cancels_prompts_without_value() {
+        let (id, receiver) = pending_prompt("cancelled");
+        finish_prompt(&id, None);
+        assert_eq!(receiver.await.unwrap(), None);
+    }
+}
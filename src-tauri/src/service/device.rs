@@ -0,0 +1,73 @@
+use common::tokio::sync::OnceCell;
+use vase::ipc::transport::impls::local_socket_new::LocalSocketTransport;
+use vase_macro::{device, handle};
+
+use super::{
+    error::ServiceError,
+    metrics::{snapshot, MetricsSnapshot},
+};
+
+// 服务进程与 UI 进程之间的 IPC 设备，服务端为 Windows 服务
+device!(ServiceDevice {
+    transport: LocalSocketTransport("daisytools.service".to_string()),
+    package: daisytools::service,
+    keepAlive,
+    ..{
+        pub mod Status;
+        pub mod Desktop;
+    }
+});
+
+/// UI 进程连接时以 package 注册会话，服务进程据此向 UI 进程发起调用
+pub const UI_PACKAGE: &str = "daisytools::service";
+
+/// 在服务进程中启动 IPC 服务端
+pub async fn setup_device() -> Result<(), ServiceError> {
+    ServiceDevice::setup()
+        .await
+        .map_err(|e| ServiceError::ServiceInternalError(e.to_string()))
+}
+
+static CONNECTED: OnceCell<()> = OnceCell::const_new();
+
+/// UI 进程首次调用时连接服务
+pub async fn connect_device() -> Result<(), ServiceError> {
+    CONNECTED
+        .get_or_try_init(|| async {
+            ServiceDevice::setup_ref()
+                .await
+                .map_err(|e| ServiceError::ConnectServiceError(e.to_string()))
+        })
+        .await?;
+    Ok(())
+}
+
+#[handle(ServiceDevice::Status)]
+fn get_metrics() -> anyhow::Result<MetricsSnapshot> {
+    Ok(snapshot())
+}
+
+#[cfg(test)]
+mod tests {
+    use common::desktop::{DesktopError, DesktopRequest};
+
+    use super::*;
+    use crate::service::desktop::forward_request;
+
+    // 服务端与 UI 进程的连接都是全局的，在同一个测试中依次检查
+    #[tokio::test(flavor = "multi_thread")]
+    async fn serves_status_and_desktop_requests() {
+        setup_device().await.unwrap();
+        // 没有 UI 进程连接时不能转发桌面请求
+        let result = forward_request(DesktopRequest::ReadClipboardText).await;
+        assert!(matches!(result, Err(DesktopError::NotConnectedError)));
+
+        connect_device().await.unwrap();
+        let metrics = ServiceDevice::Status::get_metrics().await.unwrap();
+        assert_eq!(metrics.armed_triggers, snapshot().armed_triggers);
+
+        // 请求转发到 UI 进程一侧，测试中没有 Tauri 应用，处理失败的原因随结果返回
+        let result = forward_request(DesktopRequest::ReadClipboardText).await;
+        assert!(matches!(result, Err(DesktopError::RequestError(_))));
+    }
+}
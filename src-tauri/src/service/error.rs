@@ -16,4 +16,8 @@ pub enum ServiceError {
     RunTaskError(String, String),
     #[error("Failed to notify service state: {0}")]
     NotifyServiceStateError(String),
+    #[error("Failed to connect to service: {0}")]
+    ConnectServiceError(String),
+    #[error("Plugin worker {0} failed: {1}")]
+    WorkerError(String, String),
 }
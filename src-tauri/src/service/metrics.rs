@@ -0,0 +1,261 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Write as _,
+    io::{Read, Write},
+    net::TcpListener,
+    sync::{LazyLock, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// 耗时直方图的桶上界（毫秒），最后还有一个 +Inf 桶
+pub const LATENCY_BUCKETS: [u64; 11] = [
+    10, 50, 100, 250, 500, 1000, 2500, 5000, 10000, 30000, 60000,
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Histogram {
+    /// 与 LATENCY_BUCKETS 对应的非累计计数，最后一项为 +Inf
+    pub buckets: Vec<u64>,
+    pub count: u64,
+    pub sum: u64,
+    pub max: u64,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Histogram {
+            buckets: vec![0; LATENCY_BUCKETS.len() + 1],
+            count: 0,
+            sum: 0,
+            max: 0,
+        }
+    }
+}
+
+impl Histogram {
+    pub fn observe(&mut self, elapsed: Duration) {
+        let millis = elapsed.as_millis() as u64;
+        let index = LATENCY_BUCKETS
+            .iter()
+            .position(|bound| millis <= *bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        self.buckets[index] += 1;
+        self.count += 1;
+        self.sum += millis;
+        self.max = self.max.max(millis);
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TaskMetrics {
+    pub success: u64,
+    pub failure: u64,
+    pub latency: Histogram,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsSnapshot {
+    pub uptime: u64,
+    pub armed_triggers: Vec<String>,
+    pub running_runs: u64,
+    pub queued_runs: u64,
+    pub tasks: HashMap<String, TaskMetrics>,
+    pub actions: HashMap<String, Histogram>,
+}
+
+struct Metrics {
+    started_at: Instant,
+    armed_triggers: BTreeSet<String>,
+    running_runs: u64,
+    queued_runs: u64,
+    tasks: HashMap<String, TaskMetrics>,
+    actions: HashMap<String, Histogram>,
+}
+
+static METRICS: LazyLock<Mutex<Metrics>> = LazyLock::new(|| {
+    Mutex::new(Metrics {
+        started_at: Instant::now(),
+        armed_triggers: BTreeSet::new(),
+        running_runs: 0,
+        queued_runs: 0,
+        tasks: HashMap::new(),
+        actions: HashMap::new(),
+    })
+});
+
+fn with_metrics<T>(f: impl FnOnce(&mut Metrics) -> T) -> T {
+    let mut metrics = METRICS.lock().unwrap_or_else(|e| e.into_inner());
+    f(&mut metrics)
+}
+
+/// 服务启动时调用，以此作为运行时长的起点
+pub fn init() {
+    with_metrics(|metrics| metrics.started_at = Instant::now());
+}
+
+pub fn trigger_armed(trigger_id: &str) {
+    with_metrics(|metrics| metrics.armed_triggers.insert(trigger_id.to_string()));
+}
+
+pub fn trigger_disarmed(trigger_id: &str) {
+    with_metrics(|metrics| metrics.armed_triggers.remove(trigger_id));
+}
+
+/// 调度器关闭时所有触发器都已失效
+pub fn clear_triggers() {
+    with_metrics(|metrics| metrics.armed_triggers.clear());
+}
+
+pub fn run_queued(count: usize) {
+    with_metrics(|metrics| metrics.queued_runs += count as u64);
+}
+
+pub fn run_started() {
+    with_metrics(|metrics| {
+        metrics.queued_runs = metrics.queued_runs.saturating_sub(1);
+        metrics.running_runs += 1;
+    });
+}
+
+pub fn run_finished(task_id: &str, success: bool, elapsed: Duration) {
+    with_metrics(|metrics| {
+        metrics.running_runs = metrics.running_runs.saturating_sub(1);
+        let task = metrics.tasks.entry(task_id.to_string()).or_default();
+        if success {
+            task.success += 1;
+        } else {
+            task.failure += 1;
+        }
+        task.latency.observe(elapsed);
+    });
+}
+
+pub fn action_finished(action_id: &str, elapsed: Duration) {
+    with_metrics(|metrics| {
+        metrics
+            .actions
+            .entry(action_id.to_string())
+            .or_default()
+            .observe(elapsed)
+    });
+}
+
+pub fn snapshot() -> MetricsSnapshot {
+    with_metrics(|metrics| MetricsSnapshot {
+        uptime: metrics.started_at.elapsed().as_secs(),
+        armed_triggers: metrics.armed_triggers.iter().cloned().collect(),
+        running_runs: metrics.running_runs,
+        queued_runs: metrics.queued_runs,
+        tasks: metrics.tasks.clone(),
+        actions: metrics.actions.clone(),
+    })
+}
+
+fn write_histogram(out: &mut String, name: &str, label: &str, value: &str, histogram: &Histogram) {
+    let mut cumulative = 0;
+    for (bound, count) in LATENCY_BUCKETS.iter().zip(&histogram.buckets) {
+        cumulative += count;
+        let le = *bound as f64 / 1000.0;
+        let _ = writeln!(
+            out,
+            "{name}_bucket{{{label}=\"{value}\",le=\"{le}\"}} {cumulative}"
+        );
+    }
+    let _ = writeln!(
+        out,
+        "{name}_bucket{{{label}=\"{value}\",le=\"+Inf\"}} {}",
+        histogram.count
+    );
+    let _ = writeln!(
+        out,
+        "{name}_sum{{{label}=\"{value}\"}} {}",
+        histogram.sum as f64 / 1000.0
+    );
+    let _ = writeln!(out, "{name}_count{{{label}=\"{value}\"}} {}", histogram.count);
+}
+
+/// 转换为 Prometheus 文本格式
+pub fn to_prometheus(snapshot: &MetricsSnapshot) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# TYPE daisytools_uptime_seconds gauge");
+    let _ = writeln!(out, "daisytools_uptime_seconds {}", snapshot.uptime);
+    let _ = writeln!(out, "# TYPE daisytools_armed_triggers gauge");
+    let _ = writeln!(
+        out,
+        "daisytools_armed_triggers {}",
+        snapshot.armed_triggers.len()
+    );
+    let _ = writeln!(out, "# TYPE daisytools_running_runs gauge");
+    let _ = writeln!(out, "daisytools_running_runs {}", snapshot.running_runs);
+    let _ = writeln!(out, "# TYPE daisytools_queued_runs gauge");
+    let _ = writeln!(out, "daisytools_queued_runs {}", snapshot.queued_runs);
+
+    let _ = writeln!(out, "# TYPE daisytools_task_runs_total counter");
+    for (task_id, task) in &snapshot.tasks {
+        let _ = writeln!(
+            out,
+            "daisytools_task_runs_total{{task_id=\"{task_id}\",status=\"success\"}} {}",
+            task.success
+        );
+        let _ = writeln!(
+            out,
+            "daisytools_task_runs_total{{task_id=\"{task_id}\",status=\"failure\"}} {}",
+            task.failure
+        );
+    }
+    let _ = writeln!(out, "# TYPE daisytools_task_duration_seconds histogram");
+    for (task_id, task) in &snapshot.tasks {
+        write_histogram(
+            &mut out,
+            "daisytools_task_duration_seconds",
+            "task_id",
+            task_id,
+            &task.latency,
+        );
+    }
+    let _ = writeln!(out, "# TYPE daisytools_action_duration_seconds histogram");
+    for (action_id, histogram) in &snapshot.actions {
+        write_histogram(
+            &mut out,
+            "daisytools_action_duration_seconds",
+            "action_id",
+            action_id,
+            histogram,
+        );
+    }
+    out
+}
+
+/// 在本机端口上提供 Prometheus 抓取接口
+pub fn serve_prometheus(port: u16) {
+    thread::spawn(move || {
+        let listener = match TcpListener::bind(("127.0.0.1", port)) {
+            Ok(listener) => listener,
+            Err(e) => {
+                log::error!("Failed to bind metrics endpoint on port {}: {}", port, e);
+                return;
+            }
+        };
+        log::info!("Metrics endpoint listening on 127.0.0.1:{}", port);
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else {
+                continue;
+            };
+            // 只读取请求头，不区分路径
+            let mut buf = [0; 1024];
+            let _ = stream.read(&mut buf);
+            let body = to_prometheus(&snapshot());
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            if let Err(e) = stream.write_all(response.as_bytes()) {
+                log::warn!("Failed to write metrics response: {}", e);
+            }
+        }
+    });
+}
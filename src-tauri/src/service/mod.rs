@@ -1,9 +1,14 @@
 pub mod action;
+pub mod desktop;
+pub mod device;
 pub mod error;
+pub mod metrics;
+pub mod reload;
 pub mod service_main;
 pub mod status;
 pub mod task;
 pub mod trigger;
+pub mod worker;
 
 use std::{
     ffi::{OsStr, OsString},
@@ -0,0 +1,58 @@
+use std::sync::OnceLock;
+
+use aster_loader::reload::{pin_action_libraries, watch_action_libraries, ReloadReport};
+use common::application::Application;
+use tauri::{async_runtime::block_on, AppHandle, Emitter};
+use vase_macro::expose;
+
+use super::device::{ServiceDevice, UI_PACKAGE};
+use crate::application::config::ConfigManager;
+
+/// 前端监听的事件
+const RELOADED_EVENT: &str = "action-reloaded";
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+
+fn report_reload(report: ReloadReport) {
+    match &report.error {
+        None => log::info!(
+            "Action library {} reloaded (version {}) after draining for {}ms",
+            report.group,
+            report.version,
+            report.drain_time
+        ),
+        Some(e) => log::error!("Failed to reload action library {}: {}", report.group, e),
+    }
+    let group = report.group.clone();
+    if let Err(e) = block_on(ServiceDevice::Desktop::call(
+        UI_PACKAGE,
+        "action_reloaded",
+        report,
+    )) {
+        log::debug!("Failed to report reload of {} to the UI: {}", group, e);
+    }
+}
+
+/// 在服务进程中按配置监听动作包，未开启开发模式时固定启动时加载的版本
+pub fn setup_hot_reload() {
+    if Application::get_config().dev_config.hot_reload {
+        log::info!("Hot reload is enabled, watching action libraries");
+        watch_action_libraries(report_reload);
+    } else {
+        pin_action_libraries();
+    }
+}
+
+// 服务进程重载动作包后调用
+#[expose(ServiceDevice::Desktop)]
+async fn action_reloaded(report: ReloadReport) -> anyhow::Result<()> {
+    if let Some(app) = APP.get() {
+        app.emit(RELOADED_EVENT, report)?;
+    }
+    Ok(())
+}
+
+/// 在 UI 进程中将重载结果转发给前端
+pub fn serve_reload_reports(app: AppHandle) {
+    let _ = APP.set(app);
+}
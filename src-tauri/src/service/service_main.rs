@@ -7,9 +7,40 @@ use tauri::async_runtime::block_on;
 
 // use crate::ipc::service::{setup_tcp_server, TcpServer};
 
-use super::task::{scheduler::setup_task, TaskManager};
+use crate::application::config::ConfigManager;
+
+use super::{
+    action::plugin::{get_plugin_dir, setup_plugins},
+    desktop::forward_desktop_requests,
+    device::setup_device,
+    metrics,
+    reload::setup_hot_reload,
+    task::{lifecycle::TaskLifecycleManager, scheduler::setup_task, TaskManager},
+    worker::setup_workers,
+};
 
 pub fn main() {
+    metrics::init();
+    // 供 UI 进程查询运行状态
+    if let Err(e) = block_on(setup_device()) {
+        log::error!("Failed to setup service device: {}", e);
+    }
+    // 桌面类动作需要由 UI 进程完成
+    forward_desktop_requests();
+    // 任务中可能使用插件提供的 action，隔离模式下插件崩溃不会导致服务退出
+    let setup_plugins = if Application::get_config().plugin_config.isolated {
+        setup_workers
+    } else {
+        setup_plugins
+    };
+    setup_plugins();
+    // 开发模式下动作包重新构建后在两次运行之间重载
+    setup_hot_reload();
+    let metrics_config = Application::get_config().metrics_config;
+    if metrics_config.prometheus {
+        metrics::serve_prometheus(metrics_config.port);
+    }
+
     log::info!("start setup tasks");
     // 启动所有的任务
     let mut scheduler = match setup_task() {
@@ -38,11 +69,33 @@ pub fn main() {
         .unwrap();
     log::info!("Watching path: {:?}", path);
 
+    // 任务状态独立存放，状态变更同样需要重新装配触发器
+    let state_path = Application::get_task_lifecycle_file();
+    debouncer
+        .watch(state_path.clone(), RecursiveMode::Recursive)
+        .unwrap();
+    log::info!("Watching path: {:?}", state_path);
+
+    // 插件更新后重新加载，加载时生成的副本位于子目录中，不会被监听到
+    let plugin_dir = get_plugin_dir();
+    debouncer
+        .watch(plugin_dir.clone(), RecursiveMode::NonRecursive)
+        .unwrap();
+    log::info!("Watching path: {:?}", plugin_dir);
+
     for result in rx {
         match result {
-            Ok(_) => {
+            Ok(events) => {
+                let plugin_changed = events
+                    .iter()
+                    .flat_map(|event| event.paths.iter())
+                    .any(|path| path.parent() == Some(plugin_dir.as_path()));
+                if plugin_changed {
+                    log::info!("Plugins changed, reloading plugins");
+                    setup_plugins();
+                }
                 block_on(async {
-                    log::info!("Task file changed, restarting tasks");
+                    log::info!("Task file or task state changed, restarting tasks");
                     scheduler.shutdown().await;
                     log::info!("Scheduler shutdown successfully")
                 });
@@ -5,7 +5,12 @@ use common::{application::Application, ty::type_convert::ToString};
 use log::info;
 use windows_service::service::{ServiceAccess, ServiceState, ServiceStatus};
 
-use super::{error::ServiceError, open_service};
+use super::{
+    device::{connect_device, ServiceDevice},
+    error::ServiceError,
+    metrics::{to_prometheus, MetricsSnapshot},
+    open_service,
+};
 
 // 定义服务状态管理器的特征
 pub trait ServiceStateManager {
@@ -70,6 +75,27 @@ pub async fn get_service_state() -> Result<String, String> {
     Ok(state.to_string())
 }
 
+// 通过 IPC 查询服务的运行指标
+pub async fn query_service_metrics() -> Result<MetricsSnapshot, ServiceError> {
+    connect_device().await?;
+    ServiceDevice::Status::get_metrics()
+        .await
+        .map_err(|e| ServiceError::QueryServiceStatusError(e.to_string()))
+}
+
+// 获取服务运行指标的命令
+#[tauri::command]
+pub async fn get_service_metrics() -> Result<MetricsSnapshot, String> {
+    query_service_metrics().await.map_err(|e| e.to_string())
+}
+
+// 以 Prometheus 文本格式获取服务运行指标的命令
+#[tauri::command]
+pub async fn get_service_metrics_text() -> Result<String, String> {
+    let snapshot = query_service_metrics().await.map_err(|e| e.to_string())?;
+    Ok(to_prometheus(&snapshot))
+}
+
 // 获取服务状态文件的命令
 #[tauri::command]
 pub fn get_service_state_file() -> String {
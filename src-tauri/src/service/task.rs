@@ -2,22 +2,31 @@ use std::{
     collections::HashMap,
     fs::{exists, read_to_string, write},
     path::PathBuf,
+    time::Instant,
 };
 
-use aster_loader::ActionProvider;
+use aster_loader::{reload, ActionProvider};
+use chrono::Local;
 use common::{
-    action::{entry::ActionEntry, error::ActionError, Action},
+    action::{entry::ActionEntry, error::ActionError, Action, ActionTrait},
     application::Application,
-    ty::{CardResult, Data},
+    ty::{CardResult, Data, ERROR_VARIANT},
     utils::get_uid,
 };
+use alert::{notify_run, TaskNotifyManager};
 use error::TaskError;
+use history::{RunHistoryManager, RunRecord, RunStatus};
+use lifecycle::TaskLifecycleManager;
 use log::{debug, info};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
-use super::trigger::Trigger;
+use super::{metrics, trigger::Trigger};
+use crate::logger::context as log_context;
+pub mod alert;
 pub mod error;
+pub mod history;
+pub mod lifecycle;
 pub mod scheduler;
 
 pub trait TaskManager {
@@ -66,8 +75,21 @@ impl TaskManager for Application {
 
     fn add_task(
         task_info: TaskInfo,
-        workflow: HashMap<String, ActionEntry>,
+        mut workflow: HashMap<String, ActionEntry>,
     ) -> Result<String, TaskError> {
+        // 内联卡片的参数按当前版本填写，记录版本以便之后迁移
+        for entry in workflow.values_mut() {
+            if let ActionEntry::Inline {
+                r#type, version, ..
+            } = entry
+            {
+                if version.is_none() {
+                    *version = Action::get_action_instance_from_type(r#type)
+                        .ok()
+                        .map(|instance| instance.version());
+                }
+            }
+        }
         let mut task_list = Self::get_task_list()?;
         let task_id = get_uid();
 
@@ -137,10 +159,7 @@ pub fn create_task(
 
 #[tauri::command]
 pub fn remove_task(task_id: String) -> Result<(), String> {
-    let mut task_list = Application::get_task_list().map_err(|e| e.to_string())?;
-    task_list.retain(|t| &t.id != &task_id);
-    Application::update_task_list(&task_list).map_err(|e| e.to_string())?;
-    Ok(())
+    Task::remove(&task_id).map_err(|e| e.to_string())
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -171,33 +190,78 @@ pub struct TaskInstance {
     name: String,
     context: HashMap<String, Data>,
     workflow: HashMap<String, Action>,
+    /// 由通知触发时，依次发出通知的任务 id
+    alert_chain: Vec<String>,
 }
 
 impl TaskInstance {
+    /// 在运行前向 context 中放入数据
+    pub fn with_context(mut self, key: &str, data: Data) -> Self {
+        self.context.insert(key.to_string(), data);
+        self
+    }
+    /// 记录触发本次运行的通知链，本次运行的通知不会再回到链中的任务
+    pub fn with_alert_chain(mut self, chain: Vec<String>) -> Self {
+        self.alert_chain = chain;
+        self
+    }
     pub fn run(mut self) -> Result<(), TaskError> {
         info!(
             "Run workflow {{{}}}({}): {:?}",
             &self.name, &self.id, &self.workflow
         );
 
+        // 开发模式下动作包只在两次运行之间重载
+        let _run = reload::enter_run();
+        let mut record = RunRecord::start(&self.id, &self.name);
+        let started_at = Instant::now();
+        metrics::run_started();
+        // 本次运行中的日志都带上任务与运行 id
+        let _log_scope = log_context::enter_run(&self.id, &record.run_id);
         let mut current = self.workflow.get("trigger");
+        // 错误分支只进入一次，错误分支中的卡片再失败时结束运行，避免循环
+        let mut in_error_branch = false;
 
         while let Some(action) = current {
+            log_context::set_action(Some(&action.id));
             // 执行action，传入context
-            let CardResult { variant, data } = match action.run(&self.context) {
+            let action_started_at = Instant::now();
+            let result = action.run(&self.context);
+            metrics::action_finished(&action.id, action_started_at.elapsed());
+            let CardResult { variant, mut data } = match result {
                 Ok(data) => data,
                 Err(e) => {
                     let ActionError::RunActionCardError(e) = e else {
                         log::error!("unknow error {}", &e);
+                        record.fail(&action.id, e.to_string());
                         break;
                     };
                     self.context
-                        .insert(action.id.clone(), Data::Any(json!({ "\0error": e })));
+                        .insert(action.id.clone(), Data::Any(json!({ "\0error": &e })));
+                    // 连接了错误分支时交由其处理，只有当前卡片失败
+                    let error_branch = self
+                        .workflow
+                        .get(&format!("{}:{}", &self.id, ERROR_VARIANT))
+                        .filter(|_| !in_error_branch);
+                    if let Some(next) = error_branch {
+                        log::warn!(
+                            "Action {} failed, run its error branch: {}",
+                            &action.id,
+                            &e
+                        );
+                        in_error_branch = true;
+                        current = Some(next);
+                        continue;
+                    }
+                    record.fail(&action.id, e);
                     break;
                 }
             };
 
             info!("Run action successfully, result: {}", &data);
+            if let Some(usage) = data.take_usage() {
+                record.add_usage(&usage);
+            }
 
             info!("Insert context");
             // 将结果存入context
@@ -207,6 +271,23 @@ impl TaskInstance {
 
             current = self.workflow.get(&format!("{}:{}", &self.id, variant))
         }
+        log_context::set_action(None);
+
+        if record.status.is_none() {
+            record.succeed();
+        }
+        metrics::run_finished(
+            &self.id,
+            record.status == Some(RunStatus::Success),
+            started_at.elapsed(),
+        );
+        // 记录与通知失败不影响工作流本身
+        if let Err(e) = Application::add_run_record(&record) {
+            log::error!("{}", e);
+        }
+        if let Err(e) = notify_run(&record, &self.alert_chain) {
+            log::error!("{}", e);
+        }
 
         Ok(())
     }
@@ -217,22 +298,30 @@ impl Task {
         let task = Self::find_from_id(&task_id);
         match task {
             Some(task) => {
-                if task.info.enabled {
-                    let workflow = Action::create_workflow(&task.workflow).unwrap();
-                    let task_instance = TaskInstance {
-                        id: task_id.clone(),
-                        name: task.info.name.clone(),
-                        context: HashMap::new(),
-                        workflow,
-                    };
-                    Ok(vec![task_instance])
+                // 状态与运行窗口在触发时再次检查，避免调度器重启前的竞态
+                if task.lifecycle().can_run(&Local::now()) {
+                    Ok(vec![task.create_task_instance()?])
                 } else {
+                    debug!("Task {} is inactive or outside its run windows", &task_id);
                     Ok(vec![])
                 }
             }
             None => Err(TaskError::TaskNotFoundError(task_id.clone())),
         }
     }
+    /// 创建任务实例，不检查任务状态与运行窗口
+    pub fn create_task_instance(&self) -> Result<TaskInstance, TaskError> {
+        let workflow = Action::create_workflow(&self.workflow)
+            .map_err(|e| TaskError::CreateTaskError(self.id.clone(), e.to_string()))?;
+        metrics::run_queued(1);
+        Ok(TaskInstance {
+            id: self.id.clone(),
+            name: self.info.name.clone(),
+            context: HashMap::new(),
+            workflow,
+            alert_chain: vec![],
+        })
+    }
     pub async fn setup(&self) -> Result<(), TaskError> {
         let task_id = self.id.clone();
 
@@ -240,10 +329,10 @@ impl Task {
         for trigger_id in &self.info.trigger {
             let trigger = Trigger::from_id(trigger_id)
                 .map_err(|e| TaskError::SetupTaskError(self.id.clone(), e.to_string()))?;
-            trigger
-                .setup(task_id.clone())
-                .await
-                .map_err(|e| TaskError::SetupTaskError(self.id.clone(), e.to_string()))?;
+            metrics::trigger_armed(trigger_id);
+            let result = trigger.setup(task_id.clone()).await;
+            metrics::trigger_disarmed(trigger_id);
+            result.map_err(|e| TaskError::SetupTaskError(self.id.clone(), e.to_string()))?;
         }
 
         Ok(())
@@ -255,6 +344,21 @@ impl Task {
             .find(|task| task.info.trigger.contains(&id.to_string()));
         task.cloned()
     }
+    /// 删除任务及其启动项、状态与通知规则
+    pub fn remove(task_id: &str) -> Result<(), TaskError> {
+        let mut task_list = Application::get_task_list()?;
+        task_list.retain(|t| &t.id != task_id);
+        Application::update_task_list(&task_list)?;
+
+        let mut setup_list = Application::get_setup_list()?;
+        setup_list.iter_mut().for_each(|setup| setup.task.retain(|id| id != task_id));
+        setup_list.retain(|setup| !setup.task.is_empty());
+        Application::update_setup_list(&setup_list)?;
+
+        Application::remove_task_lifecycle(task_id)?;
+        Application::remove_notify_rules(task_id)?;
+        Ok(())
+    }
     pub fn find_from_id(id: &str) -> Option<Task> {
         let task_list = Application::get_task_list().ok()?;
         task_list.iter().find(|task| task.id == id).cloned()
@@ -0,0 +1,342 @@
+use std::{
+    collections::HashMap,
+    fs::{read_to_string, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    thread,
+    time::Duration,
+};
+
+use common::{
+    application::Application,
+    ty::Data,
+    utils::{lock_file, update_file},
+};
+use notify::RecursiveMode;
+use notify_debouncer_full::new_debouncer;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_http::reqwest;
+use tauri_plugin_notification::NotificationExt;
+
+use super::{
+    error::TaskError,
+    history::{RunHistoryManager, RunRecord, RunStatus},
+    Task,
+};
+
+/// 通知的触发条件
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(tag = "type")]
+pub enum NotifyWhen {
+    Failure,
+    Success,
+    /// 连续失败达到 count 次时触发，之后每累计 count 次再触发一次
+    ConsecutiveFailures { count: u32 },
+}
+
+/// 通知的投递渠道
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(tag = "type")]
+pub enum NotifyChannel {
+    /// 桌面通知，由 UI 进程负责弹出
+    Desktop,
+    /// 以 POST JSON 的方式调用 webhook
+    Webhook { url: String },
+    /// 运行另一个任务，通知内容以 `alert` 放入其 context
+    RunTask { task_id: String },
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct NotifyRule {
+    pub when: NotifyWhen,
+    pub channel: NotifyChannel,
+}
+
+impl NotifyRule {
+    fn matches(&self, status: RunStatus, consecutive_failures: u32) -> bool {
+        match (&self.when, status) {
+            (NotifyWhen::Success, RunStatus::Success) => true,
+            (NotifyWhen::Failure, RunStatus::Failure) => true,
+            (NotifyWhen::ConsecutiveFailures { count }, RunStatus::Failure) => {
+                *count > 0 && consecutive_failures % count == 0
+            }
+            _ => false,
+        }
+    }
+}
+
+/// 发送给各渠道的通知内容
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Notification {
+    pub task_id: String,
+    pub task_name: String,
+    pub run_id: String,
+    pub status: RunStatus,
+    pub failed_node: Option<String>,
+    pub error: Option<String>,
+    pub consecutive_failures: u32,
+    /// 通知经过的任务 id，最后一个为发出本通知的任务
+    pub chain: Vec<String>,
+}
+
+impl Notification {
+    fn title(&self) -> String {
+        match self.status {
+            RunStatus::Success => format!("Task {} succeeded", &self.task_name),
+            RunStatus::Failure => format!("Task {} failed", &self.task_name),
+        }
+    }
+    fn body(&self) -> String {
+        match self.status {
+            RunStatus::Success => format!("Run {}", &self.run_id),
+            RunStatus::Failure => format!(
+                "Run {} failed at {} ({} in a row): {}",
+                &self.run_id,
+                self.failed_node.as_deref().unwrap_or("unknown"),
+                self.consecutive_failures,
+                self.error.as_deref().unwrap_or_default()
+            ),
+        }
+    }
+}
+
+pub trait TaskNotifyManager {
+    fn get_notify_rule_file() -> PathBuf;
+    fn get_notify_rule_map() -> Result<HashMap<String, Vec<NotifyRule>>, TaskError>;
+    fn get_notify_rules(task_id: &str) -> Result<Vec<NotifyRule>, TaskError>;
+    fn set_notify_rules(task_id: &str, rules: Vec<NotifyRule>) -> Result<(), TaskError>;
+    fn remove_notify_rules(task_id: &str) -> Result<(), TaskError>;
+}
+
+impl TaskNotifyManager for Application {
+    fn get_notify_rule_file() -> PathBuf {
+        Self::get_path("task_notify.json")
+    }
+    fn get_notify_rule_map() -> Result<HashMap<String, Vec<NotifyRule>>, TaskError> {
+        let path = Self::get_notify_rule_file();
+        let content = read_to_string(&path)
+            .map_err(|e| TaskError::ReadTaskFileError(path.clone(), e.to_string()))?;
+        if content.trim().is_empty() {
+            return Ok(HashMap::new());
+        }
+        serde_json::from_str(&content)
+            .map_err(|e| TaskError::ParseTaskFileError(path.clone(), e.to_string()))
+    }
+    fn get_notify_rules(task_id: &str) -> Result<Vec<NotifyRule>, TaskError> {
+        Ok(Self::get_notify_rule_map()?
+            .remove(task_id)
+            .unwrap_or_default())
+    }
+    fn set_notify_rules(task_id: &str, rules: Vec<NotifyRule>) -> Result<(), TaskError> {
+        update_file(&Self::get_notify_rule_file(), |content| {
+            let mut map: HashMap<String, Vec<NotifyRule>> = match content.trim() {
+                "" => HashMap::new(),
+                content => serde_json::from_str(content).map_err(io::Error::other)?,
+            };
+            if rules.is_empty() {
+                map.remove(task_id);
+            } else {
+                map.insert(task_id.to_string(), rules);
+            }
+            Ok((serde_json::to_string(&map)?, ()))
+        })
+        .map_err(|e| TaskError::NotifyError(e.to_string()))
+    }
+    fn remove_notify_rules(task_id: &str) -> Result<(), TaskError> {
+        Self::set_notify_rules(task_id, vec![])
+    }
+}
+
+// 服务进程没有界面，桌面通知先写入收件箱，由 UI 进程取出后弹出
+//
+// 收件箱每行一条通知，追加与清空都在原文件上进行，UI 进程对文件的监听不会失效
+pub trait NotificationOutbox {
+    fn get_notification_outbox_file() -> PathBuf;
+    fn push_notification(notification: &Notification) -> Result<(), TaskError>;
+    fn take_notifications() -> Result<Vec<Notification>, TaskError>;
+}
+
+fn outbox_error(e: impl ToString) -> TaskError {
+    TaskError::NotifyError(e.to_string())
+}
+
+impl NotificationOutbox for Application {
+    fn get_notification_outbox_file() -> PathBuf {
+        Self::get_path(".notification")
+    }
+    fn push_notification(notification: &Notification) -> Result<(), TaskError> {
+        let path = Self::get_notification_outbox_file();
+        let _lock = lock_file(&path).map_err(outbox_error)?;
+        let line = serde_json::to_string(notification).map_err(outbox_error)?;
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .map_err(outbox_error)?;
+        writeln!(file, "{}", line).map_err(outbox_error)
+    }
+    fn take_notifications() -> Result<Vec<Notification>, TaskError> {
+        let path = Self::get_notification_outbox_file();
+        let _lock = lock_file(&path).map_err(outbox_error)?;
+        let content = read_to_string(&path).map_err(outbox_error)?;
+        if content.trim().is_empty() {
+            return Ok(vec![]);
+        }
+        OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .open(&path)
+            .map_err(outbox_error)?;
+
+        let mut list = vec![];
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            match serde_json::from_str::<Notification>(line) {
+                Ok(notification) => list.push(notification),
+                Err(e) => log::error!("Skip invalid notification {}: {}", line, e),
+            }
+        }
+        Ok(list)
+    }
+}
+
+/// 根据任务的通知规则投递本次运行的结果，chain 为触发本次运行的通知链
+pub fn notify_run(record: &RunRecord, chain: &[String]) -> Result<(), TaskError> {
+    let Some(status) = record.status else {
+        return Ok(());
+    };
+    let rules = Application::get_notify_rules(&record.task_id)?;
+    if rules.is_empty() {
+        return Ok(());
+    }
+    let consecutive_failures = Application::get_consecutive_failures(&record.task_id)?;
+    let notification = Notification {
+        task_id: record.task_id.clone(),
+        task_name: record.task_name.clone(),
+        run_id: record.run_id.clone(),
+        status,
+        failed_node: record.failed_node.clone(),
+        error: record.error.clone(),
+        consecutive_failures,
+        chain: chain
+            .iter()
+            .chain(std::iter::once(&record.task_id))
+            .cloned()
+            .collect(),
+    };
+
+    for rule in rules
+        .iter()
+        .filter(|rule| rule.matches(status, consecutive_failures))
+    {
+        if let Err(e) = deliver(&rule.channel, &notification) {
+            log::error!(
+                "Failed to deliver notification of task {}: {}",
+                &record.task_id,
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+fn deliver(channel: &NotifyChannel, notification: &Notification) -> Result<(), TaskError> {
+    match channel {
+        NotifyChannel::Desktop => Application::push_notification(notification),
+        NotifyChannel::Webhook { url } => {
+            let url = url.clone();
+            let notification = notification.clone();
+            tauri::async_runtime::spawn(async move {
+                let res = reqwest::Client::new()
+                    .post(&url)
+                    .json(&notification)
+                    .send()
+                    .await
+                    .and_then(|res| res.error_for_status());
+                if let Err(e) = res {
+                    log::error!("Failed to call webhook {}: {}", &url, e);
+                }
+            });
+            Ok(())
+        }
+        NotifyChannel::RunTask { task_id } => {
+            // 避免任务之间通过通知循环触发，如 A -> B -> A
+            if notification.chain.contains(task_id) {
+                return Err(TaskError::NotifyError(format!(
+                    "task {} is already in the notification chain {}",
+                    task_id,
+                    notification.chain.join(" -> ")
+                )));
+            }
+            let instances = Task::init_task_instance(task_id.clone())?;
+            let alert = match serde_json::to_value(notification) {
+                Ok(Value::Object(map)) => map,
+                _ => Map::new(),
+            };
+            let chain = notification.chain.clone();
+            thread::spawn(move || {
+                for instance in instances {
+                    let instance = instance
+                        .with_context("alert", Data::Json(alert.clone()))
+                        .with_alert_chain(chain.clone());
+                    if let Err(e) = instance.run() {
+                        log::error!("Failed to run alert task: {}", e);
+                    }
+                }
+            });
+            Ok(())
+        }
+    }
+}
+
+/// UI 进程监听通知收件箱并弹出桌面通知
+pub fn watch_notification_outbox<R: Runtime>(app: AppHandle<R>) {
+    thread::spawn(move || {
+        let path = Application::get_notification_outbox_file();
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut debouncer = match new_debouncer(Duration::from_millis(200), None, tx) {
+            Ok(debouncer) => debouncer,
+            Err(e) => {
+                log::error!("Failed to watch notification outbox: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = debouncer.watch(path.clone(), RecursiveMode::NonRecursive) {
+            log::error!("Failed to watch notification outbox: {}", e);
+            return;
+        }
+        for result in rx {
+            if result.is_err() {
+                continue;
+            }
+            let notifications = match Application::take_notifications() {
+                Ok(notifications) => notifications,
+                Err(e) => {
+                    log::error!("Failed to read notification outbox: {}", e);
+                    continue;
+                }
+            };
+            for notification in notifications {
+                if let Err(e) = app
+                    .notification()
+                    .builder()
+                    .title(notification.title())
+                    .body(notification.body())
+                    .show()
+                {
+                    log::error!("Failed to show notification: {}", e);
+                }
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_task_notify_rules(task_id: String) -> Result<Vec<NotifyRule>, String> {
+    Application::get_notify_rules(&task_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_task_notify_rules(task_id: String, rules: Vec<NotifyRule>) -> Result<(), String> {
+    Application::set_notify_rules(&task_id, rules).map_err(|e| e.to_string())
+}
@@ -18,4 +18,12 @@ pub enum TaskError {
     ParseTaskFileError(PathBuf, String),
     #[error("Failed to update task list {0}")]
     UpdateTaskListError(String),
+    #[error("Failed to update task state {0}")]
+    UpdateTaskStateError(String),
+    #[error("Invalid run window: {0}")]
+    InvalidRunWindowError(String),
+    #[error("Failed to update run history {0}")]
+    RunHistoryError(String),
+    #[error("Failed to notify {0}")]
+    NotifyError(String),
 }
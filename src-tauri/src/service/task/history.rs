@@ -0,0 +1,127 @@
+use std::{collections::HashMap, fs::read_to_string, io, path::PathBuf};
+
+use chrono::Local;
+use common::{
+    application::Application,
+    ty::TokenUsage,
+    utils::{get_uid, update_file},
+};
+use serde::{Deserialize, Serialize};
+
+use super::error::TaskError;
+
+/// 每个任务最多保留的运行记录数
+const MAX_RECORDS_PER_TASK: usize = 100;
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub enum RunStatus {
+    Success,
+    Failure,
+}
+
+/// 一次工作流运行的记录
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RunRecord {
+    pub run_id: String,
+    pub task_id: String,
+    pub task_name: String,
+    /// 开始与结束时间（毫秒时间戳）
+    pub started_at: i64,
+    pub finished_at: Option<i64>,
+    pub status: Option<RunStatus>,
+    /// 失败的节点（action id）
+    pub failed_node: Option<String>,
+    pub error: Option<String>,
+    /// 本次运行中各节点消耗的 token 总量，没有节点调用模型时为空
+    #[serde(default)]
+    pub usage: Option<TokenUsage>,
+}
+
+impl RunRecord {
+    pub fn start(task_id: &str, task_name: &str) -> Self {
+        RunRecord {
+            run_id: get_uid(),
+            task_id: task_id.to_string(),
+            task_name: task_name.to_string(),
+            started_at: Local::now().timestamp_millis(),
+            finished_at: None,
+            status: None,
+            failed_node: None,
+            error: None,
+            usage: None,
+        }
+    }
+    pub fn succeed(&mut self) {
+        self.finished_at = Some(Local::now().timestamp_millis());
+        self.status = Some(RunStatus::Success);
+    }
+    pub fn add_usage(&mut self, usage: &TokenUsage) {
+        self.usage.get_or_insert_default().add(usage);
+    }
+    pub fn fail(&mut self, node: &str, error: String) {
+        self.finished_at = Some(Local::now().timestamp_millis());
+        self.status = Some(RunStatus::Failure);
+        self.failed_node = Some(node.to_string());
+        self.error = Some(error);
+    }
+}
+
+pub trait RunHistoryManager {
+    fn get_run_history_file() -> PathBuf;
+    fn get_run_history_map() -> Result<HashMap<String, Vec<RunRecord>>, TaskError>;
+    fn get_run_history(task_id: &str) -> Result<Vec<RunRecord>, TaskError>;
+    fn add_run_record(record: &RunRecord) -> Result<(), TaskError>;
+    /// 统计任务最近连续失败的次数
+    fn get_consecutive_failures(task_id: &str) -> Result<u32, TaskError>;
+}
+
+impl RunHistoryManager for Application {
+    fn get_run_history_file() -> PathBuf {
+        Self::get_path("run_history.json")
+    }
+    fn get_run_history_map() -> Result<HashMap<String, Vec<RunRecord>>, TaskError> {
+        let path = Self::get_run_history_file();
+        let content = read_to_string(&path)
+            .map_err(|e| TaskError::ReadTaskFileError(path.clone(), e.to_string()))?;
+        if content.trim().is_empty() {
+            return Ok(HashMap::new());
+        }
+        serde_json::from_str(&content)
+            .map_err(|e| TaskError::ParseTaskFileError(path.clone(), e.to_string()))
+    }
+    fn get_run_history(task_id: &str) -> Result<Vec<RunRecord>, TaskError> {
+        Ok(Self::get_run_history_map()?
+            .remove(task_id)
+            .unwrap_or_default())
+    }
+    fn add_run_record(record: &RunRecord) -> Result<(), TaskError> {
+        // 多个任务线程与 UI 进程可能同时写入
+        update_file(&Self::get_run_history_file(), |content| {
+            let mut map: HashMap<String, Vec<RunRecord>> = match content.trim() {
+                "" => HashMap::new(),
+                content => serde_json::from_str(content).map_err(io::Error::other)?,
+            };
+            let records = map.entry(record.task_id.clone()).or_default();
+            records.push(record.clone());
+            if records.len() > MAX_RECORDS_PER_TASK {
+                records.drain(..records.len() - MAX_RECORDS_PER_TASK);
+            }
+            Ok((serde_json::to_string(&map)?, ()))
+        })
+        .map_err(|e| TaskError::RunHistoryError(e.to_string()))
+    }
+    fn get_consecutive_failures(task_id: &str) -> Result<u32, TaskError> {
+        let records = Self::get_run_history(task_id)?;
+        let count = records
+            .iter()
+            .rev()
+            .take_while(|record| record.status == Some(RunStatus::Failure))
+            .count();
+        Ok(count as u32)
+    }
+}
+
+#[tauri::command]
+pub fn get_run_history(task_id: String) -> Result<Vec<RunRecord>, String> {
+    Application::get_run_history(&task_id).map_err(|e| e.to_string())
+}
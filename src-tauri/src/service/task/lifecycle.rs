@@ -0,0 +1,309 @@
+use std::{
+    collections::HashMap,
+    fs::{read_to_string, write},
+    path::PathBuf,
+};
+
+use chrono::{DateTime, Datelike, Local, NaiveTime};
+use common::application::Application;
+use serde::{Deserialize, Serialize};
+
+use super::{error::TaskError, Task};
+
+const TIME_FORMAT: &str = "%H:%M";
+
+/// 任务的生命周期状态，由调度器统一管理
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum TaskState {
+    /// 正常启用，触发器会被装配
+    Enabled,
+    /// 暂停至指定时间（毫秒时间戳），到期后自动恢复
+    Paused { until: i64 },
+    /// 停用，触发器不会被装配
+    Disabled,
+    /// 归档，与停用相同，但不再在任务列表中展示
+    Archived,
+}
+
+impl Default for TaskState {
+    fn default() -> Self {
+        TaskState::Enabled
+    }
+}
+
+/// 允许运行的时间窗口，例如工作日 09:00 - 18:00
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RunWindow {
+    /// 允许的星期，1 表示周一，7 表示周日；为空则表示每天
+    #[serde(default)]
+    pub days: Vec<u32>,
+    /// 开始时间，格式为 HH:MM
+    pub start: String,
+    /// 结束时间，格式为 HH:MM，早于开始时间时表示跨越零点
+    pub end: String,
+}
+
+impl RunWindow {
+    pub fn validate(&self) -> Result<(), TaskError> {
+        if let Some(day) = self.days.iter().find(|day| !(1..=7).contains(*day)) {
+            return Err(TaskError::InvalidRunWindowError(format!(
+                "day {} is out of range 1-7",
+                day
+            )));
+        }
+        self.parse_time()?;
+        Ok(())
+    }
+
+    fn parse_time(&self) -> Result<(NaiveTime, NaiveTime), TaskError> {
+        let parse = |time: &str| {
+            NaiveTime::parse_from_str(time, TIME_FORMAT)
+                .map_err(|e| TaskError::InvalidRunWindowError(format!("{}: {}", time, e)))
+        };
+        Ok((parse(&self.start)?, parse(&self.end)?))
+    }
+
+    pub fn contains(&self, now: &DateTime<Local>) -> bool {
+        let Ok((start, end)) = self.parse_time() else {
+            return false;
+        };
+        let time = now.time();
+        let weekday = now.weekday().number_from_monday();
+        if start <= end {
+            (self.days.is_empty() || self.days.contains(&weekday)) && start <= time && time < end
+        } else if time >= start {
+            // 跨越零点的窗口，前半段属于当天
+            self.days.is_empty() || self.days.contains(&weekday)
+        } else if time < end {
+            // 后半段属于前一天的窗口
+            let yesterday = now.weekday().pred().number_from_monday();
+            self.days.is_empty() || self.days.contains(&yesterday)
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct TaskLifecycle {
+    #[serde(default)]
+    pub state: TaskState,
+    #[serde(default)]
+    pub windows: Vec<RunWindow>,
+}
+
+impl TaskLifecycle {
+    /// 触发器是否需要被装配
+    pub fn is_armed(&self, now: &DateTime<Local>) -> bool {
+        match self.state {
+            TaskState::Enabled => true,
+            TaskState::Paused { until } => until <= now.timestamp_millis(),
+            TaskState::Disabled | TaskState::Archived => false,
+        }
+    }
+    /// 当前时间是否位于允许的运行窗口内
+    pub fn in_window(&self, now: &DateTime<Local>) -> bool {
+        self.windows.is_empty() || self.windows.iter().any(|window| window.contains(now))
+    }
+    pub fn can_run(&self, now: &DateTime<Local>) -> bool {
+        self.is_armed(now) && self.in_window(now)
+    }
+}
+
+pub trait TaskLifecycleManager {
+    fn get_task_lifecycle_file() -> PathBuf;
+    fn get_task_lifecycle_map() -> Result<HashMap<String, TaskLifecycle>, TaskError>;
+    fn update_task_lifecycle_map(map: &HashMap<String, TaskLifecycle>) -> Result<(), TaskError>;
+    fn update_task_lifecycle(
+        task_id: &str,
+        update: impl FnOnce(&mut TaskLifecycle),
+    ) -> Result<TaskLifecycle, TaskError>;
+    fn remove_task_lifecycle(task_id: &str) -> Result<(), TaskError>;
+}
+
+impl TaskLifecycleManager for Application {
+    fn get_task_lifecycle_file() -> PathBuf {
+        Self::get_path("task_state.json")
+    }
+    fn get_task_lifecycle_map() -> Result<HashMap<String, TaskLifecycle>, TaskError> {
+        let path = Self::get_task_lifecycle_file();
+        let content = read_to_string(&path)
+            .map_err(|e| TaskError::ReadTaskFileError(path.clone(), e.to_string()))?;
+        if content.trim().is_empty() {
+            return Ok(HashMap::new());
+        }
+        serde_json::from_str(&content)
+            .map_err(|e| TaskError::ParseTaskFileError(path.clone(), e.to_string()))
+    }
+    fn update_task_lifecycle_map(map: &HashMap<String, TaskLifecycle>) -> Result<(), TaskError> {
+        let path = Self::get_task_lifecycle_file();
+        let content = serde_json::to_string(map)
+            .map_err(|e| TaskError::UpdateTaskStateError(e.to_string()))?;
+        write(path, content).map_err(|e| TaskError::UpdateTaskStateError(e.to_string()))?;
+        Ok(())
+    }
+    /// 更新任务的生命周期，不存在时以任务原有的 enabled 字段作为初始状态
+    fn update_task_lifecycle(
+        task_id: &str,
+        update: impl FnOnce(&mut TaskLifecycle),
+    ) -> Result<TaskLifecycle, TaskError> {
+        let task = Task::find_from_id(task_id)
+            .ok_or_else(|| TaskError::TaskNotFoundError(task_id.to_string()))?;
+        let mut map = Self::get_task_lifecycle_map()?;
+        let lifecycle = map
+            .entry(task_id.to_string())
+            .or_insert_with(|| task.default_lifecycle());
+        update(lifecycle);
+        let lifecycle = lifecycle.clone();
+        Self::update_task_lifecycle_map(&map)?;
+        log::info!("Task {} lifecycle updated: {:?}", task_id, &lifecycle);
+        Ok(lifecycle)
+    }
+    fn remove_task_lifecycle(task_id: &str) -> Result<(), TaskError> {
+        let mut map = Self::get_task_lifecycle_map()?;
+        if map.remove(task_id).is_some() {
+            Self::update_task_lifecycle_map(&map)?;
+        }
+        Ok(())
+    }
+}
+
+impl Task {
+    fn default_lifecycle(&self) -> TaskLifecycle {
+        TaskLifecycle {
+            state: if self.info.enabled {
+                TaskState::Enabled
+            } else {
+                TaskState::Disabled
+            },
+            windows: vec![],
+        }
+    }
+    /// 获取任务当前的生命周期
+    pub fn lifecycle(&self) -> TaskLifecycle {
+        match Application::get_task_lifecycle_map() {
+            Ok(mut map) => map
+                .remove(&self.id)
+                .unwrap_or_else(|| self.default_lifecycle()),
+            Err(e) => {
+                log::warn!("Failed to read lifecycle of task {}: {}", &self.id, e);
+                self.default_lifecycle()
+            }
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_task_lifecycle(task_id: String) -> Result<TaskLifecycle, String> {
+    let task = Task::find_from_id(&task_id)
+        .ok_or_else(|| TaskError::TaskNotFoundError(task_id.clone()).to_string())?;
+    Ok(task.lifecycle())
+}
+
+#[tauri::command]
+pub fn set_task_state(task_id: String, state: TaskState) -> Result<TaskLifecycle, String> {
+    Application::update_task_lifecycle(&task_id, |lifecycle| lifecycle.state = state)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_task_windows(
+    task_id: String,
+    windows: Vec<RunWindow>,
+) -> Result<TaskLifecycle, String> {
+    for window in &windows {
+        window.validate().map_err(|e| e.to_string())?;
+    }
+    Application::update_task_lifecycle(&task_id, |lifecycle| lifecycle.windows = windows)
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    // 2026-10-16 为周五
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2026, 10, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    fn window(days: Vec<u32>, start: &str, end: &str) -> RunWindow {
+        RunWindow {
+            days,
+            start: start.to_string(),
+            end: end.to_string(),
+        }
+    }
+
+    #[test]
+    fn contains_same_day_window() {
+        let window = window(vec![1, 2, 3, 4, 5], "09:00", "18:00");
+        assert!(window.contains(&at(16, 9, 0)));
+        assert!(window.contains(&at(16, 17, 59)));
+        assert!(!window.contains(&at(16, 18, 0)));
+        assert!(!window.contains(&at(17, 12, 0)));
+    }
+
+    #[test]
+    fn contains_window_across_midnight() {
+        let window = window(vec![], "22:00", "06:00");
+        assert!(window.contains(&at(16, 22, 0)));
+        assert!(window.contains(&at(17, 5, 59)));
+        assert!(!window.contains(&at(17, 6, 0)));
+        assert!(!window.contains(&at(16, 12, 0)));
+    }
+
+    #[test]
+    fn window_after_midnight_belongs_to_the_previous_day() {
+        // 周五晚上开始，延续到周六早上
+        let window = window(vec![5], "22:00", "06:00");
+        assert!(window.contains(&at(16, 23, 0)));
+        assert!(window.contains(&at(17, 3, 0)));
+        assert!(!window.contains(&at(17, 23, 0)));
+        // 周五凌晨属于周四的窗口
+        assert!(!window.contains(&at(16, 3, 0)));
+    }
+
+    #[test]
+    fn rejects_invalid_windows() {
+        assert!(window(vec![8], "09:00", "18:00").validate().is_err());
+        assert!(window(vec![], "9am", "18:00").validate().is_err());
+        assert!(!window(vec![], "9am", "18:00").contains(&at(16, 12, 0)));
+    }
+
+    #[test]
+    fn resumes_when_pause_expires() {
+        let now = at(16, 12, 0);
+        let paused = |until: DateTime<Local>| TaskLifecycle {
+            state: TaskState::Paused {
+                until: until.timestamp_millis(),
+            },
+            windows: vec![],
+        };
+        assert!(!paused(at(16, 13, 0)).is_armed(&now));
+        assert!(paused(at(16, 12, 0)).is_armed(&now));
+        assert!(paused(at(16, 11, 0)).can_run(&now));
+    }
+
+    #[test]
+    fn runs_only_inside_windows() {
+        let lifecycle = TaskLifecycle {
+            state: TaskState::Enabled,
+            windows: vec![window(vec![], "09:00", "10:00"), window(vec![], "22:00", "06:00")],
+        };
+        assert!(lifecycle.can_run(&at(16, 9, 30)));
+        assert!(lifecycle.can_run(&at(17, 1, 0)));
+        assert!(!lifecycle.can_run(&at(16, 12, 0)));
+
+        let disabled = TaskLifecycle {
+            state: TaskState::Disabled,
+            ..lifecycle
+        };
+        assert!(!disabled.can_run(&at(16, 9, 30)));
+    }
+}
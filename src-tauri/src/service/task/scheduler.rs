@@ -1,13 +1,18 @@
+use chrono::Local;
 use common::application::Application;
 use common::tokio::runtime::{Builder as RuntimeBuilder, Runtime};
 use common::tokio::task::JoinHandle;
+use common::tokio::time::sleep;
 use log::debug;
 use num_cpus;
 use std::time::Duration;
 
-use crate::service::trigger::Trigger;
+use crate::service::{metrics, trigger::Trigger};
 
-use super::{Setup, SetupManager, Task, TaskManager};
+use super::{
+    lifecycle::{TaskLifecycleManager, TaskState},
+    Setup, SetupManager, Task, TaskManager,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum SchedulerError {
@@ -61,11 +66,21 @@ impl TaskScheduler {
             .map_err(|e| SchedulerError::TaskSetupError(e.to_string()))?;
 
         let mut tasks_to_setup = Vec::new();
+        let now = Local::now();
         for task_id in all_task_ids {
-            if let Some(task) = all_tasks.iter().find(|t| t.id == task_id) {
-                tasks_to_setup.push(task.clone());
-            } else {
+            let Some(task) = all_tasks.iter().find(|t| t.id == task_id) else {
                 log::warn!("Task with ID {} not found in task.json", task_id);
+                continue;
+            };
+            // 只为处于活动状态的任务装配触发器
+            let lifecycle = task.lifecycle();
+            if lifecycle.is_armed(&now) {
+                tasks_to_setup.push(task.clone());
+                continue;
+            }
+            log::info!("Task {} is {:?}, skip arming triggers", &task.id, &lifecycle.state);
+            if let TaskState::Paused { until } = lifecycle.state {
+                self.schedule_resume(task.id.clone(), until - now.timestamp_millis());
             }
         }
 
@@ -122,7 +137,21 @@ impl TaskScheduler {
 
         Ok(())
     }
+    /// 暂停到期后恢复任务，状态文件的变更会触发调度器重新装配
+    fn schedule_resume(&self, task_id: String, delay_millis: i64) {
+        let delay = Duration::from_millis(delay_millis.max(0) as u64);
+        self.runtime.spawn(async move {
+            sleep(delay).await;
+            match Application::update_task_lifecycle(&task_id, |lifecycle| {
+                lifecycle.state = TaskState::Enabled
+            }) {
+                Ok(_) => log::info!("Task {} resumed", &task_id),
+                Err(e) => log::error!("Failed to resume task {}: {}", &task_id, e),
+            }
+        });
+    }
     pub async fn shutdown(self) {
+        metrics::clear_triggers();
         for trigger in self.task_triggers {
             match Trigger::from_id(&trigger) {
                 Ok(trigger) => {
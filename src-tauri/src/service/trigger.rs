@@ -4,6 +4,7 @@ use std::{
 };
 
 use error::TriggerError;
+use imap::ImapTrigger;
 use serde::{Deserialize, Serialize};
 use time::{CronTrigger, TickerTrigger};
 
@@ -11,11 +12,16 @@ use common::{application::Application, ty::Data, utils::get_uid};
 
 pub mod command;
 pub mod error;
+pub mod imap;
 pub mod time;
 
+/// 所有内置的触发器类型
+pub const TRIGGER_TYPES: [&str; 3] = ["cron_trigger", "ticker_trigger", "imap_trigger"];
+
 pub enum TriggerType {
     Cron(CronTrigger),
     Ticker(TickerTrigger),
+    Imap(ImapTrigger),
 }
 
 impl TriggerType {
@@ -23,18 +29,21 @@ impl TriggerType {
         match self {
             TriggerType::Cron(trigger) => trigger.setup(args, task_id).await,
             TriggerType::Ticker(trigger) => trigger.setup(args, task_id).await,
+            TriggerType::Imap(trigger) => trigger.setup(args, task_id).await,
         }
     }
     pub fn lit(&self, name: String, args: Data) -> Result<String, TriggerError> {
         match self {
             TriggerType::Cron(trigger) => trigger.lit(name, args),
             TriggerType::Ticker(trigger) => trigger.lit(name, args),
+            TriggerType::Imap(trigger) => trigger.lit(name, args),
         }
     }
     pub async fn shutdown(&self) {
         match self {
             TriggerType::Cron(trigger) => trigger.shutdown().await,
             TriggerType::Ticker(trigger) => trigger.shutdown().await,
+            TriggerType::Imap(trigger) => trigger.shutdown().await,
         }
     }
 }
@@ -100,12 +109,15 @@ impl Trigger {
         Ok(())
     }
     pub fn find(trigger_type: &str) -> TriggerType {
-        let trigger: TriggerType = match trigger_type {
-            "cron_trigger" => TriggerType::Cron(CronTrigger {}),
-            "ticker_trigger" => TriggerType::Ticker(TickerTrigger {}),
-            _ => panic!(),
-        };
-        trigger
+        Self::try_find(trigger_type).unwrap()
+    }
+    pub fn try_find(trigger_type: &str) -> Result<TriggerType, TriggerError> {
+        match trigger_type {
+            "cron_trigger" => Ok(TriggerType::Cron(CronTrigger {})),
+            "ticker_trigger" => Ok(TriggerType::Ticker(TickerTrigger {})),
+            "imap_trigger" => Ok(TriggerType::Imap(ImapTrigger {})),
+            _ => Err(TriggerError::FindTriggerError(trigger_type.to_string())),
+        }
     }
     pub fn remove(id: &str) -> Result<(), TriggerError> {
         let mut trigger_list = Application::get_trigger_list();
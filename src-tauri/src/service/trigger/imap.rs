@@ -0,0 +1,65 @@
+use common::{
+    tokio::{task::spawn_blocking, time::sleep},
+    ty::Data,
+};
+use log::info;
+use mail::imap::{ImapConfig, ImapWatcher};
+
+use crate::service::task::Task;
+
+use super::{error::TriggerError, Trigger, TriggerTrait};
+
+pub struct ImapTrigger {}
+
+impl TriggerTrait for ImapTrigger {
+    fn get_trigger(&self, name: String, args: Data) -> Trigger {
+        self.new_trigger("imap_trigger", name, args)
+    }
+    async fn setup(&self, args: Data, task_id: String) -> Result<(), super::error::TriggerError> {
+        match args.r#as::<ImapConfig>() {
+            Ok(config) => imap_trigger(config, task_id).await,
+            Err(e) => Err(TriggerError::SetupTriggerError(e.to_string())),
+        }
+    }
+}
+
+/// 轮询邮箱，每封新邮件运行一次任务，邮件内容放入 context 的 mail 键
+pub async fn imap_trigger(config: ImapConfig, task_id: String) -> Result<(), TriggerError> {
+    let mut watcher = ImapWatcher::new(config);
+    let interval = watcher.interval();
+    info!("imap trigger setup");
+    loop {
+        // IMAP 客户端是阻塞的，在单独的线程中轮询
+        let (returned, result) = spawn_blocking(move || {
+            let result = watcher.poll();
+            (watcher, result)
+        })
+        .await
+        .map_err(|e| TriggerError::SetupTriggerError(e.to_string()))?;
+        watcher = returned;
+
+        match result {
+            Ok(messages) => {
+                for message in messages {
+                    info!("imap trigger activate");
+                    match Task::init_task_instance(task_id.clone()) {
+                        Ok(task_instance_list) => {
+                            for task_instance in task_instance_list {
+                                task_instance
+                                    .with_context("mail", Data::from_value(message.clone()))
+                                    .run()
+                                    .map_err(|e| {
+                                        TriggerError::RunTaskError(task_id.clone(), e.to_string())
+                                    })?;
+                            }
+                        }
+                        Err(e) => log::error!("imap trigger error: {}", e),
+                    }
+                }
+            }
+            // 网络或登录失败时不停止触发器，等待下次轮询
+            Err(e) => log::error!("imap trigger error: {}", e),
+        }
+        sleep(interval).await;
+    }
+}
@@ -0,0 +1,672 @@
+use std::{
+    fmt::Display,
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::{Arc, LazyLock, Mutex, OnceLock, RwLock},
+    time::{Duration, Instant, SystemTime},
+};
+
+use aster_loader::{
+    get_action_types,
+    plugin::{plugin_paths, set_isolated_resolver, to_card_result, Plugin},
+};
+use common::{
+    action::{
+        error::ActionError,
+        rule::{param_rules, validate_args},
+        Action, ActionTrait,
+    },
+    application::Application,
+    plugin::{PluginCallResult, PluginManifest},
+    ty::{CardResult, Data},
+    utils::get_uid,
+};
+use serde_json::Value;
+use tokio::runtime::Runtime;
+use vase::ipc::{
+    device::traits::DeviceRef, transport::impls::local_socket_new::LocalSocketTransport,
+};
+use vase_macro::{device, handle};
+
+use super::{action::plugin::get_plugin_dir, error::ServiceError};
+use crate::application::config::{ConfigManager, PluginConfig};
+
+/// worker 启动后等待其开始监听的时间
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
+/// 健康检查等待 Pong 的时间
+const PING_TIMEOUT: Duration = Duration::from_secs(3);
+/// 第一次重启前等待的时间，之后每次重启翻倍
+const RESTART_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(60);
+/// 连续重启的次数上限，达到后不再重启，直到重新加载插件
+const MAX_RESTARTS: u32 = 5;
+/// 运行超过该时间的 worker 视为已恢复，之后的重启重新计数
+const STABLE_UPTIME: Duration = Duration::from_secs(60);
+
+static WORKER_ADDRESS: OnceLock<String> = OnceLock::new();
+
+fn worker_address() -> String {
+    WORKER_ADDRESS.get().cloned().unwrap_or_default()
+}
+
+// 服务进程与插件 worker 进程之间的 IPC 设备，服务端为 worker，每个 worker 使用独立的地址
+device!(WorkerDevice {
+    transport: LocalSocketTransport(worker_address()),
+    package: daisytools::worker,
+    keepAlive,
+    ..{
+        pub mod Worker;
+    }
+});
+
+fn route(method: &str) -> String {
+    format!("{}::{}", module_path!(), method)
+}
+
+/// worker 进程中加载的插件
+static PLUGIN: OnceLock<Plugin> = OnceLock::new();
+
+#[handle(WorkerDevice::Worker)]
+fn get_manifest() -> anyhow::Result<PluginManifest> {
+    let plugin = PLUGIN.get().ok_or(anyhow::anyhow!("Plugin is not loaded"))?;
+    Ok(plugin.manifest.clone())
+}
+
+#[handle(WorkerDevice::Worker)]
+async fn run_action(action_type: String, args: Value) -> anyhow::Result<PluginCallResult> {
+    // 插件中的异步 action 会创建自己的运行时，不能在当前运行时的线程中执行
+    let result = tokio::task::spawn_blocking(move || match PLUGIN.get() {
+        Some(plugin) => plugin.call_raw(&action_type, args),
+        None => PluginCallResult::Err {
+            message: "Plugin is not loaded".to_string(),
+        },
+    })
+    .await?;
+    Ok(result)
+}
+
+/// worker 进程入口，加载插件并等待服务进程的调用
+pub fn run_worker(path: PathBuf, address: String) -> Result<(), ServiceError> {
+    let name = path.display().to_string();
+    let worker_error = |e: &dyn Display| ServiceError::WorkerError(name.clone(), e.to_string());
+    let plugin = Plugin::load(&path).map_err(|e| worker_error(&e))?;
+    let _ = PLUGIN.set(plugin);
+    let _ = WORKER_ADDRESS.set(address.clone());
+
+    let runtime = Runtime::new().map_err(|e| worker_error(&e))?;
+    runtime.block_on(async {
+        WorkerDevice::setup().await.map_err(|e| worker_error(&e))?;
+        log::info!("Plugin worker {} is listening on {}", &name, &address);
+        // 服务进程退出时标准输入随之关闭，worker 不会成为孤儿进程
+        let _ = tokio::io::copy(&mut tokio::io::stdin(), &mut tokio::io::sink()).await;
+        log::info!("Service disconnected, plugin worker exits");
+        Ok(())
+    })
+}
+
+/// 驱动所有 worker 连接的运行时，action 可能在任意线程中被调用
+static RUNTIME: LazyLock<Runtime> =
+    LazyLock::new(|| Runtime::new().expect("Failed to create runtime for plugin workers"));
+
+fn block_on<T: Send + 'static>(future: impl Future<Output = T> + Send + 'static) -> T {
+    let (tx, rx) = std::sync::mpsc::channel();
+    RUNTIME.spawn(async move {
+        let _ = tx.send(future.await);
+    });
+    rx.recv().expect("Plugin worker runtime stopped")
+}
+
+/// 正在运行的 worker 进程
+struct WorkerProcess {
+    child: Mutex<Child>,
+    device: _WorkerDevice,
+}
+
+impl WorkerProcess {
+    async fn spawn(path: &Path) -> Result<Self, ServiceError> {
+        let name = path.display().to_string();
+        let worker_error =
+            |e: &dyn Display| ServiceError::WorkerError(name.clone(), e.to_string());
+        let address = format!("daisytools.worker.{}", get_uid());
+        let exe = std::env::current_exe().map_err(|e| worker_error(&e))?;
+        let mut child = Command::new(exe)
+            .arg("worker")
+            .arg(path)
+            .arg("--address")
+            .arg(&address)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| worker_error(&e))?;
+
+        let started_at = std::time::Instant::now();
+        loop {
+            // 插件被拒绝时 worker 将原因写入标准错误后退出
+            if let Ok(Some(status)) = child.try_wait() {
+                let mut reason = String::new();
+                if let Some(mut stderr) = child.stderr.take() {
+                    let _ = stderr.read_to_string(&mut reason);
+                }
+                let reason = match reason.trim() {
+                    "" => format!("exited with {}", status),
+                    reason => reason.to_string(),
+                };
+                return Err(worker_error(&reason));
+            }
+            let mut device = _WorkerDevice {
+                transport: LocalSocketTransport::new_arc(address.clone()),
+            };
+            if DeviceRef::setup(&mut device).await.is_ok() && device.ping().await.is_ok() {
+                // 之后不再读取标准错误，避免管道写满后阻塞 worker
+                drop(child.stderr.take());
+                return Ok(WorkerProcess {
+                    child: Mutex::new(child),
+                    device,
+                });
+            }
+            if started_at.elapsed() > STARTUP_TIMEOUT {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(worker_error(&"did not start in time"));
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    fn exit_status(&self) -> Option<ExitStatus> {
+        self.child.lock().unwrap().try_wait().ok().flatten()
+    }
+
+    async fn is_alive(&self) -> bool {
+        self.exit_status().is_none()
+            && tokio::time::timeout(PING_TIMEOUT, self.device.ping())
+                .await
+                .is_ok_and(|result| result.is_ok())
+    }
+}
+
+impl Drop for WorkerProcess {
+    fn drop(&mut self) {
+        let child = self.child.get_mut().unwrap();
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+/// worker 的连续重启次数与下次允许重启的时间
+#[derive(Debug)]
+struct Backoff {
+    restarts: u32,
+    started_at: Instant,
+    retry_at: Option<Instant>,
+}
+
+impl Backoff {
+    fn new(now: Instant) -> Self {
+        Backoff {
+            restarts: 0,
+            started_at: now,
+            retry_at: None,
+        }
+    }
+
+    /// 返回现在不能重启的原因
+    fn check(&self, now: Instant) -> Result<(), String> {
+        if self.restarts >= MAX_RESTARTS {
+            return Err(format!(
+                "gave up after {} restarts, reload plugins to try again",
+                self.restarts
+            ));
+        }
+        match self.retry_at {
+            Some(retry_at) if now < retry_at => {
+                Err(format!("restarting in {} ms", (retry_at - now).as_millis()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 记录一次重启，返回连续重启的次数
+    fn restart(&mut self, now: Instant) -> u32 {
+        if now - self.started_at >= STABLE_UPTIME {
+            self.restarts = 0;
+        }
+        self.restarts += 1;
+        self.started_at = now;
+        let delay = RESTART_BACKOFF.saturating_mul(1 << (self.restarts - 1).min(16));
+        self.retry_at = Some(now + delay.min(MAX_RESTART_BACKOFF));
+        self.restarts
+    }
+}
+
+/// 运行在独立进程中的插件，worker 退出后在下次调用或健康检查时重启
+///
+/// 连续重启之间的等待时间逐次翻倍，超过次数上限后不再重启
+pub struct Worker {
+    pub manifest: PluginManifest,
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    call_timeout: Duration,
+    process: tokio::sync::Mutex<Option<Arc<WorkerProcess>>>,
+    backoff: Mutex<Backoff>,
+}
+
+impl Worker {
+    async fn start(path: &Path, config: &PluginConfig) -> Result<Self, ServiceError> {
+        let call_timeout = Duration::from_millis(config.call_timeout);
+        let process = WorkerProcess::spawn(path).await?;
+        let manifest = process
+            .device
+            .call::<(), PluginManifest>(route("get_manifest"), ());
+        let manifest = match tokio::time::timeout(call_timeout, manifest).await {
+            Ok(Ok(manifest)) => manifest,
+            Ok(Err(e)) => {
+                return Err(ServiceError::WorkerError(
+                    path.display().to_string(),
+                    e.to_string(),
+                ))
+            }
+            Err(_) => {
+                return Err(ServiceError::WorkerError(
+                    path.display().to_string(),
+                    "no response when reading the manifest".to_string(),
+                ))
+            }
+        };
+        Ok(Worker {
+            manifest,
+            path: path.to_path_buf(),
+            modified: modified(path),
+            call_timeout,
+            process: tokio::sync::Mutex::new(Some(Arc::new(process))),
+            backoff: Mutex::new(Backoff::new(Instant::now())),
+        })
+    }
+
+    /// 重新加载插件时清除重启次数
+    fn reset_restarts(&self) {
+        *self.backoff.lock().unwrap() = Backoff::new(Instant::now());
+    }
+
+    /// 获取正在运行的 worker，已退出时重启
+    async fn process(&self) -> Result<Arc<WorkerProcess>, ServiceError> {
+        let mut current = self.process.lock().await;
+        if let Some(process) = current.as_ref() {
+            match process.exit_status() {
+                None => return Ok(process.clone()),
+                Some(status) => log::warn!(
+                    "Plugin worker {} exited with {}",
+                    &self.manifest.name,
+                    status
+                ),
+            }
+        }
+        *current = None;
+        let restarts = {
+            let now = Instant::now();
+            let mut backoff = self.backoff.lock().unwrap();
+            backoff
+                .check(now)
+                .map_err(|reason| ServiceError::WorkerError(self.manifest.name.clone(), reason))?;
+            backoff.restart(now)
+        };
+        log::info!(
+            "Restarting plugin worker {} ({} restarts)",
+            &self.manifest.name,
+            restarts
+        );
+        if restarts == MAX_RESTARTS {
+            log::warn!(
+                "Plugin worker {} will not be restarted again if it exits",
+                &self.manifest.name
+            );
+        }
+        let process = Arc::new(WorkerProcess::spawn(&self.path).await?);
+        *current = Some(process.clone());
+        Ok(process)
+    }
+
+    /// 结束无响应的 worker，下次调用时重启
+    async fn discard(&self, process: &Arc<WorkerProcess>) {
+        let mut current = self.process.lock().await;
+        if current
+            .as_ref()
+            .is_some_and(|current| Arc::ptr_eq(current, process))
+        {
+            *current = None;
+        }
+        let _ = process.child.lock().unwrap().kill();
+    }
+
+    async fn call(&self, action_type: String, args: Value) -> PluginCallResult {
+        let process = match self.process().await {
+            Ok(process) => process,
+            Err(e) => {
+                return PluginCallResult::Err {
+                    message: e.to_string(),
+                }
+            }
+        };
+        let call = process
+            .device
+            .call::<(String, Value), PluginCallResult>(route("run_action"), (action_type, args));
+        let reason = match with_timeout(self.call_timeout, call).await {
+            Ok(result) => return result,
+            Err(reason) => reason,
+        };
+        // worker 崩溃或无响应时只有当前卡片失败
+        let message = match process.exit_status() {
+            Some(status) => format!(
+                "Plugin worker {} crashed with {}",
+                &self.manifest.name, status
+            ),
+            None => format!("Plugin worker {} failed: {}", &self.manifest.name, reason),
+        };
+        log::error!("{}", &message);
+        self.discard(&process).await;
+        PluginCallResult::Err { message }
+    }
+
+    /// 健康检查，worker 已退出或不响应 Ping 时按退避策略重启
+    async fn check(&self) {
+        let process = self.process.lock().await.clone();
+        if let Some(process) = process {
+            if process.is_alive().await {
+                return;
+            }
+            log::warn!("Plugin worker {} is not responding", &self.manifest.name);
+            self.discard(&process).await;
+        }
+        // 等待退避或已放弃时由之后的检查或调用重启
+        if self.backoff.lock().unwrap().check(Instant::now()).is_err() {
+            return;
+        }
+        if let Err(e) = self.process().await {
+            log::error!("{}", e);
+        }
+    }
+}
+
+/// 等待调用结果，失败或超时时返回原因
+async fn with_timeout<T, E: Display>(
+    timeout: Duration,
+    call: impl Future<Output = Result<T, E>>,
+) -> Result<T, String> {
+    match tokio::time::timeout(timeout, call).await {
+        Ok(Ok(result)) => Ok(result),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err(format!("no response in {} ms", timeout.as_millis())),
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+static WORKERS: LazyLock<RwLock<Vec<Arc<Worker>>>> = LazyLock::new(Default::default);
+
+struct WorkerAction {
+    worker: Arc<Worker>,
+    action_type: String,
+}
+
+impl ActionTrait for WorkerAction {
+    fn get_action(&self, name: String, args: Data) -> Action {
+        self.new_action(&self.action_type, name, args)
+    }
+    fn validate(&self, args: &Data) -> Result<(), ActionError> {
+        let rules = self
+            .worker
+            .manifest
+            .actions
+            .iter()
+            .find(|action| action.action_type == self.action_type)
+            .map(|action| param_rules(&action.meta))
+            .unwrap_or_default();
+        validate_args(&rules, args)
+    }
+    fn version(&self) -> u32 {
+        self.worker
+            .manifest
+            .actions
+            .iter()
+            .find(|action| action.action_type == self.action_type)
+            .and_then(|action| action.meta.get("version")?.as_u64())
+            .map_or(1, |version| version as u32)
+    }
+    fn run(&self, args: Data) -> Result<CardResult, ActionError> {
+        let worker = self.worker.clone();
+        let action_type = self.action_type.clone();
+        let result = block_on(async move { worker.call(action_type, args.to_value()).await });
+        to_card_result(result)
+    }
+}
+
+fn find_action(action_type: &str) -> Option<Box<dyn ActionTrait>> {
+    let workers = WORKERS.read().unwrap();
+    let worker = workers.iter().find(|worker| {
+        worker
+            .manifest
+            .actions
+            .iter()
+            .any(|action| action.action_type == action_type)
+    })?;
+    Some(Box::new(WorkerAction {
+        worker: worker.clone(),
+        action_type: action_type.to_string(),
+    }))
+}
+
+/// 与进程内加载相同，不允许覆盖内置的 action 或其他插件的 action
+fn conflicting_action<'a>(
+    manifest: &'a PluginManifest,
+    builtin: &[&str],
+    others: &[&PluginManifest],
+) -> Option<&'a str> {
+    manifest
+        .actions
+        .iter()
+        .map(|action| action.action_type.as_str())
+        .find(|action_type| {
+            builtin.contains(action_type)
+                || others.iter().any(|other| {
+                    other
+                        .actions
+                        .iter()
+                        .any(|other| other.action_type == *action_type)
+                })
+        })
+}
+
+async fn start_workers(dir: PathBuf, config: PluginConfig) -> Vec<String> {
+    let mut errors = vec![];
+    let previous = WORKERS.read().unwrap().clone();
+    let builtin = get_action_types();
+    let mut workers: Vec<Arc<Worker>> = vec![];
+
+    for path in plugin_paths(&dir) {
+        let unchanged = previous
+            .iter()
+            .find(|worker| worker.path == path && worker.modified == modified(&path));
+        let worker = match unchanged {
+            Some(worker) => {
+                worker.reset_restarts();
+                worker.clone()
+            }
+            None => match Worker::start(&path, &config).await {
+                Ok(worker) => Arc::new(worker),
+                Err(e) => {
+                    errors.push(e.to_string());
+                    continue;
+                }
+            },
+        };
+        let others = workers
+            .iter()
+            .map(|other| &other.manifest)
+            .collect::<Vec<_>>();
+        if let Some(action_type) = conflicting_action(&worker.manifest, &builtin, &others) {
+            errors.push(format!(
+                "Action {} of plugin {} is already provided by another action pack",
+                action_type, &worker.manifest.name
+            ));
+            continue;
+        }
+        workers.push(worker);
+    }
+
+    // 被替换或删除的 worker 在最后一次调用结束后退出
+    *WORKERS.write().unwrap() = workers;
+    errors
+}
+
+/// 健康检查，使用 vase 的 Ping/Pong
+async fn watch_workers(interval: Duration) {
+    loop {
+        tokio::time::sleep(interval).await;
+        let workers = WORKERS.read().unwrap().clone();
+        for worker in workers {
+            worker.check().await;
+        }
+    }
+}
+
+/// 在独立的 worker 进程中加载插件目录中的动作包，返回被拒绝的插件及原因
+pub fn setup_workers() -> Vec<String> {
+    static WATCHING: OnceLock<()> = OnceLock::new();
+    let config = Application::get_config().plugin_config;
+    set_isolated_resolver(find_action);
+    WATCHING.get_or_init(|| {
+        RUNTIME.spawn(watch_workers(Duration::from_millis(config.ping_interval)));
+    });
+    let errors = block_on(start_workers(get_plugin_dir(), config));
+    errors.iter().for_each(|e| log::error!("{}", e));
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use common::plugin::PluginActionMeta;
+    use serde_json::json;
+
+    use super::*;
+
+    fn manifest(name: &str, actions: &[&str]) -> PluginManifest {
+        PluginManifest {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            abi_version: 0,
+            actions: actions
+                .iter()
+                .map(|action_type| PluginActionMeta {
+                    action_type: action_type.to_string(),
+                    meta: json!({}),
+                    branches: json!([]),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn doubles_restart_backoff() {
+        let now = Instant::now();
+        let mut backoff = Backoff::new(now);
+        assert!(backoff.check(now).is_ok());
+
+        assert_eq!(backoff.restart(now), 1);
+        assert!(backoff.check(now).is_err());
+        assert!(backoff.check(now + RESTART_BACKOFF).is_ok());
+
+        let now = now + RESTART_BACKOFF;
+        assert_eq!(backoff.restart(now), 2);
+        assert!(backoff.check(now + RESTART_BACKOFF).is_err());
+        assert!(backoff.check(now + RESTART_BACKOFF * 2).is_ok());
+    }
+
+    #[test]
+    fn gives_up_after_max_restarts() {
+        let mut now = Instant::now();
+        let mut backoff = Backoff::new(now);
+        for _ in 0..MAX_RESTARTS {
+            backoff.check(now).unwrap();
+            backoff.restart(now);
+            // 每次都等到退避结束，但没有稳定运行
+            now += RESTART_BACKOFF * 16;
+        }
+        let reason = backoff.check(now + STABLE_UPTIME).unwrap_err();
+        assert!(reason.starts_with("gave up after 5 restarts"));
+    }
+
+    #[test]
+    fn resets_restarts_after_stable_uptime() {
+        let now = Instant::now();
+        let mut backoff = Backoff::new(now);
+        backoff.restart(now);
+        backoff.restart(now + Duration::from_secs(1));
+        // 上次重启后稳定运行，重新计数
+        assert_eq!(
+            backoff.restart(now + Duration::from_secs(1) + STABLE_UPTIME),
+            1
+        );
+    }
+
+    #[test]
+    fn finds_conflicting_actions() {
+        let plugin = manifest("a", &["echo_action", "shared_action"]);
+        let other = manifest("b", &["shared_action"]);
+        assert_eq!(conflicting_action(&plugin, &[], &[]), None);
+        assert_eq!(
+            conflicting_action(&plugin, &["echo_action"], &[]),
+            Some("echo_action")
+        );
+        assert_eq!(
+            conflicting_action(&plugin, &[], &[&other]),
+            Some("shared_action")
+        );
+    }
+
+    #[tokio::test]
+    async fn times_out_calls() {
+        let call = std::future::pending::<Result<(), String>>();
+        let reason = with_timeout(Duration::from_millis(10), call)
+            .await
+            .unwrap_err();
+        assert_eq!(reason, "no response in 10 ms");
+        let call = async { Err::<(), _>("closed") };
+        assert_eq!(
+            with_timeout(Duration::from_secs(1), call)
+                .await
+                .unwrap_err(),
+            "closed"
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_calls_when_worker_crashes() {
+        // 测试程序不认识 worker 的参数，启动后立即退出
+        let worker = Worker {
+            manifest: manifest("crash", &["echo_action"]),
+            path: PathBuf::from("crash"),
+            modified: None,
+            call_timeout: Duration::from_secs(1),
+            process: tokio::sync::Mutex::new(None),
+            backoff: Mutex::new(Backoff::new(Instant::now())),
+        };
+        let result = worker.call("echo_action".to_string(), json!({})).await;
+        assert!(matches!(result, PluginCallResult::Err { .. }));
+
+        // 等待退避期间不会再次启动
+        let result = worker.call("echo_action".to_string(), json!({})).await;
+        assert!(matches!(
+            result,
+            PluginCallResult::Err { message } if message.contains("restarting in")
+        ));
+
+        // 重新加载后立即重启
+        worker.reset_restarts();
+        assert!(worker.backoff.lock().unwrap().check(Instant::now()).is_ok());
+    }
+}
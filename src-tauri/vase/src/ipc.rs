@@ -1,11 +1,11 @@
 pub mod algorithm;
 mod codec;
-mod device;
+pub mod device;
 // pub mod auth;
 pub mod envelope;
 pub mod error;
 mod layers;
-mod transport;
+pub mod transport;
 // pub mod southbound;
 // pub mod tarits;
 
@@ -16,11 +16,11 @@ pub(self) const IPC_ENDPOINT: &str = "vase";
 pub use error::Error;
 use error::Result;
 
-#[allow(unused)]
-use layers::router::router::ExposedHandlerRegistration;
-use layers::router::router::HandlerRegistration;
-use layers::router::router::ListenerRegistration;
+// 供宏展开后的代码在外部 crate 中使用
+pub use layers::router::router::ExposedHandlerRegistration;
+pub use layers::router::router::HandlerRegistration;
+pub use layers::router::router::ListenerRegistration;
 
 #[allow(unused)]
 use envelope::TransportMode;
-use transport::driver::generic::GenericTransport;
+pub use transport::driver::generic::GenericTransport;
@@ -31,6 +31,8 @@ fn receive_message(message: _) -> anyhow::Result<()> {
 
 #[vasing::test]
 pub mod device_test {
+    use std::time::{Duration, Instant};
+
     use serde_json::json;
     use vase_macro::{expose, handle, listen};
 
@@ -39,6 +41,7 @@ pub mod device_test {
     signal!(ready);
     signal!(connected);
     signal!(receive_broadcast);
+    signal!(calls_done);
 
     #[handle(LocalSocketDevice::Test)]
     fn test_send_message(i: i32) -> anyhow::Result<i32> {
@@ -58,6 +61,12 @@ pub mod device_test {
         Ok(message)
     }
 
+    #[expose(LocalSocketDevice::Test)]
+    async fn test_slow_message(message: String) -> anyhow::Result<String> {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        Ok(message)
+    }
+
     #[spawn]
     async fn test_device_handle() -> anyhow::Result<()> {
         LocalSocketDevice::setup().await.unwrap();
@@ -74,6 +83,25 @@ pub mod device_test {
         .await
         .unwrap();
         assert_eq!(res, json!("hello"));
+
+        // 等待响应时不持有设备与会话的锁，并发的请求同时进行
+        let started_at = Instant::now();
+        let (first, second) = tokio::join!(
+            LocalSocketDevice::Test::call(
+                "vase::test::device",
+                "test_slow_message",
+                json!("first")
+            ),
+            LocalSocketDevice::Test::call(
+                "vase::test::device",
+                "test_slow_message",
+                json!("second")
+            ),
+        );
+        assert_eq!(first.unwrap(), json!("first"));
+        assert_eq!(second.unwrap(), json!("second"));
+        assert!(started_at.elapsed() < Duration::from_millis(550));
+        resolve!(calls_done);
         Ok(())
     }
     #[spawn]
@@ -84,6 +112,8 @@ pub mod device_test {
         assert_eq!(result.unwrap(), 10);
         resolve!(connected);
         pending!(receive_broadcast);
+        // 处理完请求后再退出
+        pending!(calls_done);
         Ok(())
     }
 }
@@ -69,10 +69,7 @@ pub static LISTENERS: LazyLock<
     for ListenerRegistration { event, func } in
         inventory::iter::<ListenerRegistration>().into_iter()
     {
-        routes
-            .entry(event.to_string())
-            .and_modify(|e| e.push(func.clone()))
-            .or_insert(vec![]);
+        routes.entry(event.to_string()).or_default().push(*func);
     }
     routes
 });
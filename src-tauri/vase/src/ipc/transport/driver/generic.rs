@@ -106,8 +106,11 @@ impl<A: TransportAdapter> TransportForServer for Arc<GenericTransport<A>> {
             .cloned()
             .ok_or(Error::SessionNotFound)?;
 
-        // 2. Pipeline
-        let mut ctx = ConnectionContext::default();
+        // 2. Pipeline，每个请求使用不同的 corr，并发的请求才能各自收到响应
+        let mut ctx = ConnectionContext {
+            corr: Uuid::new_v4(),
+            ..Default::default()
+        };
         let bytes = <Self as TransportForServer>::outbound(msg, &mut ctx).await?;
 
         // 3. 发送 Request
@@ -357,12 +357,11 @@ impl TransportForServer for Arc<LocalSocketServer> {
         package: String,
         msg: TransportMessage<Pa>,
     ) -> ipc::Result<Value> {
-        let sessions = self.sessions.lock().await;
-        let route_table = &self.route_table.lock().await;
-        let Some(route) = route_table.get(&package) else {
+        // 取出会话的发送端后立即释放锁，等待响应时不阻塞新连接与其他请求
+        let Some(route) = self.route_table.lock().await.get(&package).copied() else {
             return Err(ipc::Error::UnknownPackage(package));
         };
-        let Some(session) = sessions.get(route) else {
+        let Some(session) = self.sessions.lock().await.get(&route).cloned() else {
             return Err(ipc::Error::SessionNotFound);
         };
         let mut ctx = ConnectionContext::default();
@@ -11,7 +11,6 @@ use tokio::sync::{
 };
 use vase_macro::pipeline;
 
-use crate::MacroInit;
 use crate::ipc::{
     Result,
     layers::{
@@ -40,17 +39,13 @@ pub trait TransportAdapter: Send + Sync + 'static {
 
 #[allow(dead_code)]
 pub trait Transport {
-    fn new() -> Self;
     async fn client_entry(&mut self) -> Result<()>;
     async fn server_entry(&mut self) -> Result<()>;
     fn as_client(&self) -> Result<impl TransportForClient + Send + Sync>;
     fn as_server(&self) -> Result<impl TransportForServer + Send + Sync>;
 }
 
-impl<T: TransportForClient + TransportForServer + MacroInit + Clone> Transport for T {
-    fn new() -> Self {
-        MacroInit::new()
-    }
+impl<T: TransportForClient + TransportForServer + Clone> Transport for T {
     async fn client_entry(&mut self) -> Result<()> {
         TransportForClient::setup(self).await?;
         Ok(())
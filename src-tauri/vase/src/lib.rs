@@ -5,4 +5,3 @@ pub mod ipc;
 mod utils;
 
 pub use async_trait::async_trait;
-pub use utils::traits::MacroInit;
@@ -1,2 +1 @@
 pub mod random;
-pub mod traits;
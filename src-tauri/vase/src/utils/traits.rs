@@ -1,3 +0,0 @@
-pub trait MacroInit {
-    fn new() -> Self;
-}
@@ -51,9 +51,6 @@ pub fn device_impl(input: TokenStream) -> TokenStream {
     let mut server_cfg: Option<Meta> = None;
     let mut client_cfg: Option<Meta> = None;
 
-    let mut macro_init: ItemImpl = parse_quote! {
-        impl ::vase::MacroInit for _transport { }
-    };
     // 1. 字段解析
     fields.iter().for_each(|field| {
         let expr = &field.expr;
@@ -69,15 +66,10 @@ pub fn device_impl(input: TokenStream) -> TokenStream {
             };
             item_struct =
                 parse_quote! { pub struct #state_ident { transport: ::std::sync::Arc<#trans> } };
-            macro_init.self_ty = parse_quote! { ::std::sync::Arc<#trans> };
-            macro_init.items.push(parse_quote! {
-                fn new() -> Self {
-                    #trans::new_arc(#args)
-                }
-            });
+            // 直接在结构体中构造 transport，避免为外部类型实现 trait 导致的孤儿规则问题
             struct_init
                 .fields
-                .push(parse_quote! { transport: ::vase::ipc::transport::traits::Transport::new() });
+                .push(parse_quote! { transport: #trans::new_arc(#args) });
         } else if ident_str == "package" {
             let pkg_str = quote!(#expr).to_string().replace(" ", "");
             package_name = Some(pkg_str);
@@ -217,7 +209,6 @@ pub fn device_impl(input: TokenStream) -> TokenStream {
         #item_struct
         #struct_impl
 
-        #macro_init
         impl ::vase::ipc::device::traits::DeviceConfig for #state_ident {
             fn meta(&self) -> ::vase::ipc::envelope::meta::Metadata {
                 use ::vase::ipc::envelope::meta::*;
@@ -576,10 +567,18 @@ fn handle_mod(
 
             #s_cfg_attr
             pub async fn call<Pa: ::serde::Serialize + ::serde::de::DeserializeOwned + Send>(package: &str, method: &str, args: Pa) -> ::vase::ipc::Result<::serde_json::Value> {
-                use ::vase::ipc::device::traits::Device;
-                let instance = Self::server_instance().await?;
-                let mut instance = instance.lock().await;
-                instance.unicast(package, &format!("{}::{}", stringify!(#mod_name), method), ::serde_json::to_value((args,))?).await
+                use ::vase::ipc::transport::{msg::TransportMessage, traits::TransportForServer};
+                // 只在取出传输层时持有设备锁，等待对方响应期间其他调用不会被阻塞
+                let transport = {
+                    let instance = Self::server_instance().await?;
+                    let instance = instance.lock().await;
+                    instance.transport.clone()
+                };
+                let msg = TransportMessage::Request {
+                    method: format!("{}::{}", stringify!(#mod_name), method),
+                    payload: ::serde_json::to_value((args,))?,
+                };
+                TransportForServer::unicast(&transport, package.to_string(), msg).await
             }
         }
     });
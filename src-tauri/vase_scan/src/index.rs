@@ -2,13 +2,16 @@ pub mod path;
 use std::{
     collections::HashMap,
     env,
-    fs::File,
+    fs::{self, File},
     path::{Path, PathBuf},
 };
 
 use serde::{Deserialize, Serialize};
 
-use crate::{error::Result, utils::normalize_path};
+use crate::{
+    error::Result,
+    utils::{ToString, normalize_path},
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FileIndex {
@@ -20,9 +23,31 @@ pub struct FileIndex {
 
 const INDEX_FILE: &str = "vase-scan-index";
 
+impl FileIndex {
+    /// 读取文件当前的修改时间、大小与 crc32
+    pub fn read(path: &Path) -> Result<Self> {
+        let metadata = fs::metadata(path)?;
+        let content = fs::read(path)?;
+        Ok(FileIndex {
+            path: path.to_path_buf(),
+            mtime: metadata.modified()?.to_string(),
+            size: metadata.len(),
+            hash: crc32fast::hash(&content),
+        })
+    }
+}
+
 pub fn load_index() -> Result<HashMap<PathBuf, FileIndex>> {
     let out_dir = env::var("OUT_DIR")?;
-    let index_file = Path::new(&out_dir).join(INDEX_FILE);
+    let index_map = load_index_from(&Path::new(&out_dir).join(INDEX_FILE))?;
+    Ok(index_map
+        .into_values()
+        .map(|index| (normalize_path(&index.path), index))
+        .collect())
+}
+
+/// 从指定的索引文件读取，文件不存在时返回空索引
+pub fn load_index_from(index_file: &Path) -> Result<HashMap<PathBuf, FileIndex>> {
     let mut index_map = HashMap::new();
     if !index_file.exists() {
         return Ok(index_map);
@@ -31,7 +56,26 @@ pub fn load_index() -> Result<HashMap<PathBuf, FileIndex>> {
     let mut rdr = csv::Reader::from_reader(file);
     for result in rdr.deserialize() {
         let index: FileIndex = result?;
-        index_map.insert(normalize_path(&index.path), index);
+        index_map.insert(index.path.clone(), index);
     }
     Ok(index_map)
 }
+
+/// 更新文件的索引，返回文件内容是否发生了变化，新增与删除的文件都视为变化
+///
+/// 修改时间与大小都没有变化时不读取文件，否则以 crc32 判断内容是否变化
+pub fn update_index(index_map: &mut HashMap<PathBuf, FileIndex>, path: &Path) -> Result<bool> {
+    if !path.exists() {
+        return Ok(index_map.remove(path).is_some());
+    }
+    let metadata = fs::metadata(path)?;
+    let mtime = metadata.modified()?.to_string();
+    let previous = index_map.get(path);
+    if previous.is_some_and(|index| index.size == metadata.len() && index.mtime == mtime) {
+        return Ok(false);
+    }
+    let index = FileIndex::read(path)?;
+    let changed = previous.is_none_or(|previous| previous.hash != index.hash);
+    index_map.insert(path.to_path_buf(), index);
+    Ok(changed)
+}
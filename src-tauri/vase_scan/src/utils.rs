@@ -8,15 +8,17 @@ pub trait ToString {
 
 impl ToString for SystemTime {
     fn to_string(self) -> String {
-        let t: OffsetDateTime = SystemTime::now().into();
+        // 精确到纳秒，同一秒内的修改也能被发现
+        let t: OffsetDateTime = self.into();
         format!(
-            "{:0>4}-{:0>2}-{:0>2} {:0>2}:{:0>2}:{:0>2}",
+            "{:0>4}-{:0>2}-{:0>2} {:0>2}:{:0>2}:{:0>2}.{:0>9}",
             t.year(),
             t.month() as u8,
             t.day(),
             t.hour(),
             t.minute(),
-            t.second()
+            t.second(),
+            t.nanosecond()
         )
     }
 }
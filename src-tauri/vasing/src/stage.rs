@@ -18,6 +18,12 @@ impl Stage {
         }
     }
 
+    /// 设置所有任务的超时时间
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
     // 添加支持 Result 的任务 (支持 ?)
     pub fn spawn<F, Fut>(&mut self, name: impl Into<String>, task: F)
     where
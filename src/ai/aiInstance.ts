@@ -10,6 +10,8 @@ import {
 export interface AiConfig<P extends Providers = Providers> {
   apiKey: string;
   model: Models<P>;
+  /** OpenAI 兼容接口的地址 */
+  baseUrl?: string;
   temperature: number;
   maxTokens: number | "Default";
   topP: number;
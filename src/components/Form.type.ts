@@ -6,7 +6,9 @@ export type OptionData = {
 }[];
 
 export type FileData = {
-  uploadApi: Function;
+  placeholder: string;
+  /** 选择目录而不是文件 */
+  directory: boolean;
 };
 
 export type AutoCompleteData = {
@@ -24,6 +26,16 @@ export type InputData = {
   placeholder: string;
 };
 
+/** 由 Rust 参数上的 #[validate(...)] 生成，与后端的校验规则一致 */
+export type ValidateRule = {
+  min?: number;
+  max?: number;
+  regex?: string;
+  len?: { min?: number; max?: number };
+  url?: boolean;
+  one_of?: (string | number | boolean)[];
+};
+
 export type FormItemData =
   | OptionData
   | FileData
@@ -47,6 +59,7 @@ export type FormItem<
   tableSlot?: (res: Slot<any>, model: T, name: string) => VNode;
   data?: Partial<D>;
   plug?: string[] | null;
+  validate?: ValidateRule;
 };
 export type FormType = typeof formType;
 
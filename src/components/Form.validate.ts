@@ -0,0 +1,86 @@
+import { ValidateRule } from "./Form.type";
+import { isPlug } from "./dataInput/PlugDisplay.utils";
+
+export type ValidateError = {
+  key: string;
+  params?: Record<string, unknown>;
+};
+
+function toNumber(value: unknown) {
+  if (typeof value === "number") return value;
+  if (typeof value === "string" && value.trim() !== "") {
+    const number = Number(value);
+    return Number.isNaN(number) ? undefined : number;
+  }
+  return undefined;
+}
+
+function isUrl(value: string) {
+  try {
+    new URL(value);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+/** 按规则校验单个值，空值与插头不做校验，规则与 common::action::rule 保持一致 */
+export function validateValue(
+  rule: ValidateRule | undefined,
+  value: unknown
+): ValidateError | null {
+  if (!rule || value === undefined || value === null || value === "")
+    return null;
+  if (isPlug(value)) return null;
+
+  if (rule.min !== undefined || rule.max !== undefined) {
+    const number = toNumber(value);
+    if (number === undefined) return { key: "validate.number" };
+    if (rule.min !== undefined && number < rule.min)
+      return { key: "validate.min", params: { min: rule.min } };
+    if (rule.max !== undefined && number > rule.max)
+      return { key: "validate.max", params: { max: rule.max } };
+  }
+  if (rule.len) {
+    let len: number;
+    if (typeof value === "string") len = [...value].length;
+    else if (Array.isArray(value)) len = value.length;
+    else return { key: "validate.string" };
+    if (rule.len.min !== undefined && len < rule.len.min)
+      return { key: "validate.minLen", params: { min: rule.len.min } };
+    if (rule.len.max !== undefined && len > rule.len.max)
+      return { key: "validate.maxLen", params: { max: rule.len.max } };
+  }
+  if (rule.regex !== undefined || rule.url) {
+    if (typeof value !== "string") return { key: "validate.string" };
+    if (rule.regex !== undefined && !new RegExp(rule.regex, "u").test(value))
+      return { key: "validate.regex", params: { regex: rule.regex } };
+    if (rule.url && !isUrl(value)) return { key: "validate.url" };
+  }
+  if (rule.one_of) {
+    const matched = rule.one_of.some(
+      (item) =>
+        item === value ||
+        (toNumber(item) !== undefined && toNumber(item) === toNumber(value))
+    );
+    if (!matched)
+      return {
+        key: "validate.oneOf",
+        params: { items: rule.one_of.join(", ") },
+      };
+  }
+  return null;
+}
+
+/** 校验整个表单，返回每个字段的错误 */
+export function validateForm(
+  form: { name: string; validate?: ValidateRule }[],
+  data: { [key: string]: any }
+) {
+  const errors: { [name: string]: ValidateError } = {};
+  form.forEach(({ name, validate }) => {
+    const error = validateValue(validate, data[name]);
+    if (error) errors[name] = error;
+  });
+  return errors;
+}
@@ -1,4 +1,5 @@
 import { createI18n, I18nOptions } from "vue-i18n";
+import fallback from "./fallback.json";
 
 // 英文序数规则
 export const nth = {
@@ -20,6 +21,8 @@ export const nth = {
 
 export function createI18nWithUtils(option: I18nOptions) {
   return createI18n({
+    // 缺失的翻译按回退链取，由 aster_codegen 生成
+    fallbackLocale: fallback,
     ...option,
     pluralRules: {
       en: nth.rule,
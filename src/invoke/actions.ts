@@ -1,9 +1,27 @@
 import { registerDisplayName } from "./helper";
 import { CardMeta } from "./type";
 
-registerDisplayName("action")("program", {
+registerDisplayName("action")("command", {
+  "zh-CN": "系统命令",
+  en: "System Command",
+})("desktop", {
+  "zh-CN": "桌面交互",
+  en: "Desktop",
+})("llm", {
+  "zh-CN": "人工智能",
+  en: "AI",
+})("mail", {
+  "zh-CN": "邮件",
+  en: "Mail",
+})("program", {
   "zh-CN": "可编程",
   en: "Programmable",
+})("fs", {
+  "zh-CN": "文件操作",
+  en: "File System",
+})("transform", {
+  "zh-CN": "数据转换",
+  en: "Data Transform",
 })("web", {
   "zh-CN": "网络相关",
   en: "Web Related",
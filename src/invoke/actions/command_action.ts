@@ -0,0 +1,211 @@
+/* This part is the automatically generated source code. Please modify it in actions/command/src/lib.rs */
+
+import { defineCard } from "../helper";
+const command_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "Result",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "Result",
+      position: "right",
+      plug: {
+        "\0type": "object",
+        stdout: "string",
+        stderr: "string",
+        code: "number",
+        json: {
+          "\0type": "object",
+        },
+      },
+    },
+    {
+      branch: "Failure",
+      type: "primary",
+      id: "Result",
+      position: "bottom",
+      plug: {
+        "\0type": "object",
+        stdout: "string",
+        stderr: "string",
+        code: "number",
+        json: {
+          "\0type": "object",
+        },
+      },
+    },
+    {
+      branch: "Timeout",
+      type: "primary",
+      id: "Result",
+      position: "top",
+    },
+  ],
+  parent: "action.command",
+  name: "command_action",
+  args: {
+    program: "String",
+    args: "Text",
+    env: "Text",
+    cwd: "String",
+    stdin: "Text",
+    timeout: "Int",
+    parse_json: "Bool",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "program",
+      },
+      {
+        key: "args",
+        width: 2,
+      },
+      {
+        key: "env",
+        width: 2,
+      },
+      {
+        key: "cwd",
+      },
+      {
+        key: "stdin",
+        width: 2,
+      },
+      {
+        key: "timeout",
+      },
+      {
+        key: "parse_json",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "program",
+        type: "String",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "args",
+        type: "TextArea",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "env",
+        type: "TextArea",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "cwd",
+        type: "String",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "stdin",
+        type: "TextArea",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "timeout",
+        type: "Number",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "parse_json",
+        type: "Switch",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      Failure: "Failure",
+      Timeout: "Timeout",
+      description: "Run an external program and capture its output",
+      title: "Run command",
+      program: {
+        title: "Program",
+        description: "Program name or path",
+      },
+      args: {
+        title: "Arguments",
+        description: "One argument per line",
+      },
+      env: {
+        title: "Environment variables",
+        description: "One KEY=VALUE per line",
+      },
+      cwd: {
+        title: "Working directory",
+        description: "Directory the program runs in",
+      },
+      stdin: {
+        title: "Standard input",
+        description: "Content written to stdin, can be plugged from upstream output",
+      },
+      timeout: {
+        title: "Timeout",
+        description:
+          "Kill the program after this many milliseconds, wait forever if empty",
+      },
+      parse_json: {
+        title: "Parse JSON",
+        description: "Parse standard output as JSON",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      Failure: "失败",
+      Timeout: "超时",
+      description: "运行一个外部程序并捕获其输出",
+      title: "执行命令",
+      program: {
+        title: "程序",
+        description: "程序名称或路径",
+      },
+      args: {
+        title: "参数",
+        description: "每行一个参数",
+      },
+      env: {
+        title: "环境变量",
+        description: "每行一个 KEY=VALUE",
+      },
+      cwd: {
+        title: "工作目录",
+        description: "程序运行时所在的目录",
+      },
+      stdin: {
+        title: "标准输入",
+        description: "写入程序标准输入的内容，可连接上游输出",
+      },
+      timeout: {
+        title: "超时时间",
+        description: "超时后结束程序（毫秒），不填则一直等待",
+      },
+      parse_json: {
+        title: "解析 JSON",
+        description: "将标准输出解析为 JSON",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default command_action;
@@ -0,0 +1,119 @@
+/* This part is the automatically generated source code. Please modify it in actions/transform/src/lib.rs */
+
+import { defineCard } from "../helper";
+const csv_to_json_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "TableResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "TableResult",
+      position: "right",
+      plug: {
+        "\0type": "object",
+        rows: {
+          "\0type": "object",
+        },
+        count: "number",
+      },
+    },
+    {
+      branch: "Invalid",
+      type: "primary",
+      id: "TableResult",
+      position: "bottom",
+      plug: "string",
+    },
+  ],
+  parent: "action.transform",
+  name: "csv_to_json_action",
+  args: {
+    csv: "Text",
+    delimiter: "String",
+    no_header: "Bool",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "csv",
+        width: 2,
+      },
+      {
+        key: "delimiter",
+      },
+      {
+        key: "no_header",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "csv",
+        type: "TextArea",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "delimiter",
+        type: "String",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "no_header",
+        type: "Switch",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      Invalid: "Invalid",
+      description: "Parse CSV into a list of rows",
+      title: "CSV to JSON",
+      csv: {
+        title: "CSV",
+        description: "CSV text to parse",
+      },
+      delimiter: {
+        title: "Delimiter",
+        description: "Comma by default, write \\t for tab",
+      },
+      no_header: {
+        title: "No header",
+        description:
+          "The first line is data instead of a header, rows become arrays",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      Invalid: "无效输入",
+      description: "将 CSV 解析为行的列表",
+      title: "CSV 转 JSON",
+      csv: {
+        title: "CSV",
+        description: "要解析的 CSV 文本",
+      },
+      delimiter: {
+        title: "分隔符",
+        description: "默认为逗号，制表符可写作 \\t",
+      },
+      no_header: {
+        title: "无表头",
+        description: "第一行是数据而不是表头，每行输出为数组",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default csv_to_json_action;
@@ -0,0 +1,148 @@
+/* This part is the automatically generated source code. Please modify it in actions/transform/src/lib.rs */
+
+import { defineCard } from "../helper";
+const date_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "DateResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "DateResult",
+      position: "right",
+      plug: {
+        "\0type": "object",
+        text: "string",
+        timestamp: "number",
+        iso: "string",
+      },
+    },
+    {
+      branch: "Invalid",
+      type: "primary",
+      id: "DateResult",
+      position: "bottom",
+      plug: "string",
+    },
+  ],
+  parent: "action.transform",
+  name: "date_action",
+  args: {
+    date: "String",
+    input_format: "String",
+    output_format: "String",
+    timezone: "String",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "date",
+      },
+      {
+        key: "input_format",
+      },
+      {
+        key: "output_format",
+      },
+      {
+        key: "timezone",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "date",
+        type: "String",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "input_format",
+        type: "String",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "output_format",
+        type: "String",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "timezone",
+        type: "Option",
+        optional: true,
+        data: [
+          {
+            label: "Local",
+            value: "Local",
+          },
+          {
+            label: "Utc",
+            value: "Utc",
+          },
+        ],
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Timezone_Local: "Local",
+      Success: "Success",
+      Invalid: "Invalid",
+      description: "Parse a date and output it in a format",
+      title: "Format date",
+      date: {
+        title: "Date",
+        description:
+          "RFC 3339, RFC 2822, common date formats or a timestamp, empty for now",
+      },
+      input_format: {
+        title: "Input format",
+        description:
+          "Such as %d/%m/%Y %H:%M, detected automatically when empty",
+      },
+      output_format: {
+        title: "Output format",
+        description: "%Y-%m-%d %H:%M:%S by default",
+      },
+      timezone: {
+        title: "Timezone",
+        description:
+          "Timezone used to read and output the date, local by default",
+      },
+    },
+    "zh-CN": {
+      Timezone_Local: "本地",
+      Success: "成功",
+      Invalid: "无效日期",
+      description: "解析日期并按格式输出",
+      title: "日期格式化",
+      date: {
+        title: "日期",
+        description: "RFC 3339、RFC 2822、常见日期格式或时间戳，留空为当前时间",
+      },
+      input_format: {
+        title: "输入格式",
+        description: "如 %d/%m/%Y %H:%M，留空时自动识别",
+      },
+      output_format: {
+        title: "输出格式",
+        description: "默认为 %Y-%m-%d %H:%M:%S",
+      },
+      timezone: {
+        title: "时区",
+        description: "解释与输出日期使用的时区，默认为本地",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default date_action;
@@ -0,0 +1,113 @@
+/* This part is the automatically generated source code. Please modify it in actions/fs/src/lib.rs */
+
+import { defineCard } from "../helper";
+const delete_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "PathResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "PathResult",
+      position: "right",
+      plug: "string",
+    },
+    {
+      branch: "NotFound",
+      type: "primary",
+      id: "PathResult",
+      position: "bottom",
+      plug: "string",
+    },
+    {
+      branch: "Permission",
+      type: "primary",
+      id: "PathResult",
+      position: "bottom",
+      plug: "string",
+    },
+    {
+      branch: "Exists",
+      type: "primary",
+      id: "PathResult",
+      position: "top",
+      plug: "string",
+    },
+  ],
+  parent: "action.fs",
+  name: "delete_action",
+  args: {
+    path: "File",
+    recursive: "Bool",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "path",
+      },
+      {
+        key: "recursive",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "path",
+        type: "File",
+        optional: false,
+        data: {
+          directory: false,
+        },
+      },
+      {
+        name: "recursive",
+        type: "Switch",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      NotFound: "Not found",
+      Permission: "Permission denied",
+      Exists: "Exists",
+      description: "Delete a file or directory",
+      title: "Delete",
+      path: {
+        title: "Path",
+        description: "File or directory to delete",
+      },
+      recursive: {
+        title: "Recursive",
+        description: "Delete a non-empty directory and everything in it",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      NotFound: "不存在",
+      Permission: "无权限",
+      Exists: "已存在",
+      description: "删除文件或目录",
+      title: "删除",
+      path: {
+        title: "路径",
+        description: "要删除的文件或目录",
+      },
+      recursive: {
+        title: "递归删除",
+        description: "删除非空目录及其中的全部内容",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default delete_action;
@@ -0,0 +1,95 @@
+/* This part is the automatically generated source code. Please modify it in actions/fs/src/lib.rs */
+
+import { defineCard } from "../helper";
+const ensure_dir_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "PathResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "PathResult",
+      position: "right",
+      plug: "string",
+    },
+    {
+      branch: "NotFound",
+      type: "primary",
+      id: "PathResult",
+      position: "bottom",
+      plug: "string",
+    },
+    {
+      branch: "Permission",
+      type: "primary",
+      id: "PathResult",
+      position: "bottom",
+      plug: "string",
+    },
+    {
+      branch: "Exists",
+      type: "primary",
+      id: "PathResult",
+      position: "top",
+      plug: "string",
+    },
+  ],
+  parent: "action.fs",
+  name: "ensure_dir_action",
+  args: {
+    path: "Directory",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "path",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "path",
+        type: "File",
+        optional: false,
+        data: {
+          directory: true,
+        },
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      NotFound: "Not found",
+      Permission: "Permission denied",
+      Exists: "Exists",
+      description: "Create the directory and its parents if missing",
+      title: "Ensure directory",
+      path: {
+        title: "Directory",
+        description: "Directory that should exist",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      NotFound: "不存在",
+      Permission: "无权限",
+      Exists: "已存在",
+      description: "目录不存在时创建，包括上级目录",
+      title: "确保目录存在",
+      path: {
+        title: "目录",
+        description: "需要存在的目录",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default ensure_dir_action;
@@ -16,40 +16,118 @@ const fetch_action = defineCard({
       position: "right",
       plug: {
         "\0type": "object",
-        a: {
+        status: "number",
+        headers: {
+          "\0type": "object",
+        },
+        body: {
           "\0type": "object",
-          hello: "string",
         },
       },
     },
     {
-      branch: "Error",
+      branch: "Redirect",
+      type: "primary",
+      id: "Result",
+      position: "top",
+      plug: {
+        "\0type": "object",
+        status: "number",
+        headers: {
+          "\0type": "object",
+        },
+        body: {
+          "\0type": "object",
+        },
+      },
+    },
+    {
+      branch: "ClientError",
       type: "primary",
       id: "Result",
       position: "bottom",
       plug: {
         "\0type": "object",
-        timeout: null,
+        status: "number",
+        headers: {
+          "\0type": "object",
+        },
+        body: {
+          "\0type": "object",
+        },
       },
     },
+    {
+      branch: "ServerError",
+      type: "primary",
+      id: "Result",
+      position: "bottom",
+      plug: {
+        "\0type": "object",
+        status: "number",
+        headers: {
+          "\0type": "object",
+        },
+        body: {
+          "\0type": "object",
+        },
+      },
+    },
+    {
+      branch: "Timeout",
+      type: "primary",
+      id: "Result",
+      position: "top",
+    },
   ],
   parent: "action.web",
   name: "fetch_action",
   args: {
     url: "String",
     method: "String",
+    query: "Text",
+    headers: "Text",
+    body_type: "String",
+    body: "Text",
+    auth: "String",
+    username: "String",
+    credential: "String",
     timeout: "Int",
     proxy: "String",
     http2: "Bool",
+    redirect: "String",
   },
   litCardView: () => {
     return [
       {
         key: "url",
+        width: 2,
       },
       {
         key: "method",
       },
+      {
+        key: "query",
+      },
+      {
+        key: "headers",
+      },
+      {
+        key: "body_type",
+      },
+      {
+        key: "body",
+        width: 2,
+      },
+      {
+        key: "auth",
+      },
+      {
+        key: "username",
+      },
+      {
+        key: "credential",
+      },
       {
         key: "timeout",
       },
@@ -59,6 +137,9 @@ const fetch_action = defineCard({
       {
         key: "http2",
       },
+      {
+        key: "redirect",
+      },
     ];
   },
   view: {
@@ -69,6 +150,9 @@ const fetch_action = defineCard({
         type: "String",
         optional: false,
         data: {},
+        validate: {
+          url: true,
+        },
       },
       {
         name: "method",
@@ -91,13 +175,96 @@ const fetch_action = defineCard({
             label: "Put",
             value: "Put",
           },
+          {
+            label: "Patch",
+            value: "Patch",
+          },
+          {
+            label: "Head",
+            value: "Head",
+          },
+          {
+            label: "Options",
+            value: "Options",
+          },
         ],
       },
+      {
+        name: "query",
+        type: "TextArea",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "headers",
+        type: "TextArea",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "body_type",
+        type: "Option",
+        optional: true,
+        data: [
+          {
+            label: "Json",
+            value: "Json",
+          },
+          {
+            label: "Form",
+            value: "Form",
+          },
+          {
+            label: "Multipart",
+            value: "Multipart",
+          },
+          {
+            label: "Raw",
+            value: "Raw",
+          },
+        ],
+      },
+      {
+        name: "body",
+        type: "TextArea",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "auth",
+        type: "Option",
+        optional: true,
+        data: [
+          {
+            label: "Basic",
+            value: "Basic",
+          },
+          {
+            label: "Bearer",
+            value: "Bearer",
+          },
+        ],
+      },
+      {
+        name: "username",
+        type: "String",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "credential",
+        type: "String",
+        optional: true,
+        data: {},
+      },
       {
         name: "timeout",
         type: "Number",
-        optional: false,
+        optional: true,
         data: {},
+        validate: {
+          min: 1,
+        },
       },
       {
         name: "proxy",
@@ -111,12 +278,35 @@ const fetch_action = defineCard({
         optional: true,
         data: {},
       },
+      {
+        name: "redirect",
+        type: "Option",
+        optional: true,
+        data: [
+          {
+            label: "Follow",
+            value: "Follow",
+          },
+          {
+            label: "Manual",
+            value: "Manual",
+          },
+        ],
+      },
     ],
   },
   i18n: {
     en: {
       Method_Get: "Get",
+      BodyType_Form: "Form",
+      BodyType_Raw: "Raw",
+      Redirect_Follow: "Follow",
+      Redirect_Manual: "Manual",
       Success: "Success",
+      Redirect: "Redirect",
+      ClientError: "Client error",
+      ServerError: "Server error",
+      Timeout: "Timeout",
       description: "Send a network request",
       title: "Network request",
       url: {
@@ -127,22 +317,66 @@ const fetch_action = defineCard({
         title: "Request Method",
         description: "Method used when sending network requests",
       },
+      query: {
+        title: "Query",
+        description: "One key=value per line",
+      },
+      headers: {
+        title: "Headers",
+        description:
+          "One Name: value per line, values may reference env: or secret: secrets",
+      },
+      body_type: {
+        title: "Body type",
+        description: "How the request body is encoded",
+      },
+      body: {
+        title: "Body",
+        description:
+          "JSON or raw text; forms take one key=value per line, use key=@path for files",
+      },
+      auth: {
+        title: "Authentication",
+        description: "Authentication used for the request",
+      },
+      username: {
+        title: "Username",
+        description: "Username for basic authentication",
+      },
+      credential: {
+        title: "Password or token",
+        description: "Reference secrets with env:NAME or secret:NAME",
+      },
       timeout: {
         title: "Timeout",
-        description: "Request timeout",
+        description: "Request timeout (ms)",
       },
       proxy: {
         title: "Network Proxy",
-        description: "Proxy server to use for the request",
+        description:
+          "Proxy server to use for the request, supports http, https and socks5",
       },
       http2: {
         title: "Enable http2",
         description: "Use the more efficient and secure http/2",
       },
+      redirect: {
+        title: "Redirect",
+        description:
+          "Whether to follow redirects automatically, up to 10 times",
+      },
     },
     "zh-CN": {
       Method_Get: "获取",
+      BodyType_Form: "表单",
+      BodyType_Raw: "原始文本",
+      Redirect_Follow: "跟随",
+      Redirect_Manual: "不跟随",
       Success: "成功",
+      Redirect: "重定向",
+      ClientError: "客户端错误",
+      ServerError: "服务端错误",
+      Timeout: "超时",
       description: "发送一个网络请求",
       title: "网络请求",
       url: {
@@ -153,18 +387,50 @@ const fetch_action = defineCard({
         title: "请求方法",
         description: "发送网络请求时使用的方法",
       },
+      query: {
+        title: "查询参数",
+        description: "每行一个 key=value",
+      },
+      headers: {
+        title: "请求头",
+        description: "每行一个 Name: value，值可使用 env: 或 secret: 引用密钥",
+      },
+      body_type: {
+        title: "请求体类型",
+        description: "请求体的编码方式",
+      },
+      body: {
+        title: "请求体",
+        description: "JSON 或原始文本；表单每行一个 key=value，文件字段使用 key=@路径",
+      },
+      auth: {
+        title: "认证方式",
+        description: "请求使用的认证方式",
+      },
+      username: {
+        title: "用户名",
+        description: "Basic 认证的用户名",
+      },
+      credential: {
+        title: "密码或令牌",
+        description: "可使用 env:NAME 或 secret:NAME 引用密钥",
+      },
       timeout: {
         title: "超时时间",
-        description: "请求超时时间",
+        description: "请求超时时间（毫秒）",
       },
       proxy: {
         title: "网络代理",
-        description: "请求时使用的代理服务器",
+        description: "请求时使用的代理服务器，支持 http、https 与 socks5",
       },
       http2: {
         title: "启用http2",
         description: "采用更高效安全的http/2",
       },
+      redirect: {
+        title: "重定向",
+        description: "是否自动跟随重定向，最多 10 次",
+      },
     },
   },
 });
@@ -0,0 +1,121 @@
+/* This part is the automatically generated source code. Please modify it in actions/fs/src/lib.rs */
+
+import { defineCard } from "../helper";
+const hash_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "ContentResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "ContentResult",
+      position: "right",
+      plug: "string",
+    },
+    {
+      branch: "NotFound",
+      type: "primary",
+      id: "ContentResult",
+      position: "bottom",
+      plug: "string",
+    },
+    {
+      branch: "Permission",
+      type: "primary",
+      id: "ContentResult",
+      position: "bottom",
+      plug: "string",
+    },
+  ],
+  parent: "action.fs",
+  name: "hash_action",
+  args: {
+    path: "File",
+    algorithm: "String",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "path",
+      },
+      {
+        key: "algorithm",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "path",
+        type: "File",
+        optional: false,
+        data: {
+          directory: false,
+        },
+      },
+      {
+        name: "algorithm",
+        type: "Option",
+        optional: true,
+        data: [
+          {
+            label: "Sha256",
+            value: "Sha256",
+          },
+          {
+            label: "Sha512",
+            value: "Sha512",
+          },
+          {
+            label: "Sha1",
+            value: "Sha1",
+          },
+          {
+            label: "Md5",
+            value: "Md5",
+          },
+        ],
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      NotFound: "Not found",
+      Permission: "Permission denied",
+      description: "Compute the digest of a file",
+      title: "Hash file",
+      path: {
+        title: "File",
+        description: "File to hash",
+      },
+      algorithm: {
+        title: "Algorithm",
+        description: "SHA-256 by default",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      NotFound: "不存在",
+      Permission: "无权限",
+      description: "计算文件的摘要",
+      title: "计算哈希",
+      path: {
+        title: "文件",
+        description: "要计算的文件",
+      },
+      algorithm: {
+        title: "算法",
+        description: "默认为 SHA-256",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default hash_action;
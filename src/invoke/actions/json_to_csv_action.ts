@@ -0,0 +1,94 @@
+/* This part is the automatically generated source code. Please modify it in actions/transform/src/lib.rs */
+
+import { defineCard } from "../helper";
+const json_to_csv_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "TextResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "TextResult",
+      position: "right",
+      plug: "string",
+    },
+    {
+      branch: "Invalid",
+      type: "primary",
+      id: "TextResult",
+      position: "bottom",
+      plug: "string",
+    },
+  ],
+  parent: "action.transform",
+  name: "json_to_csv_action",
+  args: {
+    data: "Text",
+    delimiter: "String",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "data",
+        width: 2,
+      },
+      {
+        key: "delimiter",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "data",
+        type: "TextArea",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "delimiter",
+        type: "String",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      Invalid: "Invalid",
+      description: "Write a list of objects or arrays as CSV",
+      title: "JSON to CSV",
+      data: {
+        title: "Data",
+        description: "For a list of objects, all keys become the header",
+      },
+      delimiter: {
+        title: "Delimiter",
+        description: "Comma by default, write \\t for tab",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      Invalid: "无效输入",
+      description: "将对象或数组的列表写为 CSV",
+      title: "JSON 转 CSV",
+      data: {
+        title: "数据",
+        description: "对象列表时以全部键作为表头",
+      },
+      delimiter: {
+        title: "分隔符",
+        description: "默认为逗号，制表符可写作 \\t",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default json_to_csv_action;
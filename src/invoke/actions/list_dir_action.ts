@@ -0,0 +1,129 @@
+/* This part is the automatically generated source code. Please modify it in actions/fs/src/lib.rs */
+
+import { defineCard } from "../helper";
+const list_dir_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "ListResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "ListResult",
+      position: "right",
+      plug: {
+        "\0type": "object",
+        entries: {
+          "\0type": "object",
+        },
+        count: "number",
+      },
+    },
+    {
+      branch: "NotFound",
+      type: "primary",
+      id: "ListResult",
+      position: "bottom",
+      plug: "string",
+    },
+    {
+      branch: "Permission",
+      type: "primary",
+      id: "ListResult",
+      position: "bottom",
+      plug: "string",
+    },
+  ],
+  parent: "action.fs",
+  name: "list_dir_action",
+  args: {
+    dir: "Directory",
+    pattern: "String",
+    recursive: "Bool",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "dir",
+      },
+      {
+        key: "pattern",
+      },
+      {
+        key: "recursive",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "dir",
+        type: "File",
+        optional: false,
+        data: {
+          directory: true,
+        },
+      },
+      {
+        name: "pattern",
+        type: "String",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "recursive",
+        type: "Switch",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      NotFound: "Not found",
+      Permission: "Permission denied",
+      description: "List files in a directory, optionally filtered by a glob",
+      title: "List directory",
+      dir: {
+        title: "Directory",
+        description: "Directory to list",
+      },
+      pattern: {
+        title: "Pattern",
+        description:
+          "Such as *.txt, patterns with / match relative paths, such as logs/**/*.log",
+      },
+      recursive: {
+        title: "Recursive",
+        description: "Also list files in subdirectories",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      NotFound: "不存在",
+      Permission: "无权限",
+      description: "列出目录中的文件，可按通配符过滤",
+      title: "列出目录",
+      dir: {
+        title: "目录",
+        description: "要列出的目录",
+      },
+      pattern: {
+        title: "匹配模式",
+        description: "如 *.txt，包含 / 时匹配相对路径，如 logs/**/*.log",
+      },
+      recursive: {
+        title: "包含子目录",
+        description: "同时列出子目录中的文件",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default list_dir_action;
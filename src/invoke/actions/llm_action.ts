@@ -0,0 +1,197 @@
+/* This part is the automatically generated source code. Please modify it in actions/llm/src/lib.rs */
+
+import { defineCard } from "../helper";
+const llm_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "Result",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "Result",
+      position: "right",
+      plug: {
+        "\0type": "object",
+        text: "string",
+        json: {
+          "\0type": "object",
+        },
+      },
+    },
+    {
+      branch: "Invalid",
+      type: "primary",
+      id: "Result",
+      position: "bottom",
+      plug: "string",
+    },
+    {
+      branch: "Failed",
+      type: "primary",
+      id: "Result",
+      position: "bottom",
+      plug: {
+        "\0type": "object",
+        status: "number",
+        body: "string",
+      },
+    },
+    {
+      branch: "Timeout",
+      type: "primary",
+      id: "Result",
+      position: "top",
+    },
+  ],
+  parent: "action.llm",
+  name: "llm_action",
+  args: {
+    prompt: "Text",
+    system: "Text",
+    json_mode: "Bool",
+    stream: "Bool",
+    model: "String",
+    timeout: "Int",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "prompt",
+        width: 2,
+      },
+      {
+        key: "system",
+        width: 2,
+      },
+      {
+        key: "json_mode",
+      },
+      {
+        key: "stream",
+      },
+      {
+        key: "model",
+      },
+      {
+        key: "timeout",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "prompt",
+        type: "TextArea",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "system",
+        type: "TextArea",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "json_mode",
+        type: "Switch",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "stream",
+        type: "Switch",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "model",
+        type: "String",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "timeout",
+        type: "Number",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      Invalid: "Invalid JSON",
+      Failed: "Failed",
+      Timeout: "Timeout",
+      description:
+        "Call an OpenAI-compatible endpoint with the AI config from settings",
+      title: "AI chat",
+      prompt: {
+        title: "Prompt",
+        description: "Reference upstream outputs with {{ node_id.path }}",
+      },
+      system: {
+        title: "System prompt",
+        description:
+          "Sets the role and rules of the model, also supports upstream references",
+      },
+      json_mode: {
+        title: "JSON mode",
+        description: "Ask the model for JSON and parse it into json",
+      },
+      stream: {
+        title: "Stream",
+        description:
+          "Receive the output as a stream, suitable for long replies",
+      },
+      model: {
+        title: "Model",
+        description: "The model from settings when empty",
+      },
+      timeout: {
+        title: "Timeout",
+        description: "Request timeout (ms)",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      Invalid: "无效 JSON",
+      Failed: "请求失败",
+      Timeout: "超时",
+      description: "使用设置中的 AI 配置调用 OpenAI 兼容的接口",
+      title: "AI 对话",
+      prompt: {
+        title: "提示词",
+        description: "可使用 {{ 节点id.路径 }} 引用上游输出",
+      },
+      system: {
+        title: "系统提示词",
+        description: "设定模型的角色与规则，同样支持引用上游输出",
+      },
+      json_mode: {
+        title: "JSON 模式",
+        description: "要求模型输出 JSON，并解析到 json 中",
+      },
+      stream: {
+        title: "流式传输",
+        description: "以流式接收输出，适合较长的回复",
+      },
+      model: {
+        title: "模型",
+        description: "留空时使用设置中的模型",
+      },
+      timeout: {
+        title: "超时时间",
+        description: "请求超时时间（毫秒）",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default llm_action;
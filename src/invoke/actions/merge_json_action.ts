@@ -0,0 +1,116 @@
+/* This part is the automatically generated source code. Please modify it in actions/transform/src/lib.rs */
+
+import { defineCard } from "../helper";
+const merge_json_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "ValueResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "ValueResult",
+      position: "right",
+      plug: {
+        "\0type": "object",
+      },
+    },
+    {
+      branch: "Invalid",
+      type: "primary",
+      id: "ValueResult",
+      position: "bottom",
+      plug: "string",
+    },
+  ],
+  parent: "action.transform",
+  name: "merge_json_action",
+  args: {
+    base: "Text",
+    other: "Text",
+    deep: "Bool",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "base",
+        width: 2,
+      },
+      {
+        key: "other",
+        width: 2,
+      },
+      {
+        key: "deep",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "base",
+        type: "TextArea",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "other",
+        type: "TextArea",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "deep",
+        type: "Switch",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      Invalid: "Invalid",
+      description: "Merge the second object into the first, later keys win",
+      title: "Merge JSON",
+      base: {
+        title: "Base",
+        description: "JSON to merge into",
+      },
+      other: {
+        title: "Other",
+        description: "JSON to merge in",
+      },
+      deep: {
+        title: "Deep",
+        description:
+          "Merge nested objects recursively instead of replacing them",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      Invalid: "无效输入",
+      description: "将第二个对象合并到第一个中，同名键以后者为准",
+      title: "合并 JSON",
+      base: {
+        title: "基础对象",
+        description: "被合并的 JSON",
+      },
+      other: {
+        title: "合并对象",
+        description: "要合并进来的 JSON",
+      },
+      deep: {
+        title: "深度合并",
+        description: "递归合并嵌套的对象，而不是整体替换",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default merge_json_action;
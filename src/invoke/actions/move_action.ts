@@ -0,0 +1,133 @@
+/* This part is the automatically generated source code. Please modify it in actions/fs/src/lib.rs */
+
+import { defineCard } from "../helper";
+const move_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "PathResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "PathResult",
+      position: "right",
+      plug: "string",
+    },
+    {
+      branch: "NotFound",
+      type: "primary",
+      id: "PathResult",
+      position: "bottom",
+      plug: "string",
+    },
+    {
+      branch: "Permission",
+      type: "primary",
+      id: "PathResult",
+      position: "bottom",
+      plug: "string",
+    },
+    {
+      branch: "Exists",
+      type: "primary",
+      id: "PathResult",
+      position: "top",
+      plug: "string",
+    },
+  ],
+  parent: "action.fs",
+  name: "move_action",
+  args: {
+    source: "File",
+    target: "File",
+    overwrite: "Bool",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "source",
+      },
+      {
+        key: "target",
+      },
+      {
+        key: "overwrite",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "source",
+        type: "File",
+        optional: false,
+        data: {
+          directory: false,
+        },
+      },
+      {
+        name: "target",
+        type: "File",
+        optional: false,
+        data: {
+          directory: false,
+        },
+      },
+      {
+        name: "overwrite",
+        type: "Switch",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      NotFound: "Not found",
+      Permission: "Permission denied",
+      Exists: "Exists",
+      description: "Move a file or directory",
+      title: "Move",
+      source: {
+        title: "Source",
+        description: "File or directory to move",
+      },
+      target: {
+        title: "Target",
+        description: "Path to move to",
+      },
+      overwrite: {
+        title: "Overwrite",
+        description: "Overwrite the target if it exists",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      NotFound: "不存在",
+      Permission: "无权限",
+      Exists: "已存在",
+      description: "移动文件或目录",
+      title: "移动",
+      source: {
+        title: "源路径",
+        description: "要移动的文件或目录",
+      },
+      target: {
+        title: "目标路径",
+        description: "移动到的路径",
+      },
+      overwrite: {
+        title: "覆盖",
+        description: "目标已存在时覆盖",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default move_action;
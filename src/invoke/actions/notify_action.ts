@@ -0,0 +1,108 @@
+/* This part is the automatically generated source code. Please modify it in actions/desktop/src/lib.rs */
+
+import { defineCard } from "../helper";
+const notify_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "DoneResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "DoneResult",
+      position: "right",
+    },
+    {
+      branch: "Timeout",
+      type: "primary",
+      id: "DoneResult",
+      position: "top",
+    },
+  ],
+  parent: "action.desktop",
+  name: "notify_action",
+  args: {
+    title: "String",
+    body: "Text",
+    timeout: "Int",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "title",
+      },
+      {
+        key: "body",
+        width: 2,
+      },
+      {
+        key: "timeout",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "title",
+        type: "String",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "body",
+        type: "TextArea",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "timeout",
+        type: "Number",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      Timeout: "Timeout",
+      description: "Show a notification on the desktop",
+      title: {
+        title: "Title",
+        description: "Title of the notification",
+      },
+      body: {
+        title: "Body",
+        description: "Body of the notification",
+      },
+      timeout: {
+        title: "Timeout",
+        description: "Time to wait for the UI (ms), 5000 by default",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      Timeout: "超时",
+      description: "在桌面上显示一条通知",
+      title: {
+        title: "标题",
+        description: "通知的标题",
+      },
+      body: {
+        title: "内容",
+        description: "通知的内容",
+      },
+      timeout: {
+        title: "超时时间",
+        description: "等待界面响应的时间（毫秒），默认为 5000",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default notify_action;
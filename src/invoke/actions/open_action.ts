@@ -0,0 +1,91 @@
+/* This part is the automatically generated source code. Please modify it in actions/desktop/src/lib.rs */
+
+import { defineCard } from "../helper";
+const open_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "DoneResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "DoneResult",
+      position: "right",
+    },
+    {
+      branch: "Timeout",
+      type: "primary",
+      id: "DoneResult",
+      position: "top",
+    },
+  ],
+  parent: "action.desktop",
+  name: "open_action",
+  args: {
+    target: "String",
+    timeout: "Int",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "target",
+      },
+      {
+        key: "timeout",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "target",
+        type: "String",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "timeout",
+        type: "Number",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      Timeout: "Timeout",
+      description: "Open a URL or file with the default app",
+      title: "Open",
+      target: {
+        title: "Target",
+        description: "URL or file path",
+      },
+      timeout: {
+        title: "Timeout",
+        description: "Time to wait for the UI (ms), 5000 by default",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      Timeout: "超时",
+      description: "使用默认程序打开网址或文件",
+      title: "打开",
+      target: {
+        title: "目标",
+        description: "网址或文件路径",
+      },
+      timeout: {
+        title: "超时时间",
+        description: "等待界面响应的时间（毫秒），默认为 5000",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default open_action;
@@ -0,0 +1,97 @@
+/* This part is the automatically generated source code. Please modify it in actions/transform/src/lib.rs */
+
+import { defineCard } from "../helper";
+const pick_keys_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "ValueResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "ValueResult",
+      position: "right",
+      plug: {
+        "\0type": "object",
+      },
+    },
+    {
+      branch: "Invalid",
+      type: "primary",
+      id: "ValueResult",
+      position: "bottom",
+      plug: "string",
+    },
+  ],
+  parent: "action.transform",
+  name: "pick_keys_action",
+  args: {
+    data: "Text",
+    keys: "Text",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "data",
+        width: 2,
+      },
+      {
+        key: "keys",
+        width: 2,
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "data",
+        type: "TextArea",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "keys",
+        type: "TextArea",
+        optional: false,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      Invalid: "Invalid",
+      description: "Keep only the given keys of an object",
+      title: "Pick keys",
+      data: {
+        title: "Data",
+        description: "JSON object to pick from",
+      },
+      keys: {
+        title: "Keys",
+        description: "One path per line, such as user.name",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      Invalid: "无效输入",
+      description: "只保留对象中指定的字段",
+      title: "选取字段",
+      data: {
+        title: "数据",
+        description: "要选取的 JSON 对象",
+      },
+      keys: {
+        title: "字段",
+        description: "每行一个路径，如 user.name",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default pick_keys_action;
@@ -1,87 +1,201 @@
-/* This part is the automatically generated source code. Please modify it in src\service\action\program.rs */
+/* This part is the automatically generated source code. Please modify it in actions/program/src/lib.rs */
 
+import { api } from "..";
 import { defineCard } from "../helper";
 const program_action = defineCard({
-    branches: [
-        {
-            branch: "source",
-            type: "source",
-            id: "Result",
-            position: "left"
-        },
-        {
-            branch: "Success",
-            type: "primary",
-            id: "Result",
-            position: "right"
-        }
-    ],
-    parent: "action.program",
-    name: "program_action",
-    args: {
-        code: "Code",
-        lang: "String"
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "Result",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "Result",
+      position: "right",
+      plug: {
+        "\0type": "object",
+      },
+    },
+    {
+      branch: "Error",
+      type: "primary",
+      id: "Result",
+      position: "bottom",
+      plug: "string",
+    },
+    {
+      branch: "Timeout",
+      type: "primary",
+      id: "Result",
+      position: "top",
     },
-    litCardView: ()=>{
-        return [
-            {
-                key: "code",
-                width: 2
-            },
-            {
-                key: "lang"
-            }
-        ];
+    {
+      branch: "Denied",
+      type: "primary",
+      id: "Result",
+      position: "top",
+      plug: "string",
     },
-    view: {
-        title: "",
-        form: [
-            {
-                name: "code",
-                type: "Code",
-                optional: false,
-                data: {}
-            },
-            {
-                name: "lang",
-                type: "Option",
-                optional: false,
-                data: [
-                    {
-                        label: "JavaScript",
-                        value: "JavaScript"
-                    }
-                ]
-            }
-        ]
+  ],
+  parent: "action.program",
+  name: "program_action",
+  args: {
+    code: "Code",
+    lang: "String",
+    timeout: "Int",
+    memory: "Int",
+    capabilities: "Text",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "code",
+        width: 2,
+      },
+      {
+        key: "lang",
+      },
+      {
+        key: "timeout",
+      },
+      {
+        key: "memory",
+      },
+      {
+        key: "capabilities",
+        width: 2,
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "code",
+        type: "Code",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "lang",
+        type: "Option",
+        optional: false,
+        data: [
+          {
+            label: "JavaScript",
+            value: "JavaScript",
+          },
+          {
+            label: "Lua",
+            value: "Lua",
+          },
+          {
+            label: "Python",
+            value: "Python",
+          },
+          {
+            label: "PowerShell",
+            value: "PowerShell",
+          },
+          {
+            label: "Bash",
+            value: "Bash",
+          },
+        ],
+      },
+      {
+        name: "timeout",
+        type: "Number",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "memory",
+        type: "Number",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "capabilities",
+        type: "TextArea",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      Error: "Error",
+      Timeout: "Timeout",
+      Denied: "Denied",
+      description: "Execute a code segment",
+      title: "Execute Code",
+      code: {
+        title: "Code content",
+        description:
+          "Code to execute, read upstream outputs from context, the returned value or output is the result",
+      },
+      lang: {
+        title: "Language",
+        description: "Programming language used",
+      },
+      timeout: {
+        title: "Timeout",
+        description: "Maximum execution time (ms)",
+      },
+      memory: {
+        title: "Memory limit",
+        description: "Memory available to the script (MB)",
+      },
+      capabilities: {
+        title: "Capabilities",
+        description:
+          "JSON declaring fs, net, process and env, any undeclared access is denied; Python, PowerShell and Bash cannot restrict fs and net, so only process and env may be declared",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      Error: "失败",
+      Timeout: "超时",
+      Denied: "权限不足",
+      description: "执行一段代码",
+      title: "执行代码",
+      code: {
+        title: "代码内容",
+        description: "要执行的代码，上游输出可通过 context 读取，返回值或输出作为结果",
+      },
+      lang: {
+        title: "语言",
+        description: "使用的编程语言",
+      },
+      timeout: {
+        title: "超时时间",
+        description: "脚本最长执行时间（毫秒）",
+      },
+      memory: {
+        title: "内存上限",
+        description: "脚本可使用的内存（MB）",
+      },
+      capabilities: {
+        title: "权限清单",
+        description:
+          "JSON 格式，可声明 fs、net、process、env，未声明的访问一律拒绝；Python、PowerShell 与 Bash 无法限制 fs 与 net，只能声明 process 与 env",
+      },
     },
-    i18n: {
-        en: {
-            description: "Execute a code segment",
-            title: "Execute Code",
-            code: {
-                title: "Code content",
-                description: "Code to execute"
-            },
-            lang: {
-                title: "Language",
-                description: "Programming language used"
-            }
-        },
-        "zh-CN": {
-            description: "执行一段代码",
-            title: "执行代码",
-            code: {
-                title: "代码内容",
-                description: "要执行的代码"
-            },
-            lang: {
-                title: "语言",
-                description: "使用的编程语言"
-            }
-        }
-    }
+  },
 });
 
 /* This section can be used to extend or override */
-export default program_action;
\ No newline at end of file
+// 只提供主机上存在解释器的语言
+api.getAvailableLanguages().then((languages) => {
+  const lang = program_action.view.form.find(({ name }) => name === "lang");
+  if (lang && Array.isArray(lang.data)) {
+    lang.data = lang.data.filter(({ value }) => languages.includes(value));
+  }
+});
+
+export default program_action;
@@ -0,0 +1,135 @@
+/* This part is the automatically generated source code. Please modify it in actions/desktop/src/lib.rs */
+
+import { defineCard } from "../helper";
+const prompt_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "PromptResult",
+      position: "left",
+    },
+    {
+      branch: "Submitted",
+      type: "primary",
+      id: "PromptResult",
+      position: "right",
+      plug: "string",
+    },
+    {
+      branch: "Cancelled",
+      type: "primary",
+      id: "PromptResult",
+      position: "bottom",
+    },
+    {
+      branch: "Timeout",
+      type: "primary",
+      id: "PromptResult",
+      position: "top",
+    },
+  ],
+  parent: "action.desktop",
+  name: "prompt_action",
+  args: {
+    title: "String",
+    message: "Text",
+    default: "String",
+    timeout: "Int",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "title",
+      },
+      {
+        key: "message",
+        width: 2,
+      },
+      {
+        key: "default",
+      },
+      {
+        key: "timeout",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "title",
+        type: "String",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "message",
+        type: "TextArea",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "default",
+        type: "String",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "timeout",
+        type: "Number",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Submitted: "Submitted",
+      Cancelled: "Cancelled",
+      Timeout: "Timeout",
+      description: "Show a dialog and wait for the user's input",
+      title: {
+        title: "Title",
+        description: "Title of the dialog",
+      },
+      message: {
+        title: "Message",
+        description: "Tell the user what to input",
+      },
+      default: {
+        title: "Default",
+        description: "Value prefilled in the dialog",
+      },
+      timeout: {
+        title: "Timeout",
+        description: "Time to wait for the input (ms), 300000 by default",
+      },
+    },
+    "zh-CN": {
+      Submitted: "已提交",
+      Cancelled: "已取消",
+      Timeout: "超时",
+      description: "弹出输入框并等待用户输入",
+      title: {
+        title: "标题",
+        description: "输入框的标题",
+      },
+      message: {
+        title: "提示",
+        description: "向用户说明需要输入什么",
+      },
+      default: {
+        title: "默认值",
+        description: "输入框中预先填入的值",
+      },
+      timeout: {
+        title: "超时时间",
+        description: "等待用户输入的时间（毫秒），默认为 300000",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default prompt_action;
@@ -0,0 +1,149 @@
+/* This part is the automatically generated source code. Please modify it in actions/transform/src/lib.rs */
+
+import { defineCard } from "../helper";
+const query_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "QueryResult",
+      position: "left",
+    },
+    {
+      branch: "Found",
+      type: "primary",
+      id: "QueryResult",
+      position: "right",
+      plug: {
+        "\0type": "object",
+      },
+    },
+    {
+      branch: "Empty",
+      type: "primary",
+      id: "QueryResult",
+      position: "bottom",
+    },
+    {
+      branch: "Invalid",
+      type: "primary",
+      id: "QueryResult",
+      position: "bottom",
+      plug: "string",
+    },
+  ],
+  parent: "action.transform",
+  name: "query_action",
+  args: {
+    data: "Text",
+    query: "String",
+    syntax: "String",
+    first: "Bool",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "data",
+        width: 2,
+      },
+      {
+        key: "query",
+      },
+      {
+        key: "syntax",
+      },
+      {
+        key: "first",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "data",
+        type: "TextArea",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "query",
+        type: "String",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "syntax",
+        type: "Option",
+        optional: true,
+        data: [
+          {
+            label: "JsonPath",
+            value: "JsonPath",
+          },
+          {
+            label: "JmesPath",
+            value: "JmesPath",
+          },
+        ],
+      },
+      {
+        name: "first",
+        type: "Switch",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Found: "Found",
+      Empty: "Empty",
+      Invalid: "Invalid",
+      description: "Select values from JSON with JSONPath or JMESPath",
+      title: "Query JSON",
+      data: {
+        title: "Data",
+        description: "JSON to query",
+      },
+      query: {
+        title: "Query",
+        description: "Such as $.items[*].name or items[].name",
+      },
+      syntax: {
+        title: "Syntax",
+        description: "JSONPath by default",
+      },
+      first: {
+        title: "First only",
+        description: "Output the first result instead of a list",
+      },
+    },
+    "zh-CN": {
+      Found: "找到",
+      Empty: "无结果",
+      Invalid: "无效输入",
+      description: "使用 JSONPath 或 JMESPath 从 JSON 中取值",
+      title: "查询 JSON",
+      data: {
+        title: "数据",
+        description: "要查询的 JSON",
+      },
+      query: {
+        title: "查询语句",
+        description: "如 $.items[*].name 或 items[].name",
+      },
+      syntax: {
+        title: "语法",
+        description: "默认为 JSONPath",
+      },
+      first: {
+        title: "仅第一个",
+        description: "只输出第一个结果，而不是结果列表",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default query_action;
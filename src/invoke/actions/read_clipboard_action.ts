@@ -0,0 +1,82 @@
+/* This part is the automatically generated source code. Please modify it in actions/desktop/src/lib.rs */
+
+import { defineCard } from "../helper";
+const read_clipboard_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "TextResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "TextResult",
+      position: "right",
+      plug: "string",
+    },
+    {
+      branch: "Empty",
+      type: "primary",
+      id: "TextResult",
+      position: "bottom",
+    },
+    {
+      branch: "Timeout",
+      type: "primary",
+      id: "TextResult",
+      position: "top",
+    },
+  ],
+  parent: "action.desktop",
+  name: "read_clipboard_action",
+  args: {
+    timeout: "Int",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "timeout",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "timeout",
+        type: "Number",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      Empty: "Empty",
+      Timeout: "Timeout",
+      description: "Read the text in the clipboard",
+      title: "Read clipboard",
+      timeout: {
+        title: "Timeout",
+        description: "Time to wait for the UI (ms), 5000 by default",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      Empty: "无内容",
+      Timeout: "超时",
+      description: "读取剪贴板中的文本",
+      title: "读取剪贴板",
+      timeout: {
+        title: "超时时间",
+        description: "等待界面响应的时间（毫秒），默认为 5000",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default read_clipboard_action;
@@ -0,0 +1,109 @@
+/* This part is the automatically generated source code. Please modify it in actions/desktop/src/lib.rs */
+
+import { defineCard } from "../helper";
+const read_clipboard_image_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "ImageResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "ImageResult",
+      position: "right",
+      plug: {
+        "\0type": "object",
+        width: "number",
+        height: "number",
+        png: "string",
+        path: "string",
+      },
+    },
+    {
+      branch: "Empty",
+      type: "primary",
+      id: "ImageResult",
+      position: "bottom",
+    },
+    {
+      branch: "Timeout",
+      type: "primary",
+      id: "ImageResult",
+      position: "top",
+    },
+  ],
+  parent: "action.desktop",
+  name: "read_clipboard_image_action",
+  args: {
+    path: "File",
+    timeout: "Int",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "path",
+      },
+      {
+        key: "timeout",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "path",
+        type: "File",
+        optional: true,
+        data: {
+          directory: false,
+        },
+      },
+      {
+        name: "timeout",
+        type: "Number",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      Empty: "Empty",
+      Timeout: "Timeout",
+      description: "Read the image in the clipboard as PNG",
+      title: "Read clipboard image",
+      path: {
+        title: "Save path",
+        description:
+          "Save the image as a PNG file, only output Base64 when empty",
+      },
+      timeout: {
+        title: "Timeout",
+        description: "Time to wait for the UI (ms), 5000 by default",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      Empty: "无内容",
+      Timeout: "超时",
+      description: "读取剪贴板中的图片，以 PNG 输出",
+      title: "读取剪贴板图片",
+      path: {
+        title: "保存路径",
+        description: "将图片保存为 PNG 文件，留空时只输出 Base64",
+      },
+      timeout: {
+        title: "超时时间",
+        description: "等待界面响应的时间（毫秒），默认为 5000",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default read_clipboard_image_action;
@@ -0,0 +1,115 @@
+/* This part is the automatically generated source code. Please modify it in actions/fs/src/lib.rs */
+
+import { defineCard } from "../helper";
+const read_file_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "ContentResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "ContentResult",
+      position: "right",
+      plug: "string",
+    },
+    {
+      branch: "NotFound",
+      type: "primary",
+      id: "ContentResult",
+      position: "bottom",
+      plug: "string",
+    },
+    {
+      branch: "Permission",
+      type: "primary",
+      id: "ContentResult",
+      position: "bottom",
+      plug: "string",
+    },
+  ],
+  parent: "action.fs",
+  name: "read_file_action",
+  args: {
+    path: "File",
+    encoding: "String",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "path",
+      },
+      {
+        key: "encoding",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "path",
+        type: "File",
+        optional: false,
+        data: {
+          directory: false,
+        },
+      },
+      {
+        name: "encoding",
+        type: "Option",
+        optional: true,
+        data: [
+          {
+            label: "Utf8",
+            value: "Utf8",
+          },
+          {
+            label: "Base64",
+            value: "Base64",
+          },
+        ],
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Encoding_Utf8: "Text",
+      Success: "Success",
+      NotFound: "Not found",
+      Permission: "Permission denied",
+      description: "Read the content of a file",
+      title: "Read file",
+      path: {
+        title: "File",
+        description: "File to read",
+      },
+      encoding: {
+        title: "Encoding",
+        description: "Read as text, or encode binary content as Base64",
+      },
+    },
+    "zh-CN": {
+      Encoding_Utf8: "文本",
+      Success: "成功",
+      NotFound: "不存在",
+      Permission: "无权限",
+      description: "读取文件的内容",
+      title: "读取文件",
+      path: {
+        title: "文件",
+        description: "要读取的文件",
+      },
+      encoding: {
+        title: "编码",
+        description: "以文本读取，或将二进制内容编码为 Base64",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default read_file_action;
@@ -0,0 +1,136 @@
+/* This part is the automatically generated source code. Please modify it in actions/transform/src/lib.rs */
+
+import { defineCard } from "../helper";
+const regex_extract_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "ExtractResult",
+      position: "left",
+    },
+    {
+      branch: "Matched",
+      type: "primary",
+      id: "ExtractResult",
+      position: "right",
+      plug: {
+        "\0type": "object",
+        matches: {
+          "\0type": "object",
+        },
+        count: "number",
+      },
+    },
+    {
+      branch: "NoMatch",
+      type: "primary",
+      id: "ExtractResult",
+      position: "bottom",
+    },
+  ],
+  parent: "action.transform",
+  name: "regex_extract_action",
+  args: {
+    text: "Text",
+    pattern: "String",
+    group: "String",
+    ignore_case: "Bool",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "text",
+        width: 2,
+      },
+      {
+        key: "pattern",
+      },
+      {
+        key: "group",
+      },
+      {
+        key: "ignore_case",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "text",
+        type: "TextArea",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "pattern",
+        type: "String",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "group",
+        type: "String",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "ignore_case",
+        type: "Switch",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Matched: "Matched",
+      NoMatch: "No match",
+      description: "Extract the text of every match",
+      title: "Regex extract",
+      text: {
+        title: "Text",
+        description: "Text to extract from",
+      },
+      pattern: {
+        title: "Pattern",
+        description: "Regular expression to match",
+      },
+      group: {
+        title: "Group",
+        description:
+          "Index or name of the group to output, the whole match by default",
+      },
+      ignore_case: {
+        title: "Ignore case",
+        description: "Match case-insensitively",
+      },
+    },
+    "zh-CN": {
+      Matched: "匹配",
+      NoMatch: "不匹配",
+      description: "提取全部匹配的文本",
+      title: "正则提取",
+      text: {
+        title: "文本",
+        description: "要提取的文本",
+      },
+      pattern: {
+        title: "正则表达式",
+        description: "要匹配的正则表达式",
+      },
+      group: {
+        title: "分组",
+        description: "输出的分组序号或名称，默认为整个匹配",
+      },
+      ignore_case: {
+        title: "忽略大小写",
+        description: "匹配时不区分大小写",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default regex_extract_action;
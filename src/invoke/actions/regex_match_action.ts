@@ -0,0 +1,120 @@
+/* This part is the automatically generated source code. Please modify it in actions/transform/src/lib.rs */
+
+import { defineCard } from "../helper";
+const regex_match_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "MatchResult",
+      position: "left",
+    },
+    {
+      branch: "Matched",
+      type: "primary",
+      id: "MatchResult",
+      position: "right",
+      plug: {
+        "\0type": "object",
+        text: "string",
+        groups: {
+          "\0type": "object",
+        },
+        named: {
+          "\0type": "object",
+        },
+      },
+    },
+    {
+      branch: "NoMatch",
+      type: "primary",
+      id: "MatchResult",
+      position: "bottom",
+    },
+  ],
+  parent: "action.transform",
+  name: "regex_match_action",
+  args: {
+    text: "Text",
+    pattern: "String",
+    ignore_case: "Bool",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "text",
+        width: 2,
+      },
+      {
+        key: "pattern",
+      },
+      {
+        key: "ignore_case",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "text",
+        type: "TextArea",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "pattern",
+        type: "String",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "ignore_case",
+        type: "Switch",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Matched: "Matched",
+      NoMatch: "No match",
+      description: "Match the first occurrence and output its groups",
+      title: "Regex match",
+      text: {
+        title: "Text",
+        description: "Text to match",
+      },
+      pattern: {
+        title: "Pattern",
+        description: "Use (?<name>...) to declare named groups",
+      },
+      ignore_case: {
+        title: "Ignore case",
+        description: "Match case-insensitively",
+      },
+    },
+    "zh-CN": {
+      Matched: "匹配",
+      NoMatch: "不匹配",
+      description: "匹配第一处并输出其中的分组",
+      title: "正则匹配",
+      text: {
+        title: "文本",
+        description: "要匹配的文本",
+      },
+      pattern: {
+        title: "正则表达式",
+        description: "可使用 (?<name>...) 声明命名分组",
+      },
+      ignore_case: {
+        title: "忽略大小写",
+        description: "匹配时不区分大小写",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default regex_match_action;
@@ -0,0 +1,125 @@
+/* This part is the automatically generated source code. Please modify it in actions/transform/src/lib.rs */
+
+import { defineCard } from "../helper";
+const regex_replace_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "ReplaceResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "ReplaceResult",
+      position: "right",
+      plug: {
+        "\0type": "object",
+        text: "string",
+        count: "number",
+      },
+    },
+  ],
+  parent: "action.transform",
+  name: "regex_replace_action",
+  args: {
+    text: "Text",
+    pattern: "String",
+    replacement: "String",
+    ignore_case: "Bool",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "text",
+        width: 2,
+      },
+      {
+        key: "pattern",
+      },
+      {
+        key: "replacement",
+      },
+      {
+        key: "ignore_case",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "text",
+        type: "TextArea",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "pattern",
+        type: "String",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "replacement",
+        type: "String",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "ignore_case",
+        type: "Switch",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      description: "Replace every match in the text",
+      title: "Regex replace",
+      text: {
+        title: "Text",
+        description: "Text to replace in",
+      },
+      pattern: {
+        title: "Pattern",
+        description: "Regular expression to match",
+      },
+      replacement: {
+        title: "Replacement",
+        description: "Reference groups with $1 or ${name}",
+      },
+      ignore_case: {
+        title: "Ignore case",
+        description: "Match case-insensitively",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      description: "替换全部匹配的文本",
+      title: "正则替换",
+      text: {
+        title: "文本",
+        description: "要替换的文本",
+      },
+      pattern: {
+        title: "正则表达式",
+        description: "要匹配的正则表达式",
+      },
+      replacement: {
+        title: "替换为",
+        description: "可使用 $1 或 ${name} 引用分组",
+      },
+      ignore_case: {
+        title: "忽略大小写",
+        description: "匹配时不区分大小写",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default regex_replace_action;
@@ -0,0 +1,113 @@
+/* This part is the automatically generated source code. Please modify it in actions/fs/src/lib.rs */
+
+import { defineCard } from "../helper";
+const rename_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "PathResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "PathResult",
+      position: "right",
+      plug: "string",
+    },
+    {
+      branch: "NotFound",
+      type: "primary",
+      id: "PathResult",
+      position: "bottom",
+      plug: "string",
+    },
+    {
+      branch: "Permission",
+      type: "primary",
+      id: "PathResult",
+      position: "bottom",
+      plug: "string",
+    },
+    {
+      branch: "Exists",
+      type: "primary",
+      id: "PathResult",
+      position: "top",
+      plug: "string",
+    },
+  ],
+  parent: "action.fs",
+  name: "rename_action",
+  args: {
+    path: "File",
+    name: "String",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "path",
+      },
+      {
+        key: "name",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "path",
+        type: "File",
+        optional: false,
+        data: {
+          directory: false,
+        },
+      },
+      {
+        name: "name",
+        type: "String",
+        optional: false,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      NotFound: "Not found",
+      Permission: "Permission denied",
+      Exists: "Exists",
+      description: "Rename a file or directory in place",
+      title: "Rename",
+      path: {
+        title: "Path",
+        description: "File or directory to rename",
+      },
+      name: {
+        title: "New name",
+        description: "New name without directories",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      NotFound: "不存在",
+      Permission: "无权限",
+      Exists: "已存在",
+      description: "在原目录中重命名文件或目录",
+      title: "重命名",
+      path: {
+        title: "路径",
+        description: "要重命名的文件或目录",
+      },
+      name: {
+        title: "新名称",
+        description: "不包含目录的新名称",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default rename_action;
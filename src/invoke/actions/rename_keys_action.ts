@@ -0,0 +1,98 @@
+/* This part is the automatically generated source code. Please modify it in actions/transform/src/lib.rs */
+
+import { defineCard } from "../helper";
+const rename_keys_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "ValueResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "ValueResult",
+      position: "right",
+      plug: {
+        "\0type": "object",
+      },
+    },
+    {
+      branch: "Invalid",
+      type: "primary",
+      id: "ValueResult",
+      position: "bottom",
+      plug: "string",
+    },
+  ],
+  parent: "action.transform",
+  name: "rename_keys_action",
+  args: {
+    data: "Text",
+    mapping: "Text",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "data",
+        width: 2,
+      },
+      {
+        key: "mapping",
+        width: 2,
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "data",
+        type: "TextArea",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "mapping",
+        type: "TextArea",
+        optional: false,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      Invalid: "Invalid",
+      description: "Rename keys of an object by a mapping",
+      title: "Rename keys",
+      data: {
+        title: "Data",
+        description: "JSON object to rename keys in",
+      },
+      mapping: {
+        title: "Mapping",
+        description:
+          "One old=new per line, paths such as user.name are allowed",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      Invalid: "无效输入",
+      description: "按映射重命名对象中的字段",
+      title: "重命名字段",
+      data: {
+        title: "数据",
+        description: "要重命名的 JSON 对象",
+      },
+      mapping: {
+        title: "映射",
+        description: "每行一个 old=new，可使用 user.name 形式的路径",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default rename_keys_action;
@@ -0,0 +1,330 @@
+/* This part is the automatically generated source code. Please modify it in actions/mail/src/lib.rs */
+
+import { defineCard } from "../helper";
+const send_mail_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "Result",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "Result",
+      position: "right",
+      plug: {
+        "\0type": "object",
+        code: "number",
+        message: "string",
+      },
+    },
+    {
+      branch: "Rejected",
+      type: "primary",
+      id: "Result",
+      position: "bottom",
+      plug: "string",
+    },
+  ],
+  parent: "action.mail",
+  name: "send_mail_action",
+  args: {
+    host: "String",
+    port: "Int",
+    security: "String",
+    username: "String",
+    password: "String",
+    from: "String",
+    to: "String",
+    cc: "String",
+    bcc: "String",
+    subject: "String",
+    body: "Text",
+    format: "String",
+    attachments: "Text",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "host",
+      },
+      {
+        key: "port",
+      },
+      {
+        key: "security",
+      },
+      {
+        key: "username",
+      },
+      {
+        key: "password",
+      },
+      {
+        key: "from",
+      },
+      {
+        key: "to",
+      },
+      {
+        key: "cc",
+      },
+      {
+        key: "bcc",
+      },
+      {
+        key: "subject",
+      },
+      {
+        key: "body",
+        width: 2,
+      },
+      {
+        key: "format",
+      },
+      {
+        key: "attachments",
+        width: 2,
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "host",
+        type: "String",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "port",
+        type: "Number",
+        optional: true,
+        data: {},
+        validate: {
+          min: 1,
+          max: 65535,
+        },
+      },
+      {
+        name: "security",
+        type: "Option",
+        optional: true,
+        data: [
+          {
+            label: "Tls",
+            value: "Tls",
+          },
+          {
+            label: "StartTls",
+            value: "StartTls",
+          },
+          {
+            label: "Plain",
+            value: "Plain",
+          },
+        ],
+      },
+      {
+        name: "username",
+        type: "String",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "password",
+        type: "String",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "from",
+        type: "String",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "to",
+        type: "String",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "cc",
+        type: "String",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "bcc",
+        type: "String",
+        optional: true,
+        data: {},
+      },
+      {
+        name: "subject",
+        type: "String",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "body",
+        type: "TextArea",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "format",
+        type: "Option",
+        optional: true,
+        data: [
+          {
+            label: "Text",
+            value: "Text",
+          },
+          {
+            label: "Html",
+            value: "Html",
+          },
+        ],
+      },
+      {
+        name: "attachments",
+        type: "TextArea",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Security_Tls: "SSL/TLS",
+      Security_Plain: "None",
+      BodyFormat_Text: "Text",
+      Success: "Success",
+      Rejected: "Rejected",
+      description: "Send an email over SMTP",
+      title: "Send mail",
+      host: {
+        title: "SMTP server",
+        description: "Such as smtp.example.com",
+      },
+      port: {
+        title: "Port",
+        description: "465, 587 or 25 by default, depending on the security",
+      },
+      security: {
+        title: "Security",
+        description: "SSL/TLS by default",
+      },
+      username: {
+        title: "Username",
+        description:
+          "No authentication when empty, may reference env: or secret: secrets",
+      },
+      password: {
+        title: "Password",
+        description: "Reference secrets with env:NAME or secret:NAME",
+      },
+      from: {
+        title: "From",
+        description: "Such as Daisy <daisy@example.com>",
+      },
+      to: {
+        title: "To",
+        description: "Separate multiple recipients with commas",
+      },
+      cc: {
+        title: "Cc",
+        description: "Separate multiple recipients with commas",
+      },
+      bcc: {
+        title: "Bcc",
+        description: "Separate multiple recipients with commas",
+      },
+      subject: {
+        title: "Subject",
+        description: "Subject of the email",
+      },
+      body: {
+        title: "Body",
+        description: "Body of the email",
+      },
+      format: {
+        title: "Body format",
+        description: "Plain text by default",
+      },
+      attachments: {
+        title: "Attachments",
+        description: "One file path per line, or name=base64:content",
+      },
+    },
+    "zh-CN": {
+      Security_Tls: "SSL/TLS",
+      Security_Plain: "不加密",
+      BodyFormat_Text: "纯文本",
+      Success: "成功",
+      Rejected: "被拒绝",
+      description: "通过 SMTP 发送邮件",
+      title: "发送邮件",
+      host: {
+        title: "SMTP 服务器",
+        description: "如 smtp.example.com",
+      },
+      port: {
+        title: "端口",
+        description: "默认为 465、587 或 25，取决于加密方式",
+      },
+      security: {
+        title: "加密方式",
+        description: "默认为 SSL/TLS",
+      },
+      username: {
+        title: "用户名",
+        description: "留空时不进行认证，可使用 env: 或 secret: 引用密钥",
+      },
+      password: {
+        title: "密码",
+        description: "可使用 env:NAME 或 secret:NAME 引用密钥",
+      },
+      from: {
+        title: "发件人",
+        description: "如 Daisy <daisy@example.com>",
+      },
+      to: {
+        title: "收件人",
+        description: "多个收件人以逗号分隔",
+      },
+      cc: {
+        title: "抄送",
+        description: "多个收件人以逗号分隔",
+      },
+      bcc: {
+        title: "密送",
+        description: "多个收件人以逗号分隔",
+      },
+      subject: {
+        title: "主题",
+        description: "邮件的主题",
+      },
+      body: {
+        title: "正文",
+        description: "邮件的正文",
+      },
+      format: {
+        title: "正文格式",
+        description: "默认为纯文本",
+      },
+      attachments: {
+        title: "附件",
+        description: "每行一个文件路径，或 name=base64:内容",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default send_mail_action;
@@ -0,0 +1,96 @@
+/* This part is the automatically generated source code. Please modify it in actions/transform/src/lib.rs */
+
+import { defineCard } from "../helper";
+const template_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "TextResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "TextResult",
+      position: "right",
+      plug: "string",
+    },
+    {
+      branch: "Invalid",
+      type: "primary",
+      id: "TextResult",
+      position: "bottom",
+      plug: "string",
+    },
+  ],
+  parent: "action.transform",
+  name: "template_action",
+  args: {
+    template: "Text",
+    data: "Text",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "template",
+        width: 2,
+      },
+      {
+        key: "data",
+        width: 2,
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "template",
+        type: "TextArea",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "data",
+        type: "TextArea",
+        optional: false,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      Invalid: "Invalid",
+      description: "Fill {{ path }} placeholders with JSON data",
+      title: "Template",
+      template: {
+        title: "Template",
+        description:
+          "Such as Hello {{ user.name }}, missing paths render as empty",
+      },
+      data: {
+        title: "Data",
+        description: "JSON used to fill the template",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      Invalid: "无效输入",
+      description: "使用 JSON 数据填充 {{ path }} 占位符",
+      title: "文本模板",
+      template: {
+        title: "模板",
+        description: "如 Hello {{ user.name }}，不存在的路径输出为空",
+      },
+      data: {
+        title: "数据",
+        description: "用于填充的 JSON",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default template_action;
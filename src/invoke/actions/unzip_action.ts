@@ -0,0 +1,115 @@
+/* This part is the automatically generated source code. Please modify it in actions/fs/src/lib.rs */
+
+import { defineCard } from "../helper";
+const unzip_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "PathResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "PathResult",
+      position: "right",
+      plug: "string",
+    },
+    {
+      branch: "NotFound",
+      type: "primary",
+      id: "PathResult",
+      position: "bottom",
+      plug: "string",
+    },
+    {
+      branch: "Permission",
+      type: "primary",
+      id: "PathResult",
+      position: "bottom",
+      plug: "string",
+    },
+    {
+      branch: "Exists",
+      type: "primary",
+      id: "PathResult",
+      position: "top",
+      plug: "string",
+    },
+  ],
+  parent: "action.fs",
+  name: "unzip_action",
+  args: {
+    archive: "File",
+    target: "Directory",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "archive",
+      },
+      {
+        key: "target",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "archive",
+        type: "File",
+        optional: false,
+        data: {
+          directory: false,
+        },
+      },
+      {
+        name: "target",
+        type: "File",
+        optional: false,
+        data: {
+          directory: true,
+        },
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      NotFound: "Not found",
+      Permission: "Permission denied",
+      Exists: "Exists",
+      description: "Extract a zip into a directory",
+      title: "Unzip",
+      archive: {
+        title: "Archive",
+        description: "The zip file to extract",
+      },
+      target: {
+        title: "Target directory",
+        description: "Directory to extract into",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      NotFound: "不存在",
+      Permission: "无权限",
+      Exists: "已存在",
+      description: "将 zip 解压到目录",
+      title: "解压",
+      archive: {
+        title: "压缩包",
+        description: "要解压的 zip 文件",
+      },
+      target: {
+        title: "目标目录",
+        description: "解压到的目录",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default unzip_action;
@@ -0,0 +1,92 @@
+/* This part is the automatically generated source code. Please modify it in actions/desktop/src/lib.rs */
+
+import { defineCard } from "../helper";
+const write_clipboard_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "DoneResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "DoneResult",
+      position: "right",
+    },
+    {
+      branch: "Timeout",
+      type: "primary",
+      id: "DoneResult",
+      position: "top",
+    },
+  ],
+  parent: "action.desktop",
+  name: "write_clipboard_action",
+  args: {
+    text: "Text",
+    timeout: "Int",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "text",
+        width: 2,
+      },
+      {
+        key: "timeout",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "text",
+        type: "TextArea",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "timeout",
+        type: "Number",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      Timeout: "Timeout",
+      description: "Copy text to the clipboard",
+      title: "Write clipboard",
+      text: {
+        title: "Text",
+        description: "Text to copy",
+      },
+      timeout: {
+        title: "Timeout",
+        description: "Time to wait for the UI (ms), 5000 by default",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      Timeout: "超时",
+      description: "将文本复制到剪贴板",
+      title: "写入剪贴板",
+      text: {
+        title: "文本",
+        description: "要复制的文本",
+      },
+      timeout: {
+        title: "超时时间",
+        description: "等待界面响应的时间（毫秒），默认为 5000",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default write_clipboard_action;
@@ -0,0 +1,93 @@
+/* This part is the automatically generated source code. Please modify it in actions/desktop/src/lib.rs */
+
+import { defineCard } from "../helper";
+const write_clipboard_image_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "DoneResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "DoneResult",
+      position: "right",
+    },
+    {
+      branch: "Timeout",
+      type: "primary",
+      id: "DoneResult",
+      position: "top",
+    },
+  ],
+  parent: "action.desktop",
+  name: "write_clipboard_image_action",
+  args: {
+    image: "File",
+    timeout: "Int",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "image",
+      },
+      {
+        key: "timeout",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "image",
+        type: "File",
+        optional: false,
+        data: {
+          directory: false,
+        },
+      },
+      {
+        name: "timeout",
+        type: "Number",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      Timeout: "Timeout",
+      description: "Copy a PNG or JPEG image to the clipboard",
+      title: "Write clipboard image",
+      image: {
+        title: "Image",
+        description: "Image file to copy",
+      },
+      timeout: {
+        title: "Timeout",
+        description: "Time to wait for the UI (ms), 5000 by default",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      Timeout: "超时",
+      description: "将 PNG 或 JPEG 图片复制到剪贴板",
+      title: "写入剪贴板图片",
+      image: {
+        title: "图片",
+        description: "要复制的图片文件",
+      },
+      timeout: {
+        title: "超时时间",
+        description: "等待界面响应的时间（毫秒），默认为 5000",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default write_clipboard_image_action;
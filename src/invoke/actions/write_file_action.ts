@@ -0,0 +1,181 @@
+/* This part is the automatically generated source code. Please modify it in actions/fs/src/lib.rs */
+
+import { defineCard } from "../helper";
+const write_file_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "PathResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "PathResult",
+      position: "right",
+      plug: "string",
+    },
+    {
+      branch: "NotFound",
+      type: "primary",
+      id: "PathResult",
+      position: "bottom",
+      plug: "string",
+    },
+    {
+      branch: "Permission",
+      type: "primary",
+      id: "PathResult",
+      position: "bottom",
+      plug: "string",
+    },
+    {
+      branch: "Exists",
+      type: "primary",
+      id: "PathResult",
+      position: "top",
+      plug: "string",
+    },
+  ],
+  parent: "action.fs",
+  name: "write_file_action",
+  args: {
+    path: "File",
+    content: "Text",
+    encoding: "String",
+    mode: "String",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "path",
+      },
+      {
+        key: "content",
+        width: 2,
+      },
+      {
+        key: "encoding",
+      },
+      {
+        key: "mode",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "path",
+        type: "File",
+        optional: false,
+        data: {
+          directory: false,
+        },
+      },
+      {
+        name: "content",
+        type: "TextArea",
+        optional: false,
+        data: {},
+      },
+      {
+        name: "encoding",
+        type: "Option",
+        optional: true,
+        data: [
+          {
+            label: "Utf8",
+            value: "Utf8",
+          },
+          {
+            label: "Base64",
+            value: "Base64",
+          },
+        ],
+      },
+      {
+        name: "mode",
+        type: "Option",
+        optional: true,
+        data: [
+          {
+            label: "Overwrite",
+            value: "Overwrite",
+          },
+          {
+            label: "Append",
+            value: "Append",
+          },
+          {
+            label: "CreateNew",
+            value: "CreateNew",
+          },
+        ],
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Encoding_Utf8: "Text",
+      WriteMode_Overwrite: "Overwrite",
+      WriteMode_Append: "Append",
+      WriteMode_CreateNew: "Create new",
+      Success: "Success",
+      NotFound: "Not found",
+      Permission: "Permission denied",
+      Exists: "Exists",
+      description: "Write or append content to a file",
+      title: "Write file",
+      path: {
+        title: "File",
+        description: "File to write",
+      },
+      content: {
+        title: "Content",
+        description: "Content to write",
+      },
+      encoding: {
+        title: "Encoding",
+        description: "Content is text, or Base64 encoded binary",
+      },
+      mode: {
+        title: "Mode",
+        description:
+          "Overwrite, append, or create only if the file does not exist",
+      },
+    },
+    "zh-CN": {
+      Encoding_Utf8: "文本",
+      WriteMode_Overwrite: "覆盖",
+      WriteMode_Append: "追加",
+      WriteMode_CreateNew: "仅新建",
+      Success: "成功",
+      NotFound: "不存在",
+      Permission: "无权限",
+      Exists: "已存在",
+      description: "写入或追加文件内容",
+      title: "写入文件",
+      path: {
+        title: "文件",
+        description: "要写入的文件",
+      },
+      content: {
+        title: "内容",
+        description: "写入的内容",
+      },
+      encoding: {
+        title: "编码",
+        description: "内容为文本，或为 Base64 编码的二进制",
+      },
+      mode: {
+        title: "写入方式",
+        description: "覆盖、追加，或仅在文件不存在时新建",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default write_file_action;
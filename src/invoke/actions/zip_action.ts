@@ -0,0 +1,133 @@
+/* This part is the automatically generated source code. Please modify it in actions/fs/src/lib.rs */
+
+import { defineCard } from "../helper";
+const zip_action = defineCard({
+  branches: [
+    {
+      branch: "source",
+      type: "source",
+      id: "PathResult",
+      position: "left",
+    },
+    {
+      branch: "Success",
+      type: "primary",
+      id: "PathResult",
+      position: "right",
+      plug: "string",
+    },
+    {
+      branch: "NotFound",
+      type: "primary",
+      id: "PathResult",
+      position: "bottom",
+      plug: "string",
+    },
+    {
+      branch: "Permission",
+      type: "primary",
+      id: "PathResult",
+      position: "bottom",
+      plug: "string",
+    },
+    {
+      branch: "Exists",
+      type: "primary",
+      id: "PathResult",
+      position: "top",
+      plug: "string",
+    },
+  ],
+  parent: "action.fs",
+  name: "zip_action",
+  args: {
+    source: "File",
+    target: "File",
+    overwrite: "Bool",
+  },
+  litCardView: () => {
+    return [
+      {
+        key: "source",
+      },
+      {
+        key: "target",
+      },
+      {
+        key: "overwrite",
+      },
+    ];
+  },
+  view: {
+    title: "",
+    form: [
+      {
+        name: "source",
+        type: "File",
+        optional: false,
+        data: {
+          directory: false,
+        },
+      },
+      {
+        name: "target",
+        type: "File",
+        optional: false,
+        data: {
+          directory: false,
+        },
+      },
+      {
+        name: "overwrite",
+        type: "Switch",
+        optional: true,
+        data: {},
+      },
+    ],
+  },
+  i18n: {
+    en: {
+      Success: "Success",
+      NotFound: "Not found",
+      Permission: "Permission denied",
+      Exists: "Exists",
+      description: "Compress a file or directory into a zip",
+      title: "Zip",
+      source: {
+        title: "Source",
+        description: "File or directory to compress",
+      },
+      target: {
+        title: "Archive",
+        description: "The zip file to create",
+      },
+      overwrite: {
+        title: "Overwrite",
+        description: "Overwrite the archive if it exists",
+      },
+    },
+    "zh-CN": {
+      Success: "成功",
+      NotFound: "不存在",
+      Permission: "无权限",
+      Exists: "已存在",
+      description: "将文件或目录压缩为 zip",
+      title: "压缩",
+      source: {
+        title: "源路径",
+        description: "要压缩的文件或目录",
+      },
+      target: {
+        title: "压缩包",
+        description: "生成的 zip 文件",
+      },
+      overwrite: {
+        title: "覆盖",
+        description: "压缩包已存在时覆盖",
+      },
+    },
+  },
+});
+
+/* This section can be used to extend or override */
+export default zip_action;
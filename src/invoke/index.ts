@@ -1,6 +1,18 @@
 import { Config } from "../pages/Settings.vue";
 import { createInvoke } from "./helper";
-import { CardMeta, Data, LitCard } from "./type";
+import {
+  CardMeta,
+  LitCard,
+  LogRecord,
+  MetricsSnapshot,
+  NotifyRule,
+  PluginManifest,
+  PromptInfo,
+  RunRecord,
+  RunWindow,
+  TaskLifecycle,
+  TaskState,
+} from "./type";
 import { ServiceState } from "./serviceState";
 
 export const invokeMap = {
@@ -28,14 +40,14 @@ export const invokeMap = {
     args: [] as unknown[],
     return: {} as LitCard[],
   },
+  getAvailableLanguages: {
+    args: [] as unknown[],
+    return: [] as string[],
+  },
   updateActionPlug: {
     args: ["id", "plug"] as {} as [id: string, plug: Record<string, any>],
     return: undefined as void,
   },
-  getScriptById: {
-    args: ["actionId"] as {} as [actionId: string],
-    return: "" as string,
-  },
   getServiceState: {
     args: [] as unknown[],
     return: "" as ServiceState,
@@ -48,6 +60,14 @@ export const invokeMap = {
     args: [] as unknown[],
     return: "" as string,
   },
+  getServiceMetrics: {
+    args: [] as unknown[],
+    return: {} as MetricsSnapshot,
+  },
+  getServiceMetricsText: {
+    args: [] as unknown[],
+    return: "" as string,
+  },
   createTask: {
     args: ["triggerId", "name", "workflow"] as {} as [
       trigger_id: string,
@@ -60,10 +80,6 @@ export const invokeMap = {
     args: ["id"] as {} as [id: string],
     return: undefined as void,
   },
-  runAction: {
-    args: ["actionType", "args"] as {} as [actionType: string, args: Data],
-    return: undefined as void,
-  },
   removeAction: {
     args: ["id"] as {} as [id: string],
     return: undefined as void,
@@ -76,6 +92,38 @@ export const invokeMap = {
     args: ["id"] as {} as [id: string],
     return: undefined as void,
   },
+  getTaskLifecycle: {
+    args: ["taskId"] as {} as [taskId: string],
+    return: {} as TaskLifecycle,
+  },
+  setTaskState: {
+    args: ["taskId", "state"] as {} as [taskId: string, state: TaskState],
+    return: {} as TaskLifecycle,
+  },
+  setTaskWindows: {
+    args: ["taskId", "windows"] as {} as [taskId: string, windows: RunWindow[]],
+    return: {} as TaskLifecycle,
+  },
+  getRunHistory: {
+    args: ["taskId"] as {} as [taskId: string],
+    return: {} as RunRecord[],
+  },
+  getTaskLogs: {
+    args: ["taskId", "runId", "limit"] as {} as [
+      taskId: string,
+      runId?: string,
+      limit?: number
+    ],
+    return: {} as LogRecord[],
+  },
+  getTaskNotifyRules: {
+    args: ["taskId"] as {} as [taskId: string],
+    return: {} as NotifyRule[],
+  },
+  setTaskNotifyRules: {
+    args: ["taskId", "rules"] as {} as [taskId: string, rules: NotifyRule[]],
+    return: undefined as void,
+  },
   isCronExpressionVaild: {
     args: ["expression"] as {} as [expression: string],
     return: {} as boolean,
@@ -88,6 +136,23 @@ export const invokeMap = {
     args: [] as unknown[],
     return: {} as unknown,
   },
+  getPrompt: {
+    args: ["id"] as {} as [id: string],
+    return: {} as PromptInfo | null,
+  },
+  submitPrompt: {
+    args: ["id", "value"] as {} as [id: string, value: string | null],
+    return: undefined as void,
+  },
+  getPlugins: {
+    args: [] as unknown[],
+    return: {} as PluginManifest[],
+  },
+  /** 返回被拒绝的插件及原因 */
+  reloadPlugins: {
+    args: [] as unknown[],
+    return: {} as string[],
+  },
 } as const;
 
 export const api = createInvoke(invokeMap);
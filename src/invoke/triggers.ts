@@ -5,6 +5,9 @@ import { CardMeta } from "./type";
 registerDisplayName("trigger")("time", {
   "zh-CN": "时间相关",
   en: "Time Related",
+})("mail", {
+  "zh-CN": "邮件",
+  en: "Mail",
 });
 
 const modules = import.meta.glob("./triggers/*.{ts,tsx,js,jsx}", {
@@ -0,0 +1,175 @@
+import { reactive, SetupContext } from "vue";
+import { toast } from "../../utils/components/ToastProvider.vue";
+import { defineCard } from "../helper";
+import { CardComponentProps } from "../type";
+
+type ImapConfig = {
+  host: string;
+  port: number | null;
+  security: "tls" | "start_tls" | "plain";
+  username: string;
+  password: string;
+  folder: string;
+  from: string;
+  subject: string;
+  unseen_only: boolean;
+  mark_seen: boolean;
+  /** 轮询间隔（毫秒） */
+  interval: number;
+};
+
+export default defineCard({
+  parent: "trigger.mail",
+  name: "imap_trigger",
+  args: { value: "Json" },
+  context: {
+    mail: {
+      uid: "number",
+      message_id: "string",
+      from: "string[]",
+      to: "string[]",
+      cc: "string[]",
+      subject: "string",
+      date: "string",
+      text: "string",
+      html: "string",
+      attachments: "string[]",
+    },
+  },
+  litCardView: () => {
+    return [];
+  },
+  i18n: {
+    "zh-CN": {
+      title: "IMAP触发器",
+      args_description: "接收邮箱的连接参数与过滤条件",
+      description: "邮箱收到匹配的新邮件时触发，邮件内容放入 mail 中",
+      litArgs: "邮箱",
+      imap: {
+        server: "服务器",
+        host: "IMAP 服务器",
+        port: "端口，默认为 993 或 143",
+        security: "加密方式",
+        tls: "SSL/TLS",
+        start_tls: "StartTls",
+        plain: "不加密",
+        username: "用户名",
+        password: "密码，可使用 env:NAME 或 secret:NAME",
+        filter: "过滤条件",
+        folder: "文件夹",
+        from: "发件人包含",
+        subject: "主题包含",
+        unseen_only: "只匹配未读邮件",
+        mark_seen: "读取后标记为已读",
+        interval: "轮询间隔（秒）",
+        error: "请填写服务器、用户名与密码",
+      },
+    },
+    en: {
+      title: "IMAP Trigger",
+      args_description:
+        "Receive the mailbox connection and the filters for new messages",
+      description:
+        "Triggered when a matching message arrives, the message is put into mail",
+      litArgs: "Mailbox",
+      imap: {
+        server: "Server",
+        host: "IMAP server",
+        port: "Port, 993 or 143 by default",
+        security: "Security",
+        tls: "SSL/TLS",
+        start_tls: "StartTls",
+        plain: "None",
+        username: "Username",
+        password: "Password, may use env:NAME or secret:NAME",
+        filter: "Filters",
+        folder: "Folder",
+        from: "From contains",
+        subject: "Subject contains",
+        unseen_only: "Only unread messages",
+        mark_seen: "Mark as read after fetching",
+        interval: "Polling interval (s)",
+        error: "Please fill in the server, username and password",
+      },
+    },
+  },
+  // @ts-ignore
+  view: ({ useI18n }: CardComponentProps, { expose }: SetupContext) => {
+    const { t } = useI18n();
+    const config = reactive<ImapConfig>({
+      host: "",
+      port: null,
+      security: "tls",
+      username: "",
+      password: "",
+      folder: "INBOX",
+      from: "",
+      subject: "",
+      unseen_only: false,
+      mark_seen: false,
+      interval: 60000,
+    });
+    expose({
+      getData() {
+        if (!config.host || !config.username || !config.password) {
+          toast.error(t("imap.error"));
+          return false;
+        }
+        return {
+          type: "Json",
+          value: { ...config, port: config.port || null },
+        };
+      },
+    });
+    const input = (key: keyof ImapConfig, type = "text") => (
+      <>
+        <label class="fieldset-label mt-1">{t(`imap.${key}`)}</label>
+        <input v-model={config[key]} type={type} class="input w-full" />
+      </>
+    );
+    const toggle = (key: "unseen_only" | "mark_seen") => (
+      <label class="fieldset-label mt-2">
+        <input v-model={config[key]} type="checkbox" class="toggle" />
+        {t(`imap.${key}`)}
+      </label>
+    );
+    return () => {
+      return (
+        <>
+          <fieldset class="fieldset w-full bg-base-200 border border-base-300 p-4 rounded-box">
+            <legend class="fieldset-legend text-xl">{t("imap.server")}</legend>
+            {input("host")}
+            {input("port", "number")}
+            <label class="fieldset-label mt-1">{t("imap.security")}</label>
+            <select v-model={config.security} class="select w-full">
+              {(["tls", "start_tls", "plain"] as const).map((security) => (
+                <option value={security}>{t(`imap.${security}`)}</option>
+              ))}
+            </select>
+            {input("username")}
+            {input("password", "password")}
+          </fieldset>
+          <fieldset class="fieldset w-full bg-base-200 border border-base-300 p-4 rounded-box mt-2">
+            <legend class="fieldset-legend text-xl">{t("imap.filter")}</legend>
+            {input("folder")}
+            {input("from")}
+            {input("subject")}
+            <label class="fieldset-label mt-1">{t("imap.interval")}</label>
+            <input
+              value={config.interval / 1000}
+              onInput={(e) =>
+                (config.interval =
+                  Number((e.target as HTMLInputElement).value) * 1000)
+              }
+              type="number"
+              min={5}
+              class="input w-full"
+            />
+            {toggle("unseen_only")}
+            {toggle("mark_seen")}
+          </fieldset>
+        </>
+      );
+    };
+  },
+});
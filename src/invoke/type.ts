@@ -6,10 +6,111 @@ import createScopeI18n from "../composable/useScopeI18n";
 
 type ActionEntry =
   | { LitRef: { id: string; wid: string } }
-  | { Inline: { uid: string; type: string; data: Data } };
+  | { Inline: { uid: string; type: string; data: Data; version?: number } };
 
 export type TaskMap = { [branchId: string]: ActionEntry };
 
+export type TaskState =
+  | { type: "Enabled" }
+  | { type: "Paused"; until: number }
+  | { type: "Disabled" }
+  | { type: "Archived" };
+
+export type RunWindow = {
+  /** 1 表示周一，7 表示周日；为空表示每天 */
+  days: number[];
+  /** HH:MM */
+  start: string;
+  /** HH:MM */
+  end: string;
+};
+
+export type TaskLifecycle = {
+  state: TaskState;
+  windows: RunWindow[];
+};
+
+export type RunStatus = "Success" | "Failure";
+
+export type TokenUsage = {
+  prompt_tokens: number;
+  completion_tokens: number;
+  total_tokens: number;
+};
+
+export type RunRecord = {
+  run_id: string;
+  task_id: string;
+  task_name: string;
+  started_at: number;
+  finished_at: number | null;
+  status: RunStatus | null;
+  failed_node: string | null;
+  error: string | null;
+  /** 调用模型的节点消耗的 token 总量 */
+  usage?: TokenUsage | null;
+};
+
+export type Histogram = {
+  /** 与服务端的耗时桶对应的非累计计数，最后一项为 +Inf */
+  buckets: number[];
+  count: number;
+  sum: number;
+  max: number;
+};
+
+export type MetricsSnapshot = {
+  uptime: number;
+  armed_triggers: string[];
+  running_runs: number;
+  queued_runs: number;
+  tasks: Record<
+    string,
+    { success: number; failure: number; latency: Histogram }
+  >;
+  actions: Record<string, Histogram>;
+};
+
+export type LogRecord = {
+  time: string;
+  level: string;
+  target: string;
+  message: string;
+  task_id?: string;
+  run_id?: string;
+  action_id?: string;
+};
+
+export type NotifyWhen =
+  | { type: "Failure" }
+  | { type: "Success" }
+  | { type: "ConsecutiveFailures"; count: number };
+
+export type NotifyChannel =
+  | { type: "Desktop" }
+  | { type: "Webhook"; url: string }
+  | { type: "RunTask"; task_id: string };
+
+export type NotifyRule = {
+  when: NotifyWhen;
+  channel: NotifyChannel;
+};
+
+/** 询问用户动作弹出的输入框 */
+export type PromptInfo = {
+  title: string;
+  message: string;
+  default: string | null;
+};
+
+/** 运行时加载的动作包插件 */
+export type PluginManifest = {
+  name: string;
+  version: string;
+  abi_version: number;
+  actions: { action_type: string; meta: unknown; branches: unknown[] }[];
+};
+
 type CardId = string;
 type CardName = string;
 type CardLabel = string;
@@ -27,8 +128,10 @@ export const argType = [
   "Float",
   "Text",
   "File",
+  "Directory",
   "Code",
   "Bool",
+  "Json",
 ] as const satisfies string[];
 
 export type ArgType = (typeof argType)[number];
@@ -44,6 +147,8 @@ export type CardMeta = {
   parent: string;
   name: CardName;
   args: { [key: string]: ArgType };
+  /** 触发器放入 context 的数据，用于生成卡片目录 */
+  context?: { [key: string]: any };
   litCardView: (props: litCardViewProps) => (StatProps | StatPropsWithKey)[];
   view: Renderable<CardComponentProps>;
   i18n: {
@@ -62,6 +167,10 @@ export type LitCard<T = any> = {
   type: string;
   data: T;
   plug: Record<string, any>;
+  /** 点亮时参数的版本 */
+  version?: number;
+  /** 卡片无法使用的原因，类型不存在或参数无法迁移 */
+  issue?: { kind: "missing" } | { kind: "migrate"; message: string };
 };
 
 export type IntType = { type: "Int"; value: number };
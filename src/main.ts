@@ -4,13 +4,13 @@ import "./main.css";
 import { router } from "./pages/pages";
 import "./inject";
 import { createI18n } from "vue-i18n";
+import fallback from "./i18n/fallback.json";
 
 const i18n = createI18n({
   legacy: false,
   locale: "zh-CN",
-  fallbackLocale: {
-    "zh-CN": ["zh_cn"],
-  },
+  // 由 aster_codegen 根据工作区配置的回退链生成
+  fallbackLocale: fallback,
 });
 
 createApp(App).use(router).use(i18n).mount("#app");
@@ -121,3 +121,22 @@ export function useTransition() {
     },
   };
 }
+
+// 同一侧有多个分支时，沿该侧均匀分布连接点，避免重叠
+export function handleOffset(
+  branches: { position: string }[],
+  index: number,
+): Record<string, string> {
+  const { position } = branches[index];
+  const siblings = branches.filter((branch) => branch.position === position);
+  if (siblings.length < 2) {
+    return {};
+  }
+  const order = branches
+    .slice(0, index)
+    .filter((branch) => branch.position === position).length;
+  const offset = `${((order + 1) * 100) / (siblings.length + 1)}%`;
+  return position === "top" || position === "bottom"
+    ? { left: offset }
+    : { top: offset };
+}
@@ -6,6 +6,7 @@ import Settings from "./Settings.vue";
 import Workflow from "./Workflow.vue";
 import Example from "./Example.vue";
 import Task from "./Task.vue";
+import Prompt from "./Prompt.vue";
 import { ref } from "vue";
 
 const routes: RouteRecordRaw[] = [
@@ -15,6 +16,7 @@ const routes: RouteRecordRaw[] = [
   { name: "actions", path: "/action/:name?", component: Action },
   { name: "task", path: "/task", component: Task },
   { name: "workflow", path: "/workflow", component: Workflow },
+  { name: "prompt", path: "/prompt/:id", component: Prompt },
   { path: "/example", component: Example },
 ];
 
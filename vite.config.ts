@@ -51,7 +51,6 @@ export default defineConfig(async () => ({
     rollupOptions: {
       input: {
         main: resolve(__dirname, "index.html"),
-        js_engine: resolve(__dirname, "js_engine/index.html"),
       },
     },
   },